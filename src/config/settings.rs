@@ -4,6 +4,7 @@ use ratatui::style::Color;
 use serde::Deserialize;
 
 use super::theme::Theme;
+use crate::git::external_diff::DiffRenderer;
 
 /// Parse a color string into a ratatui Color
 /// Supports: named colors, hex (#ff0000, #f00), rgb (rgb(255, 0, 0)), indexed (0-255)
@@ -82,6 +83,8 @@ pub struct ColorOverrides {
     pub tag_label: Option<String>,
     pub diff_addition: Option<String>,
     pub diff_deletion: Option<String>,
+    pub diff_addition_refined: Option<String>,
+    pub diff_deletion_refined: Option<String>,
     pub diff_context: Option<String>,
     pub diff_hunk: Option<String>,
     pub remote_branch: Option<String>,
@@ -126,6 +129,33 @@ pub struct Config {
 
     #[serde(default)]
     pub language: Option<String>,
+
+    /// Write commit messages in Magi's own editor instead of `$EDITOR`
+    #[serde(default)]
+    pub builtin_commit_editor: bool,
+
+    /// Show diffs in two columns (old and new) when the terminal is wide enough
+    #[serde(default)]
+    pub side_by_side_diff: bool,
+
+    /// Group the untracked, unstaged and staged files of the status by
+    /// directory
+    #[serde(default)]
+    pub file_tree: bool,
+
+    /// Color diff content by the syntax of the file's language
+    #[serde(default)]
+    pub syntax_highlighting: bool,
+
+    /// Command the diffs of commit and stash previews are piped through,
+    /// like `delta`
+    #[serde(default)]
+    pub diff_pager: Option<String>,
+
+    /// Command git runs as its external diff tool for commit and stash
+    /// previews, like `difft`; takes precedence over `diff_pager`
+    #[serde(default)]
+    pub external_diff: Option<String>,
 }
 
 fn default_theme_name() -> String {
@@ -140,6 +170,12 @@ impl Default for Config {
             theme_light: None,
             colors: ColorOverrides::default(),
             language: None,
+            builtin_commit_editor: false,
+            side_by_side_diff: false,
+            file_tree: false,
+            syntax_highlighting: false,
+            diff_pager: None,
+            external_diff: None,
         }
     }
 }
@@ -192,6 +228,20 @@ impl Config {
         toml::from_str(&contents).map_err(|e| ConfigError::ParseError(e.to_string()))
     }
 
+    /// The external program previews render their diffs with, if any
+    pub fn diff_renderer(&self) -> Option<DiffRenderer> {
+        let configured = |command: &Option<String>| {
+            command
+                .as_deref()
+                .map(str::trim)
+                .filter(|command| !command.is_empty())
+                .map(str::to_string)
+        };
+        configured(&self.external_diff)
+            .map(DiffRenderer::External)
+            .or_else(|| configured(&self.diff_pager).map(DiffRenderer::Pager))
+    }
+
     /// Whether the theme should be picked from the detected terminal background
     pub fn is_auto_theme(&self) -> bool {
         self.theme.eq_ignore_ascii_case("auto")
@@ -232,6 +282,8 @@ impl Config {
             tag_label,
             diff_addition,
             diff_deletion,
+            diff_addition_refined,
+            diff_deletion_refined,
             diff_context,
             diff_hunk,
             remote_branch,
@@ -384,6 +436,62 @@ mod tests {
         assert_eq!(theme, Theme::catppuccin_mocha());
     }
 
+    #[test]
+    fn test_builtin_commit_editor_defaults_to_off() {
+        let config: Config = toml::from_str("theme = \"default\"").unwrap();
+        assert!(!config.builtin_commit_editor);
+
+        let config: Config = toml::from_str("builtin_commit_editor = true").unwrap();
+        assert!(config.builtin_commit_editor);
+    }
+
+    #[test]
+    fn test_syntax_highlighting_defaults_to_off() {
+        let config: Config = toml::from_str("theme = \"default\"").unwrap();
+        assert!(!config.syntax_highlighting);
+        assert!(!Config::default().syntax_highlighting);
+
+        let config: Config = toml::from_str("syntax_highlighting = true").unwrap();
+        assert!(config.syntax_highlighting);
+    }
+
+    #[test]
+    fn test_diff_renderer_prefers_external_diff() {
+        assert_eq!(Config::default().diff_renderer(), None);
+
+        let config: Config = toml::from_str("diff_pager = \"delta\"").unwrap();
+        assert_eq!(
+            config.diff_renderer(),
+            Some(DiffRenderer::Pager("delta".to_string()))
+        );
+
+        let config: Config =
+            toml::from_str("diff_pager = \"delta\"\nexternal_diff = \"difft --color=always\"")
+                .unwrap();
+        assert_eq!(
+            config.diff_renderer(),
+            Some(DiffRenderer::External("difft --color=always".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_side_by_side_diff_defaults_to_off() {
+        let config: Config = toml::from_str("theme = \"default\"").unwrap();
+        assert!(!config.side_by_side_diff);
+
+        let config: Config = toml::from_str("side_by_side_diff = true").unwrap();
+        assert!(config.side_by_side_diff);
+    }
+
+    #[test]
+    fn test_file_tree_defaults_to_off() {
+        let config: Config = toml::from_str("theme = \"default\"").unwrap();
+        assert!(!config.file_tree);
+
+        let config: Config = toml::from_str("file_tree = true").unwrap();
+        assert!(config.file_tree);
+    }
+
     #[test]
     fn test_is_auto_theme_case_insensitive() {
         let config = Config {
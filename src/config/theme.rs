@@ -11,6 +11,9 @@ pub struct Theme {
     // Diff colors
     pub diff_addition: Color,
     pub diff_deletion: Color,
+    /// Background of the changed words within added/removed lines
+    pub diff_addition_refined: Color,
+    pub diff_deletion_refined: Color,
     pub diff_context: Color,
     pub diff_hunk: Color,
 
@@ -65,6 +68,8 @@ impl Theme {
             tag_label: Color::Yellow,
             diff_addition: Color::Green,
             diff_deletion: Color::Red,
+            diff_addition_refined: Color::Rgb(35, 80, 35),
+            diff_deletion_refined: Color::Rgb(95, 30, 35),
             diff_context: Color::Reset,
             diff_hunk: Color::Cyan,
             remote_branch: Color::Green,
@@ -100,6 +105,8 @@ impl Theme {
             tag_label: Color::Rgb(176, 121, 0),
             diff_addition: Color::Rgb(0, 128, 0),   // Dark green
             diff_deletion: Color::Rgb(200, 30, 45), // Dark red
+            diff_addition_refined: Color::Rgb(190, 235, 190),
+            diff_deletion_refined: Color::Rgb(245, 200, 200),
             diff_context: Color::Reset,
             diff_hunk: Color::Rgb(0, 120, 135), // Dark teal
             remote_branch: Color::Rgb(0, 128, 0),
@@ -136,6 +143,8 @@ impl Theme {
             tag_label: Color::Rgb(229, 200, 144),             // Yellow
             diff_addition: Color::Rgb(166, 209, 137),         // Green
             diff_deletion: Color::Rgb(231, 130, 132),         // Red
+            diff_addition_refined: Color::Rgb(77, 96, 78),    // Green on Base
+            diff_deletion_refined: Color::Rgb(103, 69, 82),   // Red on Base
             diff_context: Color::Rgb(198, 208, 245),          // Text
             diff_hunk: Color::Rgb(140, 170, 238),             // Blue
             remote_branch: Color::Rgb(166, 209, 137),         // Green
@@ -170,6 +179,8 @@ impl Theme {
             tag_label: Color::Rgb(249, 226, 175),             // Yellow
             diff_addition: Color::Rgb(166, 227, 161),         // Green
             diff_deletion: Color::Rgb(243, 139, 168),         // Red
+            diff_addition_refined: Color::Rgb(55, 78, 62),    // Green on Base
+            diff_deletion_refined: Color::Rgb(88, 53, 70),    // Red on Base
             diff_context: Color::Rgb(205, 214, 244),          // Text
             diff_hunk: Color::Rgb(137, 180, 250),             // Blue
             remote_branch: Color::Rgb(166, 227, 161),         // Green
@@ -199,26 +210,28 @@ impl Theme {
     /// Catppuccin Latte theme (light)
     pub fn catppuccin_latte() -> Self {
         Self {
-            section_header: Color::Rgb(223, 142, 29),   // Yellow
-            ref_label: Color::Rgb(223, 142, 29),        // Yellow
-            tag_label: Color::Rgb(223, 142, 29),        // Yellow
-            diff_addition: Color::Rgb(64, 160, 43),     // Green
-            diff_deletion: Color::Rgb(210, 15, 57),     // Red
-            diff_context: Color::Rgb(76, 79, 105),      // Text
-            diff_hunk: Color::Rgb(30, 102, 245),        // Blue
-            remote_branch: Color::Rgb(64, 160, 43),     // Green
-            local_branch: Color::Rgb(30, 102, 245),     // Blue
-            detached_head: Color::Rgb(210, 15, 57),     // Red
-            untracked_file: Color::Rgb(210, 15, 57),    // Red
-            unstaged_status: Color::Rgb(234, 118, 203), // Pink
-            staged_status: Color::Rgb(64, 160, 43),     // Green
-            file_path: Color::Rgb(76, 79, 105),         // Text
-            commit_hash: Color::Rgb(254, 100, 11),      // Peach
-            text: Color::Rgb(76, 79, 105),              // Text
-            dim_text: Color::Rgb(140, 143, 161),        // Overlay1
-            selection_bg: Color::Rgb(204, 208, 218),    // Surface0
-            status_bar_bg: Color::Rgb(230, 233, 239),   // Mantle
-            status_bar_fg: Color::Rgb(76, 79, 105),     // Text
+            section_header: Color::Rgb(223, 142, 29),         // Yellow
+            ref_label: Color::Rgb(223, 142, 29),              // Yellow
+            tag_label: Color::Rgb(223, 142, 29),              // Yellow
+            diff_addition: Color::Rgb(64, 160, 43),           // Green
+            diff_deletion: Color::Rgb(210, 15, 57),           // Red
+            diff_addition_refined: Color::Rgb(196, 228, 190), // Green on Base
+            diff_deletion_refined: Color::Rgb(240, 190, 202), // Red on Base
+            diff_context: Color::Rgb(76, 79, 105),            // Text
+            diff_hunk: Color::Rgb(30, 102, 245),              // Blue
+            remote_branch: Color::Rgb(64, 160, 43),           // Green
+            local_branch: Color::Rgb(30, 102, 245),           // Blue
+            detached_head: Color::Rgb(210, 15, 57),           // Red
+            untracked_file: Color::Rgb(210, 15, 57),          // Red
+            unstaged_status: Color::Rgb(234, 118, 203),       // Pink
+            staged_status: Color::Rgb(64, 160, 43),           // Green
+            file_path: Color::Rgb(76, 79, 105),               // Text
+            commit_hash: Color::Rgb(254, 100, 11),            // Peach
+            text: Color::Rgb(76, 79, 105),                    // Text
+            dim_text: Color::Rgb(140, 143, 161),              // Overlay1
+            selection_bg: Color::Rgb(204, 208, 218),          // Surface0
+            status_bar_bg: Color::Rgb(230, 233, 239),         // Mantle
+            status_bar_fg: Color::Rgb(76, 79, 105),           // Text
             // Latte accents are dark, so pills use light Base as foreground
             // (the inverse of Frappe/Mocha).
             status_mode_normal_bg: Color::Rgb(30, 102, 245), // Blue
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::path::Path;
 use std::process::Command;
 
@@ -25,16 +26,22 @@ pub fn read_commit_message(workdir: &Path, hash: &str) -> Option<String> {
     }
 }
 
+pub mod absorb;
+pub mod ansi;
 pub mod apply;
+pub mod blob;
 pub mod checkout;
 pub mod cherry_pick;
 pub mod commit;
 mod commit_utils;
 pub mod config;
+pub mod conflict;
 pub mod credential;
-mod diff_utils;
+pub mod diff_utils;
 pub mod discard;
+pub mod external_diff;
 pub mod file_checkout;
+pub mod file_tree;
 pub mod info;
 pub mod log;
 pub mod merge;
@@ -44,10 +51,12 @@ pub mod pty_command;
 pub mod push;
 pub mod rebase;
 pub mod recent_commits;
+pub mod refine;
 pub mod releases;
 pub mod reset;
 pub mod reverse;
 pub mod revert;
+pub mod signing;
 pub mod snapshot;
 pub mod stage;
 pub mod staged_changes;
@@ -55,14 +64,24 @@ pub mod stashes;
 pub mod test_repo;
 pub mod unmerged_changes;
 pub mod unpulled_commits;
+pub mod unpushed_commits;
 pub mod unstaged_changes;
 pub mod untracked_files;
+pub mod visit;
 pub mod wip;
 pub mod worktree;
 pub mod worktree_stash;
 
 pub struct GitInfo {
     pub repository: Repository,
+    /// Options of the diffs shown in the status and previews
+    pub diff_settings: DiffSettings,
+    /// Whether the untracked, unstaged and staged files are grouped by
+    /// directory in the status
+    pub file_tree: bool,
+    /// The untracked files expanded in the status, the only ones read for
+    /// a preview
+    pub expanded_untracked: HashSet<String>,
 }
 
 impl GitInfo {
@@ -72,7 +91,12 @@ impl GitInfo {
 
     pub fn new_from_path<P: AsRef<Path>>(path: P) -> Result<Self, Git2Error> {
         let repository = Repository::discover(path)?;
-        Ok(Self { repository })
+        Ok(Self {
+            repository,
+            diff_settings: DiffSettings::default(),
+            file_tree: false,
+            expanded_untracked: HashSet::new(),
+        })
     }
 
     pub fn has_staged_changes(&self) -> MagiResult<bool> {
@@ -101,11 +125,21 @@ impl GitInfo {
         let rebasing_lines = rebase::get_rebasing_lines(workdir)?;
         let reverting_lines = revert::get_reverting_lines(workdir)?;
         let cherry_picking_lines = cherry_pick::get_cherry_picking_lines(workdir)?;
-        let untracked_files = untracked_files::get_lines(&self.repository)?;
-        let unstaged_changes = unstaged_changes::get_lines(&self.repository)?;
-        let staged_changes = staged_changes::get_lines(&self.repository)?;
+        let mut untracked_files =
+            untracked_files::get_lines(&self.repository, &self.expanded_untracked)?;
+        let mut unstaged_changes =
+            unstaged_changes::get_lines(&self.repository, &self.diff_settings)?;
+        let mut staged_changes = staged_changes::get_lines(&self.repository, &self.diff_settings)?;
+        if self.file_tree {
+            untracked_files = file_tree::group_untracked_files(untracked_files);
+            unstaged_changes = file_tree::group_unstaged_changes(unstaged_changes);
+            staged_changes = file_tree::group_staged_changes(staged_changes);
+        }
         let stashes = stashes::get_lines(&self.repository)?;
         let unpulled_commits = unpulled_commits::get_lines(&self.repository)?;
+        let unpulled_from_push_remote = unpulled_commits::get_push_remote_lines(&self.repository)?;
+        let unmerged_commits = unpushed_commits::get_lines(&self.repository)?;
+        let unpushed_to_push_remote = unpushed_commits::get_push_remote_lines(&self.repository)?;
         let recent_commits = recent_commits::get_lines(&self.repository)?;
 
         let all_sections = [
@@ -118,6 +152,9 @@ impl GitInfo {
             staged_changes,
             stashes,
             unpulled_commits,
+            unpulled_from_push_remote,
+            unmerged_commits,
+            unpushed_to_push_remote,
             recent_commits,
         ];
         let result = all_sections
@@ -137,6 +174,63 @@ impl GitInfo {
     }
 }
 
+/// Context lines of diffs unless changed, as in git
+pub const DEFAULT_CONTEXT_LINES: u32 = 3;
+
+/// Options of the diffs shown in the status and previews, like the `-U<n>`
+/// and `-w` arguments of Magit's diff buffers. Staging, unstaging and
+/// discarding cut their patches from diffs made with the same options, so
+/// hunk indices match what is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffSettings {
+    /// Lines of context around each change
+    pub context_lines: u32,
+    /// Ignore changes in whitespace (`-w`)
+    pub ignore_whitespace: bool,
+}
+
+impl Default for DiffSettings {
+    fn default() -> Self {
+        Self {
+            context_lines: DEFAULT_CONTEXT_LINES,
+            ignore_whitespace: false,
+        }
+    }
+}
+
+impl DiffSettings {
+    /// Sets the options on a libgit2 diff.
+    pub fn apply_to<'a>(&self, options: &'a mut DiffOptions) -> &'a mut DiffOptions {
+        options
+            .context_lines(self.context_lines)
+            .ignore_whitespace(self.ignore_whitespace)
+    }
+
+    /// Arguments giving `git diff`/`git show` the same options.
+    pub fn diff_args(&self) -> Vec<String> {
+        let mut args = vec![format!("-U{}", self.context_lines)];
+        if self.ignore_whitespace {
+            args.push("--ignore-all-space".to_string());
+        }
+        args
+    }
+
+    /// Arguments `git apply` needs for patches cut from such diffs: patches
+    /// without context, and context lines whose whitespace differs from the
+    /// file's (with line counts recomputed, as whitespace-only changes were
+    /// left out of the hunks).
+    pub fn apply_args(&self) -> Vec<&'static str> {
+        let mut args = Vec::new();
+        if self.context_lines == 0 {
+            args.push("--unidiff-zero");
+        }
+        if self.ignore_whitespace {
+            args.extend(["--ignore-whitespace", "--recount"]);
+        }
+        args
+    }
+}
+
 /// Represents a Git reference with its name, commit hash, and message
 #[derive(Debug, Clone)]
 pub struct GitRef {
@@ -144,6 +238,9 @@ pub struct GitRef {
     pub commit_hash: String,
     pub commit_summary: String,
     pub reference_type: ReferenceType,
+    /// How many commits HEAD is ahead of and behind the branch this ref is
+    /// compared with, when it has one
+    pub ahead_behind: Option<(usize, usize)>,
 }
 
 /// Represents a git tag with name and number of commits ahead
@@ -217,9 +314,16 @@ impl GitRef {
             commit_hash,
             commit_summary,
             reference_type,
+            ahead_behind: None,
         }
     }
 
+    /// Sets how many commits HEAD is ahead of and behind the compared branch
+    pub fn with_ahead_behind(mut self, ahead_behind: Option<(usize, usize)>) -> Self {
+        self.ahead_behind = ahead_behind;
+        self
+    }
+
     /// Creates a new GitRef for a remote branch
     pub fn new_remote_branch(name: String, commit_hash: String, commit_summary: String) -> Self {
         Self::new(
@@ -0,0 +1,496 @@
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::Path,
+    process::{Output, Stdio},
+};
+
+use super::{commit::CommitResult, git_cmd, rebase::get_upstream_merge_base};
+use crate::errors::{MagiError, MagiResult};
+
+/// How many commits absorb considers when no upstream is configured
+/// (the default stack size of `git absorb`).
+const STACK_LIMIT: usize = 10;
+
+/// A commit a staged hunk is absorbed into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbsorbTarget {
+    pub hash: String,
+    pub summary: String,
+}
+
+impl AbsorbTarget {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+}
+
+/// A zero-context staged hunk (`git diff --cached -U0`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbsorbHunk {
+    pub path: String,
+    /// First line of the hunk in the staged file
+    pub new_start: usize,
+    /// The commit that last touched the lines the hunk changes, or `None`
+    /// when no single commit in the absorb range did
+    pub target: Option<AbsorbTarget>,
+    file_header: String,
+    old_start: usize,
+    old_count: usize,
+    new_count: usize,
+    body: Vec<String>,
+}
+
+/// The proposed hunk → commit mapping shown before absorbing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbsorbPlan {
+    pub hunks: Vec<AbsorbHunk>,
+    /// The distinct targets, oldest commit first
+    pub targets: Vec<AbsorbTarget>,
+}
+
+/// Works out which commit each staged hunk belongs to, like `git absorb`.
+///
+/// The lines a hunk removes or modifies are blamed on HEAD, restricted to
+/// the unpushed range: commits after the upstream merge base, or the last
+/// [`STACK_LIMIT`] commits without an upstream. A hunk that only adds lines
+/// is attributed through the lines around it. A hunk is absorbed only when
+/// all of those lines come from a single commit in the range; new, deleted,
+/// renamed and binary files are never absorbed.
+pub fn plan_absorb(workdir: &Path) -> MagiResult<AbsorbPlan> {
+    let range = absorb_range(workdir);
+    let mut hunks = staged_hunks(workdir)?;
+
+    let mut blames: HashMap<String, FileBlame> = HashMap::new();
+    for hunk in &mut hunks {
+        if !blames.contains_key(&hunk.path) {
+            let blame = blame_file(workdir, &range, &hunk.path)?;
+            blames.insert(hunk.path.clone(), blame);
+        }
+        let blame = &blames[&hunk.path];
+
+        let lines: Vec<usize> = if hunk.old_count > 0 {
+            (hunk.old_start..hunk.old_start + hunk.old_count).collect()
+        } else {
+            [hunk.old_start, hunk.old_start + 1]
+                .into_iter()
+                .filter(|line| *line >= 1 && *line <= blame.lines.len())
+                .collect()
+        };
+        let mut commits = lines.iter().map(|line| &blame.lines[line - 1]);
+        let Some(Some(first)) = commits.next() else {
+            continue;
+        };
+        if commits.all(|commit| commit.as_ref() == Some(first)) {
+            hunk.target = Some(AbsorbTarget {
+                hash: first.clone(),
+                summary: blame.summaries[first].clone(),
+            });
+        }
+    }
+
+    let mut targets: Vec<AbsorbTarget> = Vec::new();
+    for target in hunks.iter().filter_map(|h| h.target.as_ref()) {
+        if !targets.contains(target) {
+            targets.push(target.clone());
+        }
+    }
+    let order = rev_list(workdir, &range)?;
+    targets.sort_by_key(|t| std::cmp::Reverse(order.iter().position(|h| *h == t.hash)));
+
+    Ok(AbsorbPlan { hunks, targets })
+}
+
+/// Creates one `fixup!` commit per target in `plan`, each containing exactly
+/// the hunks mapped to it. Hunks without a target stay staged. The staged
+/// tree is restored afterwards, also when creating a fixup fails, so nothing
+/// staged is lost. `flags` are passed to every `git commit`.
+pub fn run_absorb(workdir: &Path, plan: &AbsorbPlan, flags: &[String]) -> MagiResult<CommitResult> {
+    let staged_tree = git_output(workdir, &["write-tree"])?;
+
+    let mut absorbed: Vec<&AbsorbHunk> = Vec::new();
+    let mut result = Ok(());
+    for target in &plan.targets {
+        let hunks: Vec<&AbsorbHunk> = plan
+            .hunks
+            .iter()
+            .filter(|h| h.target.as_ref() == Some(target))
+            .collect();
+        result = create_fixup(workdir, target, &hunks, &absorbed, flags);
+        if result.is_err() {
+            break;
+        }
+        absorbed.extend(hunks);
+    }
+
+    // HEAD now holds the absorbed hunks, so the original staged tree leaves
+    // exactly the remaining ones staged
+    git_output(workdir, &["read-tree", &staged_tree])?;
+    result?;
+
+    Ok(CommitResult {
+        success: true,
+        message: format!(
+            "Absorbed {} hunk(s) into {} commit(s)",
+            absorbed.len(),
+            plan.targets.len()
+        ),
+    })
+}
+
+/// Stages `hunks` on top of HEAD and commits them as a fixup of `target`.
+/// `absorbed` hunks are already part of HEAD and shift later line numbers.
+fn create_fixup(
+    workdir: &Path,
+    target: &AbsorbTarget,
+    hunks: &[&AbsorbHunk],
+    absorbed: &[&AbsorbHunk],
+    flags: &[String],
+) -> MagiResult<()> {
+    git_output(workdir, &["read-tree", "HEAD"])?;
+
+    let mut patch = String::new();
+    let mut current_file = None;
+    for hunk in hunks {
+        if current_file != Some(&hunk.path) {
+            patch.push_str(&hunk.file_header);
+            current_file = Some(&hunk.path);
+        }
+        let shift: isize = absorbed
+            .iter()
+            .filter(|h| h.path == hunk.path && h.old_start < hunk.old_start)
+            .map(|h| h.new_count as isize - h.old_count as isize)
+            .sum();
+        let old_start = (hunk.old_start as isize + shift) as usize;
+        let new_start = old_start + usize::from(hunk.old_count == 0)
+            - usize::from(hunk.new_count == 0 && old_start > 0);
+        patch.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start, hunk.old_count, new_start, hunk.new_count
+        ));
+        for line in &hunk.body {
+            patch.push_str(line);
+            patch.push('\n');
+        }
+    }
+
+    let mut child = git_cmd(workdir, &["apply", "--cached", "--unidiff-zero"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(patch.as_bytes())?;
+    }
+    check(child.wait_with_output()?, "git apply --cached")?;
+
+    let output = git_cmd(
+        workdir,
+        &["commit", &format!("--fixup={}", target.hash), "--no-edit"],
+    )
+    .args(flags)
+    .output()?;
+    check(output, "git commit --fixup")
+}
+
+/// The blame revision range: `<upstream merge base>..HEAD`, the last
+/// [`STACK_LIMIT`] commits, or all of HEAD for shorter histories.
+fn absorb_range(workdir: &Path) -> String {
+    if let Some(base) = get_upstream_merge_base(workdir) {
+        return format!("{base}..HEAD");
+    }
+    let stack_base = format!("HEAD~{STACK_LIMIT}");
+    if git_output(workdir, &["rev-parse", "--verify", "-q", &stack_base]).is_ok() {
+        format!("{stack_base}..HEAD")
+    } else {
+        "HEAD".to_string()
+    }
+}
+
+fn rev_list(workdir: &Path, range: &str) -> MagiResult<Vec<String>> {
+    let output = git_output(workdir, &["rev-list", range])?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Parses `git diff --cached -U0` into hunks. Only plain modifications of
+/// text files are returned.
+fn staged_hunks(workdir: &Path) -> MagiResult<Vec<AbsorbHunk>> {
+    let diff = git_output(
+        workdir,
+        &["diff", "--cached", "-U0", "--no-color", "--no-ext-diff"],
+    )?;
+    let mut hunks = Vec::new();
+    for file in diff.split("\ndiff --git ").filter(|f| !f.is_empty()) {
+        let file = file.strip_prefix("diff --git ").unwrap_or(file);
+        let Some(header_end) = file.find("\n@@") else {
+            continue;
+        };
+        let header = &file[..header_end];
+        if header.lines().any(|l| {
+            l.starts_with("new file")
+                || l.starts_with("deleted file")
+                || l.starts_with("rename ")
+                || l.starts_with("copy ")
+        }) {
+            continue;
+        }
+        let Some(path) = header
+            .lines()
+            .find_map(|l| l.strip_prefix("+++ b/"))
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        let file_header = format!("diff --git {}\n", header);
+
+        let mut current: Option<AbsorbHunk> = None;
+        for line in file[header_end + 1..].lines() {
+            if line.starts_with("@@") {
+                hunks.extend(current.take());
+                let Some((old_start, old_count, new_start, new_count)) = parse_hunk_header(line)
+                else {
+                    continue;
+                };
+                current = Some(AbsorbHunk {
+                    path: path.clone(),
+                    new_start,
+                    target: None,
+                    file_header: file_header.clone(),
+                    old_start,
+                    old_count,
+                    new_count,
+                    body: Vec::new(),
+                });
+            } else if let Some(hunk) = current.as_mut() {
+                hunk.body.push(line.to_string());
+            }
+        }
+        hunks.extend(current);
+    }
+    Ok(hunks)
+}
+
+/// Parses `@@ -a[,b] +c[,d] @@` into `(a, b, c, d)`.
+fn parse_hunk_header(line: &str) -> Option<(usize, usize, usize, usize)> {
+    let ranges = line.strip_prefix("@@ -")?.split(" @@").next()?;
+    let (old, new) = ranges.split_once(" +")?;
+    let parse = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_count) = parse(old)?;
+    let (new_start, new_count) = parse(new)?;
+    Some((old_start, old_count, new_start, new_count))
+}
+
+/// Blame of a file at HEAD: the commit of every line (`None` for lines
+/// from outside the absorb range) and the summaries of those commits.
+struct FileBlame {
+    lines: Vec<Option<String>>,
+    summaries: HashMap<String, String>,
+}
+
+fn blame_file(workdir: &Path, range: &str, path: &str) -> MagiResult<FileBlame> {
+    let mut args = vec!["blame", "--line-porcelain"];
+    // Without a range the root commit is part of the stack too
+    if range == "HEAD" {
+        args.push("--root");
+    }
+    args.extend([range, "--", path]);
+    let output = git_output(workdir, &args)?;
+
+    let mut blame = FileBlame {
+        lines: Vec::new(),
+        summaries: HashMap::new(),
+    };
+    let mut hash: Option<String> = None;
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            blame.lines.push(hash.take());
+        } else if let Some(summary) = line.strip_prefix("summary ") {
+            if let Some(hash) = &hash {
+                blame.summaries.insert(hash.clone(), summary.to_string());
+            }
+        } else if line == "boundary" {
+            hash = None;
+        } else if hash.is_none()
+            && let Some((first, _)) = line.split_once(' ')
+            && first.len() == 40
+            && first.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            hash = Some(first.to_string());
+        }
+    }
+    Ok(blame)
+}
+
+fn git_output(workdir: &Path, args: &[&str]) -> MagiResult<String> {
+    let output = git_cmd(workdir, args).output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string();
+    check(output, &format!("git {}", args[0]))?;
+    Ok(stdout)
+}
+
+fn check(output: Output, what: &str) -> MagiResult<()> {
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(MagiError::Generic(format!(
+            "{} failed: {}",
+            what,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_repo::TestRepo;
+
+    const LINES: &str = "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\n";
+
+    fn log_subjects(workdir: &Path) -> Vec<String> {
+        git_output(workdir, &["log", "--format=%s"])
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// A file whose first half comes from "First" and second half from
+    /// "Second", with a staged change in each half.
+    fn repo_with_two_owners() -> TestRepo {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("file.txt", "one\ntwo\nthree\nfour\n", "First");
+        test_repo.commit_file("file.txt", LINES, "Second");
+        test_repo
+            .write_file_content(
+                "file.txt",
+                "one\nTWO\nthree\nfour\nfive\nsix\nSEVEN\neight\n",
+            )
+            .stage_files(&["file.txt"]);
+        test_repo
+    }
+
+    #[test]
+    fn test_parse_hunk_header_defaults_counts_to_one() {
+        assert_eq!(parse_hunk_header("@@ -3 +3,2 @@ fn"), Some((3, 1, 3, 2)));
+        assert_eq!(parse_hunk_header("@@ -4,0 +5 @@"), Some((4, 0, 5, 1)));
+        assert_eq!(parse_hunk_header("not a hunk"), None);
+    }
+
+    #[test]
+    fn test_plan_maps_hunks_to_commits_that_last_touched_them() {
+        let test_repo = repo_with_two_owners();
+        let first = git_output(test_repo.repo_path(), &["rev-parse", "HEAD~1"]).unwrap();
+        let second = test_repo.head_hash();
+
+        let plan = plan_absorb(test_repo.repo_path()).unwrap();
+
+        let targets: Vec<_> = plan
+            .hunks
+            .iter()
+            .map(|h| h.target.as_ref().map(|t| t.hash.clone()))
+            .collect();
+        assert_eq!(targets, vec![Some(first.clone()), Some(second.clone())]);
+        let order: Vec<_> = plan.targets.iter().map(|t| t.summary.as_str()).collect();
+        assert_eq!(order, vec!["First", "Second"]);
+    }
+
+    #[test]
+    fn test_plan_leaves_hunks_outside_the_range_unassigned() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("file.txt", LINES, "Pushed");
+        test_repo.set_upstream_at_head();
+        test_repo.commit_file("other.txt", "a\n", "Unpushed");
+        test_repo
+            .write_file_content(
+                "file.txt",
+                "ONE\ntwo\nthree\nfour\nfive\nsix\nseven\neight\n",
+            )
+            .stage_files(&["file.txt"]);
+
+        let plan = plan_absorb(test_repo.repo_path()).unwrap();
+
+        assert_eq!(plan.hunks.len(), 1);
+        assert_eq!(plan.hunks[0].target, None);
+        assert!(plan.targets.is_empty());
+    }
+
+    #[test]
+    fn test_plan_skips_new_files() {
+        let test_repo = TestRepo::new();
+        test_repo
+            .write_file_content("new.txt", "new\n")
+            .stage_files(&["new.txt"]);
+
+        let plan = plan_absorb(test_repo.repo_path()).unwrap();
+
+        assert!(plan.hunks.is_empty());
+    }
+
+    #[test]
+    fn test_run_absorb_creates_one_fixup_per_target() {
+        let test_repo = repo_with_two_owners();
+        let workdir = test_repo.repo_path();
+        let plan = plan_absorb(workdir).unwrap();
+
+        let result = run_absorb(workdir, &plan, &[]).unwrap();
+
+        assert!(result.success);
+        assert_eq!(
+            log_subjects(workdir),
+            vec![
+                "fixup! Second",
+                "fixup! First",
+                "Second",
+                "First",
+                "Initial commit"
+            ]
+        );
+        assert_eq!(
+            git_output(workdir, &["show", "HEAD~1:file.txt"]).unwrap(),
+            "one\nTWO\nthree\nfour\nfive\nsix\nseven\neight"
+        );
+        assert_eq!(
+            git_output(workdir, &["show", "HEAD:file.txt"]).unwrap(),
+            "one\nTWO\nthree\nfour\nfive\nsix\nSEVEN\neight"
+        );
+        assert_eq!(git_output(workdir, &["diff", "--cached"]).unwrap(), "");
+    }
+
+    #[test]
+    fn test_run_absorb_shifts_later_hunks_and_keeps_unassigned_staged() {
+        let test_repo = TestRepo::new();
+        let workdir = test_repo.repo_path();
+        test_repo.commit_file("file.txt", "one\ntwo\nthree\nfour\n", "First");
+        test_repo.commit_file("file.txt", LINES, "Second");
+        test_repo
+            .write_file_content("new.txt", "new\n")
+            .stage_files(&["new.txt"]);
+        // "First" gains two lines before the change that belongs to "Second"
+        test_repo
+            .write_file_content(
+                "file.txt",
+                "one\nTWO\nextra\nextra\nthree\nfour\nfive\nsix\nSEVEN\neight\n",
+            )
+            .stage_files(&["file.txt"]);
+        let plan = plan_absorb(workdir).unwrap();
+
+        run_absorb(workdir, &plan, &[]).unwrap();
+
+        assert_eq!(
+            git_output(workdir, &["show", "HEAD:file.txt"]).unwrap(),
+            "one\nTWO\nextra\nextra\nthree\nfour\nfive\nsix\nSEVEN\neight"
+        );
+        assert_eq!(
+            git_output(workdir, &["diff", "--cached", "--name-only"]).unwrap(),
+            "new.txt"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Splitting of ANSI-colored output (`git log --color` graphs, external
+//! diff renderers) into text and the SGR color codes between it.
+
+/// A piece of colored output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment<'a> {
+    /// Plain text
+    Text(&'a str),
+    /// The parameters of an SGR sequence, e.g. `1;31` for `\x1b[1;31m`
+    Sgr(&'a str),
+}
+
+/// Splits `text` on its escape sequences. SGR (color) sequences are
+/// returned; other sequences (cursor movement, hyperlinks, ...) are dropped.
+pub fn segments(text: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+
+    while let Some(esc) = rest.find('\x1b') {
+        if esc > 0 {
+            segments.push(Segment::Text(&rest[..esc]));
+        }
+        let sequence = &rest[esc + 1..];
+        let (length, segment) = match sequence.as_bytes().first() {
+            // CSI: parameters, then a final byte in @..~
+            Some(b'[') => match sequence[1..].find(|c: char| ('@'..='~').contains(&c)) {
+                Some(end) => {
+                    let params = &sequence[1..end + 1];
+                    let sgr = sequence[end + 1..].starts_with('m');
+                    (end + 2, sgr.then_some(Segment::Sgr(params)))
+                }
+                None => (sequence.len(), None),
+            },
+            // OSC (e.g. hyperlinks): ends with BEL or ESC \
+            Some(b']') => {
+                let end = sequence
+                    .find('\x07')
+                    .map(|end| end + 1)
+                    .or_else(|| sequence.find("\x1b\\").map(|end| end + 2))
+                    .unwrap_or(sequence.len());
+                (end, None)
+            }
+            Some(_) => (sequence.chars().next().map_or(0, char::len_utf8), None),
+            None => (0, None),
+        };
+        segments.extend(segment);
+        rest = &sequence[length..];
+    }
+
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    segments
+}
+
+/// `text` without its escape sequences.
+pub fn strip(text: &str) -> String {
+    segments(text)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Text(text) => Some(text),
+            Segment::Sgr(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments_split_text_and_colors() {
+        assert_eq!(
+            segments("\x1b[1;31mred\x1b[m plain"),
+            vec![
+                Segment::Sgr("1;31"),
+                Segment::Text("red"),
+                Segment::Sgr(""),
+                Segment::Text(" plain"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_segments_drop_other_sequences() {
+        assert_eq!(
+            segments("\x1b]8;;file:///a.rs\x1b\\a.rs\x1b]8;;\x1b\\\x1b[K"),
+            vec![Segment::Text("a.rs")]
+        );
+    }
+
+    #[test]
+    fn test_strip() {
+        assert_eq!(strip("\x1b[32m+\x1b[0m added"), "+ added");
+        assert_eq!(strip("no colors"), "no colors");
+    }
+}
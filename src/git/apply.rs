@@ -1,13 +1,19 @@
 use std::path::Path;
 use std::process::Stdio;
 
-use super::git_cmd;
+use super::{DiffSettings, git_cmd};
 use crate::errors::{MagiError, MagiResult};
 
 /// Applies a patch to the working tree by piping it to `git apply`.
-pub fn apply_patch<P: AsRef<Path>>(repo_path: P, patch: &str) -> MagiResult<()> {
+/// `settings` are those of the diff the patch was cut from.
+pub fn apply_patch<P: AsRef<Path>>(
+    repo_path: P,
+    patch: &str,
+    settings: &DiffSettings,
+) -> MagiResult<()> {
     use std::io::Write;
     let mut child = git_cmd(&repo_path, &["apply"])
+        .args(settings.apply_args())
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -0,0 +1,400 @@
+use std::{collections::HashMap, path::Path};
+
+use super::git_cmd;
+use crate::{
+    errors::{MagiError, MagiResult},
+    model::{BlameInfo, Line, LineContent},
+};
+
+/// Runs git and returns its stdout, or an error carrying its stderr.
+fn git_stdout(workdir: &Path, args: &[&str]) -> MagiResult<String> {
+    let output = git_cmd(workdir, args).output()?;
+    if !output.status.success() {
+        return Err(MagiError::Generic(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Lists the files in the tree of `revision` (`git ls-tree -r --name-only`).
+pub fn files_at_revision(workdir: &Path, revision: &str) -> MagiResult<Vec<String>> {
+    Ok(
+        git_stdout(workdir, &["ls-tree", "-r", "--name-only", revision])?
+            .lines()
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Returns the lines of `path` at `revision`, annotated with the commit that
+/// last changed each of them when `blame` is set (`git blame --porcelain`).
+pub fn blob_lines(
+    workdir: &Path,
+    revision: &str,
+    path: &str,
+    blame: bool,
+) -> MagiResult<Vec<Line>> {
+    let content = git_stdout(workdir, &["show", &format!("{}:{}", revision, path)])?;
+    let mut blame_lines = if blame {
+        blame_file(workdir, revision, path)?.into_iter()
+    } else {
+        Vec::new().into_iter()
+    };
+
+    Ok(content
+        .lines()
+        .enumerate()
+        .map(|(index, text)| Line {
+            content: LineContent::BlobLine {
+                number: index + 1,
+                content: text.to_string(),
+                blame: blame_lines.next(),
+            },
+            section: None,
+        })
+        .collect())
+}
+
+/// Blames every line of `path` at `revision`.
+fn blame_file(workdir: &Path, revision: &str, path: &str) -> MagiResult<Vec<BlameInfo>> {
+    let porcelain = git_stdout(workdir, &["blame", "--porcelain", revision, "--", path])?;
+    let lines = parse_blame_porcelain(&porcelain);
+    if lines.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Porcelain output only has raw timestamps; let git format the dates
+    let mut hashes: Vec<&str> = lines.iter().map(|(hash, _)| hash.as_str()).collect();
+    hashes.sort_unstable();
+    hashes.dedup();
+    let mut args = vec![
+        "log",
+        "--no-walk=unsorted",
+        "--format=%H %ad",
+        "--date=short",
+    ];
+    args.extend(&hashes);
+    let output = git_stdout(workdir, &args)?;
+    let dates: HashMap<&str, &str> = output
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .collect();
+
+    Ok(lines
+        .iter()
+        .map(|(hash, author)| BlameInfo {
+            hash: hash.chars().take(7).collect(),
+            author: author.clone(),
+            date: dates.get(hash.as_str()).unwrap_or(&"").to_string(),
+        })
+        .collect())
+}
+
+/// Parses `git blame --porcelain` output into the commit hash and author of
+/// each final line. Commit details are only printed on the first line blamed
+/// on a commit, so they are remembered for the following ones.
+fn parse_blame_porcelain(output: &str) -> Vec<(String, String)> {
+    let mut authors: HashMap<String, String> = HashMap::new();
+    let mut result = Vec::new();
+    let mut current: Option<String> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            if let Some(hash) = current.take() {
+                let author = authors.get(&hash).cloned().unwrap_or_default();
+                result.push((hash, author));
+            }
+        } else if let Some(name) = line.strip_prefix("author ")
+            && let Some(hash) = &current
+        {
+            authors.insert(hash.clone(), name.to_string());
+        } else if current.is_none()
+            && let Some(hash) = line.split_whitespace().next()
+            && hash.len() == 40
+            && hash.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            current = Some(hash.to_string());
+        }
+    }
+    result
+}
+
+/// The commit that produced the version of `path` older than the one at
+/// `revision`, with the file's path there (renames are followed).
+pub fn previous_revision(
+    workdir: &Path,
+    revision: &str,
+    path: &str,
+) -> MagiResult<Option<(String, String)>> {
+    // The first commit is the one that made the shown version
+    let output = git_stdout(
+        workdir,
+        &[
+            "log",
+            "--follow",
+            "--format=commit %H",
+            "--name-status",
+            "-n",
+            "2",
+            revision,
+            "--",
+            path,
+        ],
+    )?;
+    let mut commits = Vec::new();
+    for line in output.lines() {
+        if let Some(hash) = line.strip_prefix("commit ") {
+            commits.push((hash, None));
+        } else if let Some((_, changed)) = commits.last_mut()
+            && let Some((_, paths)) = line.split_once('\t')
+        {
+            // Renames and copies list the old path first
+            *changed = paths.rsplit('\t').next();
+        }
+    }
+    Ok(match commits.get(1) {
+        Some((hash, Some(old_path))) => Some((hash.to_string(), old_path.to_string())),
+        _ => None,
+    })
+}
+
+/// The first commit after `revision` on the way to HEAD that changed `path`,
+/// with the file's path there (renames are followed). `None` when there is
+/// no newer version, including when the file is deleted on the way.
+pub fn next_revision(
+    workdir: &Path,
+    revision: &str,
+    path: &str,
+) -> MagiResult<Option<(String, String)>> {
+    let output = git_stdout(
+        workdir,
+        &[
+            "log",
+            "--reverse",
+            "--ancestry-path",
+            "--name-status",
+            "--format=commit %H",
+            &format!("{}..HEAD", revision),
+            "--",
+            path,
+        ],
+    )?;
+    let mut hash = "";
+    for line in output.lines() {
+        if let Some(commit) = line.strip_prefix("commit ") {
+            hash = commit;
+            continue;
+        }
+        match line.split_once('\t') {
+            // The pathspec hides where the file went, so a deletion is
+            // checked for a rename in that one commit
+            Some(("D", changed)) if changed == path => {
+                return Ok(
+                    renamed_in(workdir, hash, path)?.map(|new_path| (hash.to_string(), new_path))
+                );
+            }
+            Some((_, changed)) if changed == path => {
+                return Ok(Some((hash.to_string(), path.to_string())));
+            }
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+/// The path `path` is renamed to in `commit`, if it is.
+fn renamed_in(workdir: &Path, commit: &str, path: &str) -> MagiResult<Option<String>> {
+    let output = git_stdout(
+        workdir,
+        &[
+            "diff-tree",
+            "-r",
+            "--no-commit-id",
+            "--find-renames",
+            "--name-status",
+            commit,
+        ],
+    )?;
+    Ok(output.lines().find_map(
+        |line| match line.split('\t').collect::<Vec<_>>().as_slice() {
+            [status, old, new] if status.starts_with('R') && *old == path => Some(new.to_string()),
+            _ => None,
+        },
+    ))
+}
+
+/// Abbreviates `revision` to a short commit hash (`git rev-parse --short`).
+pub fn short_revision(workdir: &Path, revision: &str) -> MagiResult<String> {
+    Ok(git_stdout(
+        workdir,
+        &["rev-parse", "--short", &format!("{}^{{commit}}", revision)],
+    )?
+    .trim()
+    .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_repo::TestRepo;
+
+    fn rev_parse(test_repo: &TestRepo, revision: &str) -> String {
+        git_stdout(test_repo.repo_path(), &["rev-parse", revision])
+            .unwrap()
+            .trim()
+            .to_string()
+    }
+
+    fn contents(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .filter_map(|line| match &line.content {
+                LineContent::BlobLine { content, .. } => Some(content.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_blob_lines_shows_revision_contents() {
+        let test_repo = TestRepo::new();
+        test_repo
+            .commit_file("file.txt", "one\ntwo\n", "First")
+            .commit_file("file.txt", "three\n", "Second");
+
+        let lines = blob_lines(test_repo.repo_path(), "HEAD~1", "file.txt", false).unwrap();
+
+        assert_eq!(contents(&lines), vec!["one", "two"]);
+        assert!(matches!(
+            lines[1].content,
+            LineContent::BlobLine {
+                number: 2,
+                blame: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_blob_lines_with_blame_annotates_each_line() {
+        let test_repo = TestRepo::new();
+        test_repo
+            .commit_file("file.txt", "one\n", "First")
+            .commit_file("file.txt", "one\ntwo\n", "Second");
+        let first = rev_parse(&test_repo, "HEAD~1");
+        let second = test_repo.head_hash();
+
+        let lines = blob_lines(test_repo.repo_path(), "HEAD", "file.txt", true).unwrap();
+
+        let hashes: Vec<String> = lines
+            .iter()
+            .filter_map(|line| match &line.content {
+                LineContent::BlobLine { blame, .. } => blame.as_ref().map(|b| b.hash.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            hashes,
+            vec![first[..7].to_string(), second[..7].to_string()]
+        );
+        let LineContent::BlobLine {
+            blame: Some(info), ..
+        } = &lines[0].content
+        else {
+            panic!("Expected blame info");
+        };
+        assert!(!info.author.is_empty());
+        assert_eq!(info.date.len(), "2024-01-01".len());
+    }
+
+    #[test]
+    fn test_parse_blame_porcelain_reuses_commit_details() {
+        let hash = "a".repeat(40);
+        let output = format!(
+            "{hash} 1 1 2\nauthor Alice\nsummary First\nfilename f\n\tone\n{hash} 2 2\n\ttwo\n"
+        );
+
+        assert_eq!(
+            parse_blame_porcelain(&output),
+            vec![
+                (hash.clone(), "Alice".to_string()),
+                (hash, "Alice".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_previous_and_next_revision_step_through_history() {
+        let test_repo = TestRepo::new();
+        test_repo
+            .commit_file("file.txt", "one\n", "First")
+            .commit_file("other.txt", "other\n", "Unrelated")
+            .commit_file("file.txt", "two\n", "Second");
+        let first = rev_parse(&test_repo, "HEAD~2");
+        let second = test_repo.head_hash();
+        let workdir = test_repo.repo_path();
+
+        assert_eq!(
+            previous_revision(workdir, "HEAD", "file.txt").unwrap(),
+            Some((first.clone(), "file.txt".to_string()))
+        );
+        assert_eq!(
+            previous_revision(workdir, &first, "file.txt").unwrap(),
+            None
+        );
+        assert_eq!(
+            next_revision(workdir, &first, "file.txt").unwrap(),
+            Some((second, "file.txt".to_string()))
+        );
+        assert_eq!(next_revision(workdir, "HEAD", "file.txt").unwrap(), None);
+    }
+
+    #[test]
+    fn test_next_revision_follows_renames() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("old.txt", "one\ntwo\nthree\n", "First");
+        let first = test_repo.head_hash();
+        let workdir = test_repo.repo_path();
+        git_stdout(workdir, &["mv", "old.txt", "new.txt"]).unwrap();
+        git_stdout(workdir, &["commit", "-m", "Rename"]).unwrap();
+        let renamed = test_repo.head_hash();
+
+        assert_eq!(
+            next_revision(workdir, &first, "old.txt").unwrap(),
+            Some((renamed.clone(), "new.txt".to_string()))
+        );
+        assert_eq!(
+            previous_revision(workdir, &renamed, "new.txt").unwrap(),
+            Some((first, "old.txt".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_next_revision_stops_when_file_is_deleted() {
+        let test_repo = TestRepo::new();
+        test_repo
+            .commit_file("file.txt", "one\n", "First")
+            .commit_file("other.txt", "other\n", "Unrelated");
+        let workdir = test_repo.repo_path();
+        git_stdout(workdir, &["rm", "file.txt"]).unwrap();
+        git_stdout(workdir, &["commit", "-m", "Delete"]).unwrap();
+
+        assert_eq!(next_revision(workdir, "HEAD~1", "file.txt").unwrap(), None);
+    }
+
+    #[test]
+    fn test_files_at_revision_lists_tree() {
+        let test_repo = TestRepo::new();
+        test_repo
+            .commit_file("a.txt", "a\n", "First")
+            .commit_file("b.txt", "b\n", "Second");
+
+        assert_eq!(
+            files_at_revision(test_repo.repo_path(), "HEAD~1").unwrap(),
+            vec!["a.txt", "initial_file.txt"]
+        );
+    }
+}
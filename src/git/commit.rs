@@ -1,7 +1,12 @@
-use std::{path::Path, process::ExitStatus};
+use std::{
+    io::Write,
+    path::Path,
+    process::{ExitStatus, Stdio},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use super::git_cmd;
-use crate::errors::MagiResult;
+use crate::errors::{MagiError, MagiResult};
 
 /// Result of a commit operation
 pub struct CommitResult {
@@ -53,6 +58,76 @@ pub fn run_commit_with_editor<P: AsRef<Path>>(
     get_commit_result(repo_path, status, "Commit")
 }
 
+/// Runs `git commit -F -` (plus `--amend` when `amend` is set), feeding
+/// `message` on stdin instead of opening an editor. Output is captured, so a
+/// failing hook's message ends up in the result.
+pub fn run_commit_with_message<P: AsRef<Path>>(
+    repo_path: P,
+    message: &str,
+    amend: bool,
+    flags: Vec<String>,
+) -> MagiResult<CommitResult> {
+    let op = if amend { "Amend" } else { "Commit" };
+    let mut cmd = git_cmd(&repo_path, &["commit", "-F", "-"]);
+    if amend {
+        cmd.arg("--amend");
+    }
+    let mut child = cmd
+        .args(flags)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(message.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+
+    if output.status.success() {
+        return get_commit_result(repo_path, output.status, op);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let detail = if stderr.trim().is_empty() {
+        stdout.trim()
+    } else {
+        stderr.trim()
+    };
+    Ok(CommitResult {
+        success: false,
+        message: format!("{} failed: {}", op, detail),
+    })
+}
+
+/// Returns the full message (summary and body) of `HEAD`, or an empty
+/// string when there is no commit yet.
+pub fn head_commit_message<P: AsRef<Path>>(repo_path: P) -> String {
+    git_cmd(&repo_path, &["log", "-1", "--format=%B"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim_end().to_string())
+        .unwrap_or_default()
+}
+
+/// Returns the full messages of the last `limit` commits, most recent first.
+pub fn recent_commit_messages<P: AsRef<Path>>(repo_path: P, limit: usize) -> Vec<String> {
+    let count = format!("-n{}", limit);
+    git_cmd(&repo_path, &["log", &count, "--format=%B%x00"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .split('\0')
+                .map(str::trim)
+                .filter(|m| !m.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Lists authors from the commit history as `Name <email>` strings,
 /// deduplicated and ordered from most recent commit to oldest.
 pub fn list_authors<P: AsRef<Path>>(repo_path: P) -> MagiResult<Vec<String>> {
@@ -81,6 +156,110 @@ pub fn run_amend_commit_with_editor<P: AsRef<Path>>(
     get_commit_result(repo_path, status, "Amend")
 }
 
+/// Runs `git commit --amend --no-edit` to add the staged changes to HEAD
+/// without touching its message or dates: the committer date is pinned to
+/// HEAD's (the author date is kept by `--amend` anyway).
+pub fn run_extend_commit<P: AsRef<Path>>(
+    repo_path: P,
+    flags: Vec<String>,
+) -> MagiResult<CommitResult> {
+    let committer_date = head_date(&repo_path, "%cd")?;
+    let output = git_cmd(&repo_path, &["commit", "--amend", "--no-edit"])
+        .args(flags)
+        .env("GIT_COMMITTER_DATE", committer_date)
+        .output()?;
+
+    get_commit_result(repo_path, output.status, "Extend")
+}
+
+/// Moves HEAD to another point in time: rewrites both its author and
+/// committer date to `date` without changing its content or message.
+/// `date` is an exact date (e.g. `2024-05-01 12:00`, with or without an
+/// offset) or one relative to now: `now`, `yesterday` or `<n> <unit> ago`.
+///
+/// Both dates are set by a single amend, which is also where git parses an
+/// exact date, keeping its offset.
+pub fn run_reshelve_commit<P: AsRef<Path>>(
+    repo_path: P,
+    date: &str,
+    flags: Vec<String>,
+) -> MagiResult<CommitResult> {
+    let date = resolve_date(date, SystemTime::now());
+    let output = git_cmd(
+        &repo_path,
+        &["commit", "--amend", "--only", "--allow-empty", "--no-edit"],
+    )
+    .arg(format!("--date={}", date))
+    .args(flags)
+    .env("GIT_COMMITTER_DATE", &date)
+    .output()?;
+
+    if String::from_utf8_lossy(&output.stderr).contains("invalid date format") {
+        return Ok(CommitResult {
+            success: false,
+            message: format!("Reshelve failed: invalid date '{}'", date),
+        });
+    }
+    get_commit_result(repo_path, output.status, "Reshelve")
+}
+
+/// Resolves a date relative to `now` (`now`, `yesterday`, `<n> <unit> ago`
+/// with a unit from seconds to weeks) to the exact `@<seconds>` form, which
+/// git gives the local offset. Other dates are returned as they are.
+fn resolve_date(date: &str, now: SystemTime) -> String {
+    let now = now
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let date = date.trim();
+    let ago = match date.to_lowercase().as_str() {
+        "now" => Some(0),
+        "yesterday" => Some(24 * 60 * 60),
+        relative => relative
+            .strip_suffix(" ago")
+            .and_then(|amount| amount.split_once(' '))
+            .and_then(|(count, unit)| {
+                let seconds = match unit.trim_end_matches('s') {
+                    "second" => 1,
+                    "minute" => 60,
+                    "hour" => 60 * 60,
+                    "day" => 24 * 60 * 60,
+                    "week" => 7 * 24 * 60 * 60,
+                    _ => return None,
+                };
+                count.parse::<u64>().ok().map(|count| count * seconds)
+            }),
+    };
+    match ago {
+        Some(ago) => format!("@{}", now.saturating_sub(ago)),
+        None => date.to_string(),
+    }
+}
+
+/// Returns a date of HEAD (`%ad` or `%cd`) in the `@<seconds> <offset>`
+/// form that `GIT_COMMITTER_DATE` accepts.
+fn head_date<P: AsRef<Path>>(repo_path: P, placeholder: &str) -> MagiResult<String> {
+    let output = git_cmd(
+        &repo_path,
+        &[
+            "log",
+            "-1",
+            "--date=raw",
+            &format!("--format={}", placeholder),
+        ],
+    )
+    .output()?;
+    if !output.status.success() {
+        return Err(MagiError::Generic(format!(
+            "Failed to read HEAD's date: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(format!(
+        "@{}",
+        String::from_utf8_lossy(&output.stdout).trim()
+    ))
+}
+
 /// Runs `git commit --fixup=<commit_hash> --no-edit` to create a fixup commit.
 pub fn run_fixup_commit<P: AsRef<Path>>(
     repo_path: P,
@@ -171,13 +350,40 @@ mod tests {
     /// Helper to get log entries for testing (filters out graph-only entries)
     fn get_log_entries_for_test(test_repo: &TestRepo) -> Vec<crate::model::LogEntry> {
         let repo = git2::Repository::open(test_repo.repo_path()).unwrap();
-        let mut entries =
-            super::super::log::get_log_entries(&repo, &crate::msg::LogType::Current, true, false)
-                .unwrap();
+        let mut entries = super::super::log::get_log_entries(
+            &repo,
+            &crate::msg::LogType::Current,
+            true,
+            false,
+            false,
+        )
+        .unwrap();
         entries.retain(|e| e.is_commit());
         entries
     }
 
+    #[test]
+    fn test_resolve_date_relative_to_now() {
+        let now = UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+
+        assert_eq!(resolve_date("now", now), "@1000000");
+        assert_eq!(resolve_date("Yesterday", now), "@913600");
+        assert_eq!(resolve_date("3 days ago", now), "@740800");
+        assert_eq!(resolve_date("1 hour ago", now), "@996400");
+        assert_eq!(resolve_date("2 weeks ago", now), "@0");
+    }
+
+    #[test]
+    fn test_resolve_date_keeps_exact_dates() {
+        let now = SystemTime::now();
+
+        assert_eq!(
+            resolve_date("2021-06-01 12:00:00 +0200", now),
+            "2021-06-01 12:00:00 +0200"
+        );
+        assert_eq!(resolve_date("3 fortnights ago", now), "3 fortnights ago");
+    }
+
     #[test]
     fn test_run_fixup_commit() {
         let test_repo = TestRepo::new();
@@ -260,6 +466,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_run_commit_with_message() {
+        let test_repo = TestRepo::new();
+        test_repo
+            .write_file_content("file1.txt", "content1")
+            .stage_files(&["file1.txt"]);
+
+        let result = run_commit_with_message(
+            test_repo.repo_path(),
+            "Add file1\n\nWith a body",
+            false,
+            vec![],
+        )
+        .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.message, "Commit: Add file1");
+        assert_eq!(
+            head_commit_message(test_repo.repo_path()),
+            "Add file1\n\nWith a body"
+        );
+    }
+
+    #[test]
+    fn test_run_commit_with_message_amend() {
+        let test_repo = TestRepo::new();
+        test_repo
+            .write_file_content("file1.txt", "content1")
+            .stage_files(&["file1.txt"])
+            .commit("First commit");
+
+        let result =
+            run_commit_with_message(test_repo.repo_path(), "Reworded", true, vec![]).unwrap();
+
+        assert!(result.success);
+        assert_eq!(head_commit_message(test_repo.repo_path()), "Reworded");
+        assert_eq!(
+            recent_commit_messages(test_repo.repo_path(), 1),
+            vec!["Reworded".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_run_commit_with_message_reports_failure() {
+        let test_repo = TestRepo::new();
+
+        let result =
+            run_commit_with_message(test_repo.repo_path(), "Nothing", false, vec![]).unwrap();
+
+        assert!(!result.success);
+        assert!(result.message.starts_with("Commit failed"));
+    }
+
     #[test]
     fn test_run_squash_commit_without_staged_changes() {
         let test_repo = TestRepo::new();
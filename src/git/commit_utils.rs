@@ -9,6 +9,9 @@ use crate::{
 
 use super::{CommitInfo, CommitRef, CommitRefType};
 
+/// The most commits listed in a section of commits relative to a remote branch
+const MAX_RANGE_COMMITS: usize = 10;
+
 /// Build a map of commit OID -> tag name
 pub fn build_tag_map(repository: &Repository) -> MagiResult<HashMap<git2::Oid, String>> {
     let mut tag_map = HashMap::new();
@@ -268,6 +271,71 @@ pub fn create_commit_line(
     }
 }
 
+/// Returns the checked out branch's name and commit, or None on a detached
+/// HEAD or before the first commit
+pub fn head_branch(repository: &Repository) -> Option<(String, git2::Oid)> {
+    let head = repository.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    let commit = head.peel_to_commit().ok()?;
+    Some((head.shorthand().ok()?.to_string(), commit.id()))
+}
+
+/// Returns the lines of a section listing the commits reachable from `tip`
+/// but not from `hide`, below a header reading `prefix` followed by
+/// `remote_name`. The header counts all of them, though at most
+/// `MAX_RANGE_COMMITS` are listed. Returns no lines when there are none.
+pub fn commit_range_lines(
+    repository: &Repository,
+    tip: git2::Oid,
+    hide: git2::Oid,
+    prefix: &str,
+    remote_name: &str,
+    section: SectionType,
+) -> MagiResult<Vec<Line>> {
+    let mut lines = Vec::new();
+
+    let mut revwalk = repository.revwalk()?;
+    revwalk.push(tip)?;
+    revwalk.hide(hide)?;
+
+    let commit_oids: Vec<_> = revwalk
+        .take(MAX_RANGE_COMMITS)
+        .filter_map(|oid| oid.ok())
+        .collect();
+
+    if commit_oids.is_empty() {
+        return Ok(lines);
+    }
+
+    // Build maps for tags and branches
+    let tag_map = build_tag_map(repository)?;
+    let local_branch_map = build_local_branch_map(repository)?;
+    let remote_branch_map = build_remote_branch_map(repository)?;
+
+    lines.push(Line {
+        content: LineContent::RemoteSectionHeader {
+            prefix: prefix.to_string(),
+            remote_name: remote_name.to_string(),
+            count: repository.graph_ahead_behind(tip, hide)?.0,
+        },
+        section: Some(section.clone()),
+    });
+
+    for oid in &commit_oids {
+        let commit = match repository.find_commit(*oid) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+
+        let refs = build_refs_for_commit(oid, &local_branch_map, &remote_branch_map, &tag_map);
+        lines.push(create_commit_line(&commit, refs, section.clone()));
+    }
+
+    Ok(lines)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,4 @@
-use git2::Repository;
+use git2::{Oid, Repository};
 
 use crate::errors::MagiResult;
 
@@ -46,6 +46,31 @@ pub fn get_push_remote(repo: &Repository, branch: &str) -> Option<String> {
     None
 }
 
+/// Gets the upstream of the given branch as its short name and commit,
+/// e.g. `origin/main`.
+/// Returns None if no upstream is configured or it doesn't exist.
+pub fn get_upstream_branch(repo: &Repository, branch: &str) -> Option<(String, Oid)> {
+    let upstream_name = repo
+        .branch_upstream_name(&format!("refs/heads/{}", branch))
+        .ok()?;
+    let upstream_ref = repo.find_reference(upstream_name.as_str().ok()?).ok()?;
+    let commit = upstream_ref.peel_to_commit().ok()?;
+    Some((upstream_ref.shorthand().ok()?.to_string(), commit.id()))
+}
+
+/// Gets the branch the given branch is pushed to as its short name and
+/// commit, e.g. `fork/feature`.
+/// Returns None if no push remote is configured or the branch hasn't been
+/// pushed there.
+pub fn get_push_branch(repo: &Repository, branch: &str) -> Option<(String, Oid)> {
+    let push_remote = get_push_remote(repo, branch)?;
+    let push_ref = repo
+        .find_reference(&format!("refs/remotes/{}/{}", push_remote, branch))
+        .ok()?;
+    let commit = push_ref.peel_to_commit().ok()?;
+    Some((push_ref.shorthand().ok()?.to_string(), commit.id()))
+}
+
 /// Gets the push remote, if set.
 /// Otherwise the upstream remote, if set.
 /// Otherwise "origin"
@@ -0,0 +1,361 @@
+use std::{fs, path::Path};
+
+use super::git_cmd;
+use crate::errors::{MagiError, MagiResult};
+
+/// Which part of a conflict region is kept (like smerge's keep-upper,
+/// keep-lower, keep-all and keep-base).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictSide {
+    /// The `<<<<<<<` side (HEAD)
+    Ours,
+    /// The `>>>>>>>` side (the commit being merged or replayed)
+    Theirs,
+    /// Ours followed by theirs
+    Both,
+    /// The `|||||||` section (only with `merge.conflictStyle=diff3`)
+    Base,
+}
+
+/// How a whole conflicted file is checked out from the index stages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictCheckout {
+    /// `git checkout --ours`
+    Ours,
+    /// `git checkout --theirs`
+    Theirs,
+    /// `git checkout --merge`: recreate the conflict markers
+    Merge,
+}
+
+/// A conflict region of a file, as 0-based line indices of its markers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ConflictRegion {
+    start: usize,
+    base: Option<usize>,
+    separator: usize,
+    end: usize,
+}
+
+fn is_marker(line: &str, marker: &str) -> bool {
+    line.starts_with(marker)
+        && line[marker.len()..]
+            .chars()
+            .next()
+            .is_none_or(|c| c.is_whitespace())
+}
+
+/// Finds the conflict regions of a file, in order. A region missing its
+/// separator or closing marker is ignored.
+fn conflict_regions(lines: &[&str]) -> Vec<ConflictRegion> {
+    let mut regions = Vec::new();
+    let mut start = None;
+    let mut base = None;
+    let mut separator = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        if is_marker(line, "<<<<<<<") {
+            start = Some(index);
+            base = None;
+            separator = None;
+        } else if start.is_some() && separator.is_none() && is_marker(line, "|||||||") {
+            base = Some(index);
+        } else if start.is_some() && is_marker(line, "=======") {
+            separator = Some(index);
+        } else if is_marker(line, ">>>>>>>")
+            && let (Some(start), Some(separator)) = (start.take(), separator.take())
+        {
+            regions.push(ConflictRegion {
+                start,
+                base: base.take(),
+                separator,
+                end: index,
+            });
+        }
+    }
+
+    regions
+}
+
+/// Returns true if `content` still contains a complete conflict region.
+pub fn has_conflict_markers(content: &str) -> bool {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    !conflict_regions(&lines).is_empty()
+}
+
+/// Rewrites the `index`-th conflict region of `content` (0-based) to the
+/// lines of `side`.
+fn resolve_region(content: &str, index: usize, side: ConflictSide) -> MagiResult<String> {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let regions = conflict_regions(&lines);
+    let Some(region) = regions.get(index) else {
+        return Err(MagiError::Generic(
+            "No conflict at this position".to_string(),
+        ));
+    };
+
+    let ours_end = region.base.unwrap_or(region.separator);
+    let ours = &lines[region.start + 1..ours_end];
+    let theirs = &lines[region.separator + 1..region.end];
+    let kept: Vec<&str> = match side {
+        ConflictSide::Ours => ours.to_vec(),
+        ConflictSide::Theirs => theirs.to_vec(),
+        ConflictSide::Both => ours.iter().chain(theirs).copied().collect(),
+        ConflictSide::Base => match region.base {
+            Some(base) => lines[base + 1..region.separator].to_vec(),
+            None => {
+                return Err(MagiError::Generic(
+                    "This conflict has no base version (set merge.conflictStyle to diff3)"
+                        .to_string(),
+                ));
+            }
+        },
+    };
+
+    let mut result = String::with_capacity(content.len());
+    lines[..region.start]
+        .iter()
+        .for_each(|l| result.push_str(l));
+    kept.iter().for_each(|l| result.push_str(l));
+    lines[region.end + 1..]
+        .iter()
+        .for_each(|l| result.push_str(l));
+
+    // The closing marker may have been the last line without a newline
+    if !lines[region.end].ends_with('\n') && result.ends_with('\n') {
+        result.pop();
+    }
+    Ok(result)
+}
+
+/// Keeps `side` of the `index`-th conflict region (0-based) of `path`,
+/// rewriting the working file. Once no conflict markers remain the file is
+/// staged, marking it resolved. Returns whether it was staged.
+pub fn resolve_conflict(
+    workdir: &Path,
+    path: &str,
+    index: usize,
+    side: ConflictSide,
+) -> MagiResult<bool> {
+    let file = workdir.join(path);
+    let content = fs::read_to_string(&file)?;
+    let resolved = resolve_region(&content, index, side)?;
+    fs::write(&file, &resolved)?;
+
+    if has_conflict_markers(&resolved) {
+        return Ok(false);
+    }
+    stage_resolved(workdir, path)?;
+    Ok(true)
+}
+
+/// Checks out `path` from the side given by `checkout`. Taking ours or
+/// theirs resolves the file, so it is staged; `Merge` brings the conflict
+/// markers back (also after the file was staged).
+pub fn checkout_conflict(workdir: &Path, path: &str, checkout: ConflictCheckout) -> MagiResult<()> {
+    let flag = match checkout {
+        ConflictCheckout::Ours => "--ours",
+        ConflictCheckout::Theirs => "--theirs",
+        ConflictCheckout::Merge => "--merge",
+    };
+    let output = git_cmd(workdir, &["checkout", flag, "--", path]).output()?;
+    if !output.status.success() {
+        return Err(MagiError::Generic(format!(
+            "Failed to checkout {} {}: {}",
+            flag,
+            path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    if checkout != ConflictCheckout::Merge {
+        stage_resolved(workdir, path)?;
+    }
+    Ok(())
+}
+
+/// Lists the paths that still have unmerged index entries.
+pub fn unmerged_paths(workdir: &Path) -> MagiResult<Vec<String>> {
+    let output = git_cmd(workdir, &["diff", "--name-only", "--diff-filter=U"]).output()?;
+    if !output.status.success() {
+        return Err(MagiError::Generic(format!(
+            "Failed to list unmerged files: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Runs `git mergetool` for `paths` (every unmerged file when empty) with
+/// the user's configured merge tool. The caller must ensure the TUI is
+/// suspended (via `RunningState::LaunchExternalCommand`) before calling this.
+/// Returns the requested paths that remain unresolved afterwards.
+pub fn run_mergetool(workdir: &Path, paths: &[String]) -> MagiResult<Vec<String>> {
+    // The exit status only tells that some file was left unresolved, which
+    // is reported from the index below
+    git_cmd(workdir, &["mergetool", "--"])
+        .args(paths)
+        .status()?;
+
+    let remaining = unmerged_paths(workdir)?;
+    if paths.is_empty() {
+        return Ok(remaining);
+    }
+    Ok(remaining
+        .into_iter()
+        .filter(|path| paths.contains(path))
+        .collect())
+}
+
+fn stage_resolved(workdir: &Path, path: &str) -> MagiResult<()> {
+    let output = git_cmd(workdir, &["add", "--", path]).output()?;
+    if !output.status.success() {
+        return Err(MagiError::Generic(format!(
+            "Failed to stage {}: {}",
+            path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_repo::TestRepo;
+
+    const TWO_CONFLICTS: &str = "\
+top
+<<<<<<< HEAD
+ours one
+=======
+theirs one
+>>>>>>> other
+middle
+<<<<<<< HEAD
+ours two
+||||||| base
+base two
+=======
+theirs two
+>>>>>>> other
+bottom
+";
+
+    #[test]
+    fn test_conflict_regions_finds_markers() {
+        let lines: Vec<&str> = TWO_CONFLICTS.split_inclusive('\n').collect();
+        assert_eq!(
+            conflict_regions(&lines),
+            vec![
+                ConflictRegion {
+                    start: 1,
+                    base: None,
+                    separator: 3,
+                    end: 5,
+                },
+                ConflictRegion {
+                    start: 7,
+                    base: Some(9),
+                    separator: 11,
+                    end: 13,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_region_keeps_each_side() {
+        let ours = resolve_region(TWO_CONFLICTS, 0, ConflictSide::Ours).unwrap();
+        assert!(ours.starts_with("top\nours one\nmiddle\n<<<<<<< HEAD\n"));
+
+        let theirs = resolve_region(TWO_CONFLICTS, 1, ConflictSide::Theirs).unwrap();
+        assert!(theirs.ends_with(">>>>>>> other\nmiddle\ntheirs two\nbottom\n"));
+
+        let both = resolve_region(TWO_CONFLICTS, 0, ConflictSide::Both).unwrap();
+        assert!(both.starts_with("top\nours one\ntheirs one\nmiddle\n"));
+
+        let base = resolve_region(TWO_CONFLICTS, 1, ConflictSide::Base).unwrap();
+        assert!(base.ends_with("middle\nbase two\nbottom\n"));
+    }
+
+    #[test]
+    fn test_resolve_region_base_requires_diff3() {
+        assert!(resolve_region(TWO_CONFLICTS, 0, ConflictSide::Base).is_err());
+        assert!(resolve_region(TWO_CONFLICTS, 2, ConflictSide::Ours).is_err());
+    }
+
+    #[test]
+    fn test_resolve_region_without_trailing_newline() {
+        let content = "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> other";
+        assert_eq!(
+            resolve_region(content, 0, ConflictSide::Theirs).unwrap(),
+            "theirs"
+        );
+    }
+
+    #[test]
+    fn test_resolve_conflict_stages_file_when_no_markers_remain() {
+        let test_repo = TestRepo::new();
+        test_repo.create_merge_conflict("conflict.txt");
+        let workdir = test_repo.repo_path();
+
+        let staged = resolve_conflict(workdir, "conflict.txt", 0, ConflictSide::Theirs).unwrap();
+
+        assert!(staged);
+        assert_eq!(
+            fs::read_to_string(workdir.join("conflict.txt")).unwrap(),
+            "other content\n"
+        );
+        let index = test_repo.repo.index().unwrap();
+        assert!(!index.has_conflicts());
+    }
+
+    #[test]
+    fn test_checkout_conflict_ours_then_merge_restores_markers() {
+        let test_repo = TestRepo::new();
+        test_repo.create_merge_conflict("conflict.txt");
+        let workdir = test_repo.repo_path();
+
+        checkout_conflict(workdir, "conflict.txt", ConflictCheckout::Ours).unwrap();
+        assert_eq!(
+            fs::read_to_string(workdir.join("conflict.txt")).unwrap(),
+            "main content\n"
+        );
+        assert!(!test_repo.repo.index().unwrap().has_conflicts());
+
+        checkout_conflict(workdir, "conflict.txt", ConflictCheckout::Merge).unwrap();
+        let content = fs::read_to_string(workdir.join("conflict.txt")).unwrap();
+        assert!(has_conflict_markers(&content));
+    }
+
+    #[test]
+    fn test_run_mergetool_resolves_file() {
+        let test_repo = TestRepo::new();
+        test_repo.create_merge_conflict("conflict.txt");
+        test_repo.with_mergetool("cp \"$REMOTE\" \"$MERGED\"");
+        let workdir = test_repo.repo_path();
+
+        let remaining = run_mergetool(workdir, &["conflict.txt".to_string()]).unwrap();
+
+        assert!(remaining.is_empty());
+        assert_eq!(
+            fs::read_to_string(workdir.join("conflict.txt")).unwrap(),
+            "other content\n"
+        );
+    }
+
+    #[test]
+    fn test_run_mergetool_reports_unresolved_files() {
+        let test_repo = TestRepo::new();
+        test_repo.create_merge_conflict("conflict.txt");
+        test_repo.with_mergetool("false");
+
+        let remaining = run_mergetool(test_repo.repo_path(), &[]).unwrap();
+
+        assert_eq!(remaining, vec!["conflict.txt".to_string()]);
+    }
+}
@@ -1,16 +1,31 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
 use crate::{
     errors::MagiResult,
+    git::{
+        refine::{Change, refine_hunk},
+        stage::{StagedRename, parse_hunk_header_starts},
+    },
     model::{
-        DiffHunk, DiffLine, DiffLineType, FileChange, FileStatus, Line, LineContent, SectionType,
+        DiffHunk, DiffLine, DiffLineType, FileChange, FileDetail, FileStatus, Line, LineContent,
+        SectionType,
     },
 };
-use git2::Diff;
-
-/// A collection of file changes with their associated hunks and diff lines
-pub type FileChangesWithDiffs = Vec<(FileChange, Vec<(DiffHunk, Vec<DiffLine>)>)>;
-
-/// Collects file changes with their associated hunks and diff lines from a git diff
-pub fn collect_file_changes(diff: &Diff) -> MagiResult<FileChangesWithDiffs> {
+use git2::{Diff, DiffDelta, FileMode, Repository};
+
+/// A collection of file changes with the details their hunks don't show
+/// and their associated hunks and diff lines
+pub type FileChangesWithDiffs = Vec<(FileChange, Vec<FileDetail>, Vec<(DiffHunk, Vec<DiffLine>)>)>;
+
+/// Collects file changes with their associated hunks and diff lines from a git diff.
+/// The similarity of a renamed file is taken from its staged rename in `renames`.
+pub fn collect_file_changes(
+    diff: &Diff,
+    renames: &HashMap<String, StagedRename>,
+) -> MagiResult<FileChangesWithDiffs> {
     let mut result: FileChangesWithDiffs = Vec::new();
 
     diff.print(git2::DiffFormat::Patch, |delta, hunk, line| {
@@ -27,31 +42,39 @@ pub fn collect_file_changes(diff: &Diff) -> MagiResult<FileChangesWithDiffs> {
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_else(|| "<unknown>".to_string());
 
-        let status = match delta.status() {
-            git2::Delta::Modified => FileStatus::Modified,
-            git2::Delta::Deleted => FileStatus::Deleted,
-            git2::Delta::Added => FileStatus::New,
-            git2::Delta::Renamed => FileStatus::Renamed,
-            git2::Delta::Copied => FileStatus::Copied,
-            git2::Delta::Typechange => FileStatus::TypeChange,
-            _ => FileStatus::Modified,
-        };
-
         // Find or create the file entry
         let file_idx = result
             .iter()
-            .position(|(fc, _)| fc.path == file_path)
+            .position(|(fc, _, _)| fc.path == file_path)
             .unwrap_or_else(|| {
                 result.push((
                     FileChange {
                         path: file_path.clone(),
-                        status,
+                        status: file_status(&delta, renames),
                     },
+                    file_details(&delta),
                     Vec::new(),
                 ));
                 result.len() - 1
             });
 
+        // libgit2 emits a "Binary files differ" line in place of hunks
+        if line.origin() == 'B' {
+            let binary = FileDetail::Binary {
+                old_size: delta.old_file().size(),
+                new_size: delta.new_file().size(),
+            };
+            if !result[file_idx].1.contains(&binary) {
+                result[file_idx].1.push(binary);
+            }
+            return true;
+        }
+
+        // A submodule's "Subproject commit" hunk is shown as its detail
+        if is_submodule(&delta) {
+            return true;
+        }
+
         // Handle hunk header
         if let Some(hunk_info) = hunk {
             let header = String::from_utf8_lossy(hunk_info.header())
@@ -59,12 +82,12 @@ pub fn collect_file_changes(diff: &Diff) -> MagiResult<FileChangesWithDiffs> {
                 .to_string();
 
             // Check if this hunk already exists for this file
-            let hunk_exists = result[file_idx].1.iter().any(|(h, _)| h.header == header);
+            let hunk_exists = result[file_idx].2.iter().any(|(h, _)| h.header == header);
 
             if !hunk_exists {
-                let hunk_index = result[file_idx].1.len();
+                let hunk_index = result[file_idx].2.len();
                 result[file_idx]
-                    .1
+                    .2
                     .push((DiffHunk { header, hunk_index }, Vec::new()));
             }
         }
@@ -81,20 +104,120 @@ pub fn collect_file_changes(diff: &Diff) -> MagiResult<FileChangesWithDiffs> {
         };
 
         if let Some(lt) = line_type
-            && let Some((_, diff_lines)) = result[file_idx].1.last_mut()
+            && let Some((_, diff_lines)) = result[file_idx].2.last_mut()
         {
             diff_lines.push(DiffLine {
                 content,
                 line_type: lt,
+                refined: Vec::new(),
             });
         }
 
         true
     })?;
 
+    for (_, _, hunks) in &mut result {
+        for (_, diff_lines) in hunks {
+            refine_diff_lines(diff_lines);
+        }
+    }
+
     Ok(result)
 }
 
+fn file_status(delta: &DiffDelta, renames: &HashMap<String, StagedRename>) -> FileStatus {
+    let from = || {
+        delta
+            .old_file()
+            .path()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+    // git2 doesn't expose the similarity index `find_similar` gave the file,
+    // and the renames are the ones git found anyway
+    let similarity = || {
+        delta
+            .new_file()
+            .path()
+            .and_then(|p| renames.get(&*p.to_string_lossy()))
+            .map_or(0, |rename| rename.similarity)
+    };
+    match delta.status() {
+        git2::Delta::Modified => FileStatus::Modified,
+        git2::Delta::Deleted => FileStatus::Deleted,
+        git2::Delta::Added => FileStatus::New,
+        git2::Delta::Renamed => FileStatus::Renamed {
+            from: from(),
+            similarity: similarity(),
+        },
+        git2::Delta::Copied => FileStatus::Copied {
+            from: from(),
+            similarity: similarity(),
+        },
+        git2::Delta::Typechange => FileStatus::TypeChange,
+        _ => FileStatus::Modified,
+    }
+}
+
+fn is_submodule(delta: &DiffDelta) -> bool {
+    delta.old_file().mode() == FileMode::Commit || delta.new_file().mode() == FileMode::Commit
+}
+
+/// The mode change and submodule commits of a file, which have no hunk
+/// lines. Binary files are detected while printing the diff.
+fn file_details(delta: &DiffDelta) -> Vec<FileDetail> {
+    let (old_file, new_file) = (delta.old_file(), delta.new_file());
+    if is_submodule(delta) {
+        let short_id = |id: git2::Oid| id.to_string()[..7].to_string();
+        return vec![FileDetail::Submodule {
+            old_id: short_id(old_file.id()),
+            new_id: short_id(new_file.id()),
+        }];
+    }
+    if old_file.exists() && new_file.exists() && old_file.mode() != new_file.mode() {
+        return vec![FileDetail::ModeChange {
+            old_mode: old_file.mode().into(),
+            new_mode: new_file.mode().into(),
+        }];
+    }
+    Vec::new()
+}
+
+/// The paths of the files added with intent to add (`git add -N`). Their
+/// index entries have no content yet: they are unstaged new files, not
+/// staged empty ones.
+pub fn intent_to_add_paths(repository: &Repository) -> MagiResult<HashSet<String>> {
+    Ok(repository
+        .index()?
+        .iter()
+        .filter(|entry| {
+            git2::IndexEntryExtendedFlag::from_bits_truncate(entry.flags_extended)
+                .is_intent_to_add()
+        })
+        .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+        .collect())
+}
+
+/// Marks the words that changed between the paired removed and added lines
+/// of a hunk.
+fn refine_diff_lines(diff_lines: &mut [DiffLine]) {
+    let changes: Vec<(Change, &str)> = diff_lines
+        .iter()
+        .map(|line| {
+            let change = match line.line_type {
+                DiffLineType::Deletion => Change::Removed,
+                DiffLineType::Addition => Change::Added,
+                _ => Change::Other,
+            };
+            (change, line.content.as_str())
+        })
+        .collect();
+    let refined = refine_hunk(&changes);
+    for (line, refined) in diff_lines.iter_mut().zip(refined) {
+        line.refined = refined;
+    }
+}
+
 /// Converts file changes into lines for display.
 ///
 /// This function takes file changes and closures that create the appropriate
@@ -129,7 +252,7 @@ where
     });
 
     // Add each file with its diff
-    for (file_change, hunks) in file_changes {
+    for (file_change, details, hunks) in file_changes {
         let file_path = file_change.path.clone();
 
         // Add file line
@@ -138,6 +261,14 @@ where
             section: Some(make_file_section(file_path.clone())),
         });
 
+        // Add the details, hidden with the hunks when the file is collapsed
+        for detail in details {
+            lines.push(Line {
+                content: LineContent::FileDetail(detail),
+                section: Some(make_file_section(file_path.clone())),
+            });
+        }
+
         // Add hunks and diff lines
         for (hunk_index, (hunk, diff_lines)) in hunks.into_iter().enumerate() {
             let hunk_section = make_hunk_section(file_path.clone(), hunk_index);
@@ -161,10 +292,117 @@ where
     lines
 }
 
+/// Splits the hunks whose section is in `split` at the unchanged lines
+/// between their blocks of changes, like the `s` of `git add -p`. Each part
+/// gets a `DiffHunk` header with its own line numbers, starting with the
+/// context before its changes. The lines keep the section of the hunk they
+/// came from, so a part is staged as the hunk's lines in it.
+pub fn split_hunks(lines: Vec<Line>, split: &HashSet<SectionType>) -> Vec<Line> {
+    let mut result = Vec::with_capacity(lines.len());
+    let mut lines = lines.into_iter().peekable();
+
+    while let Some(line) = lines.next() {
+        let (LineContent::DiffHunk(hunk), Some(section)) = (&line.content, &line.section) else {
+            result.push(line);
+            continue;
+        };
+        if !split.contains(section) {
+            result.push(line);
+            continue;
+        }
+        let Ok((old_start, new_start)) = parse_hunk_header_starts(&hunk.header) else {
+            result.push(line);
+            continue;
+        };
+
+        let mut diff_lines = Vec::new();
+        while let Some(next) = lines.next_if(|next| {
+            next.section == line.section && matches!(next.content, LineContent::DiffLine(_))
+        }) {
+            diff_lines.push(next);
+        }
+
+        let parts = hunk_parts(&diff_lines);
+        if parts.len() < 2 {
+            result.push(line);
+            result.extend(diff_lines);
+            continue;
+        }
+
+        let (mut old_line, mut new_line) = (old_start, new_start);
+        for part in parts {
+            let (old_count, new_count) = side_counts(&diff_lines[part.clone()]);
+            result.push(Line {
+                content: LineContent::DiffHunk(DiffHunk {
+                    header: format!(
+                        "@@ -{},{} +{},{} @@",
+                        old_line, old_count, new_line, new_count
+                    ),
+                    hunk_index: hunk.hunk_index,
+                }),
+                section: line.section.clone(),
+            });
+            old_line += old_count;
+            new_line += new_count;
+            result.extend(diff_lines[part].iter().cloned());
+        }
+    }
+    result
+}
+
+/// The ranges of the parts of a hunk: each starts at the unchanged lines
+/// before a block of changes, the last one keeps the trailing context.
+fn hunk_parts(diff_lines: &[Line]) -> Vec<Range<usize>> {
+    let is_change = |line: &Line| {
+        matches!(
+            &line.content,
+            LineContent::DiffLine(DiffLine {
+                line_type: DiffLineType::Addition | DiffLineType::Deletion,
+                ..
+            })
+        )
+    };
+
+    let mut starts = vec![0];
+    for index in 1..diff_lines.len() {
+        if !is_change(&diff_lines[index])
+            && is_change(&diff_lines[index - 1])
+            && diff_lines[index..].iter().any(is_change)
+        {
+            starts.push(index);
+        }
+    }
+
+    let mut ends: Vec<usize> = starts[1..].to_vec();
+    ends.push(diff_lines.len());
+    starts
+        .into_iter()
+        .zip(ends)
+        .map(|(start, end)| start..end)
+        .collect()
+}
+
+/// Line counts of the old and new side of diff lines.
+fn side_counts(diff_lines: &[Line]) -> (usize, usize) {
+    diff_lines
+        .iter()
+        .fold((0, 0), |(old, new), line| match &line.content {
+            LineContent::DiffLine(DiffLine {
+                line_type: DiffLineType::Addition,
+                ..
+            }) => (old, new + 1),
+            LineContent::DiffLine(DiffLine {
+                line_type: DiffLineType::Deletion,
+                ..
+            }) => (old + 1, new),
+            _ => (old + 1, new + 1),
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::git::test_repo::TestRepo;
+    use crate::git::{stage::staged_renames, test_repo::TestRepo};
     use git2::DiffOptions;
 
     fn open_repo(test_repo: &TestRepo) -> git2::Repository {
@@ -176,14 +414,14 @@ mod tests {
         let mut opts = DiffOptions::new();
         opts.include_untracked(false);
         let diff = repo.diff_index_to_workdir(None, Some(&mut opts)).unwrap();
-        collect_file_changes(&diff).unwrap()
+        collect_file_changes(&diff, &HashMap::new()).unwrap()
     }
 
     /// Collects file changes from the HEAD-to-index (staged) diff.
     fn staged_changes(repo: &git2::Repository) -> FileChangesWithDiffs {
         let head = repo.head().unwrap().peel_to_tree().unwrap();
         let diff = repo.diff_tree_to_index(Some(&head), None, None).unwrap();
-        collect_file_changes(&diff).unwrap()
+        collect_file_changes(&diff, &HashMap::new()).unwrap()
     }
 
     /// Maps diff lines to (content, line_type) pairs for easy comparison.
@@ -213,7 +451,7 @@ mod tests {
         let changes = unstaged_changes(&repo);
 
         assert_eq!(changes.len(), 1);
-        let (file_change, hunks) = &changes[0];
+        let (file_change, _, hunks) = &changes[0];
         assert_eq!(file_change.path, "file.txt");
         assert_eq!(file_change.status, FileStatus::Modified);
 
@@ -244,7 +482,7 @@ mod tests {
         let changes = staged_changes(&repo);
 
         assert_eq!(changes.len(), 1);
-        let (file_change, hunks) = &changes[0];
+        let (file_change, _, hunks) = &changes[0];
         assert_eq!(file_change.path, "new.txt");
         assert_eq!(file_change.status, FileStatus::New);
 
@@ -273,7 +511,7 @@ mod tests {
         let changes = staged_changes(&repo);
 
         assert_eq!(changes.len(), 1);
-        let (file_change, hunks) = &changes[0];
+        let (file_change, _, hunks) = &changes[0];
         assert_eq!(file_change.path, "doomed.txt");
         assert_eq!(file_change.status, FileStatus::Deleted);
 
@@ -309,7 +547,7 @@ mod tests {
         let changes = unstaged_changes(&repo);
 
         assert_eq!(changes.len(), 1);
-        let (_, hunks) = &changes[0];
+        let (_, _, hunks) = &changes[0];
         assert_eq!(hunks.len(), 2);
 
         let (first_hunk, first_lines) = &hunks[0];
@@ -348,9 +586,9 @@ mod tests {
         assert_eq!(changes.len(), 2);
 
         for (path, expected_addition) in [("a.txt", "aaa changed"), ("b.txt", "bbb changed")] {
-            let (file_change, hunks) = changes
+            let (file_change, _, hunks) = changes
                 .iter()
-                .find(|(fc, _)| fc.path == path)
+                .find(|(fc, _, _)| fc.path == path)
                 .unwrap_or_else(|| panic!("missing file change for {}", path));
             assert_eq!(file_change.status, FileStatus::Modified);
             assert_eq!(hunks.len(), 1);
@@ -375,7 +613,7 @@ mod tests {
         let changes = unstaged_changes(&repo);
 
         assert_eq!(changes.len(), 1);
-        let (_, hunks) = &changes[0];
+        let (_, _, hunks) = &changes[0];
         assert_eq!(hunks.len(), 1);
 
         // The "\ No newline at end of file" marker lines (origins '<', '>', '=')
@@ -390,6 +628,119 @@ mod tests {
         );
     }
 
+    fn git(test_repo: &TestRepo, args: &[&str]) {
+        let status = crate::git::git_cmd(test_repo.repo_path(), args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_collect_file_changes_rename_has_source_and_similarity() {
+        let test_repo = TestRepo::new();
+        let content: String = (1..=10).map(|i| format!("line {}\n", i)).collect();
+        test_repo.commit_file("old.txt", &content, "Add old");
+        git(&test_repo, &["mv", "old.txt", "new.txt"]);
+        test_repo
+            .write_file_content("new.txt", &content.replace("line 10", "changed"))
+            .stage_files(&["new.txt"]);
+        let repo = open_repo(&test_repo);
+
+        let head = repo.head().unwrap().peel_to_tree().unwrap();
+        let mut diff = repo.diff_tree_to_index(Some(&head), None, None).unwrap();
+        diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))
+            .unwrap();
+        let renames = staged_renames(test_repo.repo_path()).unwrap();
+        let changes = collect_file_changes(&diff, &renames).unwrap();
+
+        assert_eq!(changes.len(), 1);
+        let (file_change, _, hunks) = &changes[0];
+        assert_eq!(file_change.path, "new.txt");
+        assert_eq!(
+            file_change.status,
+            FileStatus::Renamed {
+                from: "old.txt".to_string(),
+                similarity: 88,
+            }
+        );
+        // The hunk has the changes since the old path
+        assert_eq!(hunks.len(), 1);
+    }
+
+    #[test]
+    fn test_collect_file_changes_mode_change_is_a_detail() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("run.sh", "echo hi\n", "Add script");
+        git(&test_repo, &["update-index", "--chmod=+x", "run.sh"]);
+        let repo = open_repo(&test_repo);
+
+        let changes = staged_changes(&repo);
+
+        let (_, details, hunks) = &changes[0];
+        assert_eq!(
+            details,
+            &vec![FileDetail::ModeChange {
+                old_mode: 0o100644,
+                new_mode: 0o100755,
+            }]
+        );
+        assert!(hunks.is_empty());
+        assert_eq!(details[0].description(), "mode 100644 -> 100755");
+    }
+
+    #[test]
+    fn test_collect_file_changes_binary_file_has_sizes() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("image.bin", "a\0b", "Add binary");
+        test_repo.write_file_content("image.bin", "a\0bcd");
+        let repo = open_repo(&test_repo);
+
+        let changes = unstaged_changes(&repo);
+
+        let (_, details, hunks) = &changes[0];
+        assert_eq!(
+            details,
+            &vec![FileDetail::Binary {
+                old_size: 3,
+                new_size: 5,
+            }]
+        );
+        assert!(hunks.is_empty());
+    }
+
+    #[test]
+    fn test_collect_file_changes_submodule_commit_is_a_detail() {
+        let test_repo = TestRepo::new();
+        let old_id = test_repo.head_hash();
+        test_repo.commit_file("file.txt", "content\n", "Second commit");
+        let new_id = test_repo.head_hash();
+        let gitlink = |id: &str| format!("160000,{},sub", id);
+        git(
+            &test_repo,
+            &["update-index", "--add", "--cacheinfo", &gitlink(&old_id)],
+        );
+        test_repo.commit("Add submodule");
+        git(
+            &test_repo,
+            &["update-index", "--cacheinfo", &gitlink(&new_id)],
+        );
+        let repo = open_repo(&test_repo);
+
+        let changes = staged_changes(&repo);
+
+        let (file_change, details, hunks) = &changes[0];
+        assert_eq!(file_change.path, "sub");
+        assert_eq!(
+            details,
+            &vec![FileDetail::Submodule {
+                old_id: old_id[..7].to_string(),
+                new_id: new_id[..7].to_string(),
+            }]
+        );
+        assert!(hunks.is_empty());
+    }
+
     #[test]
     fn test_build_change_lines_empty_input() {
         let lines = build_change_lines(
@@ -410,6 +761,7 @@ mod tests {
                 path: "a.txt".to_string(),
                 status: FileStatus::Modified,
             },
+            Vec::new(),
             vec![(
                 DiffHunk {
                     header: "@@ -1,2 +1,2 @@".to_string(),
@@ -418,6 +770,7 @@ mod tests {
                 vec![DiffLine {
                     content: "new line".to_string(),
                     line_type: DiffLineType::Addition,
+                    refined: Vec::new(),
                 }],
             )],
         )];
@@ -469,4 +822,98 @@ mod tests {
         ));
         assert_eq!(lines[3].section, Some(hunk_section));
     }
+
+    /// The lines of one unstaged hunk of `a.txt`, from (type, content) pairs
+    fn hunk_lines(header: &str, diff: &[(DiffLineType, &str)]) -> Vec<Line> {
+        let section = Some(SectionType::UnstagedHunk {
+            path: "a.txt".to_string(),
+            hunk_index: 0,
+        });
+        let mut lines = vec![Line {
+            content: LineContent::DiffHunk(DiffHunk {
+                header: header.to_string(),
+                hunk_index: 0,
+            }),
+            section: section.clone(),
+        }];
+        lines.extend(diff.iter().map(|(line_type, content)| Line {
+            content: LineContent::DiffLine(DiffLine {
+                content: content.to_string(),
+                line_type: line_type.clone(),
+                refined: Vec::new(),
+            }),
+            section: section.clone(),
+        }));
+        lines
+    }
+
+    fn headers(lines: &[Line]) -> Vec<&str> {
+        lines
+            .iter()
+            .filter_map(|line| match &line.content {
+                LineContent::DiffHunk(hunk) => Some(hunk.header.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split_hunks_splits_at_context_between_changes() {
+        use DiffLineType::{Addition, Context, Deletion};
+        let lines = hunk_lines(
+            "@@ -10,6 +10,6 @@ fn main()",
+            &[
+                (Context, "a"),
+                (Deletion, "b"),
+                (Addition, "B"),
+                (Context, "c"),
+                (Context, "d"),
+                (Deletion, "e"),
+                (Context, "f"),
+            ],
+        );
+        let split = HashSet::from([SectionType::UnstagedHunk {
+            path: "a.txt".to_string(),
+            hunk_index: 0,
+        }]);
+
+        let split_lines = split_hunks(lines, &split);
+
+        assert_eq!(
+            headers(&split_lines),
+            vec!["@@ -10,2 +10,2 @@", "@@ -12,4 +12,3 @@"]
+        );
+        // The second part starts with the context before its change
+        assert!(matches!(
+            &split_lines[4].content,
+            LineContent::DiffHunk(hunk) if hunk.hunk_index == 0
+        ));
+        assert!(matches!(
+            &split_lines[5].content,
+            LineContent::DiffLine(line) if line.content == "c"
+        ));
+        assert_eq!(split_lines.len(), 9);
+    }
+
+    #[test]
+    fn test_split_hunks_keeps_hunks_with_one_block_and_unsplit_hunks() {
+        use DiffLineType::{Addition, Context};
+        let lines = hunk_lines(
+            "@@ -1,2 +1,3 @@ fn main()",
+            &[(Context, "a"), (Addition, "b"), (Context, "c")],
+        );
+        let split = HashSet::from([SectionType::UnstagedHunk {
+            path: "a.txt".to_string(),
+            hunk_index: 0,
+        }]);
+
+        assert_eq!(
+            headers(&split_hunks(lines.clone(), &split)),
+            vec!["@@ -1,2 +1,3 @@ fn main()"]
+        );
+        assert_eq!(
+            headers(&split_hunks(lines, &HashSet::new())),
+            vec!["@@ -1,2 +1,3 @@ fn main()"]
+        );
+    }
 }
@@ -2,8 +2,11 @@ use std::io::Write;
 use std::path::Path;
 use std::process::Stdio;
 
-use super::git_cmd;
-use super::stage::{extract_hunk_from_diff, get_file_diff, parse_hunk_header_starts};
+use super::stage::{
+    extract_hunk_from_diff, get_file_diff, get_staged_file_diff, parse_hunk_header_starts,
+    staged_file_diff, staged_renames,
+};
+use super::{DiffSettings, git_cmd};
 use crate::errors::{MagiError, MagiResult};
 
 /// Discards untracked files by running `git clean -f -- <files>`.
@@ -52,9 +55,14 @@ pub fn discard_files<P: AsRef<Path>>(repo_path: P, files: &[&str]) -> MagiResult
 
 /// Discards a single hunk from a file by extracting it from `git diff` output
 /// and piping it to `git apply --reverse`.
-pub fn discard_hunk<P: AsRef<Path>>(repo_path: P, file: &str, hunk_index: usize) -> MagiResult<()> {
-    let patch = extract_hunk_patch(&repo_path, file, hunk_index)?;
-    apply_patch_reverse(&repo_path, &patch)
+pub fn discard_hunk<P: AsRef<Path>>(
+    repo_path: P,
+    file: &str,
+    hunk_index: usize,
+    settings: &DiffSettings,
+) -> MagiResult<()> {
+    let patch = extract_hunk_patch(&repo_path, file, hunk_index, settings)?;
+    apply_patch_reverse(&repo_path, &patch, settings)
 }
 
 /// Discards specific lines within a hunk by applying a modified patch in reverse.
@@ -68,10 +76,11 @@ pub fn discard_lines<P: AsRef<Path>>(
     file: &str,
     hunk_index: usize,
     selected_line_indices: &[usize],
+    settings: &DiffSettings,
 ) -> MagiResult<()> {
-    let diff_output = get_file_diff(&repo_path, file)?;
+    let diff_output = get_file_diff(&repo_path, file, settings)?;
     let patch = build_partial_hunk_patch(&diff_output, hunk_index, selected_line_indices)?;
-    apply_patch_reverse(&repo_path, &patch)
+    apply_patch_reverse(&repo_path, &patch, settings)
 }
 
 /// Builds a patch containing only the selected lines of a hunk, so that the
@@ -169,8 +178,9 @@ fn extract_hunk_patch<P: AsRef<Path>>(
     repo_path: P,
     file: &str,
     hunk_index: usize,
+    settings: &DiffSettings,
 ) -> MagiResult<String> {
-    let diff_output = get_file_diff(&repo_path, file)?;
+    let diff_output = get_file_diff(&repo_path, file, settings)?;
     let (header, hunk_lines) = extract_hunk_from_diff(&diff_output, hunk_index)?;
 
     let mut patch = header;
@@ -182,8 +192,13 @@ fn extract_hunk_patch<P: AsRef<Path>>(
 }
 
 /// Applies a patch in reverse to the working tree (discards changes).
-pub fn apply_patch_reverse<P: AsRef<Path>>(repo_path: P, patch: &str) -> MagiResult<()> {
+pub fn apply_patch_reverse<P: AsRef<Path>>(
+    repo_path: P,
+    patch: &str,
+    settings: &DiffSettings,
+) -> MagiResult<()> {
     let mut child = git_cmd(&repo_path, &["apply", "--reverse"])
+        .args(settings.apply_args())
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -210,13 +225,19 @@ pub fn apply_patch_reverse<P: AsRef<Path>>(repo_path: P, patch: &str) -> MagiRes
 
 /// Discards staged files. For new files, this removes them entirely (from index and disk).
 /// For modified files, this applies the reverse patch to BOTH index and working tree,
-/// preserving any other unstaged changes in those files.
+/// preserving any other unstaged changes in those files. A renamed file is
+/// moved back: its new path is removed and its old path restored.
 pub fn discard_staged_files<P: AsRef<Path>>(repo_path: P, files: &[&str]) -> MagiResult<()> {
     if files.is_empty() {
         return Ok(());
     }
 
-    for file in files {
+    let renames = staged_renames(&repo_path)?;
+    let sources = files
+        .iter()
+        .filter_map(|file| renames.get(*file))
+        .map(|rename| rename.from.as_str());
+    for file in files.iter().copied().chain(sources) {
         if is_file_new_in_index(&repo_path, file)? {
             // New file: remove from index and delete from disk
             let output = git_cmd(&repo_path, &["rm", "-f", "--", file])
@@ -228,13 +249,15 @@ pub fn discard_staged_files<P: AsRef<Path>>(repo_path: P, files: &[&str]) -> Mag
                 return Err(MagiError::Generic(format!("git rm -f failed: {}", stderr)));
             }
         } else {
-            // Modified file: get the staged diff and apply reverse to both index and working tree
-            let diff_output = get_staged_file_diff(&repo_path, file)?;
+            // Modified file: get the staged diff and apply reverse to both
+            // index and working tree. Whole files ignore the diff settings.
+            let settings = DiffSettings::default();
+            let diff_output = staged_file_diff(&repo_path, file, &renames, &settings)?;
             if diff_output.trim().is_empty() {
                 continue; // No staged changes for this file
             }
             // Apply reverse to both index and working tree
-            apply_patch_reverse_index_and_worktree(&repo_path, &diff_output)?;
+            apply_patch_reverse_index_and_worktree(&repo_path, &diff_output, &settings)?;
         }
     }
     Ok(())
@@ -256,9 +279,10 @@ pub fn discard_staged_hunk<P: AsRef<Path>>(
     repo_path: P,
     file: &str,
     hunk_index: usize,
+    settings: &DiffSettings,
 ) -> MagiResult<()> {
-    let patch = extract_staged_hunk_patch(&repo_path, file, hunk_index)?;
-    apply_patch_reverse_index_and_worktree(&repo_path, &patch)
+    let patch = extract_staged_hunk_patch(&repo_path, file, hunk_index, settings)?;
+    apply_patch_reverse_index_and_worktree(&repo_path, &patch, settings)
 }
 
 /// Discards specific staged lines within a hunk.
@@ -267,19 +291,11 @@ pub fn discard_staged_lines<P: AsRef<Path>>(
     file: &str,
     hunk_index: usize,
     selected_line_indices: &[usize],
+    settings: &DiffSettings,
 ) -> MagiResult<()> {
-    let diff_output = get_staged_file_diff(&repo_path, file)?;
+    let diff_output = get_staged_file_diff(&repo_path, file, settings)?;
     let patch = build_partial_hunk_patch(&diff_output, hunk_index, selected_line_indices)?;
-    apply_patch_reverse_index_and_worktree(&repo_path, &patch)
-}
-
-/// Gets the staged diff output for a specific file (comparing index to HEAD).
-pub fn get_staged_file_diff<P: AsRef<Path>>(repo_path: P, file: &str) -> MagiResult<String> {
-    let output = git_cmd(&repo_path, &["diff", "--cached", "--", file])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()?;
-    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    apply_patch_reverse_index_and_worktree(&repo_path, &patch, settings)
 }
 
 /// Extracts a single staged hunk patch.
@@ -287,8 +303,9 @@ fn extract_staged_hunk_patch<P: AsRef<Path>>(
     repo_path: P,
     file: &str,
     hunk_index: usize,
+    settings: &DiffSettings,
 ) -> MagiResult<String> {
-    let diff_output = get_staged_file_diff(&repo_path, file)?;
+    let diff_output = get_staged_file_diff(&repo_path, file, settings)?;
     let (header, hunk_lines) = extract_hunk_from_diff(&diff_output, hunk_index)?;
 
     let mut patch = header;
@@ -308,10 +325,12 @@ fn extract_staged_hunk_patch<P: AsRef<Path>>(
 fn apply_patch_reverse_index_and_worktree<P: AsRef<Path>>(
     repo_path: P,
     patch: &str,
+    settings: &DiffSettings,
 ) -> MagiResult<()> {
     // Try --index first (applies to both index and working tree atomically,
     // but requires working tree to match index for affected paths)
     let mut child = git_cmd(&repo_path, &["apply", "--reverse", "--index"])
+        .args(settings.apply_args())
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
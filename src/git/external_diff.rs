@@ -0,0 +1,317 @@
+//! Rendering of preview diffs with an external program, like delta or
+//! difftastic. The program's colored output replaces the diff part of the
+//! preview; the line of each file's header in it is found again so the
+//! preview keeps its `DiffFileHeader` boundaries.
+
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use super::{ansi, git_cmd};
+use crate::model::{Line, LineContent, PreviewLineType};
+
+/// An external program that renders the diffs of previews
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffRenderer {
+    /// A command reading a unified diff on stdin, like `delta`
+    Pager(String),
+    /// A command git runs in place of its own diff (`GIT_EXTERNAL_DIFF`),
+    /// like `difft`
+    External(String),
+}
+
+/// Replaces the diff of the preview `lines` with the output of `renderer`.
+/// `diff_args` are the git arguments producing the diff alone, which an
+/// external diff tool is run with. The preview is returned unchanged when
+/// the program fails or prints nothing.
+pub fn render(
+    workdir: &Path,
+    lines: Vec<Line>,
+    diff_args: &[&str],
+    renderer: &DiffRenderer,
+) -> Vec<Line> {
+    let Some(diff_start) = lines.iter().position(|line| {
+        matches!(&line.content, LineContent::PreviewLine { content, .. } if content.starts_with("diff --git "))
+    }) else {
+        return lines;
+    };
+
+    let paths: Vec<String> = lines[diff_start..]
+        .iter()
+        .filter_map(|line| match &line.content {
+            LineContent::PreviewLine { content, .. } => content
+                .strip_prefix("diff --git ")
+                .and_then(|paths| paths.rsplit_once(" b/"))
+                .map(|(_, path)| path.to_string()),
+            _ => None,
+        })
+        .collect();
+
+    let output = match renderer {
+        DiffRenderer::Pager(command) => {
+            let diff: String = lines[diff_start..]
+                .iter()
+                .filter_map(|line| match &line.content {
+                    LineContent::PreviewLine { content, .. } => Some(format!("{}\n", content)),
+                    _ => None,
+                })
+                .collect();
+            run_pager(workdir, command, &diff)
+        }
+        DiffRenderer::External(command) => run_external(workdir, command, diff_args),
+    };
+    let Some(output) = output.filter(|output| !output.trim().is_empty()) else {
+        return lines;
+    };
+
+    let mut rendered = lines;
+    rendered.truncate(diff_start);
+    rendered.extend(rendered_lines(&output, &paths));
+    rendered
+}
+
+/// Types the lines of the renderer's output: the first line naming each
+/// file (in diff order) is its header, everything else is rendered content.
+fn rendered_lines(output: &str, paths: &[String]) -> Vec<Line> {
+    let mut paths = paths.iter().peekable();
+    output
+        .lines()
+        .map(|content| {
+            let is_header = paths
+                .peek()
+                .is_some_and(|path| names_file(&ansi::strip(content), path));
+            if is_header {
+                paths.next();
+            }
+            Line {
+                content: LineContent::PreviewLine {
+                    content: content.to_string(),
+                    line_type: if is_header {
+                        PreviewLineType::DiffFileHeader
+                    } else {
+                        PreviewLineType::Rendered
+                    },
+                    refined: Vec::new(),
+                },
+                section: None,
+            }
+        })
+        .collect()
+}
+
+/// Whether `line` is a file header for `path`: without the renderer's
+/// decoration (box drawing, symbols, padding) it's the path, possibly
+/// followed by more details like difftastic's language.
+fn names_file(line: &str, path: &str) -> bool {
+    let text = line
+        .trim_matches(|c: char| c.is_whitespace() || !(c.is_alphanumeric() || "._-/~".contains(c)));
+    text.strip_prefix(path)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+/// Pipes `diff` through `command`, run by the shell like git runs pagers.
+fn run_pager(workdir: &Path, command: &str, diff: &str) -> Option<String> {
+    let mut child = Command::new("sh")
+        .args(["-c", command])
+        .current_dir(workdir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    // Written from a thread so a renderer printing before it has read all
+    // of its input can't deadlock on a full pipe
+    let mut stdin = child.stdin.take()?;
+    let diff = diff.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(diff.as_bytes()));
+    let output = child.wait_with_output().ok()?;
+    let _ = writer.join();
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Runs git with `command` as its external diff tool.
+fn run_external(workdir: &Path, command: &str, diff_args: &[&str]) -> Option<String> {
+    // Given after the subcommand, which is two words for `stash show`
+    let mut args = diff_args.to_vec();
+    let options = args
+        .iter()
+        .position(|arg| arg.starts_with('-'))
+        .unwrap_or(args.len());
+    args.insert(options, "--ext-diff");
+    let output = git_cmd(workdir, &args)
+        .env("GIT_EXTERNAL_DIFF", command)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{
+        DiffSettings,
+        preview::{get_commit_preview_lines, get_stash_preview_lines},
+        test_repo::TestRepo,
+    };
+
+    fn line_types(lines: &[Line]) -> Vec<(String, PreviewLineType)> {
+        lines
+            .iter()
+            .filter_map(|line| match &line.content {
+                LineContent::PreviewLine {
+                    content, line_type, ..
+                } => Some((content.clone(), line_type.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn two_file_commit() -> TestRepo {
+        let test_repo = TestRepo::new();
+        test_repo
+            .write_file_content("a.txt", "a\n")
+            .write_file_content("b.txt", "b\n")
+            .stage_files(&["a.txt", "b.txt"])
+            .commit("Two files");
+        test_repo
+    }
+
+    #[test]
+    fn test_pager_output_replaces_diff_and_maps_file_headers() {
+        let test_repo = two_file_commit();
+        let workdir = test_repo.repo_path();
+        let lines = get_commit_preview_lines(workdir, "HEAD", &DiffSettings::default());
+        let header_count = lines
+            .iter()
+            .take_while(|line| {
+                matches!(&line.content, LineContent::PreviewLine { line_type, .. } if *line_type == PreviewLineType::Header)
+            })
+            .count();
+
+        // Prints each file name in color, like delta's file headers
+        let renderer = DiffRenderer::Pager(
+            r#"sed -n 's|^diff --git a/.* b/\(.*\)|\x1b[34m\1\x1b[0m|p; s|^+\([^+].*\)|  \1|p'"#
+                .to_string(),
+        );
+        let rendered = render(workdir, lines, &["show", "--format=", "HEAD"], &renderer);
+
+        let types = line_types(&rendered[header_count..]);
+        assert_eq!(
+            types,
+            vec![
+                (
+                    "\x1b[34ma.txt\x1b[0m".to_string(),
+                    PreviewLineType::DiffFileHeader
+                ),
+                ("  a".to_string(), PreviewLineType::Rendered),
+                (
+                    "\x1b[34mb.txt\x1b[0m".to_string(),
+                    PreviewLineType::DiffFileHeader
+                ),
+                ("  b".to_string(), PreviewLineType::Rendered),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_external_diff_is_run_by_git() {
+        let test_repo = two_file_commit();
+        let workdir = test_repo.repo_path();
+        let lines = get_commit_preview_lines(workdir, "HEAD", &DiffSettings::default());
+
+        // git passes the path as the first argument
+        let renderer = DiffRenderer::External(r#"f() { echo "$1 changed"; }; f"#.to_string());
+        let rendered = render(workdir, lines, &["show", "--format=", "HEAD"], &renderer);
+
+        let types = line_types(&rendered);
+        let diff: Vec<_> = types
+            .iter()
+            .skip_while(|(_, line_type)| *line_type == PreviewLineType::Header)
+            .collect();
+        assert_eq!(
+            diff,
+            vec![
+                &("a.txt changed".to_string(), PreviewLineType::DiffFileHeader),
+                &("b.txt changed".to_string(), PreviewLineType::DiffFileHeader),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_content_naming_another_file_is_not_a_header() {
+        let test_repo = TestRepo::new();
+        test_repo
+            .write_file_content("a.txt", "see b.txt\n")
+            .write_file_content("b.txt", "b\n")
+            .stage_files(&["a.txt", "b.txt"])
+            .commit("Two files");
+        let workdir = test_repo.repo_path();
+        let lines = get_commit_preview_lines(workdir, "HEAD", &DiffSettings::default());
+
+        let renderer = DiffRenderer::Pager(
+            r#"sed -n 's|^diff --git a/.* b/\(.*\)|─ \1 ─|p; s|^+\([^+].*\)|  \1|p'"#.to_string(),
+        );
+        let rendered = render(workdir, lines, &["show", "--format=", "HEAD"], &renderer);
+
+        let diff: Vec<_> = line_types(&rendered)
+            .into_iter()
+            .skip_while(|(_, line_type)| *line_type == PreviewLineType::Header)
+            .collect();
+        assert_eq!(
+            diff,
+            vec![
+                ("─ a.txt ─".to_string(), PreviewLineType::DiffFileHeader),
+                ("  see b.txt".to_string(), PreviewLineType::Rendered),
+                ("─ b.txt ─".to_string(), PreviewLineType::DiffFileHeader),
+                ("  b".to_string(), PreviewLineType::Rendered),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_external_diff_renders_stash_preview() {
+        let test_repo = two_file_commit();
+        test_repo
+            .write_file_content("a.txt", "changed\n")
+            .create_stash("WIP");
+        let workdir = test_repo.repo_path();
+        let lines = get_stash_preview_lines(workdir, 0, &DiffSettings::default());
+
+        let renderer = DiffRenderer::External(r#"f() { echo "$1 changed"; }; f"#.to_string());
+        let rendered = render(
+            workdir,
+            lines,
+            &["stash", "show", "-p", "stash@{0}"],
+            &renderer,
+        );
+
+        let diff: Vec<_> = line_types(&rendered)
+            .into_iter()
+            .skip_while(|(_, line_type)| *line_type == PreviewLineType::Header)
+            .collect();
+        assert_eq!(
+            diff,
+            vec![("a.txt changed".to_string(), PreviewLineType::DiffFileHeader)]
+        );
+    }
+
+    #[test]
+    fn test_failing_renderer_keeps_builtin_diff() {
+        let test_repo = two_file_commit();
+        let workdir = test_repo.repo_path();
+        let lines = get_commit_preview_lines(workdir, "HEAD", &DiffSettings::default());
+
+        let renderer = DiffRenderer::Pager("exit 1".to_string());
+        let rendered = render(workdir, lines.clone(), &["show", "HEAD"], &renderer);
+
+        assert_eq!(line_types(&rendered), line_types(&lines));
+    }
+}
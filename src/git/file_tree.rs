@@ -0,0 +1,307 @@
+//! The tree layout of the untracked, unstaged and staged files, where the
+//! files of each section are grouped below collapsible directory lines.
+
+use std::collections::BTreeMap;
+
+use crate::model::{Line, LineContent, SectionType, parent_directory};
+
+/// A directory of the tree, with the files right in it
+#[derive(Default)]
+struct Directory {
+    directories: BTreeMap<String, Directory>,
+    /// Each file's line, followed by the lines below it (hunks or preview)
+    files: Vec<Vec<Line>>,
+}
+
+impl Directory {
+    /// The number of files below the directory, at any depth
+    fn count(&self) -> usize {
+        self.files.len()
+            + self
+                .directories
+                .values()
+                .map(Directory::count)
+                .sum::<usize>()
+    }
+
+    fn insert(&mut self, path: &str, file: Vec<Line>) {
+        let mut directory = self;
+        if let Some(parent) = parent_directory(path) {
+            for name in parent.trim_end_matches('/').split('/') {
+                directory = directory.directories.entry(name.to_string()).or_default();
+            }
+        }
+        directory.files.push(file);
+    }
+
+    /// Appends the lines of the directories below this one at `path`, each
+    /// followed by its content, then the lines of its own files.
+    fn append_lines(
+        self,
+        path: &str,
+        depth: usize,
+        directory_section: &impl Fn(String) -> SectionType,
+        lines: &mut Vec<Line>,
+    ) {
+        for (name, mut directory) in self.directories {
+            // A directory holding nothing but one directory is joined with it
+            let mut name = format!("{}/", name);
+            while directory.files.is_empty() && directory.directories.len() == 1 {
+                let Some((child_name, child)) = directory.directories.pop_first() else {
+                    break;
+                };
+                name = format!("{}{}/", name, child_name);
+                directory = child;
+            }
+
+            let directory_path = format!("{}{}", path, name);
+            lines.push(Line {
+                content: LineContent::Directory {
+                    name,
+                    depth,
+                    count: directory.count(),
+                },
+                section: Some(directory_section(directory_path.clone())),
+            });
+            directory.append_lines(&directory_path, depth + 1, directory_section, lines);
+        }
+        lines.extend(self.files.into_iter().flatten());
+    }
+}
+
+/// Groups the files of a status section into directories. `lines` are the
+/// section's header followed by each file's line and the lines below it;
+/// `file_path` tells the file lines apart and gives their path.
+fn group_by_directory(
+    lines: Vec<Line>,
+    file_path: impl Fn(&Line) -> Option<String>,
+    directory_section: impl Fn(String) -> SectionType,
+) -> Vec<Line> {
+    let mut grouped: Vec<Line> = Vec::new();
+    let mut root = Directory::default();
+    let mut file: Option<(String, Vec<Line>)> = None;
+
+    for line in lines {
+        if let Some(path) = file_path(&line) {
+            if let Some((path, lines)) = file.take() {
+                root.insert(&path, lines);
+            }
+            file = Some((path, vec![line]));
+        } else if let Some((_, lines)) = &mut file {
+            lines.push(line);
+        } else {
+            grouped.push(line);
+        }
+    }
+    if let Some((path, lines)) = file {
+        root.insert(&path, lines);
+    }
+
+    root.append_lines("", 0, &directory_section, &mut grouped);
+    grouped
+}
+
+/// The "Untracked files" section in the tree layout. The lines of files in
+/// a directory take its section, so collapsing the directory hides them.
+pub fn group_untracked_files(lines: Vec<Line>) -> Vec<Line> {
+    let mut lines = group_by_directory(
+        lines,
+        |line| match &line.content {
+            LineContent::UntrackedFile(path) => Some(path.clone()),
+            _ => None,
+        },
+        |path| SectionType::UntrackedDirectory { path },
+    );
+    for line in &mut lines {
+        if let LineContent::UntrackedFile(path) = &line.content
+            && let Some(directory) = parent_directory(path)
+        {
+            line.section = Some(SectionType::UntrackedDirectory { path: directory });
+        }
+    }
+    lines
+}
+
+/// The "Unstaged changes" section in the tree layout
+pub fn group_unstaged_changes(lines: Vec<Line>) -> Vec<Line> {
+    group_by_directory(
+        lines,
+        |line| match &line.content {
+            LineContent::UnstagedFile(file_change) => Some(file_change.path.clone()),
+            _ => None,
+        },
+        |path| SectionType::UnstagedDirectory { path },
+    )
+}
+
+/// The "Staged changes" section in the tree layout
+pub fn group_staged_changes(lines: Vec<Line>) -> Vec<Line> {
+    group_by_directory(
+        lines,
+        |line| match &line.content {
+            LineContent::StagedFile(file_change) => Some(file_change.path.clone()),
+            _ => None,
+        },
+        |path| SectionType::StagedDirectory { path },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{FileChange, FileStatus};
+
+    fn header() -> Line {
+        Line {
+            content: LineContent::SectionHeader {
+                title: "Unstaged changes".to_string(),
+                count: Some(4),
+            },
+            section: Some(SectionType::UnstagedChanges),
+        }
+    }
+
+    fn unstaged_file(path: &str) -> Line {
+        Line {
+            content: LineContent::UnstagedFile(FileChange {
+                path: path.to_string(),
+                status: FileStatus::Modified,
+            }),
+            section: Some(SectionType::UnstagedFile {
+                path: path.to_string(),
+            }),
+        }
+    }
+
+    fn untracked_file(path: &str) -> Line {
+        Line {
+            content: LineContent::UntrackedFile(path.to_string()),
+            section: Some(SectionType::UntrackedFiles),
+        }
+    }
+
+    /// Each line as "<indent>name (count)" for directories and the path for files
+    fn outline(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .filter_map(|line| match &line.content {
+                LineContent::Directory { name, depth, count } => {
+                    Some(format!("{}{} ({})", "  ".repeat(*depth), name, count))
+                }
+                LineContent::UnstagedFile(file_change) => Some(file_change.path.clone()),
+                LineContent::UntrackedFile(path) => Some(path.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_files_are_grouped_below_their_directories() {
+        let lines = vec![
+            header(),
+            unstaged_file("README.md"),
+            unstaged_file("src/git/a.rs"),
+            unstaged_file("src/git/b.rs"),
+            unstaged_file("src/main.rs"),
+        ];
+
+        let grouped = group_unstaged_changes(lines);
+
+        assert!(matches!(
+            grouped[0].content,
+            LineContent::SectionHeader { .. }
+        ));
+        assert_eq!(
+            outline(&grouped),
+            vec![
+                "src/ (3)",
+                "  git/ (2)",
+                "src/git/a.rs",
+                "src/git/b.rs",
+                "src/main.rs",
+                "README.md",
+            ]
+        );
+        assert_eq!(
+            grouped[2].section,
+            Some(SectionType::UnstagedDirectory {
+                path: "src/git/".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_directories_with_a_single_directory_are_joined() {
+        let lines = vec![
+            header(),
+            unstaged_file("gen/proto/v1/a.rs"),
+            unstaged_file("gen/proto/v1/b.rs"),
+        ];
+
+        let grouped = group_unstaged_changes(lines);
+
+        assert_eq!(
+            outline(&grouped),
+            vec![
+                "gen/proto/v1/ (2)",
+                "gen/proto/v1/a.rs",
+                "gen/proto/v1/b.rs"
+            ]
+        );
+        assert_eq!(
+            grouped[1].section,
+            Some(SectionType::UnstagedDirectory {
+                path: "gen/proto/v1/".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_lines_below_a_file_move_with_it() {
+        let hunk = Line {
+            content: LineContent::EmptyLine,
+            section: Some(SectionType::UnstagedHunk {
+                path: "b/x.rs".to_string(),
+                hunk_index: 0,
+            }),
+        };
+        let lines = vec![
+            header(),
+            unstaged_file("a.rs"),
+            unstaged_file("b/x.rs"),
+            hunk,
+        ];
+
+        let grouped = group_unstaged_changes(lines);
+
+        assert_eq!(outline(&grouped), vec!["b/ (1)", "b/x.rs", "a.rs"]);
+        assert!(matches!(
+            &grouped[3].section,
+            Some(SectionType::UnstagedHunk { path, .. }) if path == "b/x.rs"
+        ));
+    }
+
+    #[test]
+    fn test_untracked_files_take_their_directory_section() {
+        let lines = vec![
+            header(),
+            untracked_file("docs/"),
+            untracked_file("src/new.rs"),
+            untracked_file("top.txt"),
+        ];
+
+        let grouped = group_untracked_files(lines);
+
+        assert_eq!(
+            outline(&grouped),
+            vec!["src/ (1)", "src/new.rs", "docs/", "top.txt"]
+        );
+        assert_eq!(
+            grouped[2].section,
+            Some(SectionType::UntrackedDirectory {
+                path: "src/".to_string()
+            })
+        );
+        assert_eq!(grouped[3].section, Some(SectionType::UntrackedFiles));
+    }
+}
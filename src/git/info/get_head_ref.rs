@@ -1,4 +1,4 @@
-use crate::git::{GitRef, ReferenceType};
+use crate::git::{GitRef, ReferenceType, config::get_upstream_branch};
 use git2::Repository;
 
 /// Get the HEAD reference information from a Git repository
@@ -20,12 +20,14 @@ pub fn get_head_ref(repo: &Repository) -> Result<GitRef, git2::Error> {
             ReferenceType::LocalBranch
         };
 
-        Ok(GitRef::new(
-            name,
-            short_hash,
-            commit_summary,
-            reference_type,
-        ))
+        // Compared with the upstream, like `git status` does
+        let ahead_behind = get_upstream_branch(repo, &name)
+            .and_then(|(_, upstream)| repo.graph_ahead_behind(commit.id(), upstream).ok());
+
+        Ok(
+            GitRef::new(name, short_hash, commit_summary, reference_type)
+                .with_ahead_behind(ahead_behind),
+        )
     } else {
         // Detached HEAD
         let commit = head.peel_to_commit()?;
@@ -36,9 +36,12 @@ pub fn get_merge_ref(repo: &Repository) -> Result<Option<GitRef>, git2::Error> {
         .unwrap_or(&upstream_name)
         .to_string();
 
-    Ok(Some(GitRef::new_remote_branch(
-        upstream_shorthand,
-        short_hash.to_string(),
-        commit_summary,
-    )))
+    let ahead_behind = repo
+        .graph_ahead_behind(head.peel_to_commit()?.id(), commit.id())
+        .ok();
+
+    Ok(Some(
+        GitRef::new_remote_branch(upstream_shorthand, short_hash.to_string(), commit_summary)
+            .with_ahead_behind(ahead_behind),
+    ))
 }
@@ -37,9 +37,12 @@ pub fn get_push_ref(repo: &Repository) -> Result<Option<GitRef>, git2::Error> {
     let commit_summary = commit.summary().ok().flatten().unwrap_or("").to_string();
     let push_shorthand = push_ref.shorthand().unwrap_or(&push_ref_name).to_string();
 
-    Ok(Some(GitRef::new_remote_branch(
-        push_shorthand,
-        short_hash.to_string(),
-        commit_summary,
-    )))
+    let ahead_behind = repo
+        .graph_ahead_behind(head.peel_to_commit()?.id(), commit.id())
+        .ok();
+
+    Ok(Some(
+        GitRef::new_remote_branch(push_shorthand, short_hash.to_string(), commit_summary)
+            .with_ahead_behind(ahead_behind),
+    ))
 }
@@ -2,7 +2,7 @@ use git2::Repository;
 
 use crate::{
     errors::MagiResult,
-    git::{CommitRef, CommitRefType},
+    git::{CommitRef, CommitRefType, signing::SignatureStatus},
     model::LogEntry,
     msg::LogType,
 };
@@ -13,11 +13,13 @@ const MAX_LOG_ENTRIES: usize = 256;
 const SEPARATOR: char = '\x0c'; // Form feed character
 
 /// Fetches git log entries, optionally with graph and colored graph lines
+/// and each commit's signature status
 pub fn get_log_entries(
     repository: &Repository,
     log_type: &LogType,
     graph: bool,
     color: bool,
+    signatures: bool,
 ) -> MagiResult<Vec<LogEntry>> {
     let workdir = repository
         .workdir()
@@ -47,12 +49,19 @@ pub fn get_log_entries(
     }
 
     // Build the git log command similar to Magit
-    // Format: hash<sep>refs<sep>author<sep>date<sep>message
-    // Reflogs use the reflog subject (%gs, e.g. "commit: fix bug") as message
+    // Format: hash<sep>refs<sep>author<sep>date<sep>[signature<sep>]message
+    // Reflogs use the reflog subject (%gs, e.g. "commit: fix bug") as message.
+    // Signatures (%G?) are only asked for when shown, since verifying them
+    // runs gpg for every signed commit.
     let message_format = if reflog { "%gs" } else { "%s" };
+    let signature_format = if signatures {
+        format!("%G?{}", SEPARATOR)
+    } else {
+        String::new()
+    };
     let format = format!(
-        "%h{}%D{}%aN{}%ar{}{}",
-        SEPARATOR, SEPARATOR, SEPARATOR, SEPARATOR, message_format
+        "%h{}%D{}%aN{}%ar{}{}{}",
+        SEPARATOR, SEPARATOR, SEPARATOR, SEPARATOR, signature_format, message_format
     );
 
     let mut args = vec![
@@ -257,7 +266,7 @@ fn none_if_empty(s: &str) -> Option<String> {
 
 /// Parse a single line from git log --graph output
 fn parse_log_line(line: &str, remotes: &[String]) -> LogEntry {
-    // The line format is: <graph><hash><sep><refs><sep><author><sep><date><sep><message>
+    // The line format is: <graph><hash><sep><refs><sep><author><sep><date><sep>[<signature><sep>]<message>
     // The graph part is everything before the first non-graph character that looks like a hash
 
     // Find where the graph ends and the commit info begins
@@ -280,8 +289,15 @@ fn parse_log_line(line: &str, remotes: &[String]) -> LogEntry {
         let refs = parse_refs(parts[1], remotes);
         let author = none_if_empty(parts[2]);
         let time = none_if_empty(parts[3]).and_then(|t| t.strip_suffix(" ago").map(String::from));
-        let message = none_if_empty(parts[4]);
-        LogEntry::new(graph, hash, refs, author, time, message)
+        // The signature field is only there when signatures are shown
+        let (signature, message) = match parts.get(5) {
+            Some(message) => (SignatureStatus::from_code(parts[4]), *message),
+            None => (None, parts[4]),
+        };
+        LogEntry {
+            signature,
+            ..LogEntry::new(graph, hash, refs, author, time, none_if_empty(message))
+        }
     } else if !parts[0].is_empty() {
         // Has some content but not in expected format
         // Treat as a commit with just a hash/message
@@ -455,6 +471,7 @@ mod tests {
             &LogType::Other("feature".to_string()),
             true,
             false,
+            false,
         )
         .unwrap();
         let messages: Vec<String> = entries.iter().filter_map(|e| e.message.clone()).collect();
@@ -473,7 +490,8 @@ mod tests {
             .stage_files(&["file.txt"])
             .commit("Second commit");
 
-        let entries = get_log_entries(&test_repo.repo, &LogType::Reflog, true, false).unwrap();
+        let entries =
+            get_log_entries(&test_repo.repo, &LogType::Reflog, true, false, false).unwrap();
         let messages: Vec<String> = entries.iter().filter_map(|e| e.message.clone()).collect();
 
         // Reflog entries use the reflog subject ("<command>: <rest>")
@@ -499,6 +517,7 @@ mod tests {
             &LogType::ReflogOther("feature".to_string()),
             true,
             false,
+            false,
         )
         .unwrap();
         let messages: Vec<String> = entries.iter().filter_map(|e| e.message.clone()).collect();
@@ -524,6 +543,7 @@ mod tests {
             &LogType::ReflogOther("HEAD".to_string()),
             true,
             false,
+            false,
         )
         .unwrap();
         let messages: Vec<String> = entries.iter().filter_map(|e| e.message.clone()).collect();
@@ -546,7 +566,8 @@ mod tests {
             .write_file_content("file.txt", "second")
             .create_stash("Second stash");
 
-        let entries = get_log_entries(&test_repo.repo, &LogType::Stashes, true, false).unwrap();
+        let entries =
+            get_log_entries(&test_repo.repo, &LogType::Stashes, true, false, false).unwrap();
         let messages: Vec<String> = entries.iter().filter_map(|e| e.message.clone()).collect();
 
         // Both stashes are listed, most recent first
@@ -563,7 +584,8 @@ mod tests {
 
         let test_repo = TestRepo::new();
 
-        let entries = get_log_entries(&test_repo.repo, &LogType::Stashes, true, false).unwrap();
+        let entries =
+            get_log_entries(&test_repo.repo, &LogType::Stashes, true, false, false).unwrap();
         assert!(entries.is_empty());
     }
 
@@ -710,7 +732,8 @@ mod tests {
             .stage_files(&["file.txt"])
             .commit("Second commit");
 
-        let entries = get_log_entries(&test_repo.repo, &LogType::Related, true, false).unwrap();
+        let entries =
+            get_log_entries(&test_repo.repo, &LogType::Related, true, false, false).unwrap();
         let messages: Vec<String> = entries.iter().filter_map(|e| e.message.clone()).collect();
 
         assert!(messages.contains(&"Initial commit".to_string()));
@@ -736,6 +759,22 @@ mod tests {
         assert_eq!(entry.message, Some("Fix bug".to_string()));
     }
 
+    #[test]
+    fn test_parse_log_line_with_signature() {
+        let remotes = vec!["origin".to_string()];
+        let line = format!(
+            "* abc1234{}{}John Doe{}2 hours ago{}G{}Signed work",
+            SEPARATOR, SEPARATOR, SEPARATOR, SEPARATOR, SEPARATOR
+        );
+        let entry = parse_log_line(&line, &remotes);
+
+        assert_eq!(entry.signature, Some(SignatureStatus::Good));
+        assert_eq!(entry.message, Some("Signed work".to_string()));
+
+        let unsigned = line.replace("G\x0cSigned", "N\x0cSigned");
+        assert_eq!(parse_log_line(&unsigned, &remotes).signature, None);
+    }
+
     #[test]
     fn test_parse_log_line_colored_graph() {
         let remotes = vec!["origin".to_string()];
@@ -760,7 +799,8 @@ mod tests {
             .stage_files(&["file.txt"])
             .commit("Second commit");
 
-        let entries = get_log_entries(&test_repo.repo, &LogType::Current, true, true).unwrap();
+        let entries =
+            get_log_entries(&test_repo.repo, &LogType::Current, true, true, false).unwrap();
 
         // The graph is colored with ANSI codes, but the commit info is not
         let entry = entries.first().unwrap();
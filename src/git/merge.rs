@@ -26,27 +26,36 @@ pub fn run_merge_continue_with_editor<P: AsRef<Path>>(repo_path: P) -> MagiResul
     get_commit_result(repo_path, status, "Merge continue")
 }
 
-/// Runs `git merge <branch>`, which may open the user's configured editor
-/// for the merge commit message. The caller must ensure the TUI is suspended
-/// (via `RunningState::LaunchExternalCommand`) before calling this.
+/// Runs `git merge [flags] <branch>`, which may open the user's configured
+/// editor for the merge commit message. The caller must ensure the TUI is
+/// suspended (via `RunningState::LaunchExternalCommand`) before calling this.
 pub fn run_merge_with_editor<P: AsRef<Path>>(
     repo_path: P,
     branch: &str,
+    flags: &[String],
 ) -> MagiResult<CommitResult> {
-    let status = git_cmd(&repo_path, &["merge", branch]).status()?;
+    let status = git_cmd(&repo_path, &["merge"])
+        .args(flags)
+        .arg(branch)
+        .status()?;
 
     get_commit_result(repo_path, status, "Merge")
 }
 
 /// Runs `git merge --edit --no-ff <branch>`, which always creates a merge
-/// commit and opens the user's configured editor for its message. The caller
+/// commit and opens the user's configured editor for its message. A
+/// `--ff-only` in `flags` contradicts the forced `--no-ff` and is dropped. The caller
 /// must ensure the TUI is suspended (via `RunningState::LaunchExternalCommand`)
 /// before calling this.
 pub fn run_merge_edit_with_editor<P: AsRef<Path>>(
     repo_path: P,
     branch: &str,
+    flags: &[String],
 ) -> MagiResult<CommitResult> {
-    let status = git_cmd(&repo_path, &["merge", "--edit", "--no-ff", branch]).status()?;
+    let status = git_cmd(&repo_path, &["merge", "--edit", "--no-ff"])
+        .args(flags.iter().filter(|flag| *flag != "--ff-only"))
+        .arg(branch)
+        .status()?;
 
     get_commit_result(repo_path, status, "Merge")
 }
@@ -55,8 +64,12 @@ pub fn run_merge_edit_with_editor<P: AsRef<Path>>(
 /// the merged branch (`git branch -D <branch>`). `--no-edit` never opens an
 /// editor, so the TUI does not need to be suspended. When the merge stops on
 /// conflicts the branch is kept so the merge can be resolved or aborted.
-pub fn run_merge_absorb<P: AsRef<Path>>(repo_path: P, branch: &str) -> MagiResult<CommitResult> {
-    merge_branch_and_delete(repo_path.as_ref(), branch, "Absorb", "Absorbed")
+pub fn run_merge_absorb<P: AsRef<Path>>(
+    repo_path: P,
+    branch: &str,
+    flags: &[String],
+) -> MagiResult<CommitResult> {
+    merge_branch_and_delete(repo_path.as_ref(), branch, flags, "Absorb", "Absorbed")
 }
 
 /// Checks out `into` and then merges `branch` into it with absorb semantics:
@@ -67,6 +80,7 @@ pub fn run_merge_dissolve<P: AsRef<Path>>(
     repo_path: P,
     branch: &str,
     into: &str,
+    flags: &[String],
 ) -> MagiResult<CommitResult> {
     let repo_path = repo_path.as_ref();
     let checkout = git_cmd(repo_path, &["checkout", into]).output()?;
@@ -81,19 +95,23 @@ pub fn run_merge_dissolve<P: AsRef<Path>>(
         });
     }
 
-    merge_branch_and_delete(repo_path, branch, "Dissolve", "Dissolved")
+    merge_branch_and_delete(repo_path, branch, flags, "Dissolve", "Dissolved")
 }
 
-/// Merges `branch` into the checked-out branch (`git merge --no-edit`) and
+/// Merges `branch` into the checked-out branch (`git merge --no-edit [flags]`) and
 /// deletes it when the merge succeeds. `merged` is the past-tense verb used
 /// when the merge succeeds but the delete fails.
 fn merge_branch_and_delete(
     repo_path: &Path,
     branch: &str,
+    flags: &[String],
     label: &str,
     merged: &str,
 ) -> MagiResult<CommitResult> {
-    let output = git_cmd(repo_path, &["merge", "--no-edit", branch]).output()?;
+    let output = git_cmd(repo_path, &["merge", "--no-edit"])
+        .args(flags)
+        .arg(branch)
+        .output()?;
 
     let result = get_commit_result(repo_path, output.status, label)?;
     if !result.success {
@@ -208,7 +226,7 @@ mod tests {
         test_repo.commit_file("feature.txt", "feature content\n", "Feature commit");
         assert!(run_git(&test_repo, &["checkout", "main"]).status.success());
 
-        let result = run_merge_with_editor(test_repo.repo_path(), "feature").unwrap();
+        let result = run_merge_with_editor(test_repo.repo_path(), "feature", &[]).unwrap();
 
         assert!(result.success);
         assert_eq!(result.message, "Merge: Feature commit");
@@ -227,7 +245,7 @@ mod tests {
             ("feature.txt", "feature content\n"),
         );
 
-        let result = run_merge_with_editor(test_repo.repo_path(), "feature").unwrap();
+        let result = run_merge_with_editor(test_repo.repo_path(), "feature", &[]).unwrap();
 
         assert!(result.success);
         assert!(
@@ -258,7 +276,7 @@ mod tests {
         test_repo.commit_file("feature.txt", "feature content\n", "Feature commit");
         assert!(run_git(&test_repo, &["checkout", "main"]).status.success());
 
-        let result = run_merge_edit_with_editor(test_repo.repo_path(), "feature").unwrap();
+        let result = run_merge_edit_with_editor(test_repo.repo_path(), "feature", &[]).unwrap();
 
         assert!(result.success);
         assert!(
@@ -284,7 +302,7 @@ mod tests {
             ("feature.txt", "feature content\n"),
         );
 
-        let result = run_merge_edit_with_editor(test_repo.repo_path(), "feature").unwrap();
+        let result = run_merge_edit_with_editor(test_repo.repo_path(), "feature", &[]).unwrap();
 
         assert!(result.success);
         assert!(
@@ -310,7 +328,7 @@ mod tests {
             ("base.txt", "feature change\n"),
         );
 
-        let result = run_merge_edit_with_editor(test_repo.repo_path(), "feature").unwrap();
+        let result = run_merge_edit_with_editor(test_repo.repo_path(), "feature", &[]).unwrap();
 
         assert!(!result.success);
         assert_eq!(result.message, "Merge aborted");
@@ -328,7 +346,7 @@ mod tests {
             ("base.txt", "feature change\n"),
         );
 
-        let result = run_merge_with_editor(test_repo.repo_path(), "feature").unwrap();
+        let result = run_merge_with_editor(test_repo.repo_path(), "feature", &[]).unwrap();
 
         assert!(!result.success);
         assert_eq!(result.message, "Merge aborted");
@@ -345,7 +363,7 @@ mod tests {
             ("feature.txt", "feature content\n"),
         );
 
-        let result = run_merge_absorb(test_repo.repo_path(), "feature").unwrap();
+        let result = run_merge_absorb(test_repo.repo_path(), "feature", &[]).unwrap();
 
         assert!(result.success);
         assert!(
@@ -383,7 +401,7 @@ mod tests {
         assert!(run_git(&test_repo, &["checkout", "main"]).status.success());
         let feature_hash = test_repo.branch_hash("feature");
 
-        let result = run_merge_absorb(test_repo.repo_path(), "feature").unwrap();
+        let result = run_merge_absorb(test_repo.repo_path(), "feature", &[]).unwrap();
 
         assert!(result.success);
         assert_eq!(result.message, "Absorb: Feature commit");
@@ -407,7 +425,7 @@ mod tests {
             ("base.txt", "feature change\n"),
         );
 
-        let result = run_merge_absorb(test_repo.repo_path(), "feature").unwrap();
+        let result = run_merge_absorb(test_repo.repo_path(), "feature", &[]).unwrap();
 
         assert!(!result.success);
         assert_eq!(result.message, "Absorb aborted");
@@ -437,7 +455,7 @@ mod tests {
                 .success()
         );
 
-        let result = run_merge_dissolve(test_repo.repo_path(), "feature", "main").unwrap();
+        let result = run_merge_dissolve(test_repo.repo_path(), "feature", "main", &[]).unwrap();
 
         assert!(result.success);
         assert!(
@@ -479,7 +497,7 @@ mod tests {
         test_repo.commit_file("feature.txt", "feature content\n", "Feature commit");
         let feature_hash = test_repo.branch_hash("feature");
 
-        let result = run_merge_dissolve(test_repo.repo_path(), "feature", "main").unwrap();
+        let result = run_merge_dissolve(test_repo.repo_path(), "feature", "main", &[]).unwrap();
 
         assert!(result.success);
         assert_eq!(result.message, "Dissolve: Feature commit");
@@ -509,7 +527,7 @@ mod tests {
                 .success()
         );
 
-        let result = run_merge_dissolve(test_repo.repo_path(), "feature", "main").unwrap();
+        let result = run_merge_dissolve(test_repo.repo_path(), "feature", "main", &[]).unwrap();
 
         assert!(!result.success);
         assert_eq!(result.message, "Dissolve aborted");
@@ -540,7 +558,7 @@ mod tests {
         );
 
         let result =
-            run_merge_dissolve(test_repo.repo_path(), "feature", "no-such-branch").unwrap();
+            run_merge_dissolve(test_repo.repo_path(), "feature", "no-such-branch", &[]).unwrap();
 
         assert!(!result.success);
         assert!(
@@ -1,13 +1,17 @@
 use std::path::Path;
 
 use crate::errors::{MagiError, MagiResult};
-use crate::git::git_cmd;
+use crate::git::{
+    DiffSettings, git_cmd,
+    refine::{Change, refine_hunk},
+    signing,
+};
 use crate::model::{Line, LineContent, PreviewLineType};
 
 /// Parse raw `git show`/`git stash show` output into model Lines.
 pub fn parse_preview_output(output: &str) -> Vec<Line> {
     let mut in_diff = false;
-    output
+    let classified: Vec<(&str, PreviewLineType)> = output
         .lines()
         .map(|line| {
             if line.starts_with("diff --git ") {
@@ -22,9 +26,18 @@ pub fn parse_preview_output(output: &str) -> Vec<Line> {
                 || line.starts_with("new file")
                 || line.starts_with("deleted file")
                 || line.starts_with("rename ")
+                || line.starts_with("copy ")
                 || line.starts_with("similarity ")
+                || line.starts_with("dissimilarity ")
             {
                 PreviewLineType::DiffFileHeader
+            } else if line.starts_with("old mode ")
+                || line.starts_with("new mode ")
+                || line.starts_with("Binary files ")
+                || line.starts_with("-Subproject commit ")
+                || line.starts_with("+Subproject commit ")
+            {
+                PreviewLineType::FileMetadata
             } else if line.starts_with("@@") {
                 PreviewLineType::HunkHeader
             } else if line.starts_with('+') {
@@ -34,26 +47,109 @@ pub fn parse_preview_output(output: &str) -> Vec<Line> {
             } else {
                 PreviewLineType::Context
             };
-            Line {
-                content: LineContent::PreviewLine {
-                    content: line.to_string(),
-                    line_type,
-                },
-                section: None,
-            }
+            (line, line_type)
+        })
+        .collect();
+
+    let changes: Vec<(Change, &str)> = classified
+        .iter()
+        .map(|(line, line_type)| match line_type {
+            PreviewLineType::Deletion => (Change::Removed, &line[1..]),
+            PreviewLineType::Addition => (Change::Added, &line[1..]),
+            _ => (Change::Other, ""),
+        })
+        .collect();
+
+    classified
+        .into_iter()
+        .zip(refine_hunk(&changes))
+        .map(|((line, line_type), refined)| Line {
+            content: LineContent::PreviewLine {
+                content: line.to_string(),
+                line_type,
+                refined,
+            },
+            section: None,
         })
         .collect()
 }
 
+/// Appends the sizes of both sides to the "Binary files ... differ" lines
+/// git prints in place of the hunks of binary files, like the status lists
+/// them. The sizes are those of the blobs on the file's `index` line; a
+/// line is left as it is when one of them can't be read (e.g. a blob only
+/// in the working tree).
+fn with_binary_sizes(workdir: &Path, mut lines: Vec<Line>) -> Vec<Line> {
+    let mut blob_ids: Option<(String, String)> = None;
+    for line in &mut lines {
+        let LineContent::PreviewLine {
+            content, line_type, ..
+        } = &mut line.content
+        else {
+            continue;
+        };
+        if content.starts_with("diff --git ") {
+            blob_ids = None;
+        } else if let Some(ids) = content.strip_prefix("index ") {
+            blob_ids = ids
+                .split(' ')
+                .next()
+                .and_then(|ids| ids.split_once(".."))
+                .map(|(old, new)| (old.to_string(), new.to_string()));
+        } else if *line_type == PreviewLineType::FileMetadata
+            && content.starts_with("Binary files ")
+            && let Some((old_id, new_id)) = &blob_ids
+            && let (Some(old_size), Some(new_size)) =
+                (blob_size(workdir, old_id), blob_size(workdir, new_id))
+        {
+            content.push_str(&format!(" ({} -> {} bytes)", old_size, new_size));
+        }
+    }
+    lines
+}
+
+/// The size of the blob `id` (abbreviated), 0 for the null id of a side
+/// without the file.
+fn blob_size(workdir: &Path, id: &str) -> Option<u64> {
+    if id.chars().all(|c| c == '0') {
+        return Some(0);
+    }
+    let output = git_cmd(workdir, &["cat-file", "-s", id])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
 /// Returns preview lines for a commit (runs `git show <hash>`).
-pub fn get_commit_preview_lines(workdir: &Path, hash: &str) -> Vec<Line> {
-    let output = git_cmd(workdir, &["show", hash])
+pub fn get_commit_preview_lines(workdir: &Path, hash: &str, settings: &DiffSettings) -> Vec<Line> {
+    let output = git_cmd(workdir, &["show"])
+        .args(settings.diff_args())
+        .arg(hash)
         .output()
         .ok()
         .filter(|o| o.status.success())
         .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
         .unwrap_or_default();
-    parse_preview_output(&output)
+    let mut lines = with_binary_sizes(workdir, parse_preview_output(&output));
+
+    // Signed commits get a `Signature:` header right below the `commit` line
+    if !lines.is_empty()
+        && let Some((status, signer)) = signing::commit_signature(workdir, hash)
+    {
+        lines.insert(
+            1,
+            Line {
+                content: LineContent::PreviewLine {
+                    content: signing::signature_header(status, &signer),
+                    line_type: PreviewLineType::Header,
+                    refined: Vec::new(),
+                },
+                section: None,
+            },
+        );
+    }
+    lines
 }
 
 /// Returns preview lines showing what merging `branch` into `head_name`
@@ -96,6 +192,7 @@ pub fn get_merge_preview_lines(
         content: LineContent::PreviewLine {
             content,
             line_type: PreviewLineType::Header,
+            refined: Vec::new(),
         },
         section: None,
     };
@@ -116,21 +213,62 @@ pub fn get_merge_preview_lines(
             branch
         )));
     } else {
-        lines.extend(parse_preview_output(&diff));
+        lines.extend(with_binary_sizes(workdir, parse_preview_output(&diff)));
     }
     Ok(lines)
 }
 
 /// Returns preview lines for a stash entry (runs `git stash show -p stash@{N}`).
-pub fn get_stash_preview_lines(workdir: &Path, stash_index: usize) -> Vec<Line> {
+pub fn get_stash_preview_lines(
+    workdir: &Path,
+    stash_index: usize,
+    settings: &DiffSettings,
+) -> Vec<Line> {
     let stash_ref = format!("stash@{{{stash_index}}}");
-    let output = git_cmd(workdir, &["stash", "show", "-p", &stash_ref])
+    let output = git_cmd(workdir, &["stash", "show", "-p"])
+        .args(settings.diff_args())
+        .arg(&stash_ref)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+        .unwrap_or_default();
+    with_binary_sizes(workdir, parse_preview_output(&output))
+}
+
+/// The id of the empty tree, used as the base when diffing a root commit.
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Returns the diff shown below the built-in commit message editor, like
+/// `git commit --verbose`: the staged changes (the whole worktree with
+/// `--all`), or, when amending, everything HEAD will contain relative to its
+/// parent (only HEAD's own changes with `--only`).
+pub fn get_commit_editor_diff_lines(workdir: &Path, amend: bool, flags: &[String]) -> Vec<Line> {
+    let has_flag = |flag: &str| flags.iter().any(|f| f == flag);
+    let base = if !amend {
+        "HEAD"
+    } else if crate::git::rebase::commit_has_parent(workdir, "HEAD") {
+        "HEAD^"
+    } else {
+        EMPTY_TREE
+    };
+    let args: Vec<&str> = if amend && has_flag("--only") {
+        vec!["diff", base, "HEAD"]
+    } else if has_flag("--all") {
+        vec!["diff", base]
+    } else if amend {
+        vec!["diff", "--cached", base]
+    } else {
+        // Without a base `--cached` also works before the first commit
+        vec!["diff", "--cached"]
+    };
+    let output = git_cmd(workdir, &args)
         .output()
         .ok()
         .filter(|o| o.status.success())
         .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
         .unwrap_or_default();
-    parse_preview_output(&output)
+    with_binary_sizes(workdir, parse_preview_output(&output))
 }
 
 #[cfg(test)]
@@ -138,6 +276,26 @@ mod tests {
     use super::*;
     use crate::git::test_repo::TestRepo;
 
+    #[test]
+    fn test_parse_preview_output_refines_changed_words() {
+        let output = "diff --git a/f b/f\n@@ -1 +1 @@\n-let x = 1;\n+let x = 2;\n";
+
+        // Ranges start after the origin character
+        let changed_words: Vec<String> = parse_preview_output(output)
+            .into_iter()
+            .filter_map(|line| match line.content {
+                LineContent::PreviewLine {
+                    content, refined, ..
+                } => refined
+                    .first()
+                    .map(|range| content[1..][range.clone()].to_string()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(changed_words, vec!["1", "2"]);
+    }
+
     fn run_git(test_repo: &TestRepo, args: &[&str]) -> std::process::Output {
         git_cmd(test_repo.repo_path(), args).output().unwrap()
     }
@@ -165,7 +323,10 @@ mod tests {
         lines
             .iter()
             .filter_map(|l| {
-                if let LineContent::PreviewLine { content, line_type } = &l.content {
+                if let LineContent::PreviewLine {
+                    content, line_type, ..
+                } = &l.content
+                {
                     Some((content.as_str(), line_type))
                 } else {
                     None
@@ -310,7 +471,10 @@ mod tests {
         let contents: Vec<(&str, &PreviewLineType)> = lines
             .iter()
             .filter_map(|l| {
-                if let LineContent::PreviewLine { content, line_type } = &l.content {
+                if let LineContent::PreviewLine {
+                    content, line_type, ..
+                } = &l.content
+                {
                     Some((content.as_str(), line_type))
                 } else {
                     None
@@ -354,6 +518,62 @@ mod tests {
         assert_eq!(*ctx.1, PreviewLineType::Context);
     }
 
+    #[test]
+    fn test_parse_file_metadata_lines() {
+        let output = "\
+diff --git a/run.sh b/run.sh
+old mode 100644
+new mode 100755
+diff --git a/a.txt b/b.txt
+similarity index 100%
+copy from a.txt
+copy to b.txt
+diff --git a/image.png b/image.png
+index 1234567..abcdefa 100644
+Binary files a/image.png and b/image.png differ
+diff --git a/sub b/sub
+index 1234567..abcdefa 160000
+--- a/sub
++++ b/sub
+@@ -1 +1 @@
+-Subproject commit 1234567
++Subproject commit abcdefa
+";
+        let lines = parse_preview_output(output);
+        let contents = preview_contents(&lines);
+
+        let line_type = |prefix: &str| {
+            contents
+                .iter()
+                .find(|(content, _)| content.starts_with(prefix))
+                .map(|(_, line_type)| (*line_type).clone())
+                .unwrap()
+        };
+        assert_eq!(line_type("old mode"), PreviewLineType::FileMetadata);
+        assert_eq!(line_type("new mode"), PreviewLineType::FileMetadata);
+        assert_eq!(line_type("copy from"), PreviewLineType::DiffFileHeader);
+        assert_eq!(line_type("copy to"), PreviewLineType::DiffFileHeader);
+        assert_eq!(line_type("Binary files"), PreviewLineType::FileMetadata);
+        assert_eq!(line_type("-Subproject"), PreviewLineType::FileMetadata);
+        assert_eq!(line_type("+Subproject"), PreviewLineType::FileMetadata);
+    }
+
+    #[test]
+    fn test_commit_preview_shows_sizes_of_binary_files() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("image.bin", "a\0b", "Add binary");
+        test_repo.commit_file("image.bin", "a\0bcd", "Grow binary");
+
+        let lines =
+            get_commit_preview_lines(test_repo.repo_path(), "HEAD", &DiffSettings::default());
+        let contents = preview_contents(&lines);
+
+        assert!(contents.contains(&(
+            "Binary files a/image.bin and b/image.bin differ (3 -> 5 bytes)",
+            &PreviewLineType::FileMetadata
+        )));
+    }
+
     #[test]
     fn test_parse_no_diff_all_headers() {
         // Output without any diff section — all lines should be Header
@@ -365,4 +585,34 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_commit_editor_diff_shows_staged_changes_only() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("file.txt", "one\n", "Add file");
+        test_repo
+            .write_file_content("file.txt", "two\n")
+            .stage_files(&["file.txt"])
+            .write_file_content("file.txt", "three\n");
+
+        let lines = get_commit_editor_diff_lines(test_repo.repo_path(), false, &[]);
+        let contents = preview_contents(&lines);
+        assert!(contents.contains(&("+two", &PreviewLineType::Addition)));
+        assert!(!contents.iter().any(|(c, _)| c.contains("three")));
+
+        let all = ["--all".to_string()];
+        let lines = get_commit_editor_diff_lines(test_repo.repo_path(), false, &all);
+        let contents = preview_contents(&lines);
+        assert!(contents.contains(&("+three", &PreviewLineType::Addition)));
+    }
+
+    #[test]
+    fn test_commit_editor_diff_for_amend_includes_head() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("file.txt", "one\n", "Add file");
+
+        let lines = get_commit_editor_diff_lines(test_repo.repo_path(), true, &[]);
+        let contents = preview_contents(&lines);
+        assert!(contents.contains(&("+one", &PreviewLineType::Addition)));
+    }
 }
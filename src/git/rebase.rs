@@ -1,17 +1,24 @@
 use std::fs;
 use std::path::Path;
 
+use git2::Repository;
+
 use super::git_cmd;
 use crate::{
     errors::{MagiError, MagiResult},
-    git::{commit::get_commit_result, read_commit_message},
+    git::{commit::get_commit_result, read_commit_message, reset::has_uncommitted_changes},
     i18n,
     model::{LineContent, SectionType},
 };
 
 pub use super::commit::CommitResult;
 
-/// Action to perform on a commit in an interactive rebase todo list.
+/// Action of a line in an interactive rebase todo list.
+///
+/// Besides the per-commit actions, this covers the instructions git puts in
+/// a todo: `exec`/`break` lines inserted by the user, and the
+/// `label`/`reset`/`merge`/`update-ref` lines generated by `--rebase-merges`
+/// and `--update-refs`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RebaseAction {
     Pick,
@@ -19,7 +26,23 @@ pub enum RebaseAction {
     Edit,
     Squash,
     Fixup,
+    /// `fixup -C`: fold into the previous commit, keeping this commit's message
+    FixupKeepMessage,
+    /// `fixup -c`: like `fixup -C`, but opens the editor on the message
+    FixupEditMessage,
     Drop,
+    /// `exec <command>`: run a shell command at this point
+    Exec,
+    /// `break`: stop the rebase here (continue with `git rebase --continue`)
+    Break,
+    /// `label <name>`: name the current HEAD
+    Label,
+    /// `reset <name>`: move HEAD to a labelled commit
+    Reset,
+    /// `merge -C <commit> <label>`: recreate a merge commit
+    Merge,
+    /// `update-ref <ref>`: point a branch at the rewritten commit
+    UpdateRef,
 }
 
 impl RebaseAction {
@@ -31,18 +54,52 @@ impl RebaseAction {
             RebaseAction::Edit => "edit",
             RebaseAction::Squash => "squash",
             RebaseAction::Fixup => "fixup",
+            RebaseAction::FixupKeepMessage => "fixup -C",
+            RebaseAction::FixupEditMessage => "fixup -c",
             RebaseAction::Drop => "drop",
+            RebaseAction::Exec => "exec",
+            RebaseAction::Break => "break",
+            RebaseAction::Label => "label",
+            RebaseAction::Reset => "reset",
+            RebaseAction::Merge => "merge",
+            RebaseAction::UpdateRef => "update-ref",
         }
     }
 
     /// Returns true if the action folds the commit into the previous one,
     /// meaning it cannot be the first entry in the todo list.
     pub fn is_fold(self) -> bool {
-        matches!(self, RebaseAction::Squash | RebaseAction::Fixup)
+        matches!(
+            self,
+            RebaseAction::Squash
+                | RebaseAction::Fixup
+                | RebaseAction::FixupKeepMessage
+                | RebaseAction::FixupEditMessage
+        )
+    }
+
+    /// Returns true for the per-commit actions, which can be swapped for one
+    /// another on the same line. The remaining actions are instructions whose
+    /// argument is not a commit to pick.
+    pub fn is_commit(self) -> bool {
+        !matches!(
+            self,
+            RebaseAction::Exec
+                | RebaseAction::Break
+                | RebaseAction::Label
+                | RebaseAction::Reset
+                | RebaseAction::Merge
+                | RebaseAction::UpdateRef
+        )
     }
 }
 
 /// A single line of an interactive rebase todo list being edited.
+///
+/// For instruction lines `hash` is empty and `message` holds the argument
+/// (the command of an `exec`, the name of a `label`/`reset`, the ref of an
+/// `update-ref`). A `merge` line keeps the merge commit it recreates in
+/// `hash` and `<label> # <subject>` in `message`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RebaseTodoEntry {
     pub action: RebaseAction,
@@ -52,6 +109,88 @@ pub struct RebaseTodoEntry {
     pub message: String,
 }
 
+impl RebaseTodoEntry {
+    /// An instruction line that is not tied to a commit (e.g. `exec make`).
+    pub fn instruction(action: RebaseAction, argument: impl Into<String>) -> Self {
+        Self {
+            action,
+            hash: String::new(),
+            message: argument.into(),
+        }
+    }
+
+    /// The label this line defines (`label`) or refers to (`reset`, `merge`).
+    pub fn label(&self) -> Option<&str> {
+        // `reset` and `merge` lines may carry a trailing `# <subject>`
+        let argument = self.message.split(" # ").next().unwrap_or("").trim();
+        match self.action {
+            RebaseAction::Label | RebaseAction::Reset => Some(argument),
+            RebaseAction::Merge => argument.split_whitespace().next(),
+            _ => None,
+        }
+    }
+
+    /// The line as written to the git-rebase-todo file (without newline).
+    pub fn to_todo_line(&self) -> String {
+        match self.action {
+            RebaseAction::Break => "break".to_string(),
+            RebaseAction::Merge if !self.hash.is_empty() => {
+                format!("merge -C {} {}", self.hash, self.message)
+            }
+            action if action.is_commit() => {
+                format!("{} {} {}", action.as_str(), self.hash, self.message)
+            }
+            action => format!("{} {}", action.as_str(), self.message),
+        }
+    }
+}
+
+/// Parses a git-rebase-todo file. Comments, blank lines and `noop` are
+/// skipped; abbreviated command words (`p`, `f`, ...) are accepted.
+pub fn parse_todo(content: &str) -> Vec<RebaseTodoEntry> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(parse_todo_line)
+        .collect()
+}
+
+fn parse_todo_line(line: &str) -> Option<RebaseTodoEntry> {
+    let (word, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let rest = rest.trim_start();
+    let commit = |action, rest: &str| {
+        let (hash, message) = rest.split_once(' ').unwrap_or((rest, ""));
+        Some(RebaseTodoEntry {
+            action,
+            hash: hash.to_string(),
+            message: message.to_string(),
+        })
+    };
+    match word {
+        "pick" | "p" => commit(RebaseAction::Pick, rest),
+        "reword" | "r" => commit(RebaseAction::Reword, rest),
+        "edit" | "e" => commit(RebaseAction::Edit, rest),
+        "squash" | "s" => commit(RebaseAction::Squash, rest),
+        "fixup" | "f" => match rest.split_once(' ') {
+            Some(("-C", rest)) => commit(RebaseAction::FixupKeepMessage, rest),
+            Some(("-c", rest)) => commit(RebaseAction::FixupEditMessage, rest),
+            _ => commit(RebaseAction::Fixup, rest),
+        },
+        "drop" | "d" => commit(RebaseAction::Drop, rest),
+        "exec" | "x" => Some(RebaseTodoEntry::instruction(RebaseAction::Exec, rest)),
+        "break" | "b" => Some(RebaseTodoEntry::instruction(RebaseAction::Break, "")),
+        "label" | "l" => Some(RebaseTodoEntry::instruction(RebaseAction::Label, rest)),
+        "reset" | "t" => Some(RebaseTodoEntry::instruction(RebaseAction::Reset, rest)),
+        "merge" | "m" => match rest.split_once(' ') {
+            Some(("-C" | "-c", rest)) => commit(RebaseAction::Merge, rest),
+            _ => Some(RebaseTodoEntry::instruction(RebaseAction::Merge, rest)),
+        },
+        "update-ref" | "u" => Some(RebaseTodoEntry::instruction(RebaseAction::UpdateRef, rest)),
+        _ => None,
+    }
+}
+
 /// Returns true if the given commit has a parent (i.e. is not a root commit).
 pub fn commit_has_parent(workdir: &Path, commit: &str) -> bool {
     git_cmd(
@@ -85,22 +224,40 @@ fn resolve_commit_hash(workdir: &Path, commit: &str) -> MagiResult<String> {
 /// the todo marks `commit` as `edit` and picks every commit after it.
 /// The rebase stops right after applying `commit`, leaving it as HEAD for
 /// amending; `git rebase --continue` then replays the remaining commits.
-pub fn run_modify_commit(workdir: &Path, commit: &str) -> MagiResult<CommitResult> {
-    run_action_on_commit(workdir, commit, RebaseAction::Edit, "modified")
+pub fn run_modify_commit(
+    workdir: &Path,
+    commit: &str,
+    extra_args: &[String],
+) -> MagiResult<CommitResult> {
+    run_action_on_commit(workdir, commit, RebaseAction::Edit, "modified", extra_args)
 }
 
 /// Starts an interactive rebase that rewords `commit`: the todo marks
 /// `commit` as `reword` and picks every commit after it. Git opens the
 /// user's editor for the new message, then the rebase finishes on its own.
-pub fn run_reword_commit(workdir: &Path, commit: &str) -> MagiResult<CommitResult> {
-    run_action_on_commit(workdir, commit, RebaseAction::Reword, "reworded")
+pub fn run_reword_commit(
+    workdir: &Path,
+    commit: &str,
+    extra_args: &[String],
+) -> MagiResult<CommitResult> {
+    run_action_on_commit(
+        workdir,
+        commit,
+        RebaseAction::Reword,
+        "reworded",
+        extra_args,
+    )
 }
 
 /// Starts an interactive rebase that removes `commit`: the todo marks
 /// `commit` as `drop` and picks every commit after it. The rebase runs to
 /// completion on its own unless replaying a later commit conflicts.
-pub fn run_remove_commit(workdir: &Path, commit: &str) -> MagiResult<CommitResult> {
-    run_action_on_commit(workdir, commit, RebaseAction::Drop, "removed")
+pub fn run_remove_commit(
+    workdir: &Path,
+    commit: &str,
+    extra_args: &[String],
+) -> MagiResult<CommitResult> {
+    run_action_on_commit(workdir, commit, RebaseAction::Drop, "removed", extra_args)
 }
 
 /// Returns the merge base of the configured upstream and HEAD, or None when
@@ -124,7 +281,13 @@ pub fn get_upstream_merge_base(workdir: &Path) -> Option<String> {
 /// merge-base path); with true, the rebase starts at `base` so fixups can be
 /// squashed into it (the picked-commit path). `squash!` commits may open the
 /// user's editor, so the caller must run this while the TUI is suspended.
-pub fn run_autosquash(workdir: &Path, base: &str, include_base: bool) -> MagiResult<CommitResult> {
+/// `extra_args` (e.g. `--gpg-sign`) are passed through to `git rebase`.
+pub fn run_autosquash(
+    workdir: &Path,
+    base: &str,
+    include_base: bool,
+    extra_args: &[String],
+) -> MagiResult<CommitResult> {
     let full_hash = resolve_commit_hash(workdir, base)?;
 
     // `true` exits 0 without touching the todo, so the autosquash order
@@ -136,16 +299,16 @@ pub fn run_autosquash(workdir: &Path, base: &str, include_base: bool) -> MagiRes
         "--interactive",
         "--autosquash",
         "--keep-empty",
-        "--autostash",
     ];
     let parent = format!("{full_hash}^");
-    if !include_base {
-        args.push(&full_hash);
+    let base_parent = if !include_base {
+        Some(full_hash.as_str())
     } else if commit_has_parent(workdir, &full_hash) {
-        args.push(&parent);
+        Some(parent.as_str())
     } else {
-        args.push("--root");
-    }
+        None
+    };
+    push_base_args(&mut args, extra_args, base_parent);
 
     let status = git_cmd(workdir, &args).status();
 
@@ -155,31 +318,80 @@ pub fn run_autosquash(workdir: &Path, base: &str, include_base: bool) -> MagiRes
 /// Starts an interactive rebase where `commit` is marked with `action` and
 /// every commit after it is picked. `action_desc` is the past-tense verb
 /// used in the error message when the commit is not in the todo.
+///
+/// The line is changed in git's own todo, so the lines `extra_args` make
+/// git add to it (`--exec`, `--update-refs`, `--rebase-merges`) are kept.
 fn run_action_on_commit(
     workdir: &Path,
     commit: &str,
     action: RebaseAction,
     action_desc: &str,
+    extra_args: &[String],
 ) -> MagiResult<CommitResult> {
     let full_hash = resolve_commit_hash(workdir, commit)?;
     let has_parent = commit_has_parent(workdir, &full_hash);
-    let mut entries = get_interactive_rebase_commits(workdir, &full_hash, has_parent)?;
-
-    let mut found = false;
-    for entry in &mut entries {
-        if entry.hash == full_hash {
-            entry.action = action;
-            found = true;
-        }
-    }
-    if !found {
+    let commits = get_interactive_rebase_commits(workdir, &full_hash, has_parent)?;
+    if !commits.iter().any(|entry| entry.hash == full_hash) {
         return Err(MagiError::Generic(format!(
             "Commit {} cannot be {} (not reachable from HEAD, or a merge commit)",
             commit, action_desc
         )));
     }
 
-    start_interactive_rebase(workdir, &full_hash, has_parent, &entries)
+    // Git runs the sequence editor with the todo path as `$1`. The `exec`
+    // line after a removed commit would only re-run on its parent, so it
+    // goes with it.
+    let script_path = workdir.join(".git").join("magi-rebase-editor");
+    let script = format!(
+        r#"awk -v hash={full_hash} -v action={action} '
+dropping && $1 == "exec" {{ next }}
+{{ dropping = 0 }}
+$1 == "pick" && $2 == hash {{ $1 = action; dropping = action == "drop" }}
+{{ print }}
+' "$1" > "$1.magi" && mv "$1.magi" "$1"
+"#,
+        action = action.as_str()
+    );
+    fs::write(&script_path, script)?;
+    let quoted_path = script_path.display().to_string().replace('\'', "'\\''");
+    let sequence_editor = format!("sequence.editor=sh '{quoted_path}'");
+
+    let mut args = vec![
+        "-c",
+        sequence_editor.as_str(),
+        // Full hashes and command words, so the line is found whatever the
+        // user's configuration
+        "-c",
+        "core.abbrev=no",
+        "-c",
+        "rebase.abbreviateCommands=false",
+        "rebase",
+        "--interactive",
+    ];
+    let parent = format!("{full_hash}^");
+    push_base_args(&mut args, extra_args, has_parent.then_some(parent.as_str()));
+
+    let status = git_cmd(workdir, &args).status();
+    let _ = fs::remove_file(&script_path);
+
+    get_commit_result(workdir, status?, "Rebase")
+}
+
+/// Appends `extra_args` and the upstream to rebase onto (`base_parent`), or
+/// `--root` when the base is the root commit. `--keep-base` needs an
+/// upstream, so it's left out with `--root`.
+fn push_base_args<'a>(
+    args: &mut Vec<&'a str>,
+    extra_args: &'a [String],
+    base_parent: Option<&'a str>,
+) {
+    args.extend(
+        extra_args
+            .iter()
+            .map(String::as_str)
+            .filter(|arg| base_parent.is_some() || *arg != "--keep-base"),
+    );
+    args.push(base_parent.unwrap_or("--root"));
 }
 
 /// Returns the initial todo entries for an interactive rebase that includes
@@ -191,11 +403,7 @@ pub fn get_interactive_rebase_commits(
     base: &str,
     base_has_parent: bool,
 ) -> MagiResult<Vec<RebaseTodoEntry>> {
-    let range = if base_has_parent {
-        format!("{base}^..HEAD")
-    } else {
-        "HEAD".to_string()
-    };
+    let range = todo_range(base, base_has_parent);
     let output = git_cmd(
         workdir,
         &[
@@ -230,23 +438,163 @@ pub fn get_interactive_rebase_commits(
     Ok(entries)
 }
 
+/// Returns the initial todo for rebasing `base` and every commit after it
+/// with `extra_args`: git's own when it would add lines to it (see
+/// [`todo_needs_git`]), otherwise one `pick` per commit.
+pub fn get_interactive_rebase_todo(
+    workdir: &Path,
+    base: &str,
+    base_has_parent: bool,
+    extra_args: &[String],
+) -> MagiResult<Vec<RebaseTodoEntry>> {
+    if todo_needs_git(workdir, base, base_has_parent, extra_args) {
+        generate_interactive_rebase_todo(workdir, base, base_has_parent, extra_args)
+    } else {
+        get_interactive_rebase_commits(workdir, base, base_has_parent)
+    }
+}
+
+/// The commits rebasing `base` and every commit after it rewrites
+fn todo_range(base: &str, base_has_parent: bool) -> String {
+    if base_has_parent {
+        format!("{base}^..HEAD")
+    } else {
+        "HEAD".to_string()
+    }
+}
+
+/// Returns true when `extra_args` (or `rebase.updateRefs`) make git add
+/// lines to the todo that cannot be derived from the log alone: `exec`
+/// lines for `--exec`, `label`/`reset`/`merge` lines for `--rebase-merges`
+/// when there are merges to rebase, and `update-ref` lines for
+/// `--update-refs` when other branches point into the rebased commits.
+///
+/// Git adds these lines before the sequence editor runs, so a todo that
+/// replaces git's must already contain them.
+pub fn todo_needs_git(
+    workdir: &Path,
+    base: &str,
+    base_has_parent: bool,
+    extra_args: &[String],
+) -> bool {
+    let stdout = |args: &[&str]| {
+        git_cmd(workdir, args)
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .unwrap_or_default()
+    };
+    let range = todo_range(base, base_has_parent);
+
+    if extra_args.iter().any(|arg| arg.starts_with("--exec")) {
+        return true;
+    }
+    if extra_args
+        .iter()
+        .any(|arg| arg.starts_with("--rebase-merges"))
+        && !stdout(&["rev-list", "--merges", "-n1", &range]).is_empty()
+    {
+        return true;
+    }
+    let update_refs = extra_args.iter().any(|arg| arg == "--update-refs")
+        || stdout(&["config", "--type=bool", "rebase.updateRefs"]) == "true";
+    if !update_refs {
+        return false;
+    }
+    // The checked out branch is rebased itself, never updated by a line
+    let mut args = vec!["log", "--format=%D", "--decorate-refs=refs/heads/"];
+    let current = stdout(&["symbolic-ref", "-q", "HEAD"]);
+    let exclude = format!("--decorate-refs-exclude={}", current);
+    if !current.is_empty() {
+        args.push(&exclude);
+    }
+    args.push(&range);
+    !stdout(&args).is_empty()
+}
+
+/// Returns the todo list git itself generates for rebasing `base` and every
+/// commit after it with `extra_args`, preserving the `label`/`reset`/`merge`
+/// and `update-ref` lines of `--rebase-merges` and `--update-refs`.
+///
+/// The sequence editor copies git's todo out and then empties it, so git
+/// stops with "nothing to do" before touching HEAD or the worktree. Git
+/// only gets that far with a clean worktree: autostashing would unstage the
+/// index when re-applied, so uncommitted changes are refused instead.
+pub fn generate_interactive_rebase_todo(
+    workdir: &Path,
+    base: &str,
+    base_has_parent: bool,
+    extra_args: &[String],
+) -> MagiResult<Vec<RebaseTodoEntry>> {
+    if has_uncommitted_changes(&Repository::open(workdir)?) {
+        return Err(MagiError::Generic(
+            "Commit or stash your changes before editing a rebase with --update-refs, \
+             --rebase-merges or --exec"
+                .to_string(),
+        ));
+    }
+
+    let capture_path = workdir.join(".git").join("magi-rebase-todo-generated");
+    let _ = fs::remove_file(&capture_path);
+
+    // Git appends `"$@"` (the todo path) to the editor command, so the
+    // trailing `: >` truncates git's todo after it has been copied.
+    let quoted_path = capture_path.display().to_string().replace('\'', "'\\''");
+    let sequence_editor = format!("sequence.editor=cp \"$1\" '{quoted_path}' && : >");
+
+    let mut args = vec![
+        "-c",
+        sequence_editor.as_str(),
+        // Full hashes, unabbreviated commands and plain subjects, whatever
+        // the user's configuration, so the todo parses predictably
+        "-c",
+        "core.abbrev=no",
+        "-c",
+        "rebase.abbreviateCommands=false",
+        "-c",
+        "rebase.instructionFormat=%s",
+        "rebase",
+        "--interactive",
+    ];
+    let parent = format!("{base}^");
+    push_base_args(
+        &mut args,
+        extra_args,
+        base_has_parent.then_some(parent.as_str()),
+    );
+    // After the arguments, so the autostash argument doesn't turn it back on
+    args.push("--no-autostash");
+
+    let output = git_cmd(workdir, &args).output()?;
+    let Ok(content) = fs::read_to_string(&capture_path) else {
+        return Err(MagiError::Generic(format!(
+            "Failed to generate rebase todo: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    };
+    let _ = fs::remove_file(&capture_path);
+
+    Ok(parse_todo(&content))
+}
+
 /// Starts an interactive rebase with a pre-built todo list.
 ///
 /// The entries are written to a file inside `.git/`, and `sequence.editor`
 /// is set to a command that copies that file over git's generated todo.
 /// Git stays authoritative for executing the rebase; reword/squash may open
 /// the user's `$GIT_EDITOR`, so the caller must run this while the TUI is
-/// suspended (via `RunningState::LaunchExternalCommand`).
+/// suspended (via `RunningState::LaunchExternalCommand`). `extra_args`
+/// (e.g. `--gpg-sign`) are passed through to `git rebase`.
 pub fn start_interactive_rebase(
     workdir: &Path,
     base: &str,
     base_has_parent: bool,
     entries: &[RebaseTodoEntry],
+    extra_args: &[String],
 ) -> MagiResult<CommitResult> {
     let todo_path = workdir.join(".git").join("magi-rebase-todo");
     let content: String = entries
         .iter()
-        .map(|e| format!("{} {} {}\n", e.action.as_str(), e.hash, e.message))
+        .map(|e| format!("{}\n", e.to_todo_line()))
         .collect();
     fs::write(&todo_path, content)?;
 
@@ -255,19 +603,13 @@ pub fn start_interactive_rebase(
     let quoted_path = todo_path.display().to_string().replace('\'', "'\\''");
     let sequence_editor = format!("sequence.editor=cp '{quoted_path}'");
 
-    let mut args = vec![
-        "-c",
-        sequence_editor.as_str(),
-        "rebase",
-        "--interactive",
-        "--autostash",
-    ];
+    let mut args = vec!["-c", sequence_editor.as_str(), "rebase", "--interactive"];
     let parent = format!("{base}^");
-    if base_has_parent {
-        args.push(&parent);
-    } else {
-        args.push("--root");
-    }
+    push_base_args(
+        &mut args,
+        extra_args,
+        base_has_parent.then_some(parent.as_str()),
+    );
 
     let status = git_cmd(workdir, &args).status();
     let _ = fs::remove_file(&todo_path);
@@ -287,10 +629,44 @@ pub fn rebase_in_progress(workdir: &Path) -> bool {
 pub struct RebasingEntry {
     pub hash: String,
     pub message: String,
+    /// The todo action of a pending entry (`Pick` for the stopped commit)
+    pub action: RebaseAction,
     /// true = the commit currently stopped on (conflict); false = pending in todo
     pub is_current: bool,
 }
 
+fn rebase_todo_path(workdir: &Path) -> std::path::PathBuf {
+    workdir
+        .join(".git")
+        .join("rebase-merge")
+        .join("git-rebase-todo")
+}
+
+/// Reads the remaining todo list of a stopped interactive rebase.
+pub fn read_rebase_todo(workdir: &Path) -> MagiResult<Vec<RebaseTodoEntry>> {
+    let content = fs::read_to_string(rebase_todo_path(workdir))
+        .map_err(|_| MagiError::Generic("No interactive rebase in progress".to_string()))?;
+    Ok(parse_todo(&content))
+}
+
+/// Replaces the remaining todo list of a stopped interactive rebase, like
+/// saving the file opened by `git rebase --edit-todo`. Git picks the new
+/// list up on `git rebase --continue`.
+pub fn write_rebase_todo(workdir: &Path, entries: &[RebaseTodoEntry]) -> MagiResult<()> {
+    let path = rebase_todo_path(workdir);
+    if !path.exists() {
+        return Err(MagiError::Generic(
+            "No interactive rebase in progress".to_string(),
+        ));
+    }
+    let content: String = entries
+        .iter()
+        .map(|e| format!("{}\n", e.to_todo_line()))
+        .collect();
+    fs::write(path, content)?;
+    Ok(())
+}
+
 /// Returns the list of rebasing entries when a rebase sequence is in progress.
 /// The stopped commit (if any) comes first, followed by pending todo entries.
 pub fn get_rebasing_entries(workdir: &Path) -> Vec<RebasingEntry> {
@@ -312,38 +688,23 @@ pub fn get_rebasing_entries(workdir: &Path) -> Vec<RebasingEntry> {
             entries.push(RebasingEntry {
                 hash: short_hash,
                 message,
+                action: RebaseAction::Pick,
                 is_current: true,
             });
         }
     }
 
-    // Pending commits from git-rebase-todo
-    let todo_path = merge_dir.join("git-rebase-todo");
-    if let Ok(content) = fs::read_to_string(&todo_path) {
-        for line in content.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with('#') {
-                continue;
-            }
-            // Format: "<cmd> <hash> <message>"
-            let parts: Vec<&str> = line.splitn(3, ' ').collect();
-            if parts.len() < 2 {
+    // Pending commits from git-rebase-todo; exec/label/reset/merge/break
+    // lines are not shown
+    if let Ok(content) = fs::read_to_string(merge_dir.join("git-rebase-todo")) {
+        for todo_entry in parse_todo(&content) {
+            if !todo_entry.action.is_commit() {
                 continue;
             }
-            // Only show commit-based commands; skip exec/label/reset/merge/break
-            match parts[0] {
-                "pick" | "p" | "edit" | "e" | "reword" | "r" | "squash" | "s" | "fixup" | "f"
-                | "drop" | "d" => {}
-                _ => continue,
-            }
-            let short_hash: String = parts[1].chars().take(7).collect();
-            let message = parts
-                .get(2)
-                .map(|s| s.trim().to_string())
-                .unwrap_or_default();
             entries.push(RebasingEntry {
-                hash: short_hash,
-                message,
+                hash: todo_entry.hash.chars().take(7).collect(),
+                message: todo_entry.message.trim().to_string(),
+                action: todo_entry.action,
                 is_current: false,
             });
         }
@@ -379,6 +740,7 @@ pub fn get_rebasing_lines(workdir: &Path) -> MagiResult<Vec<crate::model::Line>>
             content: LineContent::RebasingEntry {
                 hash: entry.hash,
                 message: entry.message,
+                action: entry.action,
                 is_current: entry.is_current,
             },
             section: Some(SectionType::Rebasing),
@@ -474,7 +836,7 @@ mod tests {
         let mut entries = get_interactive_rebase_commits(workdir, &base_hash, true).unwrap();
         entries[0].action = RebaseAction::Drop;
 
-        let result = start_interactive_rebase(workdir, &base_hash, true, &entries).unwrap();
+        let result = start_interactive_rebase(workdir, &base_hash, true, &entries, &[]).unwrap();
 
         assert!(result.success);
         assert_eq!(log_subjects(workdir), vec!["Commit B", "Initial commit"]);
@@ -493,7 +855,7 @@ mod tests {
         let mut entries = get_interactive_rebase_commits(workdir, &base_hash, true).unwrap();
         entries[1].action = RebaseAction::Fixup;
 
-        let result = start_interactive_rebase(workdir, &base_hash, true, &entries).unwrap();
+        let result = start_interactive_rebase(workdir, &base_hash, true, &entries, &[]).unwrap();
 
         assert!(result.success);
         // Commit B is folded into Commit A; its file changes survive
@@ -512,7 +874,7 @@ mod tests {
         let mut entries = get_interactive_rebase_commits(workdir, &base_hash, true).unwrap();
         entries.swap(0, 1);
 
-        let result = start_interactive_rebase(workdir, &base_hash, true, &entries).unwrap();
+        let result = start_interactive_rebase(workdir, &base_hash, true, &entries, &[]).unwrap();
 
         assert!(result.success);
         assert_eq!(
@@ -529,11 +891,258 @@ mod tests {
         let workdir = test_repo.repo_path();
 
         let entries = get_interactive_rebase_commits(workdir, &base_hash, true).unwrap();
-        start_interactive_rebase(workdir, &base_hash, true, &entries).unwrap();
+        start_interactive_rebase(workdir, &base_hash, true, &entries, &[]).unwrap();
 
         assert!(!workdir.join(".git").join("magi-rebase-todo").exists());
     }
 
+    #[test]
+    fn test_parse_todo_reads_instructions_and_fixup_variants() {
+        let content = "\
+label onto
+# a comment
+
+reset onto
+pick abc First
+fixup -C def Second
+f -c 123 Third
+exec make test
+break
+merge -C 456 feature # Merge branch 'feature'
+update-ref refs/heads/feature
+noop
+";
+        let entries = parse_todo(content);
+        let actions: Vec<RebaseAction> = entries.iter().map(|e| e.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                RebaseAction::Label,
+                RebaseAction::Reset,
+                RebaseAction::Pick,
+                RebaseAction::FixupKeepMessage,
+                RebaseAction::FixupEditMessage,
+                RebaseAction::Exec,
+                RebaseAction::Break,
+                RebaseAction::Merge,
+                RebaseAction::UpdateRef,
+            ]
+        );
+        assert_eq!(entries[3].hash, "def");
+        assert_eq!(entries[3].message, "Second");
+        assert_eq!(entries[5].message, "make test");
+        assert_eq!(entries[7].hash, "456");
+        assert_eq!(entries[7].label(), Some("feature"));
+        assert_eq!(entries[1].label(), Some("onto"));
+    }
+
+    #[test]
+    fn test_todo_lines_round_trip() {
+        let content = "\
+label onto
+reset onto
+pick abc First
+fixup -C def Second
+exec make test
+break
+merge -C 456 feature # Merge branch 'feature'
+update-ref refs/heads/feature
+";
+        let written: String = parse_todo(content)
+            .iter()
+            .map(|e| format!("{}\n", e.to_todo_line()))
+            .collect();
+        assert_eq!(written, content);
+    }
+
+    #[test]
+    fn test_todo_needs_git_for_rebase_merges_and_update_refs() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "a", "Commit A");
+        let base = test_repo.head_hash();
+        test_repo.create_branch("stacked");
+        test_repo.commit_file("b.txt", "b", "Commit B");
+        let workdir = test_repo.repo_path();
+        let needs_git = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+            todo_needs_git(workdir, &base, true, &args)
+        };
+
+        assert!(!needs_git(&["--keep-empty"]));
+        assert!(needs_git(&["--exec=make"]));
+        assert!(needs_git(&["--update-refs"]));
+        // There are no merges to rebase
+        assert!(!needs_git(&["--rebase-merges"]));
+
+        git_cmd(workdir, &["config", "rebase.updateRefs", "true"])
+            .status()
+            .unwrap();
+        assert!(needs_git(&[]));
+    }
+
+    #[test]
+    fn test_todo_needs_git_only_for_branches_in_the_rebased_commits() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "a", "Commit A");
+        test_repo.create_branch("below");
+        test_repo.commit_file("b.txt", "b", "Commit B");
+        let base = test_repo.head_hash();
+        test_repo.commit_file("c.txt", "c", "Commit C");
+        let workdir = test_repo.repo_path();
+        git_cmd(workdir, &["config", "rebase.updateRefs", "true"])
+            .status()
+            .unwrap();
+
+        // `below` points before the rebased commits and `main` is rebased
+        assert!(!todo_needs_git(workdir, &base, true, &[]));
+    }
+
+    #[test]
+    fn test_generate_interactive_rebase_todo_keeps_update_ref_lines() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "a", "Commit A");
+        let base_hash = test_repo.head_hash();
+        test_repo.create_branch("stacked");
+        test_repo.commit_file("b.txt", "b", "Commit B");
+        test_repo.write_file_content("dirty.txt", "dirty");
+        let workdir = test_repo.repo_path();
+        let head_before = test_repo.head_hash();
+
+        let entries = generate_interactive_rebase_todo(
+            workdir,
+            &base_hash,
+            true,
+            &["--update-refs".to_string()],
+        )
+        .unwrap();
+
+        let lines: Vec<String> = entries.iter().map(|e| e.to_todo_line()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                format!("pick {} Commit A", base_hash),
+                "update-ref refs/heads/stacked".to_string(),
+                format!("pick {} Commit B", head_before),
+            ]
+        );
+        // Nothing was rebased, and the worktree is untouched
+        assert_eq!(test_repo.head_hash(), head_before);
+        assert!(!rebase_in_progress(workdir));
+        assert!(workdir.join("dirty.txt").exists());
+        assert!(
+            !workdir
+                .join(".git")
+                .join("magi-rebase-todo-generated")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn test_generate_interactive_rebase_todo_refuses_uncommitted_changes() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "a", "Commit A");
+        let base_hash = test_repo.head_hash();
+        test_repo.create_branch("stacked");
+        test_repo.commit_file("b.txt", "b", "Commit B");
+        test_repo
+            .write_file_content("a.txt", "staged")
+            .stage_files(&["a.txt"]);
+        let workdir = test_repo.repo_path();
+
+        let result = generate_interactive_rebase_todo(
+            workdir,
+            &base_hash,
+            true,
+            &["--update-refs".to_string()],
+        );
+
+        assert!(result.is_err());
+        // The staged change is still staged
+        let status = git_cmd(workdir, &["status", "--porcelain"])
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&status.stdout), "M  a.txt\n");
+        assert!(!rebase_in_progress(workdir));
+    }
+
+    #[test]
+    fn test_generate_interactive_rebase_todo_with_rebase_merges() {
+        let test_repo = TestRepo::new();
+        let base_hash = test_repo.head_hash();
+        let workdir = test_repo.repo_path();
+        test_repo.create_branch("feature");
+        git_cmd(workdir, &["checkout", "-q", "feature"])
+            .status()
+            .unwrap();
+        test_repo.commit_file("feature.txt", "feature", "Feature commit");
+        git_cmd(workdir, &["checkout", "-q", "-"]).status().unwrap();
+        git_cmd(
+            workdir,
+            &["merge", "-q", "--no-ff", "-m", "Merge feature", "feature"],
+        )
+        .status()
+        .unwrap();
+
+        let entries = generate_interactive_rebase_todo(
+            workdir,
+            &base_hash,
+            false,
+            &["--rebase-merges".to_string()],
+        )
+        .unwrap();
+
+        let actions: Vec<RebaseAction> = entries.iter().map(|e| e.action).collect();
+        assert!(actions.contains(&RebaseAction::Label));
+        assert!(actions.contains(&RebaseAction::Reset));
+        let merge = entries
+            .iter()
+            .find(|e| e.action == RebaseAction::Merge)
+            .unwrap();
+        assert!(merge.message.ends_with("# Merge feature"));
+
+        // Running the preserved todo recreates the merge
+        let result = start_interactive_rebase(workdir, &base_hash, false, &entries, &[]).unwrap();
+        assert!(result.success);
+        assert_eq!(log_subjects(workdir)[0], "Merge feature");
+    }
+
+    #[test]
+    fn test_start_interactive_rebase_runs_exec_line() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "a", "Commit A");
+        let base_hash = test_repo.head_hash();
+        let workdir = test_repo.repo_path();
+
+        let mut entries = get_interactive_rebase_commits(workdir, &base_hash, true).unwrap();
+        entries.push(RebaseTodoEntry::instruction(
+            RebaseAction::Exec,
+            "touch exec-ran",
+        ));
+
+        let result = start_interactive_rebase(workdir, &base_hash, true, &entries, &[]).unwrap();
+
+        assert!(result.success);
+        assert!(workdir.join("exec-ran").exists());
+    }
+
+    #[test]
+    fn test_start_interactive_rebase_fixup_keep_message_uses_fixup_message() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "a", "Commit A");
+        let base_hash = test_repo.head_hash();
+        test_repo.commit_file("b.txt", "b", "Commit B");
+        let workdir = test_repo.repo_path();
+
+        let mut entries = get_interactive_rebase_commits(workdir, &base_hash, true).unwrap();
+        entries[1].action = RebaseAction::FixupKeepMessage;
+
+        let result = start_interactive_rebase(workdir, &base_hash, true, &entries, &[]).unwrap();
+
+        assert!(result.success);
+        assert_eq!(log_subjects(workdir), vec!["Commit B", "Initial commit"]);
+        assert!(workdir.join("a.txt").exists());
+    }
+
     #[test]
     fn test_run_modify_commit_stops_at_commit() {
         let test_repo = TestRepo::new();
@@ -542,7 +1151,7 @@ mod tests {
         test_repo.commit_file("b.txt", "b", "Commit B");
         let workdir = test_repo.repo_path();
 
-        let result = run_modify_commit(workdir, &target).unwrap();
+        let result = run_modify_commit(workdir, &target, &[]).unwrap();
 
         assert!(result.success);
         // The rebase stops at the target commit, leaving it as HEAD for amending
@@ -563,7 +1172,7 @@ mod tests {
         test_repo.commit_file("b.txt", "b", "Commit B");
         let workdir = test_repo.repo_path();
 
-        let result = run_modify_commit(workdir, &target[..7]).unwrap();
+        let result = run_modify_commit(workdir, &target[..7], &[]).unwrap();
 
         assert!(result.success);
         assert!(rebase_in_progress(workdir));
@@ -579,7 +1188,7 @@ mod tests {
         test_repo.commit_file("a.txt", "a", "Commit A");
         let workdir = test_repo.repo_path();
 
-        let result = run_modify_commit(workdir, &root).unwrap();
+        let result = run_modify_commit(workdir, &root, &[]).unwrap();
 
         assert!(result.success);
         assert!(rebase_in_progress(workdir));
@@ -593,7 +1202,7 @@ mod tests {
         let test_repo = TestRepo::new();
         let workdir = test_repo.repo_path();
 
-        let result = run_modify_commit(workdir, "0000000000000000000000000000000000000000");
+        let result = run_modify_commit(workdir, "0000000000000000000000000000000000000000", &[]);
 
         assert!(result.is_err());
         assert!(!rebase_in_progress(workdir));
@@ -608,7 +1217,7 @@ mod tests {
         let workdir = test_repo.repo_path();
 
         set_reword_editor();
-        let result = run_reword_commit(workdir, &target).unwrap();
+        let result = run_reword_commit(workdir, &target, &[]).unwrap();
 
         assert!(result.success);
         // The rebase runs to completion; only the message changes
@@ -629,7 +1238,7 @@ mod tests {
         let workdir = test_repo.repo_path();
 
         set_reword_editor();
-        let result = run_reword_commit(workdir, &root).unwrap();
+        let result = run_reword_commit(workdir, &root, &[]).unwrap();
 
         assert!(result.success);
         assert!(!rebase_in_progress(workdir));
@@ -644,7 +1253,7 @@ mod tests {
         test_repo.commit_file("b.txt", "b", "Commit B");
         let workdir = test_repo.repo_path();
 
-        let result = run_remove_commit(workdir, &target).unwrap();
+        let result = run_remove_commit(workdir, &target, &[]).unwrap();
 
         assert!(result.success);
         // The rebase runs to completion; the commit and its changes are gone
@@ -662,7 +1271,7 @@ mod tests {
         test_repo.commit_file("b.txt", "b", "Commit B");
         let workdir = test_repo.repo_path();
 
-        let result = run_remove_commit(workdir, &target[..7]).unwrap();
+        let result = run_remove_commit(workdir, &target[..7], &[]).unwrap();
 
         assert!(result.success);
         assert!(!rebase_in_progress(workdir));
@@ -677,7 +1286,7 @@ mod tests {
         let head = test_repo.head_hash();
         let workdir = test_repo.repo_path();
 
-        let result = run_remove_commit(workdir, &head).unwrap();
+        let result = run_remove_commit(workdir, &head, &[]).unwrap();
 
         assert!(result.success);
         assert!(!rebase_in_progress(workdir));
@@ -694,7 +1303,7 @@ mod tests {
         test_repo.commit_file("a.txt", "b", "Commit B");
         let workdir = test_repo.repo_path();
 
-        let result = run_remove_commit(workdir, &target).unwrap();
+        let result = run_remove_commit(workdir, &target, &[]).unwrap();
 
         assert!(!result.success);
         assert!(rebase_in_progress(workdir));
@@ -707,43 +1316,12 @@ mod tests {
         let test_repo = TestRepo::new();
         let workdir = test_repo.repo_path();
 
-        let result = run_remove_commit(workdir, "0000000000000000000000000000000000000000");
+        let result = run_remove_commit(workdir, "0000000000000000000000000000000000000000", &[]);
 
         assert!(result.is_err());
         assert!(!rebase_in_progress(workdir));
     }
 
-    /// Points main's upstream at refs/remotes/origin/main frozen at the
-    /// current HEAD, so commits made afterwards are "unpushed".
-    fn set_upstream_at_head(test_repo: &TestRepo) {
-        let workdir = test_repo.repo_path();
-        let head = test_repo.head_hash();
-        git_cmd(workdir, &["update-ref", "refs/remotes/origin/main", &head])
-            .status()
-            .unwrap();
-        // @{upstream} only resolves when the remote has a fetch refspec
-        // mapping refs/heads/* into refs/remotes/origin/*.
-        git_cmd(workdir, &["config", "remote.origin.url", "."])
-            .status()
-            .unwrap();
-        git_cmd(
-            workdir,
-            &[
-                "config",
-                "remote.origin.fetch",
-                "+refs/heads/*:refs/remotes/origin/*",
-            ],
-        )
-        .status()
-        .unwrap();
-        git_cmd(workdir, &["config", "branch.main.remote", "origin"])
-            .status()
-            .unwrap();
-        git_cmd(workdir, &["config", "branch.main.merge", "refs/heads/main"])
-            .status()
-            .unwrap();
-    }
-
     #[test]
     fn test_get_upstream_merge_base_no_upstream_returns_none() {
         let test_repo = TestRepo::new();
@@ -756,7 +1334,7 @@ mod tests {
         let test_repo = TestRepo::new();
         test_repo.commit_file("a.txt", "a", "Commit A");
         let pushed = test_repo.head_hash();
-        set_upstream_at_head(&test_repo);
+        test_repo.set_upstream_at_head();
         test_repo.commit_file("b.txt", "b", "Commit B");
 
         assert_eq!(get_upstream_merge_base(test_repo.repo_path()), Some(pushed));
@@ -770,7 +1348,7 @@ mod tests {
         test_repo.commit_file("a.txt", "fixed", "fixup! Target");
         let workdir = test_repo.repo_path();
 
-        let result = run_autosquash(workdir, &base, false).unwrap();
+        let result = run_autosquash(workdir, &base, false, &[]).unwrap();
 
         assert!(result.success);
         assert!(!rebase_in_progress(workdir));
@@ -786,7 +1364,7 @@ mod tests {
         test_repo.commit_file("a.txt", "fixed", "fixup! Target");
         let workdir = test_repo.repo_path();
 
-        let result = run_autosquash(workdir, &target, true).unwrap();
+        let result = run_autosquash(workdir, &target, true, &[]).unwrap();
 
         assert!(result.success);
         assert!(!rebase_in_progress(workdir));
@@ -801,7 +1379,7 @@ mod tests {
         test_repo.commit_file("a.txt", "a", "Commit A");
         let workdir = test_repo.repo_path();
 
-        let result = run_autosquash(workdir, &base, false).unwrap();
+        let result = run_autosquash(workdir, &base, false, &[]).unwrap();
 
         assert!(result.success);
         assert!(!rebase_in_progress(workdir));
@@ -813,7 +1391,12 @@ mod tests {
         let test_repo = TestRepo::new();
         let workdir = test_repo.repo_path();
 
-        let result = run_autosquash(workdir, "0000000000000000000000000000000000000000", false);
+        let result = run_autosquash(
+            workdir,
+            "0000000000000000000000000000000000000000",
+            false,
+            &[],
+        );
 
         assert!(result.is_err());
         assert!(!rebase_in_progress(workdir));
@@ -824,7 +1407,7 @@ mod tests {
         let test_repo = TestRepo::new();
         let workdir = test_repo.repo_path();
 
-        let result = run_reword_commit(workdir, "0000000000000000000000000000000000000000");
+        let result = run_reword_commit(workdir, "0000000000000000000000000000000000000000", &[]);
 
         assert!(result.is_err());
         assert!(!rebase_in_progress(workdir));
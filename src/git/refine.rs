@@ -0,0 +1,210 @@
+//! Word-level refinement of diffs, like Magit's `magit-diff-refine-hunk`:
+//! each removed line is compared with the added line at the same position
+//! of the block that replaces it, and the words that differ are marked.
+
+use std::ops::Range;
+
+/// Changed byte ranges of a line
+pub type Refinement = Vec<Range<usize>>;
+
+/// Lines with more tokens than this (product of both sides) are not refined,
+/// to keep the comparison cheap on huge generated lines.
+const MAX_TOKEN_PAIRS: usize = 40_000;
+
+/// How a line of a hunk changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Removed,
+    Added,
+    /// Context lines, and anything else that ends a block of changes
+    Other,
+}
+
+/// Computes the changed byte ranges of every line of a hunk. Removed lines
+/// are paired with the added lines that directly follow their block; lines
+/// without a partner, and pairs with nothing in common, get no ranges (the
+/// whole line is already highlighted).
+pub fn refine_hunk(lines: &[(Change, &str)]) -> Vec<Refinement> {
+    let mut refined = vec![Vec::new(); lines.len()];
+    let mut start = 0;
+    while start < lines.len() {
+        let removed_end = start
+            + lines[start..]
+                .iter()
+                .take_while(|(change, _)| *change == Change::Removed)
+                .count();
+        let added_end = removed_end
+            + lines[removed_end..]
+                .iter()
+                .take_while(|(change, _)| *change == Change::Added)
+                .count();
+
+        for (old, new) in (start..removed_end).zip(removed_end..added_end) {
+            if let Some((old_ranges, new_ranges)) = refine_pair(lines[old].1, lines[new].1) {
+                refined[old] = old_ranges;
+                refined[new] = new_ranges;
+            }
+        }
+        start = added_end.max(start + 1);
+    }
+    refined
+}
+
+/// Compares two lines word by word and returns the byte ranges of each that
+/// are not part of their longest common subsequence of words.
+fn refine_pair(old: &str, new: &str) -> Option<(Refinement, Refinement)> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    if old_tokens.len() * new_tokens.len() > MAX_TOKEN_PAIRS {
+        return None;
+    }
+
+    let (old_common, new_common) = common_tokens(old, &old_tokens, new, &new_tokens);
+    let shares_words = old_tokens
+        .iter()
+        .zip(&old_common)
+        .any(|(token, common)| *common && !old[token.clone()].trim().is_empty());
+    if !shares_words {
+        return None;
+    }
+
+    Some((
+        changed_ranges(&old_tokens, &old_common),
+        changed_ranges(&new_tokens, &new_common),
+    ))
+}
+
+/// Splits a line into words (runs of alphanumerics and `_`), runs of
+/// whitespace, and single punctuation characters.
+fn tokenize(text: &str) -> Vec<Range<usize>> {
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            1
+        } else if c.is_whitespace() {
+            2
+        } else {
+            0
+        }
+    };
+
+    let mut tokens: Vec<Range<usize>> = Vec::new();
+    let mut previous = None;
+    for (index, c) in text.char_indices() {
+        let current = class(c);
+        match tokens.last_mut() {
+            Some(token) if current != 0 && previous == Some(current) => {
+                token.end = index + c.len_utf8();
+            }
+            _ => tokens.push(index..index + c.len_utf8()),
+        }
+        previous = Some(current);
+    }
+    tokens
+}
+
+/// Marks the tokens of both sides that belong to their longest common
+/// subsequence.
+fn common_tokens(
+    old: &str,
+    old_tokens: &[Range<usize>],
+    new: &str,
+    new_tokens: &[Range<usize>],
+) -> (Vec<bool>, Vec<bool>) {
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+    let same = |i: usize, j: usize| old[old_tokens[i].clone()] == new[new_tokens[j].clone()];
+
+    // lengths[i][j]: LCS length of old_tokens[i..] and new_tokens[j..]
+    let mut lengths = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if same(i, j) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut old_common = vec![false; n];
+    let mut new_common = vec![false; m];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if same(i, j) {
+            old_common[i] = true;
+            new_common[j] = true;
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    (old_common, new_common)
+}
+
+/// Joins adjacent changed tokens into ranges.
+fn changed_ranges(tokens: &[Range<usize>], common: &[bool]) -> Refinement {
+    let mut ranges = Refinement::new();
+    for (token, _) in tokens.iter().zip(common).filter(|(_, common)| !**common) {
+        match ranges.last_mut() {
+            Some(range) if range.end == token.start => range.end = token.end,
+            _ => ranges.push(token.clone()),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The text of each changed range
+    fn words<'a>(text: &'a str, ranges: &[Range<usize>]) -> Vec<&'a str> {
+        ranges.iter().map(|range| &text[range.clone()]).collect()
+    }
+
+    #[test]
+    fn test_refine_pair_marks_changed_word() {
+        let (old, new) = refine_pair("let x = foo(1);", "let x = bar(1);").unwrap();
+        assert_eq!(words("let x = foo(1);", &old), vec!["foo"]);
+        assert_eq!(words("let x = bar(1);", &new), vec!["bar"]);
+    }
+
+    #[test]
+    fn test_refine_pair_marks_insertion_on_one_side_only() {
+        let (old, new) = refine_pair("a b", "a new b").unwrap();
+        assert!(old.is_empty());
+        assert_eq!(words("a new b", &new), vec!["new "]);
+    }
+
+    #[test]
+    fn test_refine_pair_without_common_words_is_not_refined() {
+        assert_eq!(refine_pair("alpha beta", "gamma delta"), None);
+    }
+
+    #[test]
+    fn test_refine_hunk_pairs_blocks_by_position() {
+        let lines = [
+            (Change::Other, "context"),
+            (Change::Removed, "one two"),
+            (Change::Removed, "three"),
+            (Change::Added, "one 2"),
+            (Change::Other, "context"),
+        ];
+
+        let refined = refine_hunk(&lines);
+
+        assert_eq!(words("one two", &refined[1]), vec!["two"]);
+        assert!(refined[2].is_empty());
+        assert_eq!(words("one 2", &refined[3]), vec!["2"]);
+        assert!(refined[0].is_empty() && refined[4].is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_splits_words_spaces_and_punctuation() {
+        let text = "foo(bar,  baz)";
+        let tokens: Vec<&str> = tokenize(text).into_iter().map(|r| &text[r]).collect();
+        assert_eq!(tokens, vec!["foo", "(", "bar", ",", "  ", "baz", ")"]);
+    }
+}
@@ -1,24 +1,34 @@
 use std::path::Path;
 
-use super::discard::{apply_patch_reverse, build_partial_hunk_patch, get_staged_file_diff};
-use super::stage::extract_hunk_from_diff;
+use super::DiffSettings;
+use super::discard::{apply_patch_reverse, build_partial_hunk_patch};
+use super::stage::{
+    extract_hunk_from_diff, get_staged_file_diff, staged_file_diff, staged_renames,
+};
 use crate::errors::MagiResult;
 
 /// Reverses a patch in the working tree by piping it to `git apply --reverse`.
 /// The index is left untouched, mirroring magit's `magit-reverse`.
-pub fn reverse_patch<P: AsRef<Path>>(repo_path: P, patch: &str) -> MagiResult<()> {
-    apply_patch_reverse(repo_path, patch)
+pub fn reverse_patch<P: AsRef<Path>>(
+    repo_path: P,
+    patch: &str,
+    settings: &DiffSettings,
+) -> MagiResult<()> {
+    apply_patch_reverse(repo_path, patch, settings)
 }
 
 /// Reverses the staged changes of the given files in the working tree only.
 /// The changes stay staged in the index; only the working tree is undone.
 pub fn reverse_staged_files<P: AsRef<Path>>(repo_path: P, files: &[&str]) -> MagiResult<()> {
+    // Whole files ignore the diff settings
+    let settings = DiffSettings::default();
+    let renames = staged_renames(&repo_path)?;
     for file in files {
-        let diff_output = get_staged_file_diff(&repo_path, file)?;
+        let diff_output = staged_file_diff(&repo_path, file, &renames, &settings)?;
         if diff_output.trim().is_empty() {
             continue; // No staged changes for this file
         }
-        apply_patch_reverse(&repo_path, &diff_output)?;
+        apply_patch_reverse(&repo_path, &diff_output, &settings)?;
     }
     Ok(())
 }
@@ -28,8 +38,9 @@ pub fn reverse_staged_hunk<P: AsRef<Path>>(
     repo_path: P,
     file: &str,
     hunk_index: usize,
+    settings: &DiffSettings,
 ) -> MagiResult<()> {
-    reverse_staged_hunks(repo_path, file, &[hunk_index])
+    reverse_staged_hunks(repo_path, file, &[hunk_index], settings)
 }
 
 /// Reverses multiple staged hunks of the same file in the working tree only.
@@ -39,8 +50,9 @@ pub fn reverse_staged_hunks<P: AsRef<Path>>(
     repo_path: P,
     file: &str,
     hunk_indices: &[usize],
+    settings: &DiffSettings,
 ) -> MagiResult<()> {
-    let diff_output = get_staged_file_diff(&repo_path, file)?;
+    let diff_output = get_staged_file_diff(&repo_path, file, settings)?;
 
     let mut indices: Vec<usize> = hunk_indices.to_vec();
     indices.sort_unstable();
@@ -57,7 +69,7 @@ pub fn reverse_staged_hunks<P: AsRef<Path>>(
         patch.push('\n');
     }
 
-    apply_patch_reverse(&repo_path, &patch)
+    apply_patch_reverse(&repo_path, &patch, settings)
 }
 
 /// Reverses specific lines within a staged hunk in the working tree only.
@@ -66,8 +78,9 @@ pub fn reverse_staged_lines<P: AsRef<Path>>(
     file: &str,
     hunk_index: usize,
     selected_line_indices: &[usize],
+    settings: &DiffSettings,
 ) -> MagiResult<()> {
-    let diff_output = get_staged_file_diff(&repo_path, file)?;
+    let diff_output = get_staged_file_diff(&repo_path, file, settings)?;
     let patch = build_partial_hunk_patch(&diff_output, hunk_index, selected_line_indices)?;
-    apply_patch_reverse(&repo_path, &patch)
+    apply_patch_reverse(&repo_path, &patch, settings)
 }
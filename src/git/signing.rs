@@ -0,0 +1,280 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::git::git_cmd;
+
+/// Verification status of a commit signature, as reported by git's `%G?`
+/// format placeholder. Unsigned commits (`N`) have no status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// `G`: good signature from a trusted key
+    Good,
+    /// `U`: good signature with unknown validity
+    Untrusted,
+    /// `X`: good signature that has expired
+    Expired,
+    /// `Y`: good signature made by an expired key
+    ExpiredKey,
+    /// `R`: good signature made by a revoked key
+    Revoked,
+    /// `B`: bad signature
+    Bad,
+    /// `E`: the signature cannot be checked (e.g. missing key)
+    Unverifiable,
+}
+
+impl SignatureStatus {
+    /// Parses a `%G?` code. Returns `None` for unsigned commits and
+    /// anything unknown.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim() {
+            "G" => Some(SignatureStatus::Good),
+            "U" => Some(SignatureStatus::Untrusted),
+            "X" => Some(SignatureStatus::Expired),
+            "Y" => Some(SignatureStatus::ExpiredKey),
+            "R" => Some(SignatureStatus::Revoked),
+            "B" => Some(SignatureStatus::Bad),
+            "E" => Some(SignatureStatus::Unverifiable),
+            _ => None,
+        }
+    }
+
+    /// Single-character marker shown next to the commit hash.
+    pub fn marker(&self) -> &'static str {
+        match self {
+            SignatureStatus::Good => "✓",
+            SignatureStatus::Untrusted | SignatureStatus::Unverifiable => "?",
+            SignatureStatus::Expired | SignatureStatus::ExpiredKey => "!",
+            SignatureStatus::Revoked | SignatureStatus::Bad => "✗",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            SignatureStatus::Good => "Good signature",
+            SignatureStatus::Untrusted => "Good signature with unknown validity",
+            SignatureStatus::Expired => "Good signature that has expired",
+            SignatureStatus::ExpiredKey => "Good signature made by an expired key",
+            SignatureStatus::Revoked => "Good signature made by a revoked key",
+            SignatureStatus::Bad => "Bad signature",
+            SignatureStatus::Unverifiable => "Signature cannot be checked",
+        }
+    }
+
+    /// True for statuses that should be flagged as a problem.
+    pub fn is_bad(&self) -> bool {
+        matches!(self, SignatureStatus::Bad | SignatureStatus::Revoked)
+    }
+}
+
+/// Returns the signature status and signer (`%GS`, may be empty) of
+/// `revision`, or `None` when the commit is unsigned.
+pub fn commit_signature(workdir: &Path, revision: &str) -> Option<(SignatureStatus, String)> {
+    let output = git_cmd(
+        workdir,
+        &["log", "-1", "--format=%G?%x00%GS", revision, "--"],
+    )
+    .output()
+    .ok()
+    .filter(|o| o.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (code, signer) = stdout.trim_end().split_once('\0')?;
+    let status = SignatureStatus::from_code(code)?;
+    Some((status, signer.to_string()))
+}
+
+/// Formats the `Signature:` header line shown in commit previews.
+pub fn signature_header(status: SignatureStatus, signer: &str) -> String {
+    if signer.is_empty() {
+        format!("Signature: {} {}", status.marker(), status.description())
+    } else {
+        format!(
+            "Signature: {} {} from {}",
+            status.marker(),
+            status.description(),
+            signer
+        )
+    }
+}
+
+/// A key offered by the `-S` (`--gpg-sign`) argument's key picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningKey {
+    /// What the picker shows
+    pub label: String,
+    /// What is passed to `--gpg-sign=`
+    pub key: String,
+}
+
+/// Lists the keys that can be used for signing: the configured
+/// `user.signingKey` first, then the OpenPGP secret keys known to
+/// `gpg.program` (skipped when `gpg.format` is `ssh` or `x509`).
+pub fn signing_keys(workdir: &Path) -> Vec<SigningKey> {
+    let mut keys = Vec::new();
+    if let Some(key) = git_config(workdir, "user.signingKey") {
+        keys.push(SigningKey {
+            label: format!("{} (user.signingKey)", key),
+            key,
+        });
+    }
+
+    let format = git_config(workdir, "gpg.format").unwrap_or_else(|| "openpgp".to_string());
+    if format == "openpgp" {
+        let program = git_config(workdir, "gpg.openpgp.program")
+            .or_else(|| git_config(workdir, "gpg.program"))
+            .unwrap_or_else(|| "gpg".to_string());
+        if let Ok(output) = Command::new(program)
+            .args(["--list-secret-keys", "--with-colons"])
+            .output()
+            && output.status.success()
+        {
+            for key in parse_gpg_secret_keys(&String::from_utf8_lossy(&output.stdout)) {
+                if !keys.iter().any(|k| k.key == key.key) {
+                    keys.push(key);
+                }
+            }
+        }
+    }
+    keys
+}
+
+/// Parses `gpg --list-secret-keys --with-colons` output into keys labelled
+/// with their primary user id.
+fn parse_gpg_secret_keys(output: &str) -> Vec<SigningKey> {
+    let mut keys: Vec<SigningKey> = Vec::new();
+    let mut pending: Option<String> = None;
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split(':').collect();
+        match fields.first() {
+            Some(&"sec") => {
+                if let Some(key) = pending.take() {
+                    keys.push(SigningKey {
+                        label: key.clone(),
+                        key,
+                    });
+                }
+                pending = fields.get(4).map(|id| id.to_string());
+            }
+            Some(&"uid") => {
+                if let Some(key) = pending.take() {
+                    let uid = fields.get(9).copied().unwrap_or_default();
+                    keys.push(SigningKey {
+                        label: format!("{} {}", key, uid),
+                        key,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(key) = pending {
+        keys.push(SigningKey {
+            label: key.clone(),
+            key,
+        });
+    }
+    keys
+}
+
+fn git_config(workdir: &Path, name: &str) -> Option<String> {
+    let output = git_cmd(workdir, &["config", "--get", name]).output().ok()?;
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !value.is_empty()).then_some(value)
+}
+
+/// The `--gpg-sign=<key>` flag for a key picked with the `-S` argument.
+pub fn gpg_sign_flag(key: &str) -> String {
+    format!("--gpg-sign={}", key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_repo::TestRepo;
+
+    #[test]
+    fn test_from_code_treats_unsigned_as_none() {
+        assert_eq!(SignatureStatus::from_code("G"), Some(SignatureStatus::Good));
+        assert_eq!(
+            SignatureStatus::from_code("B\n"),
+            Some(SignatureStatus::Bad)
+        );
+        assert_eq!(SignatureStatus::from_code("N"), None);
+        assert_eq!(SignatureStatus::from_code(""), None);
+    }
+
+    #[test]
+    fn test_parse_gpg_secret_keys_uses_first_uid() {
+        let output = "\
+sec:u:255:22:AAAA1111BBBB2222:1700000000:::u:::scESC:::+:::ed25519:::0:
+fpr:::::::::0123456789ABCDEF0123AAAA1111BBBB2222:
+uid:u::::1700000000::HASH::Ada Lovelace <ada@example.com>::::::::::0:
+uid:u::::1700000000::HASH::Ada (work) <ada@work.example>::::::::::0:
+ssb:u:255:18:CCCC3333DDDD4444:1700000000::::::e:::+:::cv25519::
+sec:u:4096:1:EEEE5555FFFF6666:1600000000:::u:::scESC:::+:::::0:
+";
+        let keys = parse_gpg_secret_keys(output);
+        assert_eq!(
+            keys,
+            vec![
+                SigningKey {
+                    label: "AAAA1111BBBB2222 Ada Lovelace <ada@example.com>".to_string(),
+                    key: "AAAA1111BBBB2222".to_string(),
+                },
+                SigningKey {
+                    label: "EEEE5555FFFF6666".to_string(),
+                    key: "EEEE5555FFFF6666".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_signing_keys_lists_configured_key_first() {
+        let test_repo = TestRepo::new();
+        let mut config = test_repo.repo.config().unwrap();
+        config.set_str("user.signingKey", "ABCDEF").unwrap();
+        // Keep the test independent of any local gpg keyring
+        config.set_str("gpg.program", "false").unwrap();
+
+        let keys = signing_keys(test_repo.repo_path());
+
+        assert_eq!(
+            keys,
+            vec![SigningKey {
+                label: "ABCDEF (user.signingKey)".to_string(),
+                key: "ABCDEF".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_commit_signature_reports_good_ssh_signature() {
+        let test_repo = TestRepo::new();
+        let key = test_repo.with_ssh_signing_key();
+        let commit = git_cmd(
+            test_repo.repo_path(),
+            &[
+                "commit",
+                "--allow-empty",
+                "-m",
+                "Signed",
+                &gpg_sign_flag(&key),
+            ],
+        )
+        .output()
+        .unwrap();
+        assert!(commit.status.success());
+
+        assert_eq!(
+            commit_signature(test_repo.repo_path(), "HEAD"),
+            Some((SignatureStatus::Good, "test@example.com".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_commit_signature_is_none_for_unsigned_commit() {
+        let test_repo = TestRepo::new();
+        assert_eq!(commit_signature(test_repo.repo_path(), "HEAD"), None);
+    }
+}
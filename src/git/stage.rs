@@ -1,7 +1,8 @@
+use std::collections::HashMap;
 use std::path::Path;
 use std::process::Stdio;
 
-use super::git_cmd;
+use super::{DiffSettings, git_cmd};
 use crate::errors::{MagiError, MagiResult};
 
 /// Stages the specified files.
@@ -18,14 +19,43 @@ pub fn stage_files<P: AsRef<Path>>(repo_path: P, files: &[&str]) -> MagiResult<(
     Ok(())
 }
 
-/// Unstages the specified files.
+/// Adds the specified untracked files with intent to add (`git add -N`):
+/// they are recorded in the index without content, so their whole content
+/// shows as unstaged changes that can be staged hunk by hunk or line by line.
+/// If `files` is empty, this is a no-op.
+pub fn intent_to_add_files<P: AsRef<Path>>(repo_path: P, files: &[&str]) -> MagiResult<()> {
+    if files.is_empty() {
+        return Ok(());
+    }
+    let output = git_cmd(&repo_path, &["add", "--intent-to-add", "--"])
+        .args(files)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()?;
+    if !output.status.success() {
+        return Err(MagiError::Generic(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Unstages the specified files. A renamed file is unstaged with the path
+/// it was renamed from, whose deletion was staged with it.
 /// If `files` is empty, this is a no-op.
 pub fn unstage_files<P: AsRef<Path>>(repo_path: P, files: &[&str]) -> MagiResult<()> {
     if files.is_empty() {
         return Ok(());
     }
+    let renames = staged_renames(&repo_path)?;
     let _output = git_cmd(&repo_path, &["reset", "HEAD", "--"])
         .args(files)
+        .args(
+            files
+                .iter()
+                .filter_map(|file| renames.get(*file))
+                .map(|rename| &rename.from),
+        )
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output()?;
@@ -34,9 +64,14 @@ pub fn unstage_files<P: AsRef<Path>>(repo_path: P, files: &[&str]) -> MagiResult
 
 /// Stages a single hunk from a file by extracting it from `git diff` output
 /// and piping it to `git apply --cached`.
-pub fn stage_hunk<P: AsRef<Path>>(repo_path: P, file: &str, hunk_index: usize) -> MagiResult<()> {
-    let patch = extract_hunk_patch(&repo_path, file, hunk_index)?;
-    apply_patch_cached(&repo_path, &patch)
+pub fn stage_hunk<P: AsRef<Path>>(
+    repo_path: P,
+    file: &str,
+    hunk_index: usize,
+    settings: &DiffSettings,
+) -> MagiResult<()> {
+    let patch = extract_hunk_patch(&repo_path, file, hunk_index, settings)?;
+    apply_patch_cached(&repo_path, &patch, settings)
 }
 
 /// Stages specific lines within a hunk. Lines not in the selection are converted:
@@ -48,9 +83,26 @@ pub fn stage_lines<P: AsRef<Path>>(
     file: &str,
     hunk_index: usize,
     selected_line_indices: &[usize],
+    settings: &DiffSettings,
 ) -> MagiResult<()> {
-    let diff_output = get_file_diff(&repo_path, file)?;
-    let (header, hunk_lines) = extract_hunk_from_diff(&diff_output, hunk_index)?;
+    let diff_output = get_file_diff(&repo_path, file, settings)?;
+    let patch = build_staging_patch(&diff_output, hunk_index, selected_line_indices)?;
+    apply_patch_cached(&repo_path, &patch, settings)
+}
+
+/// Builds a patch containing only the selected lines of a hunk, so that the
+/// patch can be applied to the index to stage just those lines:
+/// - `+` lines not selected are removed from the patch
+/// - `-` lines not selected become context lines (prefix changed to ` `)
+///
+/// `selected_line_indices` are 0-based indices within the hunk's diff lines
+/// (not counting the hunk header).
+pub fn build_staging_patch(
+    diff_output: &str,
+    hunk_index: usize,
+    selected_line_indices: &[usize],
+) -> MagiResult<String> {
+    let (header, hunk_lines) = extract_hunk_from_diff(diff_output, hunk_index)?;
 
     // Build modified hunk with only selected lines staged
     let mut modified_lines: Vec<String> = Vec::new();
@@ -125,12 +177,190 @@ pub fn stage_lines<P: AsRef<Path>>(
     patch.push_str(&modified_lines.join("\n"));
     patch.push('\n');
 
-    apply_patch_cached(&repo_path, &patch)
+    Ok(patch)
+}
+
+/// Builds the patch of a block of a hunk on its own, like a part of a hunk
+/// split by `git add -p`: the consecutive diff lines at `line_indices`
+/// followed by the unchanged lines after them, with a header of its own.
+/// It doesn't include the other blocks' changes, so it still applies once
+/// they are staged.
+pub fn build_block_patch(
+    diff_output: &str,
+    hunk_index: usize,
+    line_indices: &[usize],
+) -> MagiResult<String> {
+    let (header, hunk_lines) = extract_hunk_from_diff(diff_output, hunk_index)?;
+    let (mut old_start, mut new_start) = parse_hunk_header_starts(hunk_lines[0])?;
+    let first = line_indices.first().copied().unwrap_or(0);
+    let last = line_indices.last().copied().unwrap_or(0);
+
+    let mut block_lines: Vec<&str> = Vec::new();
+    let (mut old_count, mut new_count) = (0, 0);
+    let mut ui_index = 0;
+    let mut last_line_included = false;
+    for line in hunk_lines.iter().skip(1) {
+        // Belongs to the line before it, like in `build_staging_patch`
+        if line.starts_with('\\') {
+            if last_line_included {
+                block_lines.push(line);
+            }
+            continue;
+        }
+        let index = ui_index;
+        ui_index += 1;
+        let is_change = line.starts_with('+') || line.starts_with('-');
+        if index < first {
+            old_start += usize::from(!line.starts_with('+'));
+            new_start += usize::from(!line.starts_with('-'));
+            continue;
+        }
+        last_line_included = index <= last || !is_change;
+        if !last_line_included {
+            break;
+        }
+        block_lines.push(line);
+        old_count += usize::from(!line.starts_with('+'));
+        new_count += usize::from(!line.starts_with('-'));
+    }
+
+    Ok(format!(
+        "{}\n@@ -{},{} +{},{} @@\n{}\n",
+        header,
+        old_start,
+        old_count,
+        new_start,
+        new_count,
+        block_lines.join("\n")
+    ))
+}
+
+/// The guide appended to a hunk opened for editing; like every line
+/// starting with `#`, it is dropped again before staging.
+pub const EDIT_HUNK_GUIDE: &str = "\
+# ---
+# To remove '-' lines, make them ' ' lines (context).
+# To remove '+' lines, delete them.
+# Lines starting with # will be removed.
+# If the hunk has no changes left, nothing is staged.
+";
+
+/// The patch of an unstaged hunk, followed by a guide to editing it, to be
+/// opened in the user's editor and passed to [`stage_edited_hunk`].
+pub fn editable_hunk_patch<P: AsRef<Path>>(
+    repo_path: P,
+    file: &str,
+    hunk_index: usize,
+    settings: &DiffSettings,
+) -> MagiResult<String> {
+    let mut patch = extract_hunk_patch(&repo_path, file, hunk_index, settings)?;
+    patch.push_str(EDIT_HUNK_GUIDE);
+    Ok(patch)
+}
+
+/// Stages a hunk patch edited by the user. Returns `false` when the edit
+/// left no changes to stage.
+pub fn stage_edited_hunk<P: AsRef<Path>>(
+    repo_path: P,
+    edited: &str,
+    settings: &DiffSettings,
+) -> MagiResult<bool> {
+    let Some(patch) = edited_hunk_patch(edited)? else {
+        return Ok(false);
+    };
+    apply_patch_cached(&repo_path, &patch, settings)?;
+    Ok(true)
+}
+
+/// Validates an edited hunk patch: comments are dropped and the hunk header
+/// gets the line counts of the edited hunk, so lines can be added and
+/// removed freely. `None` when no added or removed lines are left.
+pub fn edited_hunk_patch(edited: &str) -> MagiResult<Option<String>> {
+    let lines: Vec<&str> = edited
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect();
+    let hunk_start = lines
+        .iter()
+        .position(|line| line.starts_with("@@"))
+        .ok_or_else(|| MagiError::Generic("The edited patch has no hunk header".to_string()))?;
+    if hunk_start == 0 {
+        return Err(MagiError::Generic(
+            "The edited patch has no file header".to_string(),
+        ));
+    }
+    let (old_start, new_start) = parse_hunk_header_starts(lines[hunk_start])?;
+
+    // Editors add newlines at the end of files
+    let mut body = &lines[hunk_start + 1..];
+    while let Some((last, rest)) = body.split_last()
+        && last.is_empty()
+    {
+        body = rest;
+    }
+
+    let mut hunk: Vec<&str> = Vec::new();
+    let (mut old_count, mut new_count) = (0, 0);
+    let mut has_changes = false;
+    for line in body {
+        match line.chars().next() {
+            Some('+') => {
+                new_count += 1;
+                has_changes = true;
+            }
+            Some('-') => {
+                old_count += 1;
+                has_changes = true;
+            }
+            Some(' ') => {
+                old_count += 1;
+                new_count += 1;
+            }
+            Some('\\') => {}
+            // Editors may strip the space of an empty context line
+            None => {
+                hunk.push(" ");
+                old_count += 1;
+                new_count += 1;
+                continue;
+            }
+            Some(_) if line.starts_with("@@") => {
+                return Err(MagiError::Generic(
+                    "The edited patch must keep a single hunk".to_string(),
+                ));
+            }
+            Some(_) => {
+                return Err(MagiError::Generic(format!(
+                    "Invalid line in the edited hunk: {}",
+                    line
+                )));
+            }
+        }
+        hunk.push(line);
+    }
+    if !has_changes {
+        return Ok(None);
+    }
+
+    let mut patch = lines[..hunk_start].join("\n");
+    patch.push_str(&format!(
+        "\n@@ -{},{} +{},{} @@\n",
+        old_start, old_count, new_start, new_count
+    ));
+    patch.push_str(&hunk.join("\n"));
+    patch.push('\n');
+    Ok(Some(patch))
 }
 
 /// Gets the diff output for a specific file.
-pub fn get_file_diff<P: AsRef<Path>>(repo_path: P, file: &str) -> MagiResult<String> {
-    let output = git_cmd(&repo_path, &["diff", "--", file])
+pub fn get_file_diff<P: AsRef<Path>>(
+    repo_path: P,
+    file: &str,
+    settings: &DiffSettings,
+) -> MagiResult<String> {
+    let output = git_cmd(&repo_path, &["diff"])
+        .args(settings.diff_args())
+        .args(["--", file])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output()?;
@@ -185,8 +415,9 @@ fn extract_hunk_patch<P: AsRef<Path>>(
     repo_path: P,
     file: &str,
     hunk_index: usize,
+    settings: &DiffSettings,
 ) -> MagiResult<String> {
-    let diff_output = get_file_diff(&repo_path, file)?;
+    let diff_output = get_file_diff(&repo_path, file, settings)?;
     let (header, hunk_lines) = extract_hunk_from_diff(&diff_output, hunk_index)?;
 
     let mut patch = header;
@@ -198,9 +429,14 @@ fn extract_hunk_patch<P: AsRef<Path>>(
 }
 
 /// Applies a patch to the staging area.
-fn apply_patch_cached<P: AsRef<Path>>(repo_path: P, patch: &str) -> MagiResult<()> {
+pub fn apply_patch_cached<P: AsRef<Path>>(
+    repo_path: P,
+    patch: &str,
+    settings: &DiffSettings,
+) -> MagiResult<()> {
     use std::io::Write;
     let mut child = git_cmd(&repo_path, &["apply", "--cached"])
+        .args(settings.apply_args())
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -223,9 +459,14 @@ fn apply_patch_cached<P: AsRef<Path>>(repo_path: P, patch: &str) -> MagiResult<(
 
 /// Unstages a single hunk from a file by extracting it from `git diff --cached` output
 /// and piping it to `git apply --cached --reverse`.
-pub fn unstage_hunk<P: AsRef<Path>>(repo_path: P, file: &str, hunk_index: usize) -> MagiResult<()> {
-    let patch = extract_staged_hunk_patch(&repo_path, file, hunk_index)?;
-    apply_patch_cached_reverse(&repo_path, &patch)
+pub fn unstage_hunk<P: AsRef<Path>>(
+    repo_path: P,
+    file: &str,
+    hunk_index: usize,
+    settings: &DiffSettings,
+) -> MagiResult<()> {
+    let patch = extract_staged_hunk_patch(&repo_path, file, hunk_index, settings)?;
+    apply_patch_cached_reverse(&repo_path, &patch, settings)
 }
 
 /// Unstages specific lines within a staged hunk. Lines not in the selection are converted:
@@ -238,8 +479,9 @@ pub fn unstage_lines<P: AsRef<Path>>(
     file: &str,
     hunk_index: usize,
     selected_line_indices: &[usize],
+    settings: &DiffSettings,
 ) -> MagiResult<()> {
-    let diff_output = get_staged_file_diff(&repo_path, file)?;
+    let diff_output = get_staged_file_diff(&repo_path, file, settings)?;
     let (header, hunk_lines) = extract_hunk_from_diff(&diff_output, hunk_index)?;
 
     // Build modified hunk with only selected lines unstaged
@@ -315,16 +557,100 @@ pub fn unstage_lines<P: AsRef<Path>>(
     patch.push_str(&modified_lines.join("\n"));
     patch.push('\n');
 
-    apply_patch_cached_reverse(&repo_path, &patch)
+    apply_patch_cached_reverse(&repo_path, &patch, settings)
+}
+
+/// Gets the staged diff output for a specific file (comparing index to HEAD).
+///
+/// A renamed file is diffed against the path it was renamed from, like the
+/// status shows it, but as a change of the new path: patches cut from it
+/// then leave the rename itself staged.
+pub fn get_staged_file_diff<P: AsRef<Path>>(
+    repo_path: P,
+    file: &str,
+    settings: &DiffSettings,
+) -> MagiResult<String> {
+    let renames = staged_renames(&repo_path)?;
+    staged_file_diff(repo_path, file, &renames, settings)
 }
 
-/// Gets the staged diff output for a specific file.
-fn get_staged_file_diff<P: AsRef<Path>>(repo_path: P, file: &str) -> MagiResult<String> {
-    let output = git_cmd(&repo_path, &["diff", "--cached", "--", file])
+/// Gets the staged diff output for a specific file like
+/// [`get_staged_file_diff`], with the staged renames already found, for
+/// operations on several files.
+pub fn staged_file_diff<P: AsRef<Path>>(
+    repo_path: P,
+    file: &str,
+    renames: &HashMap<String, StagedRename>,
+    settings: &DiffSettings,
+) -> MagiResult<String> {
+    let renamed_from = renames.get(file).map(|rename| rename.from.as_str());
+    let output = git_cmd(&repo_path, &["diff", "--cached", "--find-renames"])
+        .args(settings.diff_args())
+        .args(["--", file])
+        .args(renamed_from)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output()?;
-    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    let diff = String::from_utf8_lossy(&output.stdout).to_string();
+    Ok(match renamed_from {
+        Some(_) => as_change_of(&diff, file),
+        None => diff,
+    })
+}
+
+/// A staged rename of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagedRename {
+    /// The path the file was renamed from
+    pub from: String,
+    /// How much of the content (in percent) is unchanged
+    pub similarity: u16,
+}
+
+/// The staged renames, from the new path of each file to its rename,
+/// found like `git status` finds them.
+pub fn staged_renames<P: AsRef<Path>>(repo_path: P) -> MagiResult<HashMap<String, StagedRename>> {
+    let output = git_cmd(
+        &repo_path,
+        &["diff", "--cached", "--find-renames", "--name-status", "-z"],
+    )
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Each entry is its status, then its path, or both paths of a rename.
+    // A rename's status has its similarity, as in `R090`.
+    let mut fields = stdout.split('\0');
+    let mut renames = HashMap::new();
+    while let Some(status) = fields.next() {
+        let path = fields.next();
+        if let Some(score) = status.strip_prefix('R')
+            && let (Some(from), Some(to)) = (path, fields.next())
+        {
+            renames.insert(
+                to.to_string(),
+                StagedRename {
+                    from: from.to_string(),
+                    similarity: score.parse().unwrap_or_default(),
+                },
+            );
+        }
+    }
+    Ok(renames)
+}
+
+/// Rewrites the header of a rename's diff as the header of a change of
+/// `file`. A pure rename, without hunks, has no diff left.
+fn as_change_of(diff: &str, file: &str) -> String {
+    match diff.find("\n@@") {
+        Some(start) => format!(
+            "diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}{1}",
+            file,
+            &diff[start..]
+        ),
+        None => String::new(),
+    }
 }
 
 /// Extracts a single staged hunk patch (file header + hunk) ready to be applied in reverse.
@@ -332,8 +658,9 @@ fn extract_staged_hunk_patch<P: AsRef<Path>>(
     repo_path: P,
     file: &str,
     hunk_index: usize,
+    settings: &DiffSettings,
 ) -> MagiResult<String> {
-    let diff_output = get_staged_file_diff(&repo_path, file)?;
+    let diff_output = get_staged_file_diff(&repo_path, file, settings)?;
     let (header, hunk_lines) = extract_hunk_from_diff(&diff_output, hunk_index)?;
 
     let mut patch = header;
@@ -345,9 +672,14 @@ fn extract_staged_hunk_patch<P: AsRef<Path>>(
 }
 
 /// Applies a patch in reverse to the staging area (unstages changes).
-fn apply_patch_cached_reverse<P: AsRef<Path>>(repo_path: P, patch: &str) -> MagiResult<()> {
+pub fn apply_patch_cached_reverse<P: AsRef<Path>>(
+    repo_path: P,
+    patch: &str,
+    settings: &DiffSettings,
+) -> MagiResult<()> {
     use std::io::Write;
     let mut child = git_cmd(&repo_path, &["apply", "--cached", "--reverse"])
+        .args(settings.apply_args())
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -3,18 +3,55 @@ use crate::{
     i18n,
     model::{Line, LineContent, SectionType},
 };
-use git2::{DiffOptions, Repository};
+use git2::{DiffFindOptions, DiffOptions, Repository};
 
-use super::diff_utils::{build_change_lines, collect_file_changes};
+use super::{
+    DiffSettings,
+    diff_utils::{build_change_lines, collect_file_changes, intent_to_add_paths},
+    stage::staged_renames,
+};
 
 /// Returns the lines representing staged changes in the Git repository
-pub fn get_lines(repository: &Repository) -> MagiResult<Vec<Line>> {
+pub fn get_lines(repository: &Repository, settings: &DiffSettings) -> MagiResult<Vec<Line>> {
     // Get the diff between HEAD and index (staged changes)
     let head = repository.head()?.peel_to_tree()?;
     let mut diff_options = DiffOptions::new();
+    settings.apply_to(&mut diff_options);
     let diff = repository.diff_tree_to_index(Some(&head), None, Some(&mut diff_options))?;
 
-    let file_changes = collect_file_changes(&diff)?;
+    // Like `git status`, a staged deletion and addition of similar content
+    // is a rename. Which files are renames, and how similar they are, is
+    // left to git, like when staging; libgit2 only diffs each pair.
+    let workdir = repository
+        .workdir()
+        .unwrap_or_else(|| std::path::Path::new("."));
+    let renames = staged_renames(workdir)?;
+    let mut file_changes = collect_file_changes(&diff, &renames)?;
+    file_changes.retain(|(file_change, _, _)| {
+        !renames.contains_key(&file_change.path)
+            && !renames
+                .values()
+                .any(|rename| rename.from == file_change.path)
+    });
+    for (path, rename) in &renames {
+        let mut options = DiffOptions::new();
+        settings.apply_to(&mut options);
+        options
+            .pathspec(&rename.from)
+            .pathspec(path)
+            .disable_pathspec_match(true);
+        let mut diff = repository.diff_tree_to_index(Some(&head), None, Some(&mut options))?;
+        // git paired them already, however similar libgit2 finds them
+        diff.find_similar(Some(
+            DiffFindOptions::new().renames(true).rename_threshold(0),
+        ))?;
+        file_changes.extend(collect_file_changes(&diff, &renames)?);
+    }
+    file_changes.sort_by(|(a, _, _), (b, _, _)| a.path.cmp(&b.path));
+
+    // Files added with intent to add have nothing staged yet
+    let intent_to_add = intent_to_add_paths(repository)?;
+    file_changes.retain(|(file_change, _, _)| !intent_to_add.contains(&file_change.path));
 
     Ok(build_change_lines(
         file_changes,
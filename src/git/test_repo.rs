@@ -2,7 +2,7 @@ use git2::{Repository, Signature};
 use std::{fs, path::Path};
 use tempfile::TempDir;
 
-use crate::git::stage::stage_files;
+use crate::git::{git_cmd, stage::stage_files};
 
 pub struct TestRepo {
     pub repo: Repository,
@@ -204,6 +204,68 @@ impl TestRepo {
         self
     }
 
+    /// Generates an SSH key inside `.git/` and configures it for signing
+    /// (`gpg.format=ssh`), trusting it for verification so signed commits
+    /// report a good signature. Returns the private key path.
+    pub fn with_ssh_signing_key(&self) -> String {
+        use std::process::Command;
+        let key_path = self.repo.path().join("test_signing_key");
+        let keygen = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-C", "test", "-f"])
+            .arg(&key_path)
+            .output()
+            .expect("Failed to run ssh-keygen");
+        assert!(keygen.status.success());
+
+        let public_key = fs::read_to_string(key_path.with_extension("pub")).unwrap();
+        let allowed_signers = self.repo.path().join("allowed_signers");
+        fs::write(&allowed_signers, format!("test@example.com {}", public_key)).unwrap();
+
+        let key = key_path.display().to_string();
+        let mut config = self.repo.config().unwrap();
+        config.set_str("gpg.format", "ssh").unwrap();
+        config.set_str("user.signingKey", &key).unwrap();
+        config
+            .set_str(
+                "gpg.ssh.allowedSignersFile",
+                &allowed_signers.display().to_string(),
+            )
+            .unwrap();
+        key
+    }
+
+    /// Points main's upstream at refs/remotes/origin/main frozen at the
+    /// current HEAD, so commits made afterwards are "unpushed".
+    pub fn set_upstream_at_head(&self) -> &Self {
+        let workdir = self.repo_path();
+        let head = self.head_hash();
+        git_cmd(workdir, &["update-ref", "refs/remotes/origin/main", &head])
+            .status()
+            .unwrap();
+        // @{upstream} only resolves when the remote has a fetch refspec
+        // mapping refs/heads/* into refs/remotes/origin/*.
+        git_cmd(workdir, &["config", "remote.origin.url", "."])
+            .status()
+            .unwrap();
+        git_cmd(
+            workdir,
+            &[
+                "config",
+                "remote.origin.fetch",
+                "+refs/heads/*:refs/remotes/origin/*",
+            ],
+        )
+        .status()
+        .unwrap();
+        git_cmd(workdir, &["config", "branch.main.remote", "origin"])
+            .status()
+            .unwrap();
+        git_cmd(workdir, &["config", "branch.main.merge", "refs/heads/main"])
+            .status()
+            .unwrap();
+        self
+    }
+
     pub fn repo_path(&self) -> &Path {
         self.repo.workdir().unwrap()
     }
@@ -238,6 +300,20 @@ impl TestRepo {
         self
     }
 
+    /// Configures a non-interactive `fake` merge tool running `cmd`, which
+    /// git trusts to report whether it resolved the file.
+    pub fn with_mergetool(&self, cmd: &str) -> &Self {
+        let mut config = self.repo.config().unwrap();
+        config.set_str("merge.tool", "fake").unwrap();
+        config.set_str("mergetool.fake.cmd", cmd).unwrap();
+        config
+            .set_bool("mergetool.fake.trustExitCode", true)
+            .unwrap();
+        config.set_bool("mergetool.prompt", false).unwrap();
+        config.set_bool("mergetool.keepBackup", false).unwrap();
+        self
+    }
+
     pub fn create_stash(&self, message: &str) -> &Self {
         use std::process::Command;
         let workdir = self.repo.workdir().unwrap();
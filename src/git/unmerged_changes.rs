@@ -45,6 +45,7 @@ pub fn collect_unmerged_changes(repository: &Repository) -> MagiResult<FileChang
                 path,
                 status: FileStatus::Unmerged,
             },
+            Vec::new(),
             hunks,
         ));
     }
@@ -104,6 +105,7 @@ fn parse_combined_diff(diff_text: &str) -> Vec<(DiffHunk, Vec<DiffLine>)> {
         diff_lines.push(DiffLine {
             content: line.to_string(),
             line_type,
+            refined: Vec::new(),
         });
     }
 
@@ -188,7 +190,7 @@ index 1234567,89abcde..0000000
         let changes = collect_unmerged_changes(&repo).unwrap();
 
         assert_eq!(changes.len(), 1);
-        let (file_change, hunks) = &changes[0];
+        let (file_change, _, hunks) = &changes[0];
         assert_eq!(file_change.path, "conflict.txt");
         assert_eq!(file_change.status, FileStatus::Unmerged);
 
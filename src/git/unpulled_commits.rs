@@ -2,111 +2,62 @@ use git2::Repository;
 
 use crate::{
     errors::MagiResult,
-    model::{Line, LineContent, SectionType},
+    i18n,
+    model::{Line, SectionType},
 };
 
-use super::commit_utils::{
-    build_local_branch_map, build_refs_for_commit, build_remote_branch_map, build_tag_map,
-    create_commit_line,
+use super::{
+    commit_utils::{commit_range_lines, head_branch},
+    config::{get_push_branch, get_upstream_branch},
 };
 
-const MAX_COMMITS: usize = 10;
-
 /// Returns the lines representing unpulled commits from the upstream branch
 pub fn get_lines(repository: &Repository) -> MagiResult<Vec<Line>> {
-    let mut lines = Vec::new();
-
-    let head = match repository.head() {
-        Ok(head) => head,
-        Err(_) => return Ok(lines), // No commits yet
+    let Some((branch, head_oid)) = head_branch(repository) else {
+        return Ok(Vec::new());
     };
-
-    // Only proceed if we're on a branch
-    if !head.is_branch() {
-        return Ok(lines);
-    }
-
-    let head_commit = match head.peel_to_commit() {
-        Ok(commit) => commit,
-        Err(_) => return Ok(lines),
+    let Some((upstream_name, upstream_oid)) = get_upstream_branch(repository, &branch) else {
+        return Ok(Vec::new()); // No upstream configured
     };
 
-    // Get the upstream branch name
-    let branch_ref = head.resolve()?;
-    let branch_name = branch_ref.name().unwrap_or("unknown");
-
-    let upstream_name = match repository
-        .branch_upstream_name(branch_name)
-        .ok()
-        .and_then(|n| n.as_str().ok().map(|s| s.to_string()))
-    {
-        Some(name) => name,
-        None => return Ok(lines), // No upstream configured
-    };
+    // Commits that are on upstream but not in HEAD, which we need to pull
+    commit_range_lines(
+        repository,
+        upstream_oid,
+        head_oid,
+        i18n::t().section_unpulled_from_prefix,
+        &upstream_name,
+        SectionType::Unpulled,
+    )
+}
 
-    let upstream_ref = match repository.find_reference(&upstream_name) {
-        Ok(r) => r,
-        Err(_) => return Ok(lines),
+/// Returns the lines representing unpulled commits from the branch we push
+/// to, when that isn't the upstream
+pub fn get_push_remote_lines(repository: &Repository) -> MagiResult<Vec<Line>> {
+    let Some((branch, head_oid)) = head_branch(repository) else {
+        return Ok(Vec::new());
     };
-
-    let upstream_commit = match upstream_ref.peel_to_commit() {
-        Ok(commit) => commit,
-        Err(_) => return Ok(lines),
+    let Some((push_name, push_oid)) = get_push_branch(repository, &branch) else {
+        return Ok(Vec::new()); // No push remote configured
     };
-
-    // Get the shorthand name for display (e.g., "origin/main")
-    let upstream_shorthand = upstream_ref
-        .shorthand()
-        .unwrap_or(&upstream_name)
-        .to_string();
-
-    // Find commits that are on upstream but not in HEAD
-    // These are commits we need to pull
-    let mut revwalk = repository.revwalk()?;
-    revwalk.push(upstream_commit.id())?;
-    revwalk.hide(head_commit.id())?;
-
-    let commit_oids: Vec<_> = revwalk
-        .take(MAX_COMMITS)
-        .filter_map(|oid| oid.ok())
-        .collect();
-
-    if commit_oids.is_empty() {
-        return Ok(lines);
-    }
-
-    // Build maps for tags and branches
-    let tag_map = build_tag_map(repository)?;
-    let local_branch_map = build_local_branch_map(repository)?;
-    let remote_branch_map = build_remote_branch_map(repository)?;
-
-    // Add section header
-    lines.push(Line {
-        content: LineContent::UnpulledSectionHeader {
-            remote_name: upstream_shorthand,
-            count: commit_oids.len(),
-        },
-        section: Some(SectionType::Unpulled),
-    });
-
-    // Add commit lines
-    for oid in &commit_oids {
-        let commit = match repository.find_commit(*oid) {
-            Ok(c) => c,
-            Err(_) => continue,
-        };
-
-        let refs = build_refs_for_commit(oid, &local_branch_map, &remote_branch_map, &tag_map);
-        lines.push(create_commit_line(&commit, refs, SectionType::Unpulled));
+    if get_upstream_branch(repository, &branch).is_some_and(|(name, _)| name == push_name) {
+        return Ok(Vec::new()); // Already listed as unpulled from upstream
     }
 
-    Ok(lines)
+    commit_range_lines(
+        repository,
+        push_oid,
+        head_oid,
+        i18n::t().section_unpulled_from_prefix,
+        &push_name,
+        SectionType::UnpulledFromPushRemote,
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::git::test_repo::TestRepo;
+    use crate::{git::test_repo::TestRepo, model::LineContent};
     use git2::{BranchType, Signature};
     use std::fs;
 
@@ -224,11 +175,13 @@ mod tests {
 
         // First line should be section header
         match &lines[0].content {
-            LineContent::UnpulledSectionHeader { remote_name, count } => {
+            LineContent::RemoteSectionHeader {
+                remote_name, count, ..
+            } => {
                 assert_eq!(remote_name, "origin/main");
                 assert_eq!(*count, 2);
             }
-            _ => panic!("Expected UnpulledSectionHeader"),
+            _ => panic!("Expected RemoteSectionHeader"),
         }
 
         // Second line should be most recent unpulled commit
@@ -278,8 +231,9 @@ mod tests {
     #[test]
     fn test_unpulled_section_header_structure() {
         // Unit test that verifies the line structure without git operations
-        let lines = vec![Line {
-            content: LineContent::UnpulledSectionHeader {
+        let lines = [Line {
+            content: LineContent::RemoteSectionHeader {
+                prefix: "Unpulled from ".to_string(),
                 remote_name: "origin/main".to_string(),
                 count: 3,
             },
@@ -288,12 +242,57 @@ mod tests {
 
         assert_eq!(lines.len(), 1);
         match &lines[0].content {
-            LineContent::UnpulledSectionHeader { remote_name, count } => {
+            LineContent::RemoteSectionHeader {
+                remote_name, count, ..
+            } => {
                 assert_eq!(remote_name, "origin/main");
                 assert_eq!(*count, 3);
             }
-            _ => panic!("Expected UnpulledSectionHeader"),
+            _ => panic!("Expected RemoteSectionHeader"),
         }
         assert_eq!(lines[0].section, Some(SectionType::Unpulled));
     }
+
+    #[test]
+    fn test_get_push_remote_lines_with_unpulled_commits() {
+        let test_repo = TestRepo::new();
+        let head_oid = test_repo.repo.head().unwrap().target().unwrap();
+        setup_upstream(&test_repo.repo, head_oid);
+
+        // Push to a fork that has a commit we don't have
+        test_repo
+            .repo
+            .remote("fork", "https://example.com/fork.git")
+            .unwrap();
+        test_repo
+            .repo
+            .config()
+            .unwrap()
+            .set_str("remote.pushDefault", "fork")
+            .unwrap();
+        test_repo
+            .repo
+            .reference("refs/remotes/fork/main", head_oid, false, "create fork")
+            .unwrap();
+        create_commit_on_ref(
+            &test_repo.repo,
+            "refs/remotes/fork/main",
+            "Fork commit",
+            head_oid,
+        );
+
+        assert!(get_lines(&test_repo.repo).unwrap().is_empty());
+        let lines = get_push_remote_lines(&test_repo.repo).unwrap();
+        assert_eq!(lines.len(), 2);
+        match &lines[0].content {
+            LineContent::RemoteSectionHeader {
+                remote_name, count, ..
+            } => {
+                assert_eq!(remote_name, "fork/main");
+                assert_eq!(*count, 1);
+            }
+            _ => panic!("Expected RemoteSectionHeader"),
+        }
+        assert_eq!(lines[1].section, Some(SectionType::UnpulledFromPushRemote));
+    }
 }
@@ -0,0 +1,193 @@
+use git2::Repository;
+
+use crate::{
+    errors::MagiResult,
+    i18n,
+    model::{Line, SectionType},
+};
+
+use super::{
+    commit_utils::{commit_range_lines, head_branch},
+    config::{get_push_branch, get_upstream_branch},
+};
+
+/// Returns the lines representing local commits not yet merged into the upstream branch
+pub fn get_lines(repository: &Repository) -> MagiResult<Vec<Line>> {
+    let Some((branch, head_oid)) = head_branch(repository) else {
+        return Ok(Vec::new());
+    };
+    let Some((upstream_name, upstream_oid)) = get_upstream_branch(repository, &branch) else {
+        return Ok(Vec::new()); // No upstream configured
+    };
+
+    // Commits that are in HEAD but not on upstream
+    commit_range_lines(
+        repository,
+        head_oid,
+        upstream_oid,
+        i18n::t().section_unmerged_into_prefix,
+        &upstream_name,
+        SectionType::UnmergedIntoUpstream,
+    )
+}
+
+/// Returns the lines representing local commits not yet pushed to the branch
+/// we push to, when that isn't the upstream
+pub fn get_push_remote_lines(repository: &Repository) -> MagiResult<Vec<Line>> {
+    let Some((branch, head_oid)) = head_branch(repository) else {
+        return Ok(Vec::new());
+    };
+    let Some((push_name, push_oid)) = get_push_branch(repository, &branch) else {
+        return Ok(Vec::new()); // No push remote configured
+    };
+    if get_upstream_branch(repository, &branch).is_some_and(|(name, _)| name == push_name) {
+        return Ok(Vec::new()); // Already listed as unmerged into upstream
+    }
+
+    commit_range_lines(
+        repository,
+        head_oid,
+        push_oid,
+        i18n::t().section_unpushed_to_prefix,
+        &push_name,
+        SectionType::UnpushedToPushRemote,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{git::test_repo::TestRepo, model::LineContent};
+    use git2::BranchType;
+
+    /// Creates `remote` with a remote-tracking branch for main at `oid`
+    fn create_remote_branch(repo: &Repository, remote: &str, oid: git2::Oid) {
+        repo.remote(remote, &format!("https://example.com/{}.git", remote))
+            .unwrap();
+        repo.reference(
+            &format!("refs/remotes/{}/main", remote),
+            oid,
+            false,
+            "create remote branch",
+        )
+        .unwrap();
+    }
+
+    /// Sets up origin/main as the upstream of main at the current HEAD
+    fn setup_upstream(test_repo: &TestRepo) {
+        let head_oid = test_repo.repo.head().unwrap().target().unwrap();
+        create_remote_branch(&test_repo.repo, "origin", head_oid);
+        let mut branch = test_repo
+            .repo
+            .find_branch("main", BranchType::Local)
+            .unwrap();
+        branch.set_upstream(Some("origin/main")).unwrap();
+    }
+
+    /// Sets up fork/main as where main is pushed, at the current HEAD
+    fn setup_push_remote(test_repo: &TestRepo) {
+        let head_oid = test_repo.repo.head().unwrap().target().unwrap();
+        create_remote_branch(&test_repo.repo, "fork", head_oid);
+        test_repo
+            .repo
+            .config()
+            .unwrap()
+            .set_str("remote.pushDefault", "fork")
+            .unwrap();
+    }
+
+    fn header(lines: &[Line]) -> (&str, &str, usize) {
+        match &lines[0].content {
+            LineContent::RemoteSectionHeader {
+                prefix,
+                remote_name,
+                count,
+            } => (prefix, remote_name, *count),
+            _ => panic!("Expected RemoteSectionHeader"),
+        }
+    }
+
+    #[test]
+    fn test_get_lines_no_upstream() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "a", "Local commit");
+
+        assert!(get_lines(&test_repo.repo).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_lines_with_unmerged_commits() {
+        let test_repo = TestRepo::new();
+        setup_upstream(&test_repo);
+        test_repo
+            .commit_file("a.txt", "a", "Local commit 1")
+            .commit_file("b.txt", "b", "Local commit 2");
+
+        let lines = get_lines(&test_repo.repo).unwrap();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(header(&lines), ("Unmerged into ", "origin/main", 2));
+        let LineContent::Commit(ref info) = lines[1].content else {
+            panic!("Expected Commit content");
+        };
+        assert_eq!(info.message, "Local commit 2");
+        for line in &lines {
+            assert_eq!(line.section, Some(SectionType::UnmergedIntoUpstream));
+        }
+    }
+
+    #[test]
+    fn test_get_lines_counts_more_commits_than_listed() {
+        let test_repo = TestRepo::new();
+        setup_upstream(&test_repo);
+        for i in 0..12 {
+            test_repo.commit_file("a.txt", &i.to_string(), &format!("Local commit {}", i));
+        }
+
+        let lines = get_lines(&test_repo.repo).unwrap();
+
+        // Only the latest ten are listed
+        assert_eq!(lines.len(), 11);
+        assert_eq!(header(&lines), ("Unmerged into ", "origin/main", 12));
+    }
+
+    #[test]
+    fn test_get_lines_up_to_date_with_upstream() {
+        let test_repo = TestRepo::new();
+        setup_upstream(&test_repo);
+
+        assert!(get_lines(&test_repo.repo).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_push_remote_lines_with_unpushed_commits() {
+        let test_repo = TestRepo::new();
+        setup_upstream(&test_repo);
+        setup_push_remote(&test_repo);
+        test_repo.commit_file("a.txt", "a", "Local commit");
+
+        let lines = get_push_remote_lines(&test_repo.repo).unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(header(&lines), ("Unpushed to ", "fork/main", 1));
+        for line in &lines {
+            assert_eq!(line.section, Some(SectionType::UnpushedToPushRemote));
+        }
+    }
+
+    #[test]
+    fn test_get_push_remote_lines_skips_the_upstream() {
+        let test_repo = TestRepo::new();
+        setup_upstream(&test_repo);
+        test_repo
+            .repo
+            .config()
+            .unwrap()
+            .set_str("remote.pushDefault", "origin")
+            .unwrap();
+        test_repo.commit_file("a.txt", "a", "Local commit");
+
+        assert!(get_push_remote_lines(&test_repo.repo).unwrap().is_empty());
+        assert_eq!(get_lines(&test_repo.repo).unwrap().len(), 2);
+    }
+}
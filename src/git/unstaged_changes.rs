@@ -1,29 +1,42 @@
+use std::collections::HashMap;
+
 use crate::{
     errors::MagiResult,
     i18n,
-    model::{Line, LineContent, SectionType},
+    model::{FileStatus, Line, LineContent, SectionType},
 };
 use git2::{DiffOptions, Repository};
 
 use super::{
-    diff_utils::{build_change_lines, collect_file_changes},
+    DiffSettings,
+    diff_utils::{build_change_lines, collect_file_changes, intent_to_add_paths},
     unmerged_changes::collect_unmerged_changes,
 };
 
 /// Returns the lines representing unstaged changes in the Git repository
-pub fn get_lines(repository: &Repository) -> MagiResult<Vec<Line>> {
+pub fn get_lines(repository: &Repository, settings: &DiffSettings) -> MagiResult<Vec<Line>> {
     // Get the diff between index and workdir (unstaged changes)
     let mut diff_options = DiffOptions::new();
+    settings.apply_to(&mut diff_options);
     diff_options.include_untracked(false);
 
     let diff = repository.diff_index_to_workdir(None, Some(&mut diff_options))?;
 
-    let mut file_changes = collect_file_changes(&diff)?;
+    // Only staged changes have renames
+    let mut file_changes = collect_file_changes(&diff, &HashMap::new())?;
+
+    // Files added with intent to add are new files, like `git diff` shows them
+    let intent_to_add = intent_to_add_paths(repository)?;
+    for (file_change, _, _) in &mut file_changes {
+        if intent_to_add.contains(&file_change.path) {
+            file_change.status = FileStatus::New;
+        }
+    }
 
     // Unmerged (conflicted) files are shown alongside unstaged changes,
     // like in Magit, with their combined diff
     file_changes.extend(collect_unmerged_changes(repository)?);
-    file_changes.sort_by(|(a, _), (b, _)| a.path.cmp(&b.path));
+    file_changes.sort_by(|(a, _, _), (b, _, _)| a.path.cmp(&b.path));
 
     Ok(build_change_lines(
         file_changes,
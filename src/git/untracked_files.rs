@@ -1,12 +1,18 @@
+use std::{collections::HashSet, path::Path};
+
 use crate::{
     errors::MagiResult,
     i18n,
-    model::{Line, LineContent, SectionType},
+    model::{DiffHunk, DiffLine, DiffLineType, FileDetail, Line, LineContent, SectionType},
 };
 use git2::Repository;
 
-/// Returns the lines representing untracked files in the Git repository
-pub fn get_lines(repository: &Repository) -> MagiResult<Vec<Line>> {
+/// Untracked files larger than this (in bytes) are not read for a preview
+const MAX_PREVIEW_SIZE: u64 = 256 * 1024;
+
+/// Returns the lines representing untracked files in the Git repository.
+/// Only the files in `expanded` are read for a preview.
+pub fn get_lines(repository: &Repository, expanded: &HashSet<String>) -> MagiResult<Vec<Line>> {
     let mut lines = Vec::new();
 
     // Get untracked files
@@ -45,14 +51,155 @@ pub fn get_lines(repository: &Repository) -> MagiResult<Vec<Line>> {
     };
     lines.push(header_line);
 
-    // Add each untracked file
+    // Add each untracked file, followed by its preview when expanded
     for file_path in untracked_files {
+        let preview = repository
+            .workdir()
+            .filter(|_| expanded.contains(&file_path))
+            .map(|workdir| preview_lines(workdir, &file_path))
+            .unwrap_or_default();
         let file_line = Line {
             content: LineContent::UntrackedFile(file_path),
             section: Some(SectionType::UntrackedFiles),
         };
         lines.push(file_line);
+        lines.extend(preview);
     }
 
     Ok(lines)
 }
+
+/// The content of an untracked file as a hunk adding all of its lines, the
+/// way it shows up in the unstaged changes once added with intent to add.
+/// Untracked directories, binary and empty files have no preview, and files
+/// over [`MAX_PREVIEW_SIZE`] only say so instead of being read.
+fn preview_lines(workdir: &Path, file_path: &str) -> Vec<Line> {
+    if file_path.ends_with('/') {
+        return Vec::new();
+    }
+    let path = workdir.join(file_path);
+    let section = SectionType::UntrackedFile {
+        path: file_path.to_string(),
+    };
+    let Ok(metadata) = std::fs::metadata(&path) else {
+        return Vec::new();
+    };
+    if metadata.len() > MAX_PREVIEW_SIZE {
+        return vec![Line {
+            content: LineContent::FileDetail(FileDetail::TooLarge {
+                size: metadata.len(),
+            }),
+            section: Some(section),
+        }];
+    }
+    let Ok(content) = std::fs::read(&path) else {
+        return Vec::new();
+    };
+    if content.is_empty() || content.contains(&0) {
+        return Vec::new();
+    }
+
+    let content = String::from_utf8_lossy(&content);
+    let diff_lines: Vec<Line> = content
+        .lines()
+        .map(|line| Line {
+            content: LineContent::DiffLine(DiffLine {
+                content: line.to_string(),
+                line_type: DiffLineType::Addition,
+                refined: Vec::new(),
+            }),
+            section: Some(section.clone()),
+        })
+        .collect();
+
+    let mut lines = vec![Line {
+        content: LineContent::DiffHunk(DiffHunk {
+            header: format!("@@ -0,0 +1,{} @@", diff_lines.len()),
+            hunk_index: 0,
+        }),
+        section: Some(section),
+    }];
+    lines.extend(diff_lines);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_repo::TestRepo;
+
+    fn preview(lines: &[Line], path: &str) -> Vec<String> {
+        let section = Some(SectionType::UntrackedFile {
+            path: path.to_string(),
+        });
+        lines
+            .iter()
+            .filter(|line| line.section == section)
+            .map(|line| match &line.content {
+                LineContent::DiffHunk(hunk) => hunk.header.clone(),
+                LineContent::DiffLine(diff_line) => format!("+{}", diff_line.content),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    fn expanded(paths: &[&str]) -> HashSet<String> {
+        paths.iter().map(|path| path.to_string()).collect()
+    }
+
+    #[test]
+    fn test_untracked_file_is_followed_by_its_preview() {
+        let test_repo = TestRepo::new();
+        test_repo.write_file_content("new.txt", "one\ntwo\n");
+
+        let lines = get_lines(&test_repo.repo, &expanded(&["new.txt"])).unwrap();
+
+        assert!(matches!(&lines[1].content, LineContent::UntrackedFile(path) if path == "new.txt"));
+        assert_eq!(
+            preview(&lines, "new.txt"),
+            vec!["@@ -0,0 +1,2 @@", "+one", "+two"]
+        );
+    }
+
+    #[test]
+    fn test_collapsed_files_are_not_read() {
+        let test_repo = TestRepo::new();
+        test_repo
+            .write_file_content("a.txt", "a\n")
+            .write_file_content("b.txt", "b\n");
+
+        let lines = get_lines(&test_repo.repo, &expanded(&["b.txt"])).unwrap();
+
+        assert!(preview(&lines, "a.txt").is_empty());
+        assert_eq!(preview(&lines, "b.txt"), vec!["@@ -0,0 +1,1 @@", "+b"]);
+    }
+
+    #[test]
+    fn test_large_files_are_not_previewed() {
+        let test_repo = TestRepo::new();
+        let content = "line\n".repeat(MAX_PREVIEW_SIZE as usize / 5 + 1);
+        test_repo.write_file_content("large.txt", &content);
+
+        let lines = get_lines(&test_repo.repo, &expanded(&["large.txt"])).unwrap();
+
+        assert_eq!(lines.len(), 3);
+        assert!(matches!(
+            &lines[2].content,
+            LineContent::FileDetail(FileDetail::TooLarge { size }) if *size == content.len() as u64
+        ));
+    }
+
+    #[test]
+    fn test_binary_and_empty_files_have_no_preview() {
+        let test_repo = TestRepo::new();
+        test_repo
+            .write_file_content("empty.txt", "")
+            .write_file_content("image.bin", "\0\x01");
+
+        let lines = get_lines(&test_repo.repo, &expanded(&["empty.txt", "image.bin"])).unwrap();
+
+        assert_eq!(lines.len(), 3);
+        assert!(preview(&lines, "empty.txt").is_empty());
+        assert!(preview(&lines, "image.bin").is_empty());
+    }
+}
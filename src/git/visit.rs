@@ -0,0 +1,97 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use super::git_cmd;
+use crate::errors::{MagiError, MagiResult};
+
+/// Returns the editor git would use (`git var GIT_EDITOR`), which honours
+/// `$GIT_EDITOR`, `core.editor`, `$VISUAL` and `$EDITOR` in that order.
+pub fn editor_command(workdir: &Path) -> MagiResult<String> {
+    let output = git_cmd(workdir, &["var", "GIT_EDITOR"]).output()?;
+    let editor = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || editor.is_empty() {
+        return Err(MagiError::Generic("No editor configured".to_string()));
+    }
+    Ok(editor)
+}
+
+/// Opens `file` in the user's editor, at `line` when given (`+<line>`).
+/// Like git, the editor is run through the shell so it may carry arguments.
+/// The caller must ensure the TUI is suspended (via
+/// `RunningState::LaunchExternalCommand`) before calling this.
+pub fn open_in_editor(workdir: &Path, file: &Path, line: Option<usize>) -> MagiResult<()> {
+    let editor = editor_command(workdir)?;
+
+    let mut cmd = Command::new("sh");
+    cmd.current_dir(workdir)
+        .arg("-c")
+        .arg(format!("{} \"$@\"", editor))
+        .arg(&editor);
+    if let Some(line) = line {
+        cmd.arg(format!("+{}", line));
+    }
+    let status = cmd.arg(file).status()?;
+
+    if !status.success() {
+        return Err(MagiError::Generic(format!("Editor exited with {}", status)));
+    }
+    Ok(())
+}
+
+/// Writes the contents of `path` at `revision` to a temporary file named
+/// after both, so it can be opened in an editor. Returns the file's path.
+pub fn write_blob_to_temp(workdir: &Path, revision: &str, path: &str) -> MagiResult<PathBuf> {
+    let output = git_cmd(workdir, &["show", &format!("{}:{}", revision, path)]).output()?;
+    if !output.status.success() {
+        return Err(MagiError::Generic(format!(
+            "Cannot show {} at {}: {}",
+            path,
+            revision,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let revision_dir: String = revision
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let file = std::env::temp_dir()
+        .join("magi-blobs")
+        .join(revision_dir)
+        .join(path);
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&file, &output.stdout)?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_repo::TestRepo;
+
+    #[test]
+    fn test_write_blob_to_temp_uses_revision_contents() {
+        let test_repo = TestRepo::new();
+        test_repo
+            .commit_file("file.txt", "first\n", "First")
+            .commit_file("file.txt", "second\n", "Second");
+
+        let file = write_blob_to_temp(test_repo.repo_path(), "HEAD~1", "file.txt").unwrap();
+
+        assert!(file.ends_with("HEAD_1/file.txt"));
+        assert_eq!(fs::read_to_string(file).unwrap(), "first\n");
+    }
+
+    #[test]
+    fn test_write_blob_to_temp_missing_path_fails() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("file.txt", "content\n", "First");
+
+        assert!(write_blob_to_temp(test_repo.repo_path(), "HEAD", "missing.txt").is_err());
+    }
+}
@@ -33,6 +33,10 @@ pub struct Strings {
     pub section_cherry_picking: &'static str,
     /// The prefix before the remote name in "Unpulled from <remote>" (includes trailing space).
     pub section_unpulled_from_prefix: &'static str,
+    /// The prefix before the upstream in "Unmerged into <upstream>" (includes trailing space).
+    pub section_unmerged_into_prefix: &'static str,
+    /// The prefix before the push branch in "Unpushed to <remote>" (includes trailing space).
+    pub section_unpushed_to_prefix: &'static str,
 
     // Popup window titles
     pub popup_branch: &'static str,
@@ -44,6 +48,7 @@ pub struct Strings {
     pub popup_stash: &'static str,
     pub popup_tag: &'static str,
     pub popup_worktree: &'static str,
+    pub popup_diff: &'static str,
     pub popup_reset: &'static str,
     pub popup_rebase: &'static str,
     pub popup_revert: &'static str,
@@ -51,6 +56,7 @@ pub struct Strings {
     pub popup_merging: &'static str,
     pub popup_apply: &'static str,
     pub popup_applying: &'static str,
+    pub popup_resolve: &'static str,
     pub popup_help: &'static str,
     pub popup_error: &'static str,
     pub popup_confirm: &'static str,
@@ -71,8 +77,14 @@ pub struct Strings {
     pub col_actions: &'static str,
     pub col_commands: &'static str,
     pub col_applying_changes: &'static str,
+    pub col_diff_context_fmt: &'static str,
+    pub col_whitespace: &'static str,
+    pub col_hunk: &'static str,
     pub col_general: &'static str,
     pub col_apply_here: &'static str,
+    pub col_resolve_conflict: &'static str,
+    pub col_resolve_file: &'static str,
+    pub col_file: &'static str,
     pub col_apply_elsewhere: &'static str,
     pub col_reflog: &'static str,
     pub col_inspect: &'static str,
@@ -125,9 +137,14 @@ pub struct Strings {
     pub cmd_extend: &'static str,
     pub cmd_amend: &'static str,
     pub cmd_reword: &'static str,
+    pub cmd_reshelve: &'static str,
     pub cmd_fixup: &'static str,
     pub cmd_squash: &'static str,
     pub cmd_alter: &'static str,
+    pub cmd_instant_fixup: &'static str,
+    pub cmd_instant_squash: &'static str,
+    pub cmd_absorb: &'static str,
+    pub cmd_absorb_autosquash: &'static str,
     pub cmd_augment: &'static str,
     pub cmd_revise: &'static str,
     pub cmd_tag: &'static str,
@@ -152,6 +169,8 @@ pub struct Strings {
     /// Window title while picking the commit to squash fixups into
     /// (autosquash fallback when no upstream is configured)
     pub title_pick_autosquash_commit: &'static str,
+    pub title_commit_message: &'static str,
+    pub title_amend_message: &'static str,
     pub cmd_all_remotes: &'static str,
     pub cmd_another_branch: &'static str,
     pub cmd_explicit_refspec: &'static str,
@@ -166,6 +185,16 @@ pub struct Strings {
     pub cmd_both: &'static str,
     pub cmd_index: &'static str,
     pub cmd_worktree: &'static str,
+    pub cmd_diff: &'static str,
+    pub cmd_more_context: &'static str,
+    pub cmd_less_context: &'static str,
+    pub cmd_default_context: &'static str,
+    pub cmd_ignore_whitespace: &'static str,
+    pub cmd_show_whitespace: &'static str,
+    pub cmd_split_hunk: &'static str,
+    pub cmd_edit_hunk: &'static str,
+    pub cmd_review_unstaged: &'static str,
+    pub cmd_review_staged: &'static str,
     pub cmd_branch_and_worktree: &'static str,
     pub cmd_keeping_index: &'static str,
     pub cmd_to_wip_ref: &'static str,
@@ -188,6 +217,7 @@ pub struct Strings {
     pub cmd_continue: &'static str,
     pub cmd_skip: &'static str,
     pub cmd_abort: &'static str,
+    pub cmd_edit_todo: &'static str,
     pub cmd_merge: &'static str,
     pub cmd_merge_edit_message: &'static str,
     pub cmd_merge_no_commit: &'static str,
@@ -213,10 +243,22 @@ pub struct Strings {
     pub cmd_stash: &'static str,
     pub cmd_stage: &'static str,
     pub cmd_stage_all: &'static str,
+    pub cmd_intent_to_add: &'static str,
     pub cmd_unstage: &'static str,
     pub cmd_unstage_all: &'static str,
     pub cmd_discard: &'static str,
     pub cmd_reverse: &'static str,
+    pub cmd_resolve: &'static str,
+    pub cmd_keep_ours: &'static str,
+    pub cmd_keep_theirs: &'static str,
+    pub cmd_keep_both: &'static str,
+    pub cmd_keep_base: &'static str,
+    pub cmd_checkout_ours: &'static str,
+    pub cmd_checkout_theirs: &'static str,
+    pub cmd_restore_conflict: &'static str,
+    pub cmd_mergetool: &'static str,
+    pub cmd_mergetool_all: &'static str,
+    pub cmd_visit_at_revision: &'static str,
     pub cmd_quit: &'static str,
     pub cmd_refresh: &'static str,
     pub cmd_show_help: &'static str,
@@ -237,7 +279,11 @@ pub struct Strings {
     pub arg_commit_verbose: &'static str,
     pub arg_commit_disable_hooks: &'static str,
     pub arg_commit_reset_author: &'static str,
+    pub arg_commit_signoff: &'static str,
     pub arg_commit_author: &'static str,
+    pub arg_commit_co_author: &'static str,
+    pub arg_commit_reviewer: &'static str,
+    pub arg_gpg_sign: &'static str,
     pub arg_fetch_prune: &'static str,
     pub arg_fetch_tags: &'static str,
     pub arg_fetch_force: &'static str,
@@ -262,19 +308,43 @@ pub struct Strings {
     pub arg_revert_no_edit: &'static str,
     pub arg_log_graph: &'static str,
     pub arg_log_color: &'static str,
+    pub arg_log_signatures: &'static str,
     pub arg_tag_force: &'static str,
     pub arg_tag_edit: &'static str,
     pub arg_tag_annotate: &'static str,
     pub arg_tag_sign: &'static str,
     pub arg_revert_mainline: &'static str,
     pub input_revert_mainline: &'static str,
+    pub input_reshelve_date: &'static str,
+    pub input_rebase_exec: &'static str,
     pub arg_rebase_keep_empty: &'static str,
+    pub arg_rebase_autostash: &'static str,
+    pub arg_rebase_merges: &'static str,
+    pub arg_rebase_update_refs: &'static str,
+    pub arg_rebase_committer_date: &'static str,
+    pub arg_rebase_keep_base: &'static str,
+    pub arg_exec: &'static str,
+    pub arg_strategy_option: &'static str,
+    pub arg_strategy: &'static str,
+    pub arg_merge_ff_only: &'static str,
+    pub arg_merge_no_ff: &'static str,
+    pub arg_merge_no_verify: &'static str,
+    pub arg_signoff: &'static str,
+    pub arg_merge_ignore_space_change: &'static str,
+    pub arg_merge_ignore_all_space: &'static str,
+    pub arg_apply_record_origin: &'static str,
+    pub arg_apply_ff: &'static str,
+    pub arg_apply_allow_empty: &'static str,
+    pub arg_apply_keep_redundant: &'static str,
 
     // Mode labels shown in the status bar
     pub mode_normal: &'static str,
     pub mode_visual: &'static str,
     pub mode_search: &'static str,
     pub mode_preview: &'static str,
+    pub mode_blob: &'static str,
+    pub mode_insert: &'static str,
+    pub mode_review: &'static str,
 
     // Keybinding hint lines shown below the rebase todo entries
     pub rebase_hint_pick: &'static str,
@@ -282,12 +352,42 @@ pub struct Strings {
     pub rebase_hint_edit: &'static str,
     pub rebase_hint_squash: &'static str,
     pub rebase_hint_fixup: &'static str,
+    pub rebase_hint_fixup_keep: &'static str,
+    pub rebase_hint_fixup_edit: &'static str,
     pub rebase_hint_drop: &'static str,
+    pub rebase_hint_exec: &'static str,
+    pub rebase_hint_break: &'static str,
     pub rebase_hint_move: &'static str,
     pub rebase_hint_undo: &'static str,
     pub rebase_hint_show: &'static str,
     pub rebase_hint_confirm: &'static str,
+    pub rebase_hint_save: &'static str,
     pub rebase_hint_abort: &'static str,
+    /// Keybinding hint shown at the bottom of the commit message editor
+    pub commit_editor_hint: &'static str,
+
+    // Keybinding hint lines shown below the reviewed hunk
+    pub review_hint_stage: &'static str,
+    pub review_hint_unstage: &'static str,
+    pub review_hint_skip: &'static str,
+    pub review_hint_discard: &'static str,
+    pub review_hint_split: &'static str,
+    pub review_hint_edit: &'static str,
+    pub review_hint_move: &'static str,
+    pub review_hint_finish: &'static str,
+    pub review_hint_abort: &'static str,
+    /// Format template for confirming a review that discards hunks: the
+    /// number of hunks discarded, via fmt1().
+    pub review_confirm_discard_fmt: &'static str,
+    /// Format template for the review title: the hunk's number, the number
+    /// of hunks, the file's number and the number of files, via fmt1().
+    pub title_hunk_review_fmt: &'static str,
+    // What was decided on a reviewed hunk, shown after the title
+    pub review_decision_stage: &'static str,
+    pub review_decision_unstage: &'static str,
+    pub review_decision_skip: &'static str,
+    pub review_decision_discard: &'static str,
+    pub review_decision_edited: &'static str,
 
     // Popup hint lines
     pub hint_dismiss: &'static str,
@@ -298,6 +398,7 @@ pub struct Strings {
     pub completed_successfully_fmt: &'static str,
     /// Fallback operation name used when none is available.
     pub operation_fallback: &'static str,
+    pub intent_to_add_untracked_only: &'static str,
 }
 
 impl Strings {
@@ -317,6 +418,8 @@ static ENGLISH: Strings = Strings {
     section_reverting: "Reverting",
     section_cherry_picking: "Cherry Picking",
     section_unpulled_from_prefix: "Unpulled from ",
+    section_unmerged_into_prefix: "Unmerged into ",
+    section_unpushed_to_prefix: "Unpushed to ",
 
     popup_branch: "Branch",
     popup_commit: "Commit",
@@ -327,6 +430,7 @@ static ENGLISH: Strings = Strings {
     popup_stash: "Stash",
     popup_tag: "Tag",
     popup_worktree: "Worktree",
+    popup_diff: "Diff",
     popup_reset: "Reset",
     popup_rebase: "Rebase",
     popup_revert: "Revert",
@@ -334,6 +438,7 @@ static ENGLISH: Strings = Strings {
     popup_merging: "Merging",
     popup_apply: "Apply",
     popup_applying: "Applying",
+    popup_resolve: "Resolve",
     popup_help: "Help",
     popup_error: "Error",
     popup_confirm: "Confirm",
@@ -353,8 +458,14 @@ static ENGLISH: Strings = Strings {
     col_actions: "Actions",
     col_commands: "Commands",
     col_applying_changes: "Applying changes",
+    col_diff_context_fmt: "Context ({} lines)",
+    col_whitespace: "Whitespace",
+    col_hunk: "Hunk",
     col_general: "General",
     col_apply_here: "Apply here",
+    col_resolve_conflict: "This conflict",
+    col_resolve_file: "Whole file",
+    col_file: "File",
     col_apply_elsewhere: "Apply elsewhere",
     col_reflog: "Reflog",
     col_inspect: "Inspect",
@@ -398,9 +509,14 @@ static ENGLISH: Strings = Strings {
     cmd_extend: "Extend",
     cmd_amend: "Amend",
     cmd_reword: "Reword",
+    cmd_reshelve: "Reshelve",
     cmd_fixup: "Fixup",
     cmd_squash: "Squash",
     cmd_alter: "Alter",
+    cmd_instant_fixup: "Instant fixup",
+    cmd_instant_squash: "Instant squash",
+    cmd_absorb: "Absorb",
+    cmd_absorb_autosquash: "Absorb and autosquash",
     cmd_augment: "Augment",
     cmd_revise: "Revise",
     cmd_tag: "Tag",
@@ -418,6 +534,8 @@ static ENGLISH: Strings = Strings {
     title_pick_reword_commit: "Press Enter on a commit to reword its message, or Esc/q to cancel",
     title_pick_remove_commit: "Press Enter on a commit to remove it, or Esc/q to cancel",
     title_pick_autosquash_commit: "Press Enter on a commit to squash into it and then rebase, or Esc/q to cancel",
+    title_commit_message: "Commit message",
+    title_amend_message: "Amend message",
     cmd_all_remotes: "All remotes",
     cmd_another_branch: "Another branch",
     cmd_explicit_refspec: "Explicit refspec",
@@ -432,6 +550,16 @@ static ENGLISH: Strings = Strings {
     cmd_both: "Both",
     cmd_index: "Index",
     cmd_worktree: "Worktree",
+    cmd_diff: "Diff",
+    cmd_more_context: "More context",
+    cmd_less_context: "Less context",
+    cmd_default_context: "Default context",
+    cmd_ignore_whitespace: "Ignore whitespace",
+    cmd_show_whitespace: "Show whitespace changes",
+    cmd_split_hunk: "Split/join",
+    cmd_edit_hunk: "Edit and stage",
+    cmd_review_unstaged: "Review unstaged",
+    cmd_review_staged: "Review staged",
     cmd_branch_and_worktree: "Branch and worktree",
     cmd_keeping_index: "Keeping index",
     cmd_to_wip_ref: "To wip ref",
@@ -454,6 +582,7 @@ static ENGLISH: Strings = Strings {
     cmd_continue: "Continue",
     cmd_skip: "Skip",
     cmd_abort: "Abort",
+    cmd_edit_todo: "Edit todo",
     cmd_merge: "Merge",
     cmd_merge_edit_message: "Merge and edit message",
     cmd_merge_no_commit: "Merge but don't commit",
@@ -478,10 +607,22 @@ static ENGLISH: Strings = Strings {
     cmd_stash: "Stash",
     cmd_stage: "Stage",
     cmd_stage_all: "Stage all",
+    cmd_intent_to_add: "Intent to add",
     cmd_unstage: "Unstage",
     cmd_unstage_all: "Unstage all",
     cmd_discard: "Discard",
     cmd_reverse: "Reverse",
+    cmd_resolve: "Resolve conflicts",
+    cmd_keep_ours: "Keep ours",
+    cmd_keep_theirs: "Keep theirs",
+    cmd_keep_both: "Keep both",
+    cmd_keep_base: "Keep base",
+    cmd_checkout_ours: "Checkout ours",
+    cmd_checkout_theirs: "Checkout theirs",
+    cmd_restore_conflict: "Restore conflict",
+    cmd_mergetool: "Run mergetool",
+    cmd_mergetool_all: "Mergetool all unresolved",
+    cmd_visit_at_revision: "Visit at revision",
     cmd_quit: "        quit",
     cmd_refresh: "Refresh",
     cmd_show_help: "      show this help",
@@ -501,7 +642,11 @@ static ENGLISH: Strings = Strings {
     arg_commit_verbose: "Show diff of changes to be commited",
     arg_commit_disable_hooks: "Disable hooks",
     arg_commit_reset_author: "Claim authorship and reset author date",
+    arg_commit_signoff: "Add Signed-off-by trailer",
     arg_commit_author: "Override the author",
+    arg_commit_co_author: "Add co-author",
+    arg_commit_reviewer: "Add reviewer",
+    arg_gpg_sign: "Sign using gpg",
     arg_fetch_prune: "Prune deleted branches",
     arg_fetch_tags: "Fetch all tags",
     arg_fetch_force: "Force",
@@ -524,36 +669,83 @@ static ENGLISH: Strings = Strings {
     arg_revert_no_edit: "Don't edit commit message",
     arg_log_graph: "Show graph",
     arg_log_color: "Show graph in color",
+    arg_log_signatures: "Show signatures",
     arg_tag_force: "Force",
     arg_tag_edit: "Edit message",
     arg_tag_annotate: "Annotate",
     arg_tag_sign: "Sign",
     arg_revert_mainline: "Replay merge relative to parent",
     input_revert_mainline: "Mainline parent number",
+    input_reshelve_date: "Reshelve to date",
+    input_rebase_exec: "Exec command",
     arg_rebase_keep_empty: "Keep empty commits",
+    arg_rebase_autostash: "Autostash",
+    arg_rebase_merges: "Rebase merges",
+    arg_rebase_update_refs: "Update branches",
+    arg_rebase_committer_date: "Lie about committer date",
+    arg_rebase_keep_base: "Keep base commit",
+    arg_exec: "Run command after each commit",
+    arg_strategy_option: "Strategy option",
+    arg_strategy: "Strategy",
+    arg_merge_ff_only: "Fast-forward only",
+    arg_merge_no_ff: "No fast-forward",
+    arg_merge_no_verify: "Disable hooks",
+    arg_signoff: "Add Signed-off-by trailer",
+    arg_merge_ignore_space_change: "Ignore changes in amount of whitespace",
+    arg_merge_ignore_all_space: "Ignore whitespace",
+    arg_apply_record_origin: "Reference cherry in commit message",
+    arg_apply_ff: "Attempt fast-forward",
+    arg_apply_allow_empty: "Allow empty commits",
+    arg_apply_keep_redundant: "Keep redundant commits",
 
     mode_normal: "NORMAL",
     mode_visual: "VISUAL",
     mode_search: "SEARCH",
     mode_preview: "PREVIEW",
+    mode_blob: "BLOB",
+    mode_insert: "INSERT",
+    mode_review: "REVIEW",
 
     rebase_hint_pick: "Pick",
     rebase_hint_reword: "Reword",
     rebase_hint_edit: "Edit",
     rebase_hint_squash: "Squash",
     rebase_hint_fixup: "Fixup",
+    rebase_hint_fixup_keep: "Fixup, keep message (fixup -C)",
+    rebase_hint_fixup_edit: "Fixup, edit message (fixup -c)",
     rebase_hint_drop: "Drop",
+    rebase_hint_exec: "Insert exec (RET on exec to edit)",
+    rebase_hint_break: "Insert break",
     rebase_hint_move: "Move entry",
     rebase_hint_undo: "Undo",
     rebase_hint_show: "Show commit",
     rebase_hint_confirm: "Confirm rebase",
+    rebase_hint_save: "Save todo",
     rebase_hint_abort: "Abort",
+    commit_editor_hint: "C-c C-c commit · C-c C-k abort · M-p/M-n history · Esc then :wq/:q",
+    review_hint_stage: "Stage this hunk",
+    review_hint_unstage: "Unstage this hunk",
+    review_hint_skip: "Leave this hunk",
+    review_hint_discard: "Discard this hunk",
+    review_hint_split: "Split into blocks of changes",
+    review_hint_edit: "Edit this hunk, then stage it",
+    review_hint_move: "Previous/next hunk",
+    review_hint_finish: "Apply the decisions and finish",
+    review_hint_abort: "Drop the decisions and finish",
+    review_confirm_discard_fmt: "Apply the review? {} hunk(s) will be discarded from the working tree.",
+    title_hunk_review_fmt: "Review: hunk {} of {}, file {} of {}",
+    review_decision_stage: "to be staged",
+    review_decision_unstage: "to be unstaged",
+    review_decision_skip: "left as it is",
+    review_decision_discard: "to be discarded",
+    review_decision_edited: "edited, to be staged",
 
     hint_dismiss: "Press Enter or Esc to dismiss",
     hint_confirm: "y/Enter to confirm, n/Esc to cancel",
 
     completed_successfully_fmt: "{} completed successfully",
     operation_fallback: "Operation",
+    intent_to_add_untracked_only: "Intent to add only applies to untracked files",
 };
 
 static SWEDISH: Strings = Strings {
@@ -566,6 +758,8 @@ static SWEDISH: Strings = Strings {
     section_reverting: "Återgår",
     section_cherry_picking: "Plockar russin",
     section_unpulled_from_prefix: "Ej ryckta från ",
+    section_unmerged_into_prefix: "Ej sammanfogade i ",
+    section_unpushed_to_prefix: "Ej knuffade till ",
 
     popup_branch: "Grena",
     popup_commit: "Förbinda",
@@ -576,6 +770,7 @@ static SWEDISH: Strings = Strings {
     popup_stash: "Gömma",
     popup_tag: "Märke",
     popup_worktree: "Arbetsträd",
+    popup_diff: "Diff",
     popup_reset: "Återställ",
     popup_rebase: "Ympa",
     popup_revert: "Återgå",
@@ -583,6 +778,7 @@ static SWEDISH: Strings = Strings {
     popup_merging: "Sammanfogar",
     popup_apply: "Plocka russin",
     popup_applying: "Plockar russin",
+    popup_resolve: "Lös konflikt",
     popup_help: "Hjälp",
     popup_error: "Fel",
     popup_confirm: "Bekräfta",
@@ -602,8 +798,14 @@ static SWEDISH: Strings = Strings {
     col_actions: "Åtgärder",
     col_commands: "Kommandon",
     col_applying_changes: "Tillämpa ändringar",
+    col_diff_context_fmt: "Kontext ({} rader)",
+    col_whitespace: "Blanktecken",
+    col_hunk: "Stycke",
     col_general: "Allmänt",
     col_apply_here: "Plocka russin här",
+    col_resolve_conflict: "Denna konflikt",
+    col_resolve_file: "Hela filen",
+    col_file: "Fil",
     col_apply_elsewhere: "Plocka russin någon annanstans",
     col_reflog: "Reflogg",
     col_inspect: "Inspektera",
@@ -647,9 +849,14 @@ static SWEDISH: Strings = Strings {
     cmd_extend: "Förläng",
     cmd_amend: "Rätta till",
     cmd_reword: "Omformulera",
+    cmd_reshelve: "Flytta datum",
     cmd_fixup: "Fixup",
     cmd_squash: "Mosa",
     cmd_alter: "Modifiera",
+    cmd_instant_fixup: "Omedelbar fixup",
+    cmd_instant_squash: "Omedelbar mosning",
+    cmd_absorb: "Absorbera",
+    cmd_absorb_autosquash: "Absorbera och mosa",
     cmd_augment: "Utöka",
     cmd_revise: "Revidera",
     cmd_tag: "Märka",
@@ -667,6 +874,8 @@ static SWEDISH: Strings = Strings {
     title_pick_reword_commit: "Tryck Enter på en förbindelse för att omformulera dess meddelande, eller Esc/q för att avbryta",
     title_pick_remove_commit: "Tryck Enter på en förbindelse för att ta bort den, eller Esc/q för att avbryta",
     title_pick_autosquash_commit: "Tryck Enter på en förbindelse för att mosa in i den och sedan ympa, eller Esc/q för att avbryta",
+    title_commit_message: "Förbindelsemeddelande",
+    title_amend_message: "Ändra förbindelsemeddelande",
     cmd_all_remotes: "Alla fjärrar",
     cmd_another_branch: "Annan gren",
     cmd_explicit_refspec: "Explicit refspec",
@@ -681,6 +890,16 @@ static SWEDISH: Strings = Strings {
     cmd_both: "Båda",
     cmd_index: "Register",
     cmd_worktree: "Arbetsträd",
+    cmd_diff: "Diff",
+    cmd_more_context: "Mer kontext",
+    cmd_less_context: "Mindre kontext",
+    cmd_default_context: "Standardkontext",
+    cmd_ignore_whitespace: "Ignorera blanktecken",
+    cmd_show_whitespace: "Visa ändringar i blanktecken",
+    cmd_split_hunk: "Dela/slå ihop",
+    cmd_edit_hunk: "Redigera och klarmarkera",
+    cmd_review_unstaged: "Granska ej klarmarkerade",
+    cmd_review_staged: "Granska klarmarkerade",
     cmd_branch_and_worktree: "Gren och arbetsträd",
     cmd_keeping_index: "Behåll register",
     cmd_to_wip_ref: "Till wip-referens",
@@ -703,6 +922,7 @@ static SWEDISH: Strings = Strings {
     cmd_continue: "Fortsätt",
     cmd_skip: "Hoppa över",
     cmd_abort: "Avbryt",
+    cmd_edit_todo: "Redigera listan",
     cmd_merge: "Sammanfoga",
     cmd_merge_edit_message: "Sammanfoga och redigera meddelande",
     cmd_merge_no_commit: "Sammanfoga men checka inte in",
@@ -727,10 +947,22 @@ static SWEDISH: Strings = Strings {
     cmd_stash: "Göm",
     cmd_stage: "Klarmarkera",
     cmd_stage_all: "Klarmarkera allt",
+    cmd_intent_to_add: "Avsikt att lägga till",
     cmd_unstage: "Återkalla",
     cmd_unstage_all: "Återkalla allt",
     cmd_discard: "Fimpa",
     cmd_reverse: "Tvärtemot",
+    cmd_resolve: "Lös konflikter",
+    cmd_keep_ours: "Behåll vår",
+    cmd_keep_theirs: "Behåll deras",
+    cmd_keep_both: "Behåll båda",
+    cmd_keep_base: "Behåll bas",
+    cmd_checkout_ours: "Checka ut vår",
+    cmd_checkout_theirs: "Checka ut deras",
+    cmd_restore_conflict: "Återställ konflikt",
+    cmd_mergetool: "Kör mergetool",
+    cmd_mergetool_all: "Mergetool för alla olösta",
+    cmd_visit_at_revision: "Visa vid revision",
     cmd_quit: "        Avsluta",
     cmd_refresh: "Förfriska",
     cmd_show_help: "      Visa denna hjälp",
@@ -750,7 +982,11 @@ static SWEDISH: Strings = Strings {
     arg_commit_verbose: "Visa skillnad av ändringar att förbinda",
     arg_commit_disable_hooks: "Inaktivera krokar",
     arg_commit_reset_author: "Hävda upphovsrätt och återställ upphovsdatum",
+    arg_commit_signoff: "Skriv under med Signed-off-by",
     arg_commit_author: "Åsidosätt upphovsmannen",
+    arg_commit_co_author: "Lägg till medupphovsman",
+    arg_commit_reviewer: "Lägg till granskare",
+    arg_gpg_sign: "Signera med gpg",
     arg_fetch_prune: "Beskär raderade grenar",
     arg_fetch_tags: "Hämta alla märken",
     arg_fetch_force: "Tvinga",
@@ -773,36 +1009,83 @@ static SWEDISH: Strings = Strings {
     arg_revert_no_edit: "Ändra inte förbindelsemeddelandet",
     arg_log_graph: "Visa graf",
     arg_log_color: "Visa graf i färg",
+    arg_log_signatures: "Visa signaturer",
     arg_tag_force: "Tvinga",
     arg_tag_edit: "Ändra meddelandet",
     arg_tag_annotate: "Annotera",
     arg_tag_sign: "Signera",
     arg_revert_mainline: "Spela upp sammanslagning relativt förälder",
     input_revert_mainline: "Förälderantalsnummer",
+    input_reshelve_date: "Flytta till datum",
+    input_rebase_exec: "Kommando att köra",
     arg_rebase_keep_empty: "Behåll tomma förbindelser",
+    arg_rebase_autostash: "Autostash",
+    arg_rebase_merges: "Ympa sammanslagningar",
+    arg_rebase_update_refs: "Uppdatera grenar",
+    arg_rebase_committer_date: "Ljug om förbindardatum",
+    arg_rebase_keep_base: "Behåll basförbindelsen",
+    arg_exec: "Kör kommando efter varje förbindelse",
+    arg_strategy_option: "Strategialternativ",
+    arg_strategy: "Strategi",
+    arg_merge_ff_only: "Endast snabbspola",
+    arg_merge_no_ff: "Snabbspola aldrig",
+    arg_merge_no_verify: "Inaktivera krokar",
+    arg_signoff: "Skriv under med Signed-off-by",
+    arg_merge_ignore_space_change: "Ignorera ändrad mängd blanktecken",
+    arg_merge_ignore_all_space: "Ignorera blanktecken",
+    arg_apply_record_origin: "Hänvisa till körsbäret i meddelandet",
+    arg_apply_ff: "Försök snabbspola",
+    arg_apply_allow_empty: "Tillåt tomma förbindelser",
+    arg_apply_keep_redundant: "Behåll överflödiga förbindelser",
 
     mode_normal: "NORMAL",
     mode_visual: "VISUELL",
     mode_search: "SÖK",
     mode_preview: "FÖRHANDSGRANSKNING",
+    mode_blob: "BLOB",
+    mode_insert: "INFOGA",
+    mode_review: "GRANSKA",
 
     rebase_hint_pick: "Plocka",
     rebase_hint_reword: "Omformulera",
     rebase_hint_edit: "Redigera",
     rebase_hint_squash: "Mosa",
     rebase_hint_fixup: "Fixup",
+    rebase_hint_fixup_keep: "Fixup, behåll meddelande (fixup -C)",
+    rebase_hint_fixup_edit: "Fixup, redigera meddelande (fixup -c)",
     rebase_hint_drop: "Fimpa",
+    rebase_hint_exec: "Infoga exec (RET på exec för att redigera)",
+    rebase_hint_break: "Infoga paus",
     rebase_hint_move: "Flytta rad",
     rebase_hint_undo: "Ångra",
     rebase_hint_show: "Visa förbindelse",
     rebase_hint_confirm: "Bekräfta ympning",
+    rebase_hint_save: "Spara listan",
     rebase_hint_abort: "Avbryt",
+    commit_editor_hint: "C-c C-c förbind · C-c C-k avbryt · M-p/M-n historik · Esc sedan :wq/:q",
+    review_hint_stage: "Klarmarkera detta stycke",
+    review_hint_unstage: "Återkalla detta stycke",
+    review_hint_skip: "Lämna detta stycke",
+    review_hint_discard: "Fimpa detta stycke",
+    review_hint_split: "Dela i block av ändringar",
+    review_hint_edit: "Redigera detta stycke och klarmarkera det",
+    review_hint_move: "Föregående/nästa stycke",
+    review_hint_finish: "Verkställ besluten och avsluta",
+    review_hint_abort: "Släpp besluten och avsluta",
+    review_confirm_discard_fmt: "Verkställ granskningen? {} stycke(n) fimpas från arbetsträdet.",
+    title_hunk_review_fmt: "Granskning: stycke {} av {}, fil {} av {}",
+    review_decision_stage: "ska klarmarkeras",
+    review_decision_unstage: "ska återkallas",
+    review_decision_skip: "lämnas som det är",
+    review_decision_discard: "ska fimpas",
+    review_decision_edited: "redigerat, ska klarmarkeras",
 
     hint_dismiss: "Tryck Enter eller Esc för att stänga",
     hint_confirm: "y/Enter för att bekräfta, n/Esc för att avbryta",
 
     completed_successfully_fmt: "{} slutfördes",
     operation_fallback: "Åtgärd",
+    intent_to_add_untracked_only: "Avsikt att lägga till gäller bara ospårade filer",
 };
 
 static ACTIVE: OnceLock<Language> = OnceLock::new();
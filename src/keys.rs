@@ -11,11 +11,13 @@ use crate::{
         popup::{CommitPopupState, ConfirmAction, PopupContent, PopupContentCommand},
     },
     msg::{
-        Message, NavigationAction, RebaseCommand, RebaseTodoMessage, SearchMessage, SelectMessage,
+        BlobMessage, DiffMessage, HunkReviewMessage, Message, NavigationAction, RebaseCommand,
+        RebasePendingMessage, RebaseTodoMessage, SearchMessage, SelectMessage,
     },
 };
 
 mod command_popup;
+mod commit_editor;
 mod credentials_popup;
 mod edit;
 mod input_popup;
@@ -23,6 +25,7 @@ mod input_popup;
 const NONE: KeyModifiers = KeyModifiers::NONE;
 const CTRL: KeyModifiers = KeyModifiers::CONTROL;
 const ALT: KeyModifiers = KeyModifiers::ALT;
+const SHIFT: KeyModifiers = KeyModifiers::SHIFT;
 
 fn command_popup_keys(c: char) -> Option<Message> {
     match c {
@@ -48,10 +51,28 @@ fn command_popup_keys(c: char) -> Option<Message> {
             PopupContentCommand::Worktree,
         ))),
         'O' => Some(Message::ShowResetPopup),
+        'e' => Some(Message::ShowResolvePopup),
+        'D' => Some(Message::ShowPopup(PopupContent::Command(
+            PopupContentCommand::Diff,
+        ))),
         _ => None,
     }
 }
 
+/// Confirming the todo editor starts the rebase, or for the todo of a
+/// stopped rebase writes it back (no external command needed).
+fn confirm_rebase_todo(model: &Model) -> Message {
+    if model
+        .rebase_todo
+        .as_ref()
+        .is_some_and(|state| state.in_progress)
+    {
+        Message::RebaseTodo(RebaseTodoMessage::Save)
+    } else {
+        Message::Rebase(RebaseCommand::ExecuteInteractive)
+    }
+}
+
 /// Maps a key event into a [`Message`] given the application state.
 /// If function returns [`None`], no action should be triggered.
 pub fn handle_key(key: event::KeyEvent, model: &Model) -> Option<Message> {
@@ -110,6 +131,11 @@ pub fn handle_key(key: event::KeyEvent, model: &Model) -> Option<Message> {
                         remote_tags: remote_tags.clone(),
                         remote: remote.clone(),
                     },
+                    ConfirmAction::Absorb { plan, autosquash } => Message::Absorb {
+                        plan: plan.clone(),
+                        autosquash: *autosquash,
+                    },
+                    ConfirmAction::ApplyHunkReview => Message::HunkReview(HunkReviewMessage::Apply),
                 };
                 Some(msg)
             }
@@ -132,6 +158,13 @@ pub fn handle_key(key: event::KeyEvent, model: &Model) -> Option<Message> {
         return command_popup::handle_command_popup_key(key, command, model.arg_mode);
     }
 
+    // The commit message editor takes every key while it is open
+    if model.view_mode == ViewMode::CommitMessage
+        && let Some(state) = &model.commit_editor
+    {
+        return commit_editor::handle_commit_editor_key(key, state);
+    }
+
     // Let commands from help popup open popups
     if model.popup == Some(PopupContent::Help) {
         return match (key.modifiers, key.code) {
@@ -195,6 +228,25 @@ pub fn handle_key(key: event::KeyEvent, model: &Model) -> Option<Message> {
             (_, Char('q')) | (_, Esc) | (CTRL, Char('g')) | (CTRL, Char('c')) => {
                 return Some(Message::ExitPreview);
             }
+            (_, Enter) => return Some(Message::VisitFile),
+            // Nothing to reverse in a preview, so `-` shrinks the context
+            (NONE, Char('-')) => return Some(Message::Diff(DiffMessage::LessContext)),
+            _ => {}
+        }
+    }
+
+    // Blob view: step through the file's history, blame, or leave
+    if matches!(model.view_mode, ViewMode::Blob { .. }) {
+        match (key.modifiers, key.code) {
+            (_, Char('q')) | (_, Esc) | (CTRL, Char('g')) | (CTRL, Char('c')) => {
+                return Some(Message::ExitPreview);
+            }
+            (NONE, Char('p')) => return Some(Message::Blob(BlobMessage::Previous)),
+            // With an active search, n keeps jumping to the next match
+            (NONE, Char('n')) if model.ui_model.search_query.is_empty() => {
+                return Some(Message::Blob(BlobMessage::Next));
+            }
+            (NONE, Char('b')) => return Some(Message::Blob(BlobMessage::ToggleBlame)),
             _ => {}
         }
     }
@@ -220,6 +272,20 @@ pub fn handle_key(key: event::KeyEvent, model: &Model) -> Option<Message> {
         if is_previewable {
             return Some(Message::ShowPreview);
         }
+        // Files and diff lines open in the editor
+        let is_visitable = matches!(
+            cursor_content,
+            Some(
+                LineContent::UntrackedFile(_)
+                    | LineContent::UnstagedFile(_)
+                    | LineContent::StagedFile(_)
+                    | LineContent::DiffHunk(_)
+                    | LineContent::DiffLine(_)
+            )
+        );
+        if model.view_mode == ViewMode::Status && is_visitable {
+            return Some(Message::VisitFile);
+        }
     }
 
     // Enter/Esc in log pick mode
@@ -248,7 +314,7 @@ pub fn handle_key(key: event::KeyEvent, model: &Model) -> Option<Message> {
         {
             return match (key.modifiers, key.code) {
                 (_, Enter) => match cmd {
-                    "wq" | "wq!" | "x" => Some(Message::Rebase(RebaseCommand::ExecuteInteractive)),
+                    "wq" | "wq!" | "x" => Some(confirm_rebase_todo(model)),
                     "q" | "q!" => todo_msg(RebaseTodoMessage::Abort),
                     _ => todo_msg(RebaseTodoMessage::CommandInvalid),
                 },
@@ -281,12 +347,34 @@ pub fn handle_key(key: event::KeyEvent, model: &Model) -> Option<Message> {
             (NONE, Char('f')) => {
                 return todo_msg(RebaseTodoMessage::SetAction(RebaseAction::Fixup));
             }
+            (NONE | SHIFT, Char('F')) => {
+                return todo_msg(RebaseTodoMessage::SetAction(RebaseAction::FixupKeepMessage));
+            }
+            (NONE | SHIFT, Char('S')) => {
+                return todo_msg(RebaseTodoMessage::SetAction(RebaseAction::FixupEditMessage));
+            }
             (NONE, Char('d')) => return todo_msg(RebaseTodoMessage::SetAction(RebaseAction::Drop)),
+            (NONE, Char('x')) => return todo_msg(RebaseTodoMessage::ShowExecInput { edit: false }),
+            (NONE, Char('b')) => return todo_msg(RebaseTodoMessage::InsertBreak),
             (NONE, Char('u')) => return todo_msg(RebaseTodoMessage::Undo),
             (_, Char('K')) | (ALT, Up) => return todo_msg(RebaseTodoMessage::MoveEntryUp),
             (_, Char('J')) | (ALT, Down) => return todo_msg(RebaseTodoMessage::MoveEntryDown),
-            (_, Enter) => return Some(Message::ShowPreview),
-            (_, Char('R')) => return Some(Message::Rebase(RebaseCommand::ExecuteInteractive)),
+            (_, Enter) => {
+                // RET on an exec line edits its command; commits show a preview
+                let on_exec = model
+                    .ui_model
+                    .lines
+                    .get(model.ui_model.cursor_position)
+                    .is_some_and(|line| {
+                        matches!(&line.content, LineContent::RebaseTodoLine(entry)
+                            if entry.action == RebaseAction::Exec)
+                    });
+                if on_exec {
+                    return todo_msg(RebaseTodoMessage::ShowExecInput { edit: true });
+                }
+                return Some(Message::ShowPreview);
+            }
+            (_, Char('R')) => return Some(confirm_rebase_todo(model)),
             (_, Char(':')) => return todo_msg(RebaseTodoMessage::CommandStart),
             (_, Char('q')) | (_, Esc) | (CTRL, Char('g')) | (CTRL, Char('c')) => {
                 return todo_msg(RebaseTodoMessage::Abort);
@@ -295,6 +383,55 @@ pub fn handle_key(key: event::KeyEvent, model: &Model) -> Option<Message> {
         }
     }
 
+    // Hunk review: a decision per hunk, like `git add -p`. Scrolling keeps
+    // its normal behaviour for long hunks.
+    if model.view_mode == ViewMode::HunkReview {
+        let review_msg = |m| Some(Message::HunkReview(m));
+        match (key.modifiers, key.code) {
+            (CTRL, Char('u' | 'd' | 'e' | 'y')) | (NONE, Up | Down) => {}
+            (NONE, Char('y')) => return review_msg(HunkReviewMessage::Accept),
+            (NONE, Char('n')) => return review_msg(HunkReviewMessage::Skip),
+            (NONE, Char('d')) => return review_msg(HunkReviewMessage::Discard),
+            (NONE, Char('s')) => return review_msg(HunkReviewMessage::Split),
+            (NONE, Char('e')) => return review_msg(HunkReviewMessage::Edit),
+            (NONE, Char('k') | Char('p')) => return review_msg(HunkReviewMessage::Previous),
+            (NONE, Char('j')) => return review_msg(HunkReviewMessage::Next),
+            (_, Char('q')) => return review_msg(HunkReviewMessage::Finish),
+            (_, Esc) | (CTRL, Char('g')) | (CTRL, Char('c')) => {
+                return review_msg(HunkReviewMessage::Abort);
+            }
+            _ => return None,
+        }
+    }
+
+    // Pending entries of a stopped rebase can be edited in place: the todo
+    // is rewritten, like a small `git rebase --edit-todo`
+    if model.view_mode == ViewMode::Status
+        && model.ui_model.visual_mode_anchor.is_none()
+        && let Some(LineContent::RebasingEntry {
+            hash,
+            is_current: false,
+            ..
+        }) = model
+            .ui_model
+            .lines
+            .get(model.ui_model.cursor_position)
+            .map(|line| &line.content)
+    {
+        let pending = |m| Some(Message::RebasePending(m));
+        match (key.modifiers, key.code) {
+            (_, Char('x')) => return pending(RebasePendingMessage::ToggleDrop(hash.clone())),
+            (_, Char('R')) => return pending(RebasePendingMessage::ToggleReword(hash.clone())),
+            (_, Char('K')) | (ALT, Up) => {
+                return pending(RebasePendingMessage::MoveUp(hash.clone()));
+            }
+            (_, Char('J')) | (ALT, Down) => {
+                return pending(RebasePendingMessage::MoveDown(hash.clone()));
+            }
+            _ => {}
+        }
+    }
+
     match (key.modifiers, key.code) {
         // Navigation
         (CTRL, Char('u')) => Some(Message::Navigation(NavigationAction::HalfPageUp)),
@@ -313,17 +450,22 @@ pub fn handle_key(key: event::KeyEvent, model: &Model) -> Option<Message> {
         (_, Char('q')) => match model.view_mode {
             ViewMode::Log { .. } => Some(Message::ExitLogView),
             ViewMode::Status => Some(Message::Quit),
-            ViewMode::Preview => Some(Message::ExitPreview),
+            ViewMode::Preview | ViewMode::Blob { .. } => Some(Message::ExitPreview),
             ViewMode::RebaseTodo => Some(Message::RebaseTodo(RebaseTodoMessage::Abort)),
+            ViewMode::CommitMessage => None,
+            ViewMode::HunkReview => Some(Message::HunkReview(HunkReviewMessage::Finish)),
         },
         (_, Char('V')) => Some(Message::EnterVisualMode),
         (_, Char('s')) => Some(Message::StageSelected),
         (_, Char('S')) => Some(Message::StageAllModified),
+        (NONE, Char('i')) => Some(Message::IntentToAddSelected),
         (_, Char('u')) => Some(Message::UnstageSelected),
         (_, Char('U')) => Some(Message::UnstageAll),
         (_, Char('x')) => Some(Message::DiscardSelected),
         (NONE, Char('a')) => Some(Message::ApplySelected),
         (NONE, Char('-')) => Some(Message::ReverseSelected),
+        (_, Char('+')) => Some(Message::Diff(DiffMessage::MoreContext)),
+        (NONE, Char('0')) => Some(Message::Diff(DiffMessage::DefaultContext)),
 
         // Search
         (NONE, Char('/')) => Some(Message::EnterSearchMode),
@@ -338,7 +480,7 @@ pub fn handle_key(key: event::KeyEvent, model: &Model) -> Option<Message> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::Theme;
+    use crate::config::{Config, Theme};
     use crate::git::GitInfo;
     use crate::git::test_repo::TestRepo;
     use crate::model::arguments::Argument::{Fetch, Push};
@@ -374,6 +516,7 @@ mod tests {
             running_state: RunningState::Running,
             ui_model: UiModel::default(),
             theme: Theme::default(),
+            config: Config::default(),
             popup: None,
             toast: None,
             select_result: None,
@@ -382,12 +525,17 @@ mod tests {
             arg_mode: false,
             pending_g: false,
             arguments: None,
+            signing_key: None,
+            argument_values: std::collections::HashMap::new(),
             view_mode: ViewMode::Status,
             cursor_reposition_context: None,
             preview_return_mode: None,
             preview_return_ui_model: None,
             log_return_ui_model: None,
             rebase_todo: None,
+            commit_editor: None,
+            hunk_review: None,
+            commit_message_history: Vec::new(),
         }
     }
 
@@ -1140,6 +1288,109 @@ mod tests {
         assert_eq!(result, Some(Message::DismissPopup));
     }
 
+    // Diff popup tests
+
+    #[test]
+    fn test_shift_d_shows_diff_popup() {
+        let model = create_test_model();
+
+        let key = create_key_event(KeyModifiers::SHIFT, Char('D'));
+        let result = handle_key(key, &model);
+        assert_eq!(
+            result,
+            Some(Message::ShowPopup(PopupContent::Command(
+                PopupContentCommand::Diff
+            )))
+        );
+    }
+
+    #[test]
+    fn test_diff_popup_keys_change_diff_settings() {
+        let mut model = create_test_model();
+        model.popup = Some(PopupContent::Command(PopupContentCommand::Diff));
+
+        for (c, expected) in [
+            ('+', DiffMessage::MoreContext),
+            ('-', DiffMessage::LessContext),
+            ('0', DiffMessage::DefaultContext),
+            ('w', DiffMessage::ToggleIgnoreWhitespace),
+        ] {
+            let result = handle_key(create_key_event(NONE, Char(c)), &model);
+            assert_eq!(result, Some(Message::Diff(expected)));
+        }
+    }
+
+    #[test]
+    fn test_diff_popup_keys_split_and_edit_hunks() {
+        let mut model = create_test_model();
+        model.popup = Some(PopupContent::Command(PopupContentCommand::Diff));
+
+        let result = handle_key(create_key_event(NONE, Char('s')), &model);
+        assert_eq!(result, Some(Message::SplitHunk));
+        let result = handle_key(create_key_event(NONE, Char('e')), &model);
+        assert_eq!(result, Some(Message::EditHunk));
+    }
+
+    #[test]
+    fn test_diff_popup_keys_start_hunk_review() {
+        let mut model = create_test_model();
+        model.popup = Some(PopupContent::Command(PopupContentCommand::Diff));
+
+        for (c, staged) in [('r', false), ('R', true)] {
+            let result = handle_key(create_key_event(NONE, Char(c)), &model);
+            assert_eq!(
+                result,
+                Some(Message::HunkReview(HunkReviewMessage::Start { staged }))
+            );
+        }
+    }
+
+    #[test]
+    fn test_hunk_review_keys() {
+        let mut model = create_test_model();
+        model.view_mode = ViewMode::HunkReview;
+
+        for (c, expected) in [
+            ('y', HunkReviewMessage::Accept),
+            ('n', HunkReviewMessage::Skip),
+            ('d', HunkReviewMessage::Discard),
+            ('s', HunkReviewMessage::Split),
+            ('e', HunkReviewMessage::Edit),
+            ('k', HunkReviewMessage::Previous),
+            ('j', HunkReviewMessage::Next),
+            ('q', HunkReviewMessage::Finish),
+        ] {
+            let result = handle_key(create_key_event(NONE, Char(c)), &model);
+            assert_eq!(result, Some(Message::HunkReview(expected)));
+        }
+        // Esc, C-g and C-c abort instead of applying the decisions
+        for (modifiers, code) in [(NONE, Esc), (CTRL, Char('g')), (CTRL, Char('c'))] {
+            assert_eq!(
+                handle_key(create_key_event(modifiers, code), &model),
+                Some(Message::HunkReview(HunkReviewMessage::Abort))
+            );
+        }
+        // Status commands don't apply to the reviewed hunk
+        assert_eq!(handle_key(create_key_event(NONE, Char('x')), &model), None);
+        assert_eq!(
+            handle_key(create_key_event(NONE, Down), &model),
+            Some(Message::Navigation(NavigationAction::MoveDown))
+        );
+    }
+
+    #[test]
+    fn test_minus_changes_context_only_in_preview() {
+        let mut model = create_test_model();
+        let key = create_key_event(NONE, Char('-'));
+        assert_eq!(handle_key(key, &model), Some(Message::ReverseSelected));
+
+        model.view_mode = ViewMode::Preview;
+        assert_eq!(
+            handle_key(key, &model),
+            Some(Message::Diff(DiffMessage::LessContext))
+        );
+    }
+
     // Input popup tests
 
     fn create_input_popup_model() -> Model {
@@ -1922,6 +2173,7 @@ mod tests {
             picking: false,
             graph: true,
             color: false,
+            signatures: false,
         };
         model
     }
@@ -2002,6 +2254,7 @@ mod tests {
             picking: true,
             graph: true,
             color: false,
+            signatures: false,
         };
         model
     }
@@ -2319,6 +2572,7 @@ mod tests {
             content: crate::model::LineContent::PreviewLine {
                 content: "commit abc123".to_string(),
                 line_type: PreviewLineType::Header,
+                refined: Vec::new(),
             },
             section: None,
         }];
@@ -2418,6 +2672,7 @@ mod tests {
             picking: false,
             graph: true,
             color: false,
+            signatures: false,
         };
         model.ui_model.lines = vec![crate::model::Line {
             content: crate::model::LineContent::LogLine(LogEntry::new(
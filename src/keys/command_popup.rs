@@ -5,6 +5,7 @@ use crate::{model::popup::PopupContentCommand, msg::Message};
 mod apply;
 mod branch;
 mod commit;
+mod diff;
 mod fetch;
 mod log;
 mod merge;
@@ -12,6 +13,7 @@ mod pull;
 mod push;
 mod rebase;
 mod reset;
+mod resolve;
 mod revert;
 mod select;
 mod stash;
@@ -41,10 +43,12 @@ pub fn handle_command_popup_key(
         PopupContentCommand::Reset => reset::keys(key),
         PopupContentCommand::Rebase(state) => rebase::keys(key, arg_mode, state),
         PopupContentCommand::Revert(state) => revert::keys(key, arg_mode, state),
-        PopupContentCommand::Merge(state) => merge::keys(key, state),
-        PopupContentCommand::Apply(state) => apply::keys(key, state),
+        PopupContentCommand::Merge(state) => merge::keys(key, arg_mode, state),
+        PopupContentCommand::Apply(state) => apply::keys(key, arg_mode, state),
+        PopupContentCommand::Resolve(state) => resolve::keys(key, state),
         PopupContentCommand::Tag => tag::keys(key, arg_mode),
         PopupContentCommand::Worktree => worktree::keys(key),
+        PopupContentCommand::Diff => diff::keys(key),
         PopupContentCommand::Select(_) => select::keys(key),
     }
 }
@@ -1,11 +1,26 @@
 use crossterm::event::{KeyCode, KeyEvent};
 
 use crate::{
+    model::arguments::{ApplyArgument, Argument::Apply, ValueArgument},
     model::popup::ApplyPopupState,
     msg::{ApplyCommand, Message, OnSelect, OptionsSource, ShowSelectPopupConfig},
 };
 
-pub fn keys(key: KeyEvent, state: &ApplyPopupState) -> Option<Message> {
+pub fn keys(key: KeyEvent, arg_mode: bool, state: &ApplyPopupState) -> Option<Message> {
+    if arg_mode {
+        return match key.code {
+            KeyCode::Char('S') => Some(Message::ShowSigningKeySelect),
+            KeyCode::Char('m') => Some(Message::ShowArgumentValueInput(ValueArgument::Mainline)),
+            KeyCode::Char('s') => Some(Message::ShowArgumentValueInput(ValueArgument::Strategy)),
+            KeyCode::Char('X') => Some(Message::ShowArgumentValueInput(
+                ValueArgument::StrategyOption,
+            )),
+            KeyCode::Char(c) => ApplyArgument::from_key(c)
+                .map(|arg| Message::ToggleArgument(Apply(arg)))
+                .or(Some(Message::ExitArgMode)),
+            _ => Some(Message::ExitArgMode),
+        };
+    }
     if state.in_progress {
         return match key.code {
             KeyCode::Char('q') => Some(Message::DismissPopup),
@@ -125,6 +140,7 @@ pub fn keys(key: KeyEvent, state: &ApplyPopupState) -> Option<Message> {
                 }))
             }
         }
+        KeyCode::Char('-') => Some(Message::EnterArgMode),
         _ => None,
     }
 }
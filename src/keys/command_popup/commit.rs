@@ -1,14 +1,24 @@
 use crossterm::event::{KeyCode, KeyEvent};
 
 use crate::{
-    model::arguments::{Argument::Commit, CommitArgument},
+    model::{
+        arguments::{Argument::Commit, CommitArgument},
+        popup::{CommitAuthorTarget, CommitTrailer},
+    },
     msg::{CommitSelect, FixupType, Message},
 };
 
 pub fn keys(key: KeyEvent, arg_mode: bool) -> Option<Message> {
     if arg_mode {
         return match key.code {
-            KeyCode::Char('A') => Some(Message::ShowCommitAuthorSelect),
+            KeyCode::Char('A') => Some(Message::ShowCommitAuthorSelect(CommitAuthorTarget::Author)),
+            KeyCode::Char('C') => Some(Message::ShowCommitAuthorSelect(
+                CommitAuthorTarget::Trailer(CommitTrailer::CoAuthoredBy),
+            )),
+            KeyCode::Char('r') => Some(Message::ShowCommitAuthorSelect(
+                CommitAuthorTarget::Trailer(CommitTrailer::ReviewedBy),
+            )),
+            KeyCode::Char('S') => Some(Message::ShowSigningKeySelect),
             KeyCode::Char(c) => CommitArgument::from_key(c)
                 .map(|arg| Message::ToggleArgument(Commit(arg)))
                 .or(Some(Message::ExitArgMode)),
@@ -18,9 +28,10 @@ pub fn keys(key: KeyEvent, arg_mode: bool) -> Option<Message> {
     match key.code {
         KeyCode::Char('q') => Some(Message::DismissPopup),
         KeyCode::Char('c') => Some(Message::Commit),
-        KeyCode::Char('e') => Some(Message::Amend(vec!["--no-edit".to_string()])),
+        KeyCode::Char('e') => Some(Message::Extend),
         KeyCode::Char('a') => Some(Message::Amend(vec![])),
         KeyCode::Char('w') => Some(Message::Amend(vec!["--only".to_string()])),
+        KeyCode::Char('R') => Some(Message::ShowReshelveInput),
         KeyCode::Char('f') => Some(Message::ShowCommitSelect(CommitSelect::FixupCommit(
             FixupType::Fixup,
         ))),
@@ -30,10 +41,18 @@ pub fn keys(key: KeyEvent, arg_mode: bool) -> Option<Message> {
         KeyCode::Char('A') => Some(Message::ShowCommitSelect(CommitSelect::FixupCommit(
             FixupType::Alter,
         ))),
+        KeyCode::Char('F') => Some(Message::ShowCommitSelect(CommitSelect::FixupCommit(
+            FixupType::InstantFixup,
+        ))),
+        KeyCode::Char('S') => Some(Message::ShowCommitSelect(CommitSelect::FixupCommit(
+            FixupType::InstantSquash,
+        ))),
         KeyCode::Char('n') => Some(Message::ShowCommitSelect(CommitSelect::FixupCommit(
             FixupType::Augment,
         ))),
         KeyCode::Char('W') => Some(Message::ShowCommitSelect(CommitSelect::ReviseCommit)),
+        KeyCode::Char('b') => Some(Message::ShowAbsorbConfirm { autosquash: false }),
+        KeyCode::Char('B') => Some(Message::ShowAbsorbConfirm { autosquash: true }),
         KeyCode::Char('-') => Some(Message::EnterArgMode),
         _ => None,
     }
@@ -0,0 +1,22 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::msg::{DiffMessage, HunkReviewMessage, Message};
+
+pub fn keys(key: KeyEvent) -> Option<Message> {
+    match key.code {
+        KeyCode::Char('q') => Some(Message::DismissPopup),
+        KeyCode::Char('+') => Some(Message::Diff(DiffMessage::MoreContext)),
+        KeyCode::Char('-') => Some(Message::Diff(DiffMessage::LessContext)),
+        KeyCode::Char('0') => Some(Message::Diff(DiffMessage::DefaultContext)),
+        KeyCode::Char('w') => Some(Message::Diff(DiffMessage::ToggleIgnoreWhitespace)),
+        KeyCode::Char('s') => Some(Message::SplitHunk),
+        KeyCode::Char('e') => Some(Message::EditHunk),
+        KeyCode::Char('r') => Some(Message::HunkReview(HunkReviewMessage::Start {
+            staged: false,
+        })),
+        KeyCode::Char('R') => Some(Message::HunkReview(HunkReviewMessage::Start {
+            staged: true,
+        })),
+        _ => None,
+    }
+}
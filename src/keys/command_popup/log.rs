@@ -35,6 +35,11 @@ pub fn keys(key: KeyEvent, arg_mode: bool) -> Option<Message> {
             on_select: OnSelect::ReflogOther,
         })),
         KeyCode::Char('H') => Some(Message::ShowLog(LogType::ReflogOther("HEAD".to_string()))),
+        KeyCode::Char('f') => Some(Message::ShowSelectPopup(ShowSelectPopupConfig {
+            title: "Visit file at revision".to_string(),
+            source: OptionsSource::FileCheckoutRevisions,
+            on_select: OnSelect::BlobRevision,
+        })),
         KeyCode::Char('-') => Some(Message::EnterArgMode),
         _ => None,
     }
@@ -1,11 +1,25 @@
 use crossterm::event::{KeyCode, KeyEvent};
 
 use crate::{
+    model::arguments::{Argument::Merge, MergeArgument, ValueArgument},
     model::popup::MergePopupState,
     msg::{MergeCommand, Message, OnSelect, OptionsSource, ShowSelectPopupConfig},
 };
 
-pub fn keys(key: KeyEvent, state: &MergePopupState) -> Option<Message> {
+pub fn keys(key: KeyEvent, arg_mode: bool, state: &MergePopupState) -> Option<Message> {
+    if arg_mode {
+        return match key.code {
+            KeyCode::Char('S') => Some(Message::ShowSigningKeySelect),
+            KeyCode::Char('s') => Some(Message::ShowArgumentValueInput(ValueArgument::Strategy)),
+            KeyCode::Char('X') => Some(Message::ShowArgumentValueInput(
+                ValueArgument::StrategyOption,
+            )),
+            KeyCode::Char(c) => MergeArgument::from_key(c)
+                .map(|arg| Message::ToggleArgument(Merge(arg)))
+                .or(Some(Message::ExitArgMode)),
+            _ => Some(Message::ExitArgMode),
+        };
+    }
     if state.in_progress {
         return match key.code {
             KeyCode::Char('q') => Some(Message::DismissPopup),
@@ -52,6 +66,7 @@ pub fn keys(key: KeyEvent, state: &MergePopupState) -> Option<Message> {
             source: OptionsSource::LocalBranches,
             on_select: OnSelect::MergeDissolve,
         })),
+        KeyCode::Char('-') => Some(Message::EnterArgMode),
         _ => None,
     }
 }
@@ -1,7 +1,7 @@
 use crossterm::event::{KeyCode, KeyEvent};
 
 use crate::{
-    model::arguments::{Argument::Rebase, RebaseArgument},
+    model::arguments::{Argument::Rebase, RebaseArgument, ValueArgument},
     model::popup::RebasePopupState,
     msg::{CommitSelect, Message, OnSelect, OptionsSource, RebaseCommand, ShowSelectPopupConfig},
 };
@@ -9,6 +9,11 @@ use crate::{
 pub fn keys(key: KeyEvent, arg_mode: bool, state: &RebasePopupState) -> Option<Message> {
     if arg_mode {
         return match key.code {
+            KeyCode::Char('S') => Some(Message::ShowSigningKeySelect),
+            KeyCode::Char('x') => Some(Message::ShowArgumentValueInput(ValueArgument::Exec)),
+            KeyCode::Char('X') => Some(Message::ShowArgumentValueInput(
+                ValueArgument::StrategyOption,
+            )),
             KeyCode::Char(c) => RebaseArgument::from_key(c)
                 .map(|arg| Message::ToggleArgument(Rebase(arg)))
                 .or(Some(Message::ExitArgMode)),
@@ -21,6 +26,7 @@ pub fn keys(key: KeyEvent, arg_mode: bool, state: &RebasePopupState) -> Option<M
             KeyCode::Char('r') => Some(Message::Rebase(RebaseCommand::Continue)),
             KeyCode::Char('s') => Some(Message::Rebase(RebaseCommand::Skip)),
             KeyCode::Char('a') => Some(Message::Rebase(RebaseCommand::Abort)),
+            KeyCode::Char('e') => Some(Message::EditRebaseTodo),
             _ => None,
         };
     }
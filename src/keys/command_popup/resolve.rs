@@ -0,0 +1,45 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    git::conflict::{ConflictCheckout, ConflictSide},
+    model::popup::ResolvePopupState,
+    msg::{Message, ResolveCommand},
+};
+
+pub fn keys(key: KeyEvent, state: &ResolvePopupState) -> Option<Message> {
+    let path = state.path.clone();
+    let conflict = |side| {
+        state.conflict.map(|index| {
+            Message::Resolve(ResolveCommand::Conflict {
+                path: path.clone(),
+                index,
+                side,
+            })
+        })
+    };
+    let checkout = |checkout| {
+        Some(Message::Resolve(ResolveCommand::Checkout {
+            path: path.clone(),
+            checkout,
+        }))
+    };
+
+    match key.code {
+        KeyCode::Char('q') => Some(Message::DismissPopup),
+        KeyCode::Char('m') => checkout(ConflictCheckout::Merge),
+        KeyCode::Char('a') => Some(Message::Resolve(ResolveCommand::Mergetool {
+            paths: vec![],
+        })),
+        _ if !state.unmerged => None,
+        KeyCode::Char('o') => conflict(ConflictSide::Ours),
+        KeyCode::Char('t') => conflict(ConflictSide::Theirs),
+        KeyCode::Char('b') => conflict(ConflictSide::Both),
+        KeyCode::Char('B') => conflict(ConflictSide::Base),
+        KeyCode::Char('O') => checkout(ConflictCheckout::Ours),
+        KeyCode::Char('T') => checkout(ConflictCheckout::Theirs),
+        KeyCode::Char('M') => Some(Message::Resolve(ResolveCommand::Mergetool {
+            paths: vec![path.clone()],
+        })),
+        _ => None,
+    }
+}
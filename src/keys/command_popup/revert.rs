@@ -1,7 +1,7 @@
 use crossterm::event::{KeyCode, KeyEvent};
 
 use crate::{
-    model::arguments::{Argument::Revert, RevertArgument},
+    model::arguments::{Argument::Revert, RevertArgument, ValueArgument},
     model::popup::RevertPopupState,
     msg::{Message, RevertCommand},
 };
@@ -10,6 +10,11 @@ pub fn keys(key: KeyEvent, arg_mode: bool, state: &RevertPopupState) -> Option<M
     if arg_mode {
         return match key.code {
             KeyCode::Char('m') => Some(Message::ShowRevertMainlineInput),
+            KeyCode::Char('S') => Some(Message::ShowSigningKeySelect),
+            KeyCode::Char('s') => Some(Message::ShowArgumentValueInput(ValueArgument::Strategy)),
+            KeyCode::Char('X') => Some(Message::ShowArgumentValueInput(
+                ValueArgument::StrategyOption,
+            )),
             KeyCode::Char(c) => RevertArgument::from_key(c)
                 .map(|arg| Message::ToggleArgument(Revert(arg)))
                 .or(Some(Message::ExitArgMode)),
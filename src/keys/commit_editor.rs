@@ -0,0 +1,193 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{
+    keys::edit::edit_op_for_key,
+    model::{
+        EditOp,
+        commit_editor::{CommitEditorMode, CommitEditorState},
+    },
+    msg::{CommitEditorMessage, Message, NavigationAction},
+};
+
+const NONE: KeyModifiers = KeyModifiers::NONE;
+const CTRL: KeyModifiers = KeyModifiers::CONTROL;
+const ALT: KeyModifiers = KeyModifiers::ALT;
+
+/// Maps a key event in the built-in commit message editor. Insert mode
+/// takes Emacs-style keys (`C-c C-c` commits, `C-c C-k` aborts) on top of
+/// the shared readline bindings; `Esc` switches to a vim-style normal mode
+/// with a `:wq`/`:q` command line.
+pub fn handle_commit_editor_key(key: KeyEvent, state: &CommitEditorState) -> Option<Message> {
+    let msg = |m| Some(Message::CommitEditor(m));
+
+    if state.pending_ctrl_c {
+        return match (key.modifiers, key.code) {
+            (CTRL, KeyCode::Char('c')) => msg(CommitEditorMessage::Confirm),
+            (CTRL, KeyCode::Char('k')) => msg(CommitEditorMessage::Abort),
+            _ => msg(CommitEditorMessage::ClearPrefix),
+        };
+    }
+
+    if let Some(cmd) = state.command_input.as_deref() {
+        return match (key.modifiers, key.code) {
+            (_, KeyCode::Enter) => match cmd {
+                "wq" | "wq!" | "x" => msg(CommitEditorMessage::Confirm),
+                "q" | "q!" => msg(CommitEditorMessage::Abort),
+                _ => msg(CommitEditorMessage::CommandInvalid),
+            },
+            (_, KeyCode::Esc) | (CTRL, KeyCode::Char('g') | KeyCode::Char('c')) => {
+                msg(CommitEditorMessage::CommandCancel)
+            }
+            (_, KeyCode::Backspace) => msg(CommitEditorMessage::CommandBackspace),
+            (_, KeyCode::Char(c)) => msg(CommitEditorMessage::CommandChar(c)),
+            _ => None,
+        };
+    }
+
+    // Keys shared by both modes
+    match (key.modifiers, key.code) {
+        (CTRL, KeyCode::Char('c')) => return msg(CommitEditorMessage::CtrlCPrefix),
+        (ALT, KeyCode::Char('p')) => return msg(CommitEditorMessage::HistoryPrev),
+        (ALT, KeyCode::Char('n')) => return msg(CommitEditorMessage::HistoryNext),
+        (_, KeyCode::Up) | (CTRL, KeyCode::Char('p')) => {
+            return msg(CommitEditorMessage::MoveUp);
+        }
+        (_, KeyCode::Down) | (CTRL, KeyCode::Char('n')) => {
+            return msg(CommitEditorMessage::MoveDown);
+        }
+        // The diff below the message scrolls independently of the cursor
+        (_, KeyCode::PageUp) => {
+            return Some(Message::Navigation(NavigationAction::HalfPageUp));
+        }
+        (_, KeyCode::PageDown) => {
+            return Some(Message::Navigation(NavigationAction::HalfPageDown));
+        }
+        _ => {}
+    }
+
+    match state.mode {
+        CommitEditorMode::Insert => match (key.modifiers, key.code) {
+            (_, KeyCode::Esc) => msg(CommitEditorMessage::EnterNormalMode),
+            (_, KeyCode::Enter) => msg(CommitEditorMessage::Newline),
+            _ => {
+                edit_op_for_key(key).map(|op| Message::CommitEditor(CommitEditorMessage::Edit(op)))
+            }
+        },
+        CommitEditorMode::Normal => {
+            let edit = |op| msg(CommitEditorMessage::Edit(op));
+            let insert = |op| msg(CommitEditorMessage::EnterInsertMode(op));
+            match (key.modifiers, key.code) {
+                (NONE, KeyCode::Char('i')) => insert(None),
+                (NONE, KeyCode::Char('a')) => insert(Some(EditOp::MoveRight)),
+                (_, KeyCode::Char('A')) => insert(Some(EditOp::MoveToEnd)),
+                (_, KeyCode::Char('I')) => insert(Some(EditOp::MoveToStart)),
+                (NONE, KeyCode::Char('o')) => msg(CommitEditorMessage::OpenLineBelow),
+                (NONE, KeyCode::Char('h')) | (NONE, KeyCode::Left) => edit(EditOp::MoveLeft),
+                (NONE, KeyCode::Char('l')) | (NONE, KeyCode::Right) => edit(EditOp::MoveRight),
+                (NONE, KeyCode::Char('k')) => msg(CommitEditorMessage::MoveUp),
+                (NONE, KeyCode::Char('j')) => msg(CommitEditorMessage::MoveDown),
+                (NONE, KeyCode::Char('w')) => edit(EditOp::MoveWordRight),
+                (NONE, KeyCode::Char('b')) => edit(EditOp::MoveWordLeft),
+                (NONE, KeyCode::Char('0')) => edit(EditOp::MoveToStart),
+                (_, KeyCode::Char('$')) => edit(EditOp::MoveToEnd),
+                (NONE, KeyCode::Char('x')) => edit(EditOp::DeleteForward),
+                (_, KeyCode::Char('D')) => edit(EditOp::DeleteToEnd),
+                (_, KeyCode::Char(':')) => msg(CommitEditorMessage::CommandStart),
+                _ => None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{UiModel, ViewMode};
+    use crossterm::event::{KeyEventKind, KeyEventState};
+
+    fn key(modifiers: KeyModifiers, code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    fn state() -> CommitEditorState {
+        CommitEditorState::new(
+            "",
+            false,
+            vec![],
+            vec![],
+            ViewMode::Status,
+            UiModel::default(),
+        )
+    }
+
+    #[test]
+    fn ctrl_c_ctrl_c_confirms_and_ctrl_c_ctrl_k_aborts() {
+        let mut state = state();
+        assert_eq!(
+            handle_commit_editor_key(key(CTRL, KeyCode::Char('c')), &state),
+            Some(Message::CommitEditor(CommitEditorMessage::CtrlCPrefix))
+        );
+        state.pending_ctrl_c = true;
+        assert_eq!(
+            handle_commit_editor_key(key(CTRL, KeyCode::Char('c')), &state),
+            Some(Message::CommitEditor(CommitEditorMessage::Confirm))
+        );
+        assert_eq!(
+            handle_commit_editor_key(key(CTRL, KeyCode::Char('k')), &state),
+            Some(Message::CommitEditor(CommitEditorMessage::Abort))
+        );
+    }
+
+    #[test]
+    fn insert_mode_types_text_and_newlines() {
+        let state = state();
+        assert_eq!(
+            handle_commit_editor_key(key(NONE, KeyCode::Char('q')), &state),
+            Some(Message::CommitEditor(CommitEditorMessage::Edit(
+                EditOp::Insert('q')
+            )))
+        );
+        assert_eq!(
+            handle_commit_editor_key(key(NONE, KeyCode::Enter), &state),
+            Some(Message::CommitEditor(CommitEditorMessage::Newline))
+        );
+    }
+
+    #[test]
+    fn alt_p_and_alt_n_recall_history() {
+        let state = state();
+        assert_eq!(
+            handle_commit_editor_key(key(ALT, KeyCode::Char('p')), &state),
+            Some(Message::CommitEditor(CommitEditorMessage::HistoryPrev))
+        );
+        assert_eq!(
+            handle_commit_editor_key(key(ALT, KeyCode::Char('n')), &state),
+            Some(Message::CommitEditor(CommitEditorMessage::HistoryNext))
+        );
+    }
+
+    #[test]
+    fn normal_mode_command_line() {
+        let mut state = state();
+        state.mode = CommitEditorMode::Normal;
+        assert_eq!(
+            handle_commit_editor_key(key(NONE, KeyCode::Char(':')), &state),
+            Some(Message::CommitEditor(CommitEditorMessage::CommandStart))
+        );
+        state.command_input = Some("wq".to_string());
+        assert_eq!(
+            handle_commit_editor_key(key(NONE, KeyCode::Enter), &state),
+            Some(Message::CommitEditor(CommitEditorMessage::Confirm))
+        );
+        state.command_input = Some("q".to_string());
+        assert_eq!(
+            handle_commit_editor_key(key(NONE, KeyCode::Enter), &state),
+            Some(Message::CommitEditor(CommitEditorMessage::Abort))
+        );
+    }
+}
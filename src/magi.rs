@@ -5,7 +5,7 @@ use crate::{
     msg::{update::update, util::is_external_command},
 };
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     path::PathBuf,
     time::{Duration, Instant},
 };
@@ -18,8 +18,8 @@ use crate::{
     errors::MagiResult,
     git::{GitInfo, pty_command::PtyCommandResult},
     model::{
-        CredentialPopupState, InputField, Model, PopupContent, RunningState, Toast, ToastStyle,
-        UiModel, ViewMode,
+        CredentialPopupState, InputField, Line, Model, PopupContent, RunningState, Toast,
+        ToastStyle, UiModel, ViewMode,
     },
     msg::Message,
     view::view,
@@ -90,10 +90,11 @@ fn run_loop(
         .unwrap_or(Language::English);
     i18n::init(lang);
 
-    let git_info = match path {
+    let mut git_info = match path {
         Some(ref p) => GitInfo::new_from_path(p)?,
         None => GitInfo::new()?,
     };
+    git_info.file_tree = config.file_tree;
 
     // Extract workdir early - fail for bare repositories
     let workdir = git_info
@@ -105,8 +106,8 @@ fn run_loop(
     let lines = git_info.get_lines()?;
     let collapsed_sections = lines
         .iter()
-        .filter_map(|line| line.section.clone())
-        .filter(|line| line.default_collapsed())
+        .flat_map(Line::sections)
+        .filter(|section| section.default_collapsed())
         .collect::<HashSet<_>>();
     let initial_ui_model = UiModel {
         lines,
@@ -114,6 +115,7 @@ fn run_loop(
         scroll_offset: 0,
         viewport_height: 0,
         collapsed_sections,
+        split_hunks: HashMap::new(),
         visual_mode_anchor: None,
         search_query: InputField::new(),
         search_mode_active: false,
@@ -125,6 +127,7 @@ fn run_loop(
         running_state: RunningState::Running,
         ui_model: initial_ui_model,
         theme,
+        config,
         popup: None,
         toast: None,
         select_result: None,
@@ -133,12 +136,17 @@ fn run_loop(
         arg_mode: false,
         pending_g: false,
         arguments: None,
+        signing_key: None,
+        argument_values: HashMap::new(),
         view_mode: ViewMode::Status,
         cursor_reposition_context: None,
         preview_return_mode: None,
         preview_return_ui_model: None,
         log_return_ui_model: None,
         rebase_todo: None,
+        commit_editor: None,
+        hunk_review: None,
+        commit_message_history: Vec::new(),
     };
 
     while model.running_state != RunningState::Done {
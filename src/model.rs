@@ -1,10 +1,11 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 use std::path::PathBuf;
 use std::time::Instant;
 
-use crate::config::Theme;
+use crate::config::{Config, Theme};
 use crate::git::{CommitInfo, CommitRefType, GitInfo, GitRef, StashEntry, TagInfo};
-use crate::model::arguments::Arguments;
+use crate::model::arguments::{Arguments, ValueArgument};
 use crate::msg::Message;
 
 pub use input_field::{EditOp, InputField};
@@ -14,13 +15,16 @@ pub use pty_state::PtyState;
 use select_popup::SelectResult;
 
 pub mod arguments;
+pub mod commit_editor;
 pub mod cursor_context;
+pub mod hunk_review;
 pub mod input_field;
 pub mod log_view;
 pub mod popup;
 pub mod pty_state;
 pub mod rebase_todo;
 pub mod select_popup;
+pub mod text_area;
 
 /// The whole state of the application, including the Git repository Handle
 pub struct Model {
@@ -34,6 +38,8 @@ pub struct Model {
     pub workdir: PathBuf,
     /// Magi color scheme constants
     pub theme: Theme,
+    /// User settings loaded from the config file
+    pub config: Config,
     /// Modal popup that requires user action to dismiss
     pub popup: Option<PopupContent>,
     /// Toast notification that auto-dismisses after a timeout
@@ -50,6 +56,14 @@ pub struct Model {
     pub pending_g: bool,
     /// Flags to add to the next command
     pub arguments: Option<Arguments>,
+    /// Key picked with a command popup's `-S` argument (`--gpg-sign=<key>`).
+    /// Kept in the model rather than the popup so it survives the branch and
+    /// commit pickers; used by the next signing command and cleared with the
+    /// other arguments when the popup is dismissed.
+    pub signing_key: Option<String>,
+    /// Values of the arguments that take one (`--exec=<cmd>`), kept and
+    /// cleared like `signing_key`
+    pub argument_values: HashMap<ValueArgument, String>,
     /// Current view mode (status view, log view, etc.)
     pub view_mode: ViewMode,
     /// Cursor context for smart repositioning after refresh (consumed by refresh)
@@ -62,6 +76,13 @@ pub struct Model {
     pub log_return_ui_model: Option<UiModel>,
     /// State for the interactive rebase todo editor (ViewMode::RebaseTodo)
     pub rebase_todo: Option<rebase_todo::RebaseTodoState>,
+    /// State for the built-in commit message editor (ViewMode::CommitMessage)
+    pub commit_editor: Option<commit_editor::CommitEditorState>,
+    /// State for the sequential hunk review (ViewMode::HunkReview)
+    pub hunk_review: Option<hunk_review::HunkReviewState>,
+    /// Commit messages that were aborted or failed to commit in this session,
+    /// most recent first; recalled in the commit message editor with M-p
+    pub commit_message_history: Vec<String>,
 }
 
 impl Model {
@@ -122,6 +143,21 @@ pub enum PreviewLineType {
     Deletion,
     /// Unchanged context lines (space-prefixed in diff output)
     Context,
+    /// Changes without hunk lines: mode changes, binary files and
+    /// submodule commits
+    FileMetadata,
+    /// Output of an external diff renderer, with its ANSI color codes
+    Rendered,
+}
+
+/// The commit a line of the blob view is blamed on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameInfo {
+    /// Abbreviated commit hash
+    pub hash: String,
+    pub author: String,
+    /// Author date (YYYY-MM-DD)
+    pub date: String,
 }
 
 /// The current view mode of the application
@@ -139,11 +175,24 @@ pub enum ViewMode {
         graph: bool,
         /// Whether git colors the graph (--color); used when refreshing
         color: bool,
+        /// Whether each commit's signature status is shown; used when refreshing
+        signatures: bool,
     },
     /// Preview mode showing diff/show output for a commit or stash.
     Preview,
+    /// A file's contents at a revision (like `magit-find-file`).
+    Blob {
+        revision: String,
+        path: String,
+        /// Whether each line is annotated with `git blame`
+        blame: bool,
+    },
     /// Interactive rebase todo editor (pick/reword/squash/... per commit).
     RebaseTodo,
+    /// Built-in commit message editor, with the diff being committed below it.
+    CommitMessage,
+    /// Sequential review of the unstaged or staged hunks, one at a time.
+    HunkReview,
 }
 
 impl InputMode {
@@ -164,6 +213,11 @@ pub struct UiModel {
     pub scroll_offset: usize,
     pub viewport_height: usize,
     pub collapsed_sections: HashSet<SectionType>,
+    /// Hunks shown split into their blocks of changes, so each block can be
+    /// staged on its own, with their lines when split. The lines find them
+    /// again on refresh, as staging other hunks shifts the index in their
+    /// section and the line numbers in their header.
+    pub split_hunks: HashMap<SectionType, String>,
     /// When Some, visual mode is active and this is the anchor position
     /// (the line where visual mode was started). The selection spans from
     /// this anchor to the current cursor_position.
@@ -213,14 +267,29 @@ pub enum LineContent {
         title: String,
         count: Option<usize>,
     },
-    /// Section header for "Unpulled from [remote]" with separate remote name for coloring
-    UnpulledSectionHeader {
+    /// Section header for the commit sections relative to a remote branch, e.g.
+    /// "Unpulled from [remote]", with separate remote name for coloring
+    RemoteSectionHeader {
+        prefix: String,
         remote_name: String,
         count: usize,
     },
     UntrackedFile(String),
     UnstagedFile(FileChange),
     StagedFile(FileChange),
+    /// A directory grouping the files below it in the tree layout of the
+    /// untracked, unstaged and staged files
+    Directory {
+        /// The directory's name below its parent; directories holding
+        /// nothing but one directory are joined with it, like `src/git/`
+        name: String,
+        /// The number of directories above it in the tree
+        depth: usize,
+        /// The number of files below it, at any depth
+        count: usize,
+    },
+    /// A change to a file that isn't in its hunks, listed below the file
+    FileDetail(FileDetail),
     DiffHunk(DiffHunk),
     DiffLine(DiffLine),
     Commit(CommitInfo),
@@ -228,8 +297,9 @@ pub enum LineContent {
     LogLine(LogEntry),
     /// A line in the interactive rebase todo editor
     RebaseTodoLine(crate::git::rebase::RebaseTodoEntry),
-    /// A keybinding hint line shown below the rebase todo entries
-    RebaseTodoHint {
+    /// A keybinding hint line, shown below the rebase todo entries and the
+    /// reviewed hunk
+    KeyHint {
         key: &'static str,
         description: &'static str,
     },
@@ -239,6 +309,17 @@ pub enum LineContent {
     PreviewLine {
         content: String,
         line_type: PreviewLineType,
+        /// Byte ranges of `content` after its origin character that differ
+        /// from the paired line on the other side of the change
+        refined: Vec<Range<usize>>,
+    },
+    /// A line of a file shown at a revision (blob view)
+    BlobLine {
+        /// 1-based line number
+        number: usize,
+        content: String,
+        /// The commit that last changed the line, when blaming
+        blame: Option<BlameInfo>,
     },
     /// An entry in the "Reverting" sequencer section
     RevertingEntry {
@@ -251,6 +332,8 @@ pub enum LineContent {
     RebasingEntry {
         hash: String,
         message: String,
+        /// The todo action of a pending entry (`Pick` for the stopped commit)
+        action: crate::git::rebase::RebaseAction,
         /// true = the commit currently stopped on (conflict), false = pending in todo
         is_current: bool,
     },
@@ -324,13 +407,57 @@ pub enum FileStatus {
     Modified,
     Deleted,
     New,
-    Renamed,
-    Copied,
+    /// Moved from `from`; `similarity` is how much of the content (in
+    /// percent) is unchanged
+    Renamed {
+        from: String,
+        similarity: u16,
+    },
+    /// Copied from `from`, with `similarity` like a rename's
+    Copied {
+        from: String,
+        similarity: u16,
+    },
     TypeChange,
     /// A file with merge conflicts (both sides modified during merge/rebase/cherry-pick)
     Unmerged,
 }
 
+/// A change to a file that its hunks don't show
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDetail {
+    /// The file's mode changed, e.g. from 100644 to 100755 when made
+    /// executable
+    ModeChange { old_mode: u32, new_mode: u32 },
+    /// A submodule's checked out commit changed (abbreviated ids)
+    Submodule { old_id: String, new_id: String },
+    /// A binary file changed; the sizes are in bytes, 0 for a side
+    /// without the file
+    Binary { old_size: u64, new_size: u64 },
+    /// An untracked file too large to preview; the size is in bytes
+    TooLarge { size: u64 },
+}
+
+impl FileDetail {
+    /// The detail as listed below its file, in git's words
+    pub fn description(&self) -> String {
+        match self {
+            FileDetail::ModeChange { old_mode, new_mode } => {
+                format!("mode {:o} -> {:o}", old_mode, new_mode)
+            }
+            FileDetail::Submodule { old_id, new_id } => {
+                format!("Submodule {} -> {}", old_id, new_id)
+            }
+            FileDetail::Binary { old_size, new_size } => {
+                format!("Binary files differ ({} -> {} bytes)", old_size, new_size)
+            }
+            FileDetail::TooLarge { size } => {
+                format!("File too large to preview ({} bytes)", size)
+            }
+        }
+    }
+}
+
 /// Represents a diff hunk header (e.g., @@ -7,6 +7,7 @@)
 #[derive(Debug, Clone)]
 pub struct DiffHunk {
@@ -344,6 +471,9 @@ pub struct DiffHunk {
 pub struct DiffLine {
     pub content: String,
     pub line_type: DiffLineType,
+    /// Byte ranges of `content` that differ from the paired line on the
+    /// other side of the change (word-level refinement)
+    pub refined: Vec<Range<usize>>,
 }
 
 /// Addition and Deletion lines should be prefixed
@@ -382,14 +512,15 @@ impl Line {
                 return true;
             }
             // Also check if this section itself is collapsed AND this is not a header line.
-            // Headers (SectionHeader, UnpulledSectionHeader, UnstagedFile, HeadRef) should remain visible when collapsed.
+            // Headers (SectionHeader, RemoteSectionHeader, UnstagedFile, HeadRef) should remain visible when collapsed.
             if collapsed_sections.contains(section)
                 && !matches!(
                     self.content,
                     LineContent::SectionHeader { .. }
-                        | LineContent::UnpulledSectionHeader { .. }
+                        | LineContent::RemoteSectionHeader { .. }
                         | LineContent::UnstagedFile(_)
                         | LineContent::StagedFile(_)
+                        | LineContent::Directory { .. }
                         | LineContent::HeadRef(_)
                 )
             {
@@ -404,8 +535,12 @@ impl Line {
     pub fn collapsible_section(&self) -> Option<SectionType> {
         match (&self.content, &self.section) {
             (LineContent::SectionHeader { .. }, Some(section)) => Some(section.clone()),
-            (LineContent::UnpulledSectionHeader { .. }, Some(section)) => Some(section.clone()),
+            (LineContent::RemoteSectionHeader { .. }, Some(section)) => Some(section.clone()),
             (LineContent::HeadRef(_), _) => Some(SectionType::Info),
+            (LineContent::Directory { .. }, Some(section)) => Some(section.clone()),
+            (LineContent::UntrackedFile(path), _) => {
+                Some(SectionType::UntrackedFile { path: path.clone() })
+            }
             (LineContent::UnstagedFile(file_change), _) => Some(SectionType::UnstagedFile {
                 path: file_change.path.clone(),
             }),
@@ -415,14 +550,35 @@ impl Line {
             _ => None,
         }
     }
+
+    /// The section of the line and the one it heads. An untracked file that
+    /// isn't expanded has no preview lines, so its section is only known
+    /// from its own line.
+    pub fn sections(&self) -> impl Iterator<Item = SectionType> {
+        [self.section.clone(), self.collapsible_section()]
+            .into_iter()
+            .flatten()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SectionType {
     Info,
     UntrackedFiles,
+    /// The preview of an untracked file's content
+    UntrackedFile {
+        path: String,
+    },
+    /// A directory of untracked files in the tree layout; `path` ends with `/`
+    UntrackedDirectory {
+        path: String,
+    },
     /// The main "Unstaged changes" section header
     UnstagedChanges,
+    /// A directory of unstaged files in the tree layout; `path` ends with `/`
+    UnstagedDirectory {
+        path: String,
+    },
     /// A file within unstaged changes (selecting highlights all hunks)
     UnstagedFile {
         path: String,
@@ -434,6 +590,10 @@ pub enum SectionType {
     },
     /// The main "Staged changes" section header
     StagedChanges,
+    /// A directory of staged files in the tree layout; `path` ends with `/`
+    StagedDirectory {
+        path: String,
+    },
     /// A file within staged changes (selecting highlights all hunks)
     StagedFile {
         path: String,
@@ -447,6 +607,12 @@ pub enum SectionType {
     RecentCommits,
     /// The "Unpulled from upstream" section
     Unpulled,
+    /// The "Unpulled from push-remote" section
+    UnpulledFromPushRemote,
+    /// The "Unmerged into upstream" section
+    UnmergedIntoUpstream,
+    /// The "Unpushed to push-remote" section
+    UnpushedToPushRemote,
     /// The "Stashes" section
     Stashes,
     /// The "Reverting" sequencer section
@@ -460,22 +626,51 @@ pub enum SectionType {
 impl SectionType {
     /// Returns the parent section that can be collapsed to hide this section.
     /// For example, UnstagedHunk's parent is UnstagedFile, UnstagedFile's parent is UnstagedChanges.
+    ///
+    /// Files and directories below a directory have its directory section
+    /// as their parent. Without the tree layout there are no directory
+    /// lines, so those sections are never collapsed.
     pub fn parent_section(&self) -> Option<SectionType> {
+        let in_directory = |path: &str, directory: fn(String) -> SectionType, top: SectionType| {
+            Some(parent_directory(path).map_or(top, directory))
+        };
         match self {
             SectionType::Info => None,
             SectionType::UntrackedFiles => None,
+            SectionType::UntrackedFile { path } | SectionType::UntrackedDirectory { path } => {
+                in_directory(
+                    path,
+                    |path| SectionType::UntrackedDirectory { path },
+                    SectionType::UntrackedFiles,
+                )
+            }
             SectionType::UnstagedChanges => None,
-            SectionType::UnstagedFile { .. } => Some(SectionType::UnstagedChanges),
+            SectionType::UnstagedFile { path } | SectionType::UnstagedDirectory { path } => {
+                in_directory(
+                    path,
+                    |path| SectionType::UnstagedDirectory { path },
+                    SectionType::UnstagedChanges,
+                )
+            }
             SectionType::UnstagedHunk { path, .. } => {
                 Some(SectionType::UnstagedFile { path: path.clone() })
             }
             SectionType::StagedChanges => None,
-            SectionType::StagedFile { .. } => Some(SectionType::StagedChanges),
+            SectionType::StagedFile { path } | SectionType::StagedDirectory { path } => {
+                in_directory(
+                    path,
+                    |path| SectionType::StagedDirectory { path },
+                    SectionType::StagedChanges,
+                )
+            }
             SectionType::StagedHunk { path, .. } => {
                 Some(SectionType::StagedFile { path: path.clone() })
             }
             SectionType::RecentCommits => None,
             SectionType::Unpulled => None,
+            SectionType::UnpulledFromPushRemote => None,
+            SectionType::UnmergedIntoUpstream => None,
+            SectionType::UnpushedToPushRemote => None,
             SectionType::Stashes => None,
             SectionType::Reverting => None,
             SectionType::Rebasing => None,
@@ -483,6 +678,18 @@ impl SectionType {
         }
     }
 
+    /// Checks if `ancestor` is one of the parents of this section, at any level.
+    pub fn is_below(&self, ancestor: &SectionType) -> bool {
+        let mut current = self.parent_section();
+        while let Some(parent) = current {
+            if parent == *ancestor {
+                return true;
+            }
+            current = parent.parent_section();
+        }
+        false
+    }
+
     /// Checks if this section should be hidden because a parent is collapsed.
     pub fn is_hidden_by(&self, collapsed: &HashSet<SectionType>) -> bool {
         // Check if any parent section is collapsed
@@ -500,19 +707,43 @@ impl SectionType {
     pub fn default_collapsed(&self) -> bool {
         matches!(
             self,
-            SectionType::StagedFile { .. } | SectionType::UnstagedFile { .. }
+            SectionType::StagedFile { .. }
+                | SectionType::UnstagedFile { .. }
+                | SectionType::UntrackedFile { .. }
         )
     }
 
-    /// Returns the file path if this is a file-level section (UnstagedFile or StagedFile).
+    /// Returns the file path if this is a file-level section (UntrackedFile,
+    /// UnstagedFile or StagedFile).
     pub fn file_path(&self) -> Option<&str> {
         match self {
-            SectionType::UnstagedFile { path } | SectionType::StagedFile { path } => Some(path),
+            SectionType::UntrackedFile { path }
+            | SectionType::UnstagedFile { path }
+            | SectionType::StagedFile { path } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Returns the directory path (ending with `/`) if this is a directory
+    /// section of the tree layout.
+    pub fn directory_path(&self) -> Option<&str> {
+        match self {
+            SectionType::UntrackedDirectory { path }
+            | SectionType::UnstagedDirectory { path }
+            | SectionType::StagedDirectory { path } => Some(path),
             _ => None,
         }
     }
 }
 
+/// The directory holding `path` (a file, or a directory ending with `/`),
+/// with a trailing `/`. `None` at the top of the repository.
+pub fn parent_directory(path: &str) -> Option<String> {
+    path.trim_end_matches('/')
+        .rsplit_once('/')
+        .map(|(directory, _)| format!("{}/", directory))
+}
+
 #[derive(Default, PartialEq, Eq, Debug)]
 pub enum RunningState {
     #[default]
@@ -535,6 +766,15 @@ mod tests {
         assert_eq!(SectionType::UntrackedFiles.parent_section(), None);
         assert_eq!(SectionType::UnstagedChanges.parent_section(), None);
 
+        // An untracked file's preview belongs to the untracked files
+        let untracked_section = SectionType::UntrackedFile {
+            path: "new.rs".to_string(),
+        };
+        assert_eq!(
+            untracked_section.parent_section(),
+            Some(SectionType::UntrackedFiles)
+        );
+
         // UnstagedFile's parent is UnstagedChanges
         let file_section = SectionType::UnstagedFile {
             path: "foo.rs".to_string(),
@@ -557,6 +797,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_directory_sections_chain_up_to_their_section() {
+        let file_section = SectionType::UnstagedFile {
+            path: "src/git/stage.rs".to_string(),
+        };
+        let git = SectionType::UnstagedDirectory {
+            path: "src/git/".to_string(),
+        };
+        let src = SectionType::UnstagedDirectory {
+            path: "src/".to_string(),
+        };
+
+        assert_eq!(file_section.parent_section(), Some(git.clone()));
+        assert_eq!(git.parent_section(), Some(src.clone()));
+        assert_eq!(src.parent_section(), Some(SectionType::UnstagedChanges));
+        assert!(file_section.is_below(&src));
+        assert!(!src.is_below(&git));
+
+        // An untracked directory entry sits in its parent directory
+        let untracked = SectionType::UntrackedDirectory {
+            path: "gen/".to_string(),
+        };
+        assert_eq!(
+            untracked.parent_section(),
+            Some(SectionType::UntrackedFiles)
+        );
+        assert_eq!(parent_directory("gen/out/"), Some("gen/".to_string()));
+        assert_eq!(parent_directory("top.txt"), None);
+    }
+
     #[test]
     fn test_is_hidden_by_collapsed_parent() {
         let mut collapsed = HashSet::new();
@@ -690,11 +960,24 @@ mod tests {
             })
         );
 
+        // UntrackedFile returns the section of its preview
+        let untracked_line = Line {
+            content: LineContent::UntrackedFile("new.rs".to_string()),
+            section: Some(SectionType::UntrackedFiles),
+        };
+        assert_eq!(
+            untracked_line.collapsible_section(),
+            Some(SectionType::UntrackedFile {
+                path: "new.rs".to_string()
+            })
+        );
+
         // Other content types return None
         let diff_line = Line {
             content: LineContent::DiffLine(DiffLine {
                 content: "+ added".to_string(),
                 line_type: DiffLineType::Addition,
+                refined: Vec::new(),
             }),
             section: Some(SectionType::UnstagedHunk {
                 path: "foo.rs".to_string(),
@@ -725,6 +1008,12 @@ mod tests {
         };
         assert_eq!(staged.file_path(), Some("bar.rs"));
 
+        // UntrackedFile returns the path
+        let untracked = SectionType::UntrackedFile {
+            path: "new.rs".to_string(),
+        };
+        assert_eq!(untracked.file_path(), Some("new.rs"));
+
         // Other section types return None
         assert_eq!(SectionType::Info.file_path(), None);
         assert_eq!(SectionType::UntrackedFiles.file_path(), None);
@@ -762,9 +1051,11 @@ mod tests {
 
     #[test]
     fn test_visual_selection_range_ordered() {
-        let mut ui_model = UiModel::default();
-        ui_model.cursor_position = 3;
-        ui_model.visual_mode_anchor = Some(7);
+        let mut ui_model = UiModel {
+            cursor_position: 3,
+            visual_mode_anchor: Some(7),
+            ..Default::default()
+        };
 
         // Range should be ordered (start, end) regardless of anchor vs cursor position
         let range = ui_model.visual_selection_range();
@@ -780,9 +1071,11 @@ mod tests {
 
     #[test]
     fn test_visual_selection_range_same_position() {
-        let mut ui_model = UiModel::default();
-        ui_model.cursor_position = 5;
-        ui_model.visual_mode_anchor = Some(5);
+        let ui_model = UiModel {
+            cursor_position: 5,
+            visual_mode_anchor: Some(5),
+            ..Default::default()
+        };
 
         let range = ui_model.visual_selection_range();
         assert_eq!(range, Some((5, 5)));
@@ -803,23 +1096,29 @@ mod tests {
 
     #[test]
     fn test_current_mode_returns_visual_when_anchor_set() {
-        let mut ui_model = UiModel::default();
-        ui_model.visual_mode_anchor = Some(5);
+        let ui_model = UiModel {
+            visual_mode_anchor: Some(5),
+            ..Default::default()
+        };
         assert_eq!(ui_model.current_mode(), InputMode::Visual);
     }
 
     #[test]
     fn test_current_mode_returns_search_when_search_active() {
-        let mut ui_model = UiModel::default();
-        ui_model.search_mode_active = true;
+        let ui_model = UiModel {
+            search_mode_active: true,
+            ..Default::default()
+        };
         assert_eq!(ui_model.current_mode(), InputMode::Search);
     }
 
     #[test]
     fn test_search_mode_takes_priority_over_visual() {
-        let mut ui_model = UiModel::default();
-        ui_model.visual_mode_anchor = Some(5);
-        ui_model.search_mode_active = true;
+        let ui_model = UiModel {
+            visual_mode_anchor: Some(5),
+            search_mode_active: true,
+            ..Default::default()
+        };
         // Search mode should take priority
         assert_eq!(ui_model.current_mode(), InputMode::Search);
     }
@@ -12,6 +12,8 @@ pub enum Arguments {
     LogArguments(HashSet<LogArgument>),
     TagArguments(HashSet<TagArgument>),
     RebaseArguments(HashSet<RebaseArgument>),
+    MergeArguments(HashSet<MergeArgument>),
+    ApplyArguments(HashSet<ApplyArgument>),
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, Hash)]
@@ -25,6 +27,8 @@ pub enum Argument {
     Log(LogArgument),
     Tag(TagArgument),
     Rebase(RebaseArgument),
+    Merge(MergeArgument),
+    Apply(ApplyArgument),
 }
 
 pub trait PopupArgument: Sized + Eq + Hash {
@@ -178,6 +182,38 @@ impl Arguments {
             None
         }
     }
+
+    pub fn merge(&self) -> Option<&HashSet<MergeArgument>> {
+        if let Arguments::MergeArguments(args) = self {
+            Some(args)
+        } else {
+            None
+        }
+    }
+
+    pub fn merge_mut(&mut self) -> Option<&mut HashSet<MergeArgument>> {
+        if let Arguments::MergeArguments(args) = self {
+            Some(args)
+        } else {
+            None
+        }
+    }
+
+    pub fn apply(&self) -> Option<&HashSet<ApplyArgument>> {
+        if let Arguments::ApplyArguments(args) = self {
+            Some(args)
+        } else {
+            None
+        }
+    }
+
+    pub fn apply_mut(&mut self) -> Option<&mut HashSet<ApplyArgument>> {
+        if let Arguments::ApplyArguments(args) = self {
+            Some(args)
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, Hash)]
@@ -187,6 +223,7 @@ pub enum CommitArgument {
     Verbose,
     DisableHooks,
     ResetAuthor,
+    Signoff,
 }
 
 impl CommitArgument {
@@ -203,6 +240,7 @@ impl PopupArgument for CommitArgument {
             CommitArgument::Verbose,
             CommitArgument::DisableHooks,
             CommitArgument::ResetAuthor,
+            CommitArgument::Signoff,
         ]
     }
 
@@ -213,6 +251,7 @@ impl PopupArgument for CommitArgument {
             CommitArgument::Verbose => 'v',
             CommitArgument::DisableHooks => 'n',
             CommitArgument::ResetAuthor => 'R',
+            CommitArgument::Signoff => 's',
         }
     }
 
@@ -224,6 +263,7 @@ impl PopupArgument for CommitArgument {
             CommitArgument::Verbose => t.arg_commit_verbose,
             CommitArgument::DisableHooks => t.arg_commit_disable_hooks,
             CommitArgument::ResetAuthor => t.arg_commit_reset_author,
+            CommitArgument::Signoff => t.arg_commit_signoff,
         }
     }
 
@@ -234,6 +274,7 @@ impl PopupArgument for CommitArgument {
             CommitArgument::Verbose => "--verbose",
             CommitArgument::DisableHooks => "--no-verify",
             CommitArgument::ResetAuthor => "--reset-author",
+            CommitArgument::Signoff => "--signoff",
         }
     }
 }
@@ -451,6 +492,7 @@ impl PopupArgument for StashArgument {
 pub enum RevertArgument {
     Edit,
     NoEdit,
+    Signoff,
 }
 
 impl RevertArgument {
@@ -463,6 +505,7 @@ impl RevertArgument {
 pub enum LogArgument {
     Graph,
     Color,
+    Signatures,
 }
 
 impl LogArgument {
@@ -473,13 +516,18 @@ impl LogArgument {
 
 impl PopupArgument for LogArgument {
     fn all() -> Vec<LogArgument> {
-        vec![LogArgument::Graph, LogArgument::Color]
+        vec![
+            LogArgument::Graph,
+            LogArgument::Color,
+            LogArgument::Signatures,
+        ]
     }
 
     fn key(&self) -> char {
         match self {
             LogArgument::Graph => 'g',
             LogArgument::Color => 'c',
+            LogArgument::Signatures => 's',
         }
     }
 
@@ -488,6 +536,7 @@ impl PopupArgument for LogArgument {
         match self {
             LogArgument::Graph => t.arg_log_graph,
             LogArgument::Color => t.arg_log_color,
+            LogArgument::Signatures => t.arg_log_signatures,
         }
     }
 
@@ -495,19 +544,25 @@ impl PopupArgument for LogArgument {
         match self {
             LogArgument::Graph => "--graph",
             LogArgument::Color => "--color",
+            LogArgument::Signatures => "--show-signature",
         }
     }
 }
 
 impl PopupArgument for RevertArgument {
     fn all() -> Vec<RevertArgument> {
-        vec![RevertArgument::Edit, RevertArgument::NoEdit]
+        vec![
+            RevertArgument::Edit,
+            RevertArgument::NoEdit,
+            RevertArgument::Signoff,
+        ]
     }
 
     fn key(&self) -> char {
         match self {
             RevertArgument::Edit => 'e',
             RevertArgument::NoEdit => 'E',
+            RevertArgument::Signoff => 'o',
         }
     }
 
@@ -516,6 +571,7 @@ impl PopupArgument for RevertArgument {
         match self {
             RevertArgument::Edit => t.arg_revert_edit,
             RevertArgument::NoEdit => t.arg_revert_no_edit,
+            RevertArgument::Signoff => t.arg_signoff,
         }
     }
 
@@ -523,6 +579,7 @@ impl PopupArgument for RevertArgument {
         match self {
             RevertArgument::Edit => "--edit",
             RevertArgument::NoEdit => "--no-edit",
+            RevertArgument::Signoff => "--signoff",
         }
     }
 }
@@ -530,6 +587,11 @@ impl PopupArgument for RevertArgument {
 #[derive(PartialEq, Eq, Debug, Clone, Hash)]
 pub enum RebaseArgument {
     KeepEmpty,
+    Autostash,
+    RebaseMerges,
+    UpdateRefs,
+    CommitterDateIsAuthorDate,
+    KeepBase,
 }
 
 impl RebaseArgument {
@@ -540,12 +602,24 @@ impl RebaseArgument {
 
 impl PopupArgument for RebaseArgument {
     fn all() -> Vec<RebaseArgument> {
-        vec![RebaseArgument::KeepEmpty]
+        vec![
+            RebaseArgument::KeepEmpty,
+            RebaseArgument::Autostash,
+            RebaseArgument::RebaseMerges,
+            RebaseArgument::UpdateRefs,
+            RebaseArgument::CommitterDateIsAuthorDate,
+            RebaseArgument::KeepBase,
+        ]
     }
 
     fn key(&self) -> char {
         match self {
             RebaseArgument::KeepEmpty => 'k',
+            RebaseArgument::Autostash => 'A',
+            RebaseArgument::RebaseMerges => 'r',
+            RebaseArgument::UpdateRefs => 'u',
+            RebaseArgument::CommitterDateIsAuthorDate => 'd',
+            RebaseArgument::KeepBase => 'b',
         }
     }
 
@@ -553,12 +627,199 @@ impl PopupArgument for RebaseArgument {
         let t = i18n::t();
         match self {
             RebaseArgument::KeepEmpty => t.arg_rebase_keep_empty,
+            RebaseArgument::Autostash => t.arg_rebase_autostash,
+            RebaseArgument::RebaseMerges => t.arg_rebase_merges,
+            RebaseArgument::UpdateRefs => t.arg_rebase_update_refs,
+            RebaseArgument::CommitterDateIsAuthorDate => t.arg_rebase_committer_date,
+            RebaseArgument::KeepBase => t.arg_rebase_keep_base,
         }
     }
 
     fn flag(&self) -> &'static str {
         match self {
             RebaseArgument::KeepEmpty => "--keep-empty",
+            RebaseArgument::Autostash => "--autostash",
+            RebaseArgument::RebaseMerges => "--rebase-merges",
+            RebaseArgument::UpdateRefs => "--update-refs",
+            RebaseArgument::CommitterDateIsAuthorDate => "--committer-date-is-author-date",
+            RebaseArgument::KeepBase => "--keep-base",
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub enum MergeArgument {
+    FfOnly,
+    NoFf,
+    NoVerify,
+    Signoff,
+    IgnoreSpaceChange,
+    IgnoreAllSpace,
+}
+
+impl MergeArgument {
+    pub fn from_key(key: char) -> Option<MergeArgument> {
+        Self::all().into_iter().find(|arg| arg.key() == key)
+    }
+
+    /// The argument git rejects alongside this one, if any
+    pub fn conflicting(&self) -> Option<MergeArgument> {
+        match self {
+            MergeArgument::FfOnly => Some(MergeArgument::NoFf),
+            MergeArgument::NoFf => Some(MergeArgument::FfOnly),
+            _ => None,
+        }
+    }
+}
+
+impl PopupArgument for MergeArgument {
+    fn all() -> Vec<MergeArgument> {
+        vec![
+            MergeArgument::FfOnly,
+            MergeArgument::NoFf,
+            MergeArgument::NoVerify,
+            MergeArgument::Signoff,
+            MergeArgument::IgnoreSpaceChange,
+            MergeArgument::IgnoreAllSpace,
+        ]
+    }
+
+    fn key(&self) -> char {
+        match self {
+            MergeArgument::FfOnly => 'f',
+            MergeArgument::NoFf => 'n',
+            MergeArgument::NoVerify => 'h',
+            MergeArgument::Signoff => 'o',
+            MergeArgument::IgnoreSpaceChange => 'b',
+            MergeArgument::IgnoreAllSpace => 'w',
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        let t = i18n::t();
+        match self {
+            MergeArgument::FfOnly => t.arg_merge_ff_only,
+            MergeArgument::NoFf => t.arg_merge_no_ff,
+            MergeArgument::NoVerify => t.arg_merge_no_verify,
+            MergeArgument::Signoff => t.arg_signoff,
+            MergeArgument::IgnoreSpaceChange => t.arg_merge_ignore_space_change,
+            MergeArgument::IgnoreAllSpace => t.arg_merge_ignore_all_space,
+        }
+    }
+
+    fn flag(&self) -> &'static str {
+        match self {
+            MergeArgument::FfOnly => "--ff-only",
+            MergeArgument::NoFf => "--no-ff",
+            MergeArgument::NoVerify => "--no-verify",
+            MergeArgument::Signoff => "--signoff",
+            MergeArgument::IgnoreSpaceChange => "-Xignore-space-change",
+            MergeArgument::IgnoreAllSpace => "-Xignore-all-space",
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub enum ApplyArgument {
+    RecordOrigin,
+    FastForward,
+    Signoff,
+    AllowEmpty,
+    KeepRedundantCommits,
+}
+
+impl ApplyArgument {
+    pub fn from_key(key: char) -> Option<ApplyArgument> {
+        Self::all().into_iter().find(|arg| arg.key() == key)
+    }
+}
+
+impl PopupArgument for ApplyArgument {
+    fn all() -> Vec<ApplyArgument> {
+        vec![
+            ApplyArgument::RecordOrigin,
+            ApplyArgument::FastForward,
+            ApplyArgument::Signoff,
+            ApplyArgument::AllowEmpty,
+            ApplyArgument::KeepRedundantCommits,
+        ]
+    }
+
+    fn key(&self) -> char {
+        match self {
+            ApplyArgument::RecordOrigin => 'x',
+            ApplyArgument::FastForward => 'F',
+            ApplyArgument::Signoff => 'o',
+            ApplyArgument::AllowEmpty => 'e',
+            ApplyArgument::KeepRedundantCommits => 'k',
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        let t = i18n::t();
+        match self {
+            ApplyArgument::RecordOrigin => t.arg_apply_record_origin,
+            ApplyArgument::FastForward => t.arg_apply_ff,
+            ApplyArgument::Signoff => t.arg_signoff,
+            ApplyArgument::AllowEmpty => t.arg_apply_allow_empty,
+            ApplyArgument::KeepRedundantCommits => t.arg_apply_keep_redundant,
+        }
+    }
+
+    fn flag(&self) -> &'static str {
+        match self {
+            ApplyArgument::RecordOrigin => "-x",
+            ApplyArgument::FastForward => "--ff",
+            ApplyArgument::Signoff => "--signoff",
+            ApplyArgument::AllowEmpty => "--allow-empty",
+            ApplyArgument::KeepRedundantCommits => "--keep-redundant-commits",
+        }
+    }
+}
+
+/// An argument that takes a value (`--exec=<cmd>`), typed into an input
+/// popup. Like the signing key, the value lives in `Model::argument_values`
+/// rather than in the popup, so it survives the pickers shown before the
+/// command runs.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum ValueArgument {
+    /// `--exec=<cmd>`: run a command after each rebased commit
+    Exec,
+    /// `--strategy=<strategy>`: the merge strategy to use
+    Strategy,
+    /// `--strategy-option=<option>`: pass an option to the merge strategy
+    StrategyOption,
+    /// `--mainline=<parent>`: the parent a merge commit is replayed against
+    Mainline,
+}
+
+impl ValueArgument {
+    pub fn key(self) -> char {
+        match self {
+            ValueArgument::Exec => 'x',
+            ValueArgument::Strategy => 's',
+            ValueArgument::StrategyOption => 'X',
+            ValueArgument::Mainline => 'm',
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        let t = i18n::t();
+        match self {
+            ValueArgument::Exec => t.arg_exec,
+            ValueArgument::Strategy => t.arg_strategy,
+            ValueArgument::StrategyOption => t.arg_strategy_option,
+            ValueArgument::Mainline => t.arg_revert_mainline,
+        }
+    }
+
+    /// The flag up to and including `=`; the value follows it directly.
+    pub fn flag_prefix(self) -> &'static str {
+        match self {
+            ValueArgument::Exec => "--exec=",
+            ValueArgument::Strategy => "--strategy=",
+            ValueArgument::StrategyOption => "--strategy-option=",
+            ValueArgument::Mainline => "--mainline=",
         }
     }
 }
@@ -624,7 +885,11 @@ mod tests {
     fn test_revert_argument_edit_listed_above_no_edit() {
         assert_eq!(
             RevertArgument::all(),
-            vec![RevertArgument::Edit, RevertArgument::NoEdit]
+            vec![
+                RevertArgument::Edit,
+                RevertArgument::NoEdit,
+                RevertArgument::Signoff
+            ]
         );
     }
 
@@ -643,6 +908,24 @@ mod tests {
         assert_eq!(RevertArgument::from_key('x'), None);
     }
 
+    #[test]
+    fn test_apply_argument_keys_and_flags() {
+        assert_eq!(
+            ApplyArgument::from_key('x'),
+            Some(ApplyArgument::RecordOrigin)
+        );
+        assert_eq!(ApplyArgument::RecordOrigin.flag(), "-x");
+        assert_eq!(ApplyArgument::FastForward.key(), 'F');
+        assert_eq!(ApplyArgument::FastForward.flag(), "--ff");
+        assert_eq!(ApplyArgument::Signoff.flag(), "--signoff");
+        assert_eq!(ApplyArgument::AllowEmpty.flag(), "--allow-empty");
+        assert_eq!(
+            ApplyArgument::KeepRedundantCommits.flag(),
+            "--keep-redundant-commits"
+        );
+        assert_eq!(ApplyArgument::from_key('m'), None);
+    }
+
     #[test]
     fn test_tag_argument_key_and_flag() {
         assert_eq!(TagArgument::Force.key(), 'f');
@@ -672,6 +955,35 @@ mod tests {
     fn test_rebase_argument_key_and_flag() {
         assert_eq!(RebaseArgument::KeepEmpty.key(), 'k');
         assert_eq!(RebaseArgument::KeepEmpty.flag(), "--keep-empty");
+        assert_eq!(RebaseArgument::Autostash.key(), 'A');
+        assert_eq!(RebaseArgument::Autostash.flag(), "--autostash");
+        assert_eq!(RebaseArgument::RebaseMerges.flag(), "--rebase-merges");
+        assert_eq!(RebaseArgument::UpdateRefs.flag(), "--update-refs");
+        assert_eq!(
+            RebaseArgument::CommitterDateIsAuthorDate.flag(),
+            "--committer-date-is-author-date"
+        );
+        assert_eq!(RebaseArgument::KeepBase.key(), 'b');
+        assert_eq!(RebaseArgument::KeepBase.flag(), "--keep-base");
+    }
+
+    #[test]
+    fn test_merge_argument_keys_and_flags() {
+        assert_eq!(MergeArgument::from_key('f'), Some(MergeArgument::FfOnly));
+        assert_eq!(MergeArgument::FfOnly.flag(), "--ff-only");
+        assert_eq!(MergeArgument::NoFf.flag(), "--no-ff");
+        assert_eq!(MergeArgument::NoVerify.flag(), "--no-verify");
+        assert_eq!(MergeArgument::Signoff.flag(), "--signoff");
+        assert_eq!(MergeArgument::from_key('s'), None);
+        assert_eq!(ValueArgument::Strategy.flag_prefix(), "--strategy=");
+    }
+
+    #[test]
+    fn test_value_argument_keys_do_not_clash_with_rebase_arguments() {
+        for argument in [ValueArgument::Exec, ValueArgument::StrategyOption] {
+            assert_eq!(RebaseArgument::from_key(argument.key()), None);
+        }
+        assert_eq!(ValueArgument::Exec.flag_prefix(), "--exec=");
     }
 
     #[test]
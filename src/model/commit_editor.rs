@@ -0,0 +1,157 @@
+use super::{UiModel, ViewMode, text_area::TextArea};
+
+/// Summary lines longer than this are highlighted in the editor, like
+/// Magit's `git-commit-summary-max-length`.
+pub const SUMMARY_MAX_LENGTH: usize = 68;
+
+/// Whether keys type text or run editor commands (vim-style).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommitEditorMode {
+    #[default]
+    Insert,
+    Normal,
+}
+
+/// State for the built-in commit message editor (ViewMode::CommitMessage).
+///
+/// Holds the message being written, the `git commit` flags collected from
+/// the commit popup, and a ring of earlier messages that can be recalled
+/// with M-p/M-n.
+#[derive(Clone)]
+pub struct CommitEditorState {
+    pub message: TextArea,
+    /// Whether confirming amends HEAD instead of creating a new commit
+    pub amend: bool,
+    /// Extra flags for `git commit` (popup arguments, `--author=...`)
+    pub flags: Vec<String>,
+    pub mode: CommitEditorMode,
+    /// Vim-style command line buffer; `Some` while typing after `:` in
+    /// normal mode (e.g. `:wq` to commit)
+    pub command_input: Option<String>,
+    /// Set after `C-c`, waiting for the second key of `C-c C-c`/`C-c C-k`
+    pub pending_ctrl_c: bool,
+    /// The view to go back to when the editor closes
+    pub return_mode: ViewMode,
+    /// The UiModel to restore when the editor closes
+    pub return_ui_model: UiModel,
+    /// Earlier messages, most recent first
+    history: Vec<String>,
+    /// Index into `history` of the recalled message; `None` while editing
+    /// the draft
+    history_index: Option<usize>,
+    /// The message that was being written when history recall started
+    draft: Option<String>,
+}
+
+impl CommitEditorState {
+    pub fn new(
+        message: &str,
+        amend: bool,
+        flags: Vec<String>,
+        history: Vec<String>,
+        return_mode: ViewMode,
+        return_ui_model: UiModel,
+    ) -> Self {
+        Self {
+            message: TextArea::from_text(message),
+            amend,
+            flags,
+            mode: CommitEditorMode::Insert,
+            command_input: None,
+            pending_ctrl_c: false,
+            return_mode,
+            return_ui_model,
+            history,
+            history_index: None,
+            draft: None,
+        }
+    }
+
+    /// The first line of the message.
+    pub fn summary(&self) -> &str {
+        self.message.lines()[0].as_str()
+    }
+
+    /// Replaces the message with the next older history entry, saving the
+    /// draft on the first step. Returns false when there is nothing older.
+    pub fn history_prev(&mut self) -> bool {
+        let next = self.history_index.map_or(0, |i| i + 1);
+        if next >= self.history.len() {
+            return false;
+        }
+        if self.history_index.is_none() {
+            self.draft = Some(self.message.text());
+        }
+        self.history_index = Some(next);
+        self.message = TextArea::from_text(&self.history[next]);
+        true
+    }
+
+    /// Replaces the message with the next newer history entry, or restores
+    /// the draft after the newest one. Returns false when already on the
+    /// draft.
+    pub fn history_next(&mut self) -> bool {
+        match self.history_index {
+            None => false,
+            Some(0) => {
+                self.history_index = None;
+                self.message = TextArea::from_text(&self.draft.take().unwrap_or_default());
+                true
+            }
+            Some(i) => {
+                self.history_index = Some(i - 1);
+                self.message = TextArea::from_text(&self.history[i - 1]);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::EditOp;
+
+    fn state(history: &[&str]) -> CommitEditorState {
+        CommitEditorState::new(
+            "",
+            false,
+            vec![],
+            history.iter().map(|s| s.to_string()).collect(),
+            ViewMode::Status,
+            UiModel::default(),
+        )
+    }
+
+    #[test]
+    fn history_prev_walks_back_and_keeps_draft() {
+        let mut state = state(&["newest", "older"]);
+        state.message.apply(EditOp::Insert('x'));
+
+        assert!(state.history_prev());
+        assert_eq!(state.message.text(), "newest");
+        assert!(state.history_prev());
+        assert_eq!(state.message.text(), "older");
+        assert!(!state.history_prev());
+
+        assert!(state.history_next());
+        assert_eq!(state.message.text(), "newest");
+        assert!(state.history_next());
+        assert_eq!(state.message.text(), "x");
+        assert!(!state.history_next());
+    }
+
+    #[test]
+    fn history_prev_without_history_is_noop() {
+        let mut state = state(&[]);
+        assert!(!state.history_prev());
+        assert_eq!(state.message.text(), "");
+    }
+
+    #[test]
+    fn summary_is_first_line() {
+        let mut state = state(&["Summary\n\nBody"]);
+        state.history_prev();
+        assert_eq!(state.summary(), "Summary");
+    }
+}
@@ -49,6 +49,11 @@ impl CursorContext {
                 path: path.clone(),
                 line_index: cursor_position,
             },
+            // On an untracked file's preview, the file stands for it
+            (_, Some(SectionType::UntrackedFile { path })) => CursorContext::UntrackedFile {
+                path: path.clone(),
+                line_index: cursor_position,
+            },
             (LineContent::SectionHeader { .. }, Some(section)) => CursorContext::SectionHeader {
                 section: section.clone(),
                 line_index: cursor_position,
@@ -193,7 +198,7 @@ fn find_prev_section(lines: &[Line], from_index: usize) -> Option<usize> {
     for i in (0..from_index).rev() {
         if matches!(
             lines[i].content,
-            LineContent::SectionHeader { .. } | LineContent::UnpulledSectionHeader { .. }
+            LineContent::SectionHeader { .. } | LineContent::RemoteSectionHeader { .. }
         ) {
             return Some(i);
         }
@@ -208,7 +213,7 @@ fn find_next_section(lines: &[Line], from_index: usize) -> usize {
     for (i, line) in lines.iter().enumerate().skip(from_index) {
         if matches!(
             line.content,
-            LineContent::SectionHeader { .. } | LineContent::UnpulledSectionHeader { .. }
+            LineContent::SectionHeader { .. } | LineContent::RemoteSectionHeader { .. }
         ) {
             return i;
         }
@@ -218,7 +223,7 @@ fn find_next_section(lines: &[Line], from_index: usize) -> usize {
     for i in (0..from_index).rev() {
         if matches!(
             lines[i].content,
-            LineContent::SectionHeader { .. } | LineContent::UnpulledSectionHeader { .. }
+            LineContent::SectionHeader { .. } | LineContent::RemoteSectionHeader { .. }
         ) {
             return i;
         }
@@ -236,7 +241,7 @@ fn find_section_header(lines: &[Line], section: &SectionType) -> Option<usize> {
             (LineContent::SectionHeader { .. }, Some(s)) if s == section
         ) || matches!(
             (&line.content, &line.section),
-            (LineContent::UnpulledSectionHeader { .. }, Some(s)) if s == section
+            (LineContent::RemoteSectionHeader { .. }, Some(s)) if s == section
         )
     })
 }
@@ -281,7 +286,13 @@ fn find_adjacent_section_for_type(lines: &[Line], section_type: &SectionType) ->
                 SectionType::UntrackedFiles,
                 SectionType::Info,
             ],
-            vec![SectionType::RecentCommits, SectionType::Unpulled],
+            vec![
+                SectionType::RecentCommits,
+                SectionType::Unpulled,
+                SectionType::UnpulledFromPushRemote,
+                SectionType::UnmergedIntoUpstream,
+                SectionType::UnpushedToPushRemote,
+            ],
         ),
         _ => (vec![], vec![]),
     };
@@ -0,0 +1,440 @@
+use std::collections::HashSet;
+
+use crate::{
+    errors::MagiResult,
+    git::{diff_utils::split_hunks, discard::build_partial_hunk_patch, stage::build_staging_patch},
+    model::{Line, LineContent, UiModel},
+};
+
+/// State for the sequential hunk review (ViewMode::HunkReview), a
+/// `git add -p`-like walk through the unstaged (or staged) hunks.
+///
+/// Decisions are only recorded while reviewing, so any hunk can be
+/// reconsidered. They are turned into patches when the review finishes;
+/// every patch is cut from the diffs taken when the review started, so
+/// applying one doesn't shift the hunks of the others. An edited block only
+/// holds its own changes, which git finds again after the others.
+#[derive(Clone)]
+pub struct HunkReviewState {
+    /// Whether the staged hunks are reviewed; accepting one unstages it
+    pub staged: bool,
+    /// The files with reviewed hunks, in status order
+    pub files: Vec<ReviewFile>,
+    /// The hunks to decide on, file by file
+    pub hunks: Vec<ReviewHunk>,
+    /// Index in `hunks` of the hunk shown
+    pub current: usize,
+    /// The status view to go back to when the review finishes
+    pub return_ui_model: UiModel,
+}
+
+/// A file with hunks under review
+#[derive(Debug, Clone)]
+pub struct ReviewFile {
+    pub path: String,
+    /// The file's line in the status view, shown above each of its hunks
+    pub line: Line,
+    /// The file's diff when the review started, which patches are cut from
+    pub diff: String,
+}
+
+/// A hunk under review, or a block of changes of one after splitting it
+#[derive(Debug, Clone)]
+pub struct ReviewHunk {
+    /// Index of the hunk's file in [`HunkReviewState::files`]
+    pub file: usize,
+    pub hunk_index: usize,
+    /// Indices of the hunk's diff lines in the whole hunk (not counting the
+    /// hunk header); a block only has some of them
+    pub line_indices: Vec<usize>,
+    /// The hunk header and the diff lines shown
+    pub lines: Vec<Line>,
+    pub decision: Option<ReviewDecision>,
+}
+
+/// What to do with a reviewed hunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewDecision {
+    /// Stage the hunk, or unstage it when reviewing staged hunks
+    Accept,
+    /// Leave the hunk as it is
+    Skip,
+    /// Discard the hunk from the working tree
+    Discard,
+    /// Stage this patch, the hunk as edited by the user
+    Edited(String),
+}
+
+/// A patch applying decisions of the review
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewPatch {
+    /// Stage a whole hunk
+    StageHunk { path: String, hunk_index: usize },
+    /// Unstage a whole hunk
+    UnstageHunk { path: String, hunk_index: usize },
+    /// Discard a whole hunk from the working tree
+    DiscardHunk { path: String, hunk_index: usize },
+    /// Applied to the index
+    Stage(String),
+    /// Applied to the index in reverse
+    Unstage(String),
+    /// Applied to the working tree in reverse
+    Discard(String),
+}
+
+impl HunkReviewState {
+    pub fn new(
+        staged: bool,
+        files: Vec<ReviewFile>,
+        hunks: Vec<ReviewHunk>,
+        return_ui_model: UiModel,
+    ) -> Self {
+        Self {
+            staged,
+            files,
+            hunks,
+            current: 0,
+            return_ui_model,
+        }
+    }
+
+    pub fn current_hunk(&self) -> Option<&ReviewHunk> {
+        self.hunks.get(self.current)
+    }
+
+    /// Records the decision on the current hunk and moves on to the next.
+    pub fn decide(&mut self, decision: ReviewDecision) {
+        if let Some(hunk) = self.hunks.get_mut(self.current) {
+            hunk.decision = Some(decision);
+            self.current += 1;
+        }
+    }
+
+    /// Whether the last hunk has been decided on
+    pub fn is_finished(&self) -> bool {
+        self.current >= self.hunks.len()
+    }
+
+    /// Goes back to the previous hunk. Returns false on the first one.
+    pub fn previous_hunk(&mut self) -> bool {
+        if self.current == 0 {
+            return false;
+        }
+        self.current -= 1;
+        true
+    }
+
+    /// Moves on to the next hunk without deciding on the current one.
+    /// Returns false on the last one.
+    pub fn next_hunk(&mut self) -> bool {
+        if self.current + 1 >= self.hunks.len() {
+            return false;
+        }
+        self.current += 1;
+        true
+    }
+
+    /// Replaces the current hunk with its blocks of changes, each reviewed
+    /// on its own. Returns false when it has a single block.
+    pub fn split_current(&mut self) -> bool {
+        let Some(hunk) = self.hunks.get(self.current) else {
+            return false;
+        };
+        let Some(section) = hunk.lines.first().and_then(|line| line.section.clone()) else {
+            return false;
+        };
+
+        let mut parts: Vec<ReviewHunk> = Vec::new();
+        let mut line_indices = hunk.line_indices.iter();
+        for line in split_hunks(hunk.lines.clone(), &HashSet::from([section])) {
+            if matches!(line.content, LineContent::DiffHunk(_)) {
+                parts.push(ReviewHunk {
+                    line_indices: Vec::new(),
+                    lines: Vec::new(),
+                    decision: None,
+                    ..hunk.clone()
+                });
+            }
+            let Some(part) = parts.last_mut() else {
+                continue;
+            };
+            if matches!(line.content, LineContent::DiffLine(_)) {
+                part.line_indices.extend(line_indices.next());
+            }
+            part.lines.push(line);
+        }
+
+        if parts.len() < 2 {
+            return false;
+        }
+        self.hunks.splice(self.current..=self.current, parts);
+        true
+    }
+
+    /// The 1-based number of the current hunk's file, and the number of files
+    pub fn file_progress(&self) -> (usize, usize) {
+        let file = self
+            .current_hunk()
+            .map_or(self.files.len(), |hunk| hunk.file + 1);
+        (file, self.files.len())
+    }
+
+    /// The number of hunks with a decision matching `is_match`
+    pub fn count(&self, is_match: impl Fn(&ReviewDecision) -> bool) -> usize {
+        self.hunks
+            .iter()
+            .filter(|hunk| hunk.decision.as_ref().is_some_and(&is_match))
+            .count()
+    }
+
+    /// The patches applying the decisions, in the order to apply them. The
+    /// blocks of a split hunk with the same decision share a patch, and
+    /// each file's hunks go bottom-up so a patch never moves the lines of
+    /// the ones after it. Hunks staged, unstaged or discarded as a whole
+    /// are left to the functions doing that in the status view.
+    pub fn patches(&self) -> MagiResult<Vec<ReviewPatch>> {
+        let mut patches = Vec::new();
+        for (file_index, file) in self.files.iter().enumerate() {
+            let mut hunk_indices: Vec<usize> = self
+                .hunks
+                .iter()
+                .filter(|hunk| hunk.file == file_index)
+                .map(|hunk| hunk.hunk_index)
+                .collect();
+            hunk_indices.dedup();
+
+            for hunk_index in hunk_indices.into_iter().rev() {
+                let parts: Vec<&ReviewHunk> = self
+                    .hunks
+                    .iter()
+                    .filter(|hunk| hunk.file == file_index && hunk.hunk_index == hunk_index)
+                    .collect();
+                // A hunk decided on as a whole is applied by its index, which
+                // going bottom-up keeps valid
+                let whole = |decision: ReviewDecision| {
+                    parts
+                        .iter()
+                        .all(|part| part.decision.as_ref() == Some(&decision))
+                };
+                let (path, hunk_index) = (file.path.clone(), hunk_index);
+                if whole(ReviewDecision::Accept) {
+                    patches.push(if self.staged {
+                        ReviewPatch::UnstageHunk { path, hunk_index }
+                    } else {
+                        ReviewPatch::StageHunk { path, hunk_index }
+                    });
+                    continue;
+                }
+                if whole(ReviewDecision::Discard) {
+                    patches.push(ReviewPatch::DiscardHunk { path, hunk_index });
+                    continue;
+                }
+
+                let lines_with = |decision: ReviewDecision| -> Vec<usize> {
+                    parts
+                        .iter()
+                        .filter(|part| part.decision.as_ref() == Some(&decision))
+                        .flat_map(|part| part.line_indices.iter().copied())
+                        .collect()
+                };
+
+                let accepted = lines_with(ReviewDecision::Accept);
+                if !accepted.is_empty() {
+                    patches.push(if self.staged {
+                        ReviewPatch::Unstage(build_partial_hunk_patch(
+                            &file.diff, hunk_index, &accepted,
+                        )?)
+                    } else {
+                        ReviewPatch::Stage(build_staging_patch(&file.diff, hunk_index, &accepted)?)
+                    });
+                }
+                for part in &parts {
+                    if let Some(ReviewDecision::Edited(patch)) = &part.decision {
+                        patches.push(ReviewPatch::Stage(patch.clone()));
+                    }
+                }
+                let discarded = lines_with(ReviewDecision::Discard);
+                if !discarded.is_empty() {
+                    patches.push(ReviewPatch::Discard(build_partial_hunk_patch(
+                        &file.diff, hunk_index, &discarded,
+                    )?));
+                }
+            }
+        }
+        Ok(patches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{DiffHunk, DiffLine, DiffLineType, SectionType};
+
+    const DIFF: &str = "\
+diff --git a/f.txt b/f.txt
+--- a/f.txt
++++ b/f.txt
+@@ -1,4 +1,4 @@
+ 1
+-2
++two
+ 3
+-4
++four
+@@ -10,2 +10,2 @@
+ 10
+-11
++eleven
+";
+
+    fn line(content: LineContent, hunk_index: usize) -> Line {
+        Line {
+            content,
+            section: Some(SectionType::UnstagedHunk {
+                path: "f.txt".to_string(),
+                hunk_index,
+            }),
+        }
+    }
+
+    fn review_hunk(hunk_index: usize, header: &str, diff: &[(DiffLineType, &str)]) -> ReviewHunk {
+        let mut lines = vec![line(
+            LineContent::DiffHunk(DiffHunk {
+                header: header.to_string(),
+                hunk_index,
+            }),
+            hunk_index,
+        )];
+        lines.extend(diff.iter().map(|(line_type, content)| {
+            line(
+                LineContent::DiffLine(DiffLine {
+                    content: content.to_string(),
+                    line_type: line_type.clone(),
+                    refined: Vec::new(),
+                }),
+                hunk_index,
+            )
+        }));
+        ReviewHunk {
+            file: 0,
+            hunk_index,
+            line_indices: (0..diff.len()).collect(),
+            lines,
+            decision: None,
+        }
+    }
+
+    fn review() -> HunkReviewState {
+        use DiffLineType::{Addition, Context, Deletion};
+        let file = ReviewFile {
+            path: "f.txt".to_string(),
+            line: Line {
+                content: LineContent::EmptyLine,
+                section: None,
+            },
+            diff: DIFF.to_string(),
+        };
+        let hunks = vec![
+            review_hunk(
+                0,
+                "@@ -1,4 +1,4 @@",
+                &[
+                    (Context, "1"),
+                    (Deletion, "2"),
+                    (Addition, "two"),
+                    (Context, "3"),
+                    (Deletion, "4"),
+                    (Addition, "four"),
+                ],
+            ),
+            review_hunk(
+                1,
+                "@@ -10,2 +10,2 @@",
+                &[(Context, "10"), (Deletion, "11"), (Addition, "eleven")],
+            ),
+        ];
+        HunkReviewState::new(false, vec![file], hunks, UiModel::default())
+    }
+
+    #[test]
+    fn test_decide_moves_on_and_previous_reconsiders() {
+        let mut state = review();
+
+        state.decide(ReviewDecision::Accept);
+        assert_eq!(state.current, 1);
+        assert!(!state.next_hunk());
+
+        assert!(state.previous_hunk());
+        state.decide(ReviewDecision::Skip);
+        assert_eq!(state.hunks[0].decision, Some(ReviewDecision::Skip));
+        state.decide(ReviewDecision::Skip);
+        assert!(state.is_finished());
+    }
+
+    #[test]
+    fn test_split_current_reviews_blocks_on_their_own() {
+        let mut state = review();
+
+        assert!(state.split_current());
+
+        assert_eq!(state.hunks.len(), 3);
+        assert_eq!(state.hunks[0].line_indices, vec![0, 1, 2]);
+        assert_eq!(state.hunks[1].line_indices, vec![3, 4, 5]);
+        assert_eq!(state.hunks[1].hunk_index, 0);
+        // A single block can't be split further
+        state.current = 2;
+        assert!(!state.split_current());
+    }
+
+    #[test]
+    fn test_patches_go_bottom_up_and_join_blocks() {
+        let mut state = review();
+        state.split_current();
+        state.decide(ReviewDecision::Accept);
+        state.decide(ReviewDecision::Discard);
+        state.decide(ReviewDecision::Accept);
+
+        let patches = state.patches().unwrap();
+
+        let header = "diff --git a/f.txt b/f.txt\n--- a/f.txt\n+++ b/f.txt\n";
+        assert_eq!(
+            patches,
+            vec![
+                ReviewPatch::StageHunk {
+                    path: "f.txt".to_string(),
+                    hunk_index: 1
+                },
+                ReviewPatch::Stage(format!("{}@@ -1,4 +1,4 @@\n 1\n-2\n+two\n 3\n 4\n", header)),
+                ReviewPatch::Discard(format!(
+                    "{}@@ -1,4 +1,4 @@\n 1\n two\n 3\n-4\n+four\n",
+                    header
+                )),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_whole_hunk_decisions_are_applied_by_index() {
+        let mut state = review();
+        state.split_current();
+        state.decide(ReviewDecision::Discard);
+        state.decide(ReviewDecision::Discard);
+        state.decide(ReviewDecision::Skip);
+
+        assert_eq!(
+            state.patches().unwrap(),
+            vec![ReviewPatch::DiscardHunk {
+                path: "f.txt".to_string(),
+                hunk_index: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_skipped_and_undecided_hunks_have_no_patches() {
+        let mut state = review();
+        state.decide(ReviewDecision::Skip);
+
+        assert!(state.patches().unwrap().is_empty());
+    }
+}
@@ -57,6 +57,28 @@ impl InputField {
         self.cursor = 0;
     }
 
+    /// The length of the text in characters.
+    pub fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Moves the cursor to the given character index (clamped to the end).
+    pub fn set_cursor(&mut self, cursor: usize) {
+        self.cursor = cursor.min(self.char_count());
+    }
+
+    /// Removes and returns the text after the cursor, leaving the cursor at
+    /// the new end of the text.
+    pub fn split_off_at_cursor(&mut self) -> String {
+        let byte = self.byte_at(self.cursor);
+        self.text.split_off(byte)
+    }
+
+    /// Appends text at the end without moving the cursor.
+    pub fn push_str(&mut self, s: &str) {
+        self.text.push_str(s);
+    }
+
     /// Applies an edit operation. Returns `true` if the text changed
     /// (cursor movement alone returns `false`).
     pub fn apply(&mut self, op: EditOp) -> bool {
@@ -100,10 +122,6 @@ impl InputField {
         }
     }
 
-    fn char_count(&self) -> usize {
-        self.text.chars().count()
-    }
-
     /// Byte offset of the given character index (clamped to the end).
     fn byte_at(&self, char_idx: usize) -> usize {
         self.text
@@ -176,6 +194,17 @@ mod tests {
         assert_eq!(f.cursor(), 5);
     }
 
+    #[test]
+    fn split_off_at_cursor_keeps_head() {
+        let mut f = field("héllo", 2);
+        assert_eq!(f.split_off_at_cursor(), "llo");
+        assert_eq!(f.as_str(), "hé");
+        assert_eq!(f.cursor(), 2);
+        f.push_str("y");
+        assert_eq!(f.as_str(), "héy");
+        assert_eq!(f.cursor(), 2);
+    }
+
     #[test]
     fn insert_mid_text() {
         let mut f = field("hllo", 1);
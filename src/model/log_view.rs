@@ -1,4 +1,4 @@
-use crate::git::CommitRef;
+use crate::git::{CommitRef, signing::SignatureStatus};
 
 /// A single entry in the git log view
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -15,6 +15,9 @@ pub struct LogEntry {
     pub time: Option<String>,
     /// The commit message subject
     pub message: Option<String>,
+    /// Signature verification status (`%G?`), `None` when unsigned or when
+    /// the log doesn't show signatures
+    pub signature: Option<SignatureStatus>,
 }
 
 impl LogEntry {
@@ -34,6 +37,7 @@ impl LogEntry {
             author,
             time,
             message,
+            signature: None,
         }
     }
 
@@ -46,6 +50,7 @@ impl LogEntry {
             author: None,
             time: None,
             message: None,
+            signature: None,
         }
     }
 
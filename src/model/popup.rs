@@ -2,6 +2,7 @@ pub use super::select_popup::{OnSelect, OptionsSource, SelectPopupState, SelectR
 
 use crate::git::credential::CredentialType;
 use crate::i18n;
+use crate::model::arguments::ValueArgument;
 use crate::model::{InputField, LogEntry};
 use crate::msg::StashType;
 
@@ -49,6 +50,13 @@ pub enum ConfirmAction {
         remote_tags: Vec<String>,
         remote: String,
     },
+    /// Absorb staged hunks into fixup commits, then optionally autosquash
+    Absorb {
+        plan: crate::git::absorb::AbsorbPlan,
+        autosquash: bool,
+    },
+    /// Apply the decisions of a hunk review that discards hunks
+    ApplyHunkReview,
 }
 
 /// State for the credential input popup.
@@ -160,6 +168,13 @@ pub enum InputContext {
     },
     /// Creating a new tag (name input; target picked next)
     CreateTag,
+    /// Entering the command of an `exec` line in the rebase todo editor
+    RebaseExec {
+        /// The todo entry the command belongs to (or is inserted after)
+        index: usize,
+        /// Whether the command replaces that `exec` line's command
+        replace: bool,
+    },
     /// Naming a release tag for HEAD (prefilled with the suggested name)
     TagRelease {
         /// The previous (highest) release tag, or `None` for the first release
@@ -167,6 +182,14 @@ pub enum InputContext {
     },
     /// Entering the mainline parent number for a revert of a merge commit
     RevertMainline { revert_state: RevertPopupState },
+    /// Entering the date to reshelve HEAD to (prefilled with `now`)
+    Reshelve,
+    /// Entering the value of a popup argument such as `--exec=`; the
+    /// command popup is reopened afterwards
+    ArgumentValue {
+        argument: ValueArgument,
+        return_to: Box<PopupContentCommand>,
+    },
 }
 
 /// State for text input popups (e.g., new branch name)
@@ -218,6 +241,9 @@ impl InputPopupState {
                 None => t.input_first_release_tag.to_string(),
             },
             InputContext::RevertMainline { .. } => t.input_revert_mainline.to_string(),
+            InputContext::Reshelve => t.input_reshelve_date.to_string(),
+            InputContext::RebaseExec { .. } => t.input_rebase_exec.to_string(),
+            InputContext::ArgumentValue { argument, .. } => argument.description().to_string(),
         }
     }
 }
@@ -336,6 +362,68 @@ pub struct RebasePopupState {
 pub struct CommitPopupState {
     /// Author override set via the `-A` argument (`--author=<value>`)
     pub author: Option<String>,
+    /// Trailers picked via `-C`/`-r`, in the order they were added
+    pub trailers: Vec<(CommitTrailer, String)>,
+}
+
+impl CommitPopupState {
+    /// Adds `value` as a `kind` trailer, or removes it if already present.
+    pub fn toggle_trailer(&mut self, kind: CommitTrailer, value: String) {
+        let len = self.trailers.len();
+        self.trailers.retain(|(k, v)| !(*k == kind && *v == value));
+        if self.trailers.len() == len {
+            self.trailers.push((kind, value));
+        }
+    }
+
+    /// The values of all `kind` trailers.
+    pub fn trailer_values(&self, kind: CommitTrailer) -> Vec<&str> {
+        self.trailers
+            .iter()
+            .filter(|(k, _)| *k == kind)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    /// The `git commit` flags for the author override and trailers.
+    pub fn flags(&self) -> Vec<String> {
+        self.author
+            .iter()
+            .map(|author| format!("--author={}", author))
+            .chain(
+                self.trailers
+                    .iter()
+                    .map(|(kind, value)| format!("--trailer={}: {}", kind.token(), value)),
+            )
+            .collect()
+    }
+}
+
+/// A person trailer that can be added to a commit message from the Commit
+/// popup. Applied with `git commit --trailer`, so git's `trailer.*` config
+/// decides where it goes and whether duplicates are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommitTrailer {
+    CoAuthoredBy,
+    ReviewedBy,
+}
+
+impl CommitTrailer {
+    pub fn token(&self) -> &'static str {
+        match self {
+            CommitTrailer::CoAuthoredBy => "Co-authored-by",
+            CommitTrailer::ReviewedBy => "Reviewed-by",
+        }
+    }
+}
+
+/// Which Commit popup argument an author picked from history fills in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitAuthorTarget {
+    /// The `--author` override
+    Author,
+    /// A trailer naming someone other than the author
+    Trailer(CommitTrailer),
 }
 
 /// State for the Revert popup
@@ -365,6 +453,18 @@ pub struct MergePopupState {
     pub in_progress: bool,
 }
 
+/// State for the Resolve (conflict) popup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvePopupState {
+    /// The conflicted (or already resolved) file
+    pub path: String,
+    /// Index of the conflict region under the cursor, if any
+    pub conflict: Option<usize>,
+    /// Whether the file still has unmerged index entries; a file that was
+    /// resolved already only offers restoring its conflict
+    pub unmerged: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PopupContentCommand {
     Commit(CommitPopupState),
@@ -379,8 +479,10 @@ pub enum PopupContentCommand {
     Revert(RevertPopupState),
     Merge(MergePopupState),
     Apply(ApplyPopupState),
+    Resolve(ResolvePopupState),
     Tag,
     Worktree,
+    Diff,
     Select(SelectPopupState),
 }
 
@@ -2,9 +2,11 @@ use crate::git::rebase::{RebaseAction, RebaseTodoEntry};
 
 /// State for the interactive rebase todo editor (ViewMode::RebaseTodo).
 ///
-/// Holds the todo entries being edited plus an undo stack. The state
-/// guarantees that a fold action (squash/fixup) never ends up as the first
-/// entry, since git rejects such a todo list.
+/// Holds the todo entries being edited plus an undo stack. Every edit is
+/// validated so the list stays acceptable to git: a fold action
+/// (squash/fixup) never ends up as the first entry, `exec` lines always have
+/// a command, and a `reset`/`merge` never comes before the `label` it refers
+/// to.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RebaseTodoState {
     /// The selected base commit (the oldest commit included in the rebase)
@@ -13,6 +15,9 @@ pub struct RebaseTodoState {
     pub base_has_parent: bool,
     /// The todo entries, oldest commit first (same order as the todo file)
     pub entries: Vec<RebaseTodoEntry>,
+    /// Whether this is the remaining todo of a stopped rebase (`git rebase
+    /// --edit-todo`); confirming writes it back instead of starting a rebase
+    pub in_progress: bool,
     /// Vim-style command line buffer; `Some` while the user is typing after
     /// `:` (e.g. `:wq` to confirm the rebase)
     pub command_input: Option<String>,
@@ -26,54 +31,93 @@ impl RebaseTodoState {
             base,
             base_has_parent,
             entries,
+            in_progress: false,
             command_input: None,
             undo_stack: Vec::new(),
         }
     }
 
+    /// State for editing the remaining todo of a stopped rebase. There is no
+    /// base to rebase from: the entries are written back as they are.
+    pub fn for_stopped_rebase(entries: Vec<RebaseTodoEntry>) -> Self {
+        Self {
+            in_progress: true,
+            ..Self::new(String::new(), true, entries)
+        }
+    }
+
     /// Sets the action of the entry at `index`.
     /// Returns false when the change is rejected (fold action on the first
-    /// entry) or `index` is out of range.
+    /// entry of a new rebase, or the entry is not a commit line) or `index`
+    /// is out of range.
     pub fn set_action(&mut self, index: usize, action: RebaseAction) -> bool {
-        if index >= self.entries.len() || (index == 0 && action.is_fold()) {
-            return false;
+        match self.entries.get(index) {
+            Some(entry) if entry.action.is_commit() && action.is_commit() => {}
+            _ => return false,
         }
         if self.entries[index].action == action {
             return true;
         }
-        self.snapshot();
-        self.entries[index].action = action;
-        true
+        self.apply(|entries| entries[index].action = action)
     }
 
     /// Swaps the entry at `index` with the one above it.
-    /// Returns false at the top boundary, or when the swap would put a fold
-    /// action first.
+    /// Returns false at the top boundary, or when the swap would leave an
+    /// invalid list (e.g. a fold action first).
     pub fn move_entry_up(&mut self, index: usize) -> bool {
         if index == 0 || index >= self.entries.len() {
             return false;
         }
-        if index == 1 && self.entries[index].action.is_fold() {
-            return false;
-        }
-        self.snapshot();
-        self.entries.swap(index, index - 1);
-        true
+        self.apply(|entries| entries.swap(index, index - 1))
     }
 
     /// Swaps the entry at `index` with the one below it.
-    /// Returns false at the bottom boundary, or when the swap would put a
-    /// fold action first.
+    /// Returns false at the bottom boundary, or when the swap would leave an
+    /// invalid list (e.g. a fold action first).
     pub fn move_entry_down(&mut self, index: usize) -> bool {
         if index + 1 >= self.entries.len() {
             return false;
         }
-        if index == 0 && self.entries[index + 1].action.is_fold() {
-            return false;
+        self.apply(|entries| entries.swap(index, index + 1))
+    }
+
+    /// Inserts an `exec <command>` line after `index` (at the top when the
+    /// list is empty). Returns false for an empty command.
+    pub fn insert_exec(&mut self, index: usize, command: &str) -> bool {
+        let entry = RebaseTodoEntry::instruction(RebaseAction::Exec, command.trim());
+        self.insert_after(index, entry)
+    }
+
+    /// Inserts a `break` line after `index`.
+    pub fn insert_break(&mut self, index: usize) -> bool {
+        self.insert_after(index, RebaseTodoEntry::instruction(RebaseAction::Break, ""))
+    }
+
+    /// Replaces the command of the `exec` line at `index`.
+    /// Returns false when the entry is not an `exec` line or the command is
+    /// empty.
+    pub fn set_exec_command(&mut self, index: usize, command: &str) -> bool {
+        match self.entries.get(index) {
+            Some(entry) if entry.action == RebaseAction::Exec => {}
+            _ => return false,
         }
-        self.snapshot();
-        self.entries.swap(index, index + 1);
-        true
+        if self.entries[index].message == command.trim() {
+            return true;
+        }
+        self.apply(|entries| entries[index].message = command.trim().to_string())
+    }
+
+    /// Removes the instruction line at `index`. Only `exec`, `break` and
+    /// `update-ref` lines can be removed: commits are dropped instead, and
+    /// the `label`/`reset`/`merge` lines carry the shape of the history.
+    pub fn remove_entry(&mut self, index: usize) -> bool {
+        match self.entries.get(index) {
+            Some(entry) if is_removable(entry.action) => {}
+            _ => return false,
+        }
+        self.apply(|entries| {
+            entries.remove(index);
+        })
     }
 
     /// Restores the most recent snapshot. Returns false when there is
@@ -88,9 +132,79 @@ impl RebaseTodoState {
         }
     }
 
-    fn snapshot(&mut self) {
-        self.undo_stack.push(self.entries.clone());
+    fn insert_after(&mut self, index: usize, entry: RebaseTodoEntry) -> bool {
+        let position = (index + 1).min(self.entries.len());
+        self.apply(|entries| entries.insert(position, entry))
+    }
+
+    /// Applies `edit` to a copy of the entries and keeps the result only if
+    /// it is still a valid todo list, pushing the previous entries for undo.
+    fn apply(&mut self, edit: impl FnOnce(&mut Vec<RebaseTodoEntry>)) -> bool {
+        let mut entries = self.entries.clone();
+        edit(&mut entries);
+        if !is_valid(&entries, self.in_progress) {
+            return false;
+        }
+        self.undo_stack
+            .push(std::mem::replace(&mut self.entries, entries));
+        true
+    }
+}
+
+/// Returns true if the action marks a line that can be removed from the
+/// todo rather than dropped.
+pub fn is_removable(action: RebaseAction) -> bool {
+    matches!(
+        action,
+        RebaseAction::Exec | RebaseAction::Break | RebaseAction::UpdateRef
+    )
+}
+
+/// Checks the rules git enforces (or trips over) when running a todo list.
+/// In a stopped rebase the already applied commits precede the list, so a
+/// leading fold has something to fold into. Instructions like `exec` or
+/// `label` and dropped commits leave nothing to fold into, so they are
+/// skipped.
+fn is_valid(entries: &[RebaseTodoEntry], in_progress: bool) -> bool {
+    if !in_progress
+        && entries
+            .iter()
+            .find(|entry| entry.action.is_commit() && entry.action != RebaseAction::Drop)
+            .is_some_and(|entry| entry.action.is_fold())
+    {
+        return false;
+    }
+    if entries
+        .iter()
+        .any(|entry| entry.action == RebaseAction::Exec && entry.message.trim().is_empty())
+    {
+        return false;
+    }
+
+    // A reset/merge may name a label defined by this todo, or something git
+    // resolves on its own (a commit, `[new root]`). Only the former has to
+    // come after its `label` line.
+    let defined: Vec<&str> = entries
+        .iter()
+        .filter(|entry| entry.action == RebaseAction::Label)
+        .filter_map(RebaseTodoEntry::label)
+        .collect();
+    let mut seen: Vec<&str> = Vec::new();
+    for entry in entries {
+        match entry.action {
+            RebaseAction::Label => seen.extend(entry.label()),
+            RebaseAction::Reset | RebaseAction::Merge => {
+                if let Some(label) = entry.label()
+                    && defined.contains(&label)
+                    && !seen.contains(&label)
+                {
+                    return false;
+                }
+            }
+            _ => {}
+        }
     }
+    true
 }
 
 #[cfg(test)]
@@ -209,4 +323,173 @@ mod tests {
         assert!(state.set_action(0, RebaseAction::Pick));
         assert!(!state.undo());
     }
+
+    fn instruction(action: RebaseAction, argument: &str) -> RebaseTodoEntry {
+        RebaseTodoEntry::instruction(action, argument)
+    }
+
+    /// The todo git generates for `--rebase-merges` over one merged branch.
+    fn rebase_merges_state() -> RebaseTodoState {
+        let merge = RebaseTodoEntry {
+            action: RebaseAction::Merge,
+            hash: "hashm".to_string(),
+            message: "feature # Merge branch 'feature'".to_string(),
+        };
+        let entries = vec![
+            instruction(RebaseAction::Label, "onto"),
+            instruction(RebaseAction::Reset, "onto"),
+            entry("hash0", "Feature commit"),
+            instruction(RebaseAction::Label, "feature"),
+            instruction(RebaseAction::Reset, "onto"),
+            entry("hash1", "Main commit"),
+            merge,
+        ];
+        RebaseTodoState::new("base".to_string(), true, entries)
+    }
+
+    #[test]
+    fn test_stopped_rebase_allows_fold_on_first_entry() {
+        let mut state = RebaseTodoState::for_stopped_rebase(vec![entry("hash0", "Commit 0")]);
+        assert!(state.in_progress);
+        assert!(state.set_action(0, RebaseAction::Fixup));
+    }
+
+    #[test]
+    fn test_set_action_fixup_variants_are_folds() {
+        let mut state = state_with_entries(2);
+        assert!(!state.set_action(0, RebaseAction::FixupKeepMessage));
+        assert!(!state.set_action(0, RebaseAction::FixupEditMessage));
+        assert!(state.set_action(1, RebaseAction::FixupKeepMessage));
+        assert_eq!(state.entries[1].action, RebaseAction::FixupKeepMessage);
+    }
+
+    #[test]
+    fn test_insert_exec_after_index() {
+        let mut state = state_with_entries(2);
+        assert!(state.insert_exec(0, " cargo test "));
+        assert_eq!(state.entries.len(), 3);
+        assert_eq!(
+            state.entries[1],
+            instruction(RebaseAction::Exec, "cargo test")
+        );
+        assert_eq!(state.entries[2].hash, "hash1");
+    }
+
+    #[test]
+    fn test_insert_exec_rejects_empty_command() {
+        let mut state = state_with_entries(2);
+        assert!(!state.insert_exec(0, "  "));
+        assert_eq!(state.entries.len(), 2);
+        assert!(!state.undo());
+    }
+
+    #[test]
+    fn test_insert_break_at_end() {
+        let mut state = state_with_entries(2);
+        assert!(state.insert_break(1));
+        assert_eq!(state.entries[2].action, RebaseAction::Break);
+    }
+
+    #[test]
+    fn test_set_exec_command_only_edits_exec_lines() {
+        let mut state = state_with_entries(2);
+        state.insert_exec(0, "make");
+        assert!(state.set_exec_command(1, "make check"));
+        assert_eq!(state.entries[1].message, "make check");
+        assert!(!state.set_exec_command(1, ""));
+        assert!(!state.set_exec_command(0, "make"));
+        assert_eq!(state.entries[0].message, "Commit 0");
+    }
+
+    #[test]
+    fn test_set_action_rejects_instruction_lines() {
+        let mut state = state_with_entries(2);
+        state.insert_break(0);
+        assert!(!state.set_action(1, RebaseAction::Pick));
+        assert!(!state.set_action(0, RebaseAction::Exec));
+        assert_eq!(state.entries[1].action, RebaseAction::Break);
+    }
+
+    #[test]
+    fn test_remove_entry_only_removes_instructions() {
+        let mut state = state_with_entries(2);
+        state.insert_break(0);
+        assert!(!state.remove_entry(0));
+        assert!(state.remove_entry(1));
+        assert_eq!(state.entries.len(), 2);
+
+        assert!(state.undo());
+        assert_eq!(state.entries[1].action, RebaseAction::Break);
+    }
+
+    #[test]
+    fn test_fold_after_instruction_is_allowed() {
+        let mut state = state_with_entries(2);
+        state.insert_break(0);
+        // The break sits between the two commits, the fold still has a
+        // commit before it
+        assert!(state.set_action(2, RebaseAction::Fixup));
+    }
+
+    #[test]
+    fn test_move_rejects_fold_before_first_commit_via_instruction() {
+        let mut state = state_with_entries(2);
+        state.set_action(1, RebaseAction::Squash);
+        state.insert_exec(0, "make");
+        // [pick, exec, squash]: the squash may pass the exec but not the pick
+        assert!(state.move_entry_up(2));
+        assert!(!state.move_entry_up(1));
+    }
+
+    #[test]
+    fn test_fold_needs_a_commit_before_it_not_just_an_instruction() {
+        let mut state = state_with_entries(2);
+        state.set_action(1, RebaseAction::Fixup);
+        state.insert_exec(0, "make");
+        // [exec, pick, fixup]: the fixup may not pass the pick
+        assert!(state.move_entry_down(0));
+        assert!(!state.move_entry_up(2));
+        // Nor may the commit before it be dropped
+        assert!(!state.set_action(1, RebaseAction::Drop));
+    }
+
+    #[test]
+    fn test_rebase_merges_lines_are_kept_in_label_order() {
+        let mut state = rebase_merges_state();
+        // `reset onto` cannot move above `label onto`
+        assert!(!state.move_entry_up(1));
+        // The merge cannot move above the `label feature` it merges
+        assert!(state.move_entry_up(6));
+        assert!(state.move_entry_up(5));
+        assert!(!state.move_entry_up(4));
+        assert_eq!(state.entries[4].action, RebaseAction::Merge);
+    }
+
+    #[test]
+    fn test_rebase_merges_structure_lines_cannot_be_removed() {
+        let mut state = rebase_merges_state();
+        assert!(!state.remove_entry(0));
+        assert!(!state.remove_entry(1));
+        assert!(!state.remove_entry(6));
+        assert!(!state.set_action(6, RebaseAction::Drop));
+    }
+
+    #[test]
+    fn test_update_ref_line_can_be_removed() {
+        let mut state = state_with_entries(1);
+        state
+            .entries
+            .push(instruction(RebaseAction::UpdateRef, "refs/heads/feature"));
+        assert!(state.remove_entry(1));
+        assert_eq!(state.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_reset_to_unlabelled_name_is_valid() {
+        let mut state = state_with_entries(1);
+        state
+            .entries
+            .insert(0, instruction(RebaseAction::Reset, "[new root]"));
+        assert!(state.move_entry_down(0));
+    }
 }
@@ -1,4 +1,6 @@
+use crate::git::signing::SigningKey;
 use crate::model::InputField;
+use crate::model::popup::{CommitAuthorTarget, CommitPopupState, PopupContentCommand};
 use crate::msg::{FixupType, ResetMode};
 
 /// Result returned when select popup closes
@@ -142,6 +144,10 @@ pub enum OnSelect {
     LogOther,
     /// Select a branch/ref to show the reflog for
     ReflogOther,
+    /// Select a revision to visit a file at (step 1 of 2)
+    BlobRevision,
+    /// Select a file to visit (step 2 of 2, carries the chosen revision)
+    BlobFile { revision: String },
     /// Select a commit to donate (step 1 of 2, no pre-selection)
     DonateCommitPick,
     /// Select a commit to spin out (step 1 of 3, no pre-selection)
@@ -164,8 +170,19 @@ pub enum OnSelect {
     DeleteTag,
     /// Select a remote to prune tags against
     PruneTagsRemotePick,
-    /// Select an author to override the commit author with (`--author=`)
-    CommitAuthor,
+    /// Select an author for the commit popup's `--author=` override or one
+    /// of its trailers (carries the popup state to return to)
+    CommitAuthor {
+        target: CommitAuthorTarget,
+        state: CommitPopupState,
+    },
+    /// Select a key for a popup's `-S` argument. Typed text that matches no
+    /// key is used as the key itself.
+    SigningKey {
+        keys: Vec<SigningKey>,
+        /// The popup to reopen once a key is picked
+        return_to: Box<PopupContentCommand>,
+    },
     /// Select the mainline parent number when reverting a merge commit
     RevertMergeMainline {
         hashes: Vec<String>,
@@ -202,6 +219,8 @@ pub enum OptionsSource {
     Stashes,
     /// Tracked files (get_tracked_files)
     TrackedFiles,
+    /// Files in the tree of a revision (files_at_revision)
+    FilesAtRevision { revision: String },
 }
 
 /// State for the select popup (fuzzy finder style)
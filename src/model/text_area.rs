@@ -0,0 +1,212 @@
+use super::input_field::{EditOp, InputField};
+
+/// A multi-line text buffer with a cursor, made of one [`InputField`] per
+/// line so that every readline-style [`EditOp`] works within a line.
+///
+/// Deleting at a line boundary joins the neighbouring lines and moving past
+/// either end of a line wraps to the adjacent one. There is always at least
+/// one (possibly empty) line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextArea {
+    lines: Vec<InputField>,
+    /// Index of the line holding the cursor
+    row: usize,
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self {
+            lines: vec![InputField::new()],
+            row: 0,
+        }
+    }
+}
+
+impl TextArea {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a buffer with the given text and the cursor at the very start.
+    pub fn from_text(text: &str) -> Self {
+        let lines = text
+            .split('\n')
+            .map(|line| {
+                let mut field = InputField::from_text(line);
+                field.set_cursor(0);
+                field
+            })
+            .collect();
+        Self { lines, row: 0 }
+    }
+
+    /// The whole text, lines joined with `\n`.
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(InputField::as_str)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn lines(&self) -> &[InputField] {
+        &self.lines
+    }
+
+    /// The cursor position as `(row, column)`, column being a character index.
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.lines[self.row].cursor())
+    }
+
+    /// True when the buffer holds nothing but whitespace.
+    pub fn is_blank(&self) -> bool {
+        self.lines
+            .iter()
+            .all(|line| line.as_str().trim().is_empty())
+    }
+
+    /// Applies an edit operation to the current line, joining or wrapping
+    /// across line boundaries where the operation reaches one. Returns `true`
+    /// if the text changed.
+    pub fn apply(&mut self, op: EditOp) -> bool {
+        let line = &self.lines[self.row];
+        let at_start = line.cursor() == 0;
+        let at_end = line.cursor() == line.char_count();
+        let has_prev = self.row > 0;
+        let has_next = self.row + 1 < self.lines.len();
+
+        match op {
+            EditOp::DeleteBackward | EditOp::DeleteWordBackward if at_start && has_prev => {
+                let removed = self.lines.remove(self.row);
+                self.row -= 1;
+                let prev = &mut self.lines[self.row];
+                prev.apply(EditOp::MoveToEnd);
+                prev.push_str(removed.as_str());
+                true
+            }
+            EditOp::DeleteForward | EditOp::DeleteWordForward | EditOp::DeleteToEnd
+                if at_end && has_next =>
+            {
+                let removed = self.lines.remove(self.row + 1);
+                self.lines[self.row].push_str(removed.as_str());
+                true
+            }
+            EditOp::MoveLeft | EditOp::MoveWordLeft if at_start && has_prev => {
+                self.row -= 1;
+                self.lines[self.row].apply(EditOp::MoveToEnd);
+                false
+            }
+            EditOp::MoveRight | EditOp::MoveWordRight if at_end && has_next => {
+                self.row += 1;
+                self.lines[self.row].apply(EditOp::MoveToStart);
+                false
+            }
+            op => self.lines[self.row].apply(op),
+        }
+    }
+
+    /// Splits the current line at the cursor, moving the cursor to the start
+    /// of the new line.
+    pub fn insert_newline(&mut self) {
+        let rest = self.lines[self.row].split_off_at_cursor();
+        let mut field = InputField::from_text(rest);
+        field.set_cursor(0);
+        self.row += 1;
+        self.lines.insert(self.row, field);
+    }
+
+    /// Moves the cursor one line up, keeping the column where possible.
+    /// Returns false on the first line.
+    pub fn move_up(&mut self) -> bool {
+        if self.row == 0 {
+            return false;
+        }
+        let column = self.lines[self.row].cursor();
+        self.row -= 1;
+        self.lines[self.row].set_cursor(column);
+        true
+    }
+
+    /// Moves the cursor one line down, keeping the column where possible.
+    /// Returns false on the last line.
+    pub fn move_down(&mut self) -> bool {
+        if self.row + 1 >= self.lines.len() {
+            return false;
+        }
+        let column = self.lines[self.row].cursor();
+        self.row += 1;
+        self.lines[self.row].set_cursor(column);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EditOp::*, TextArea};
+
+    #[test]
+    fn from_text_round_trips_and_starts_at_top() {
+        let area = TextArea::from_text("Summary\n\nBody");
+        assert_eq!(area.text(), "Summary\n\nBody");
+        assert_eq!(area.lines().len(), 3);
+        assert_eq!(area.cursor(), (0, 0));
+    }
+
+    #[test]
+    fn newline_splits_line_at_cursor() {
+        let mut area = TextArea::from_text("helloworld");
+        for _ in 0..5 {
+            area.apply(MoveRight);
+        }
+        area.insert_newline();
+        assert_eq!(area.text(), "hello\nworld");
+        assert_eq!(area.cursor(), (1, 0));
+    }
+
+    #[test]
+    fn backspace_at_line_start_joins_with_previous() {
+        let mut area = TextArea::from_text("ab\ncd");
+        area.move_down();
+        assert!(area.apply(DeleteBackward));
+        assert_eq!(area.text(), "abcd");
+        assert_eq!(area.cursor(), (0, 2));
+    }
+
+    #[test]
+    fn delete_at_line_end_joins_with_next() {
+        let mut area = TextArea::from_text("ab\ncd");
+        area.apply(MoveToEnd);
+        assert!(area.apply(DeleteForward));
+        assert_eq!(area.text(), "abcd");
+        assert_eq!(area.cursor(), (0, 2));
+    }
+
+    #[test]
+    fn horizontal_moves_wrap_across_lines() {
+        let mut area = TextArea::from_text("ab\ncd");
+        area.apply(MoveToEnd);
+        area.apply(MoveRight);
+        assert_eq!(area.cursor(), (1, 0));
+        area.apply(MoveLeft);
+        assert_eq!(area.cursor(), (0, 2));
+    }
+
+    #[test]
+    fn vertical_moves_clamp_column() {
+        let mut area = TextArea::from_text("long line\nab");
+        area.apply(MoveToEnd);
+        assert!(area.move_down());
+        assert_eq!(area.cursor(), (1, 2));
+        assert!(!area.move_down());
+        assert!(area.move_up());
+        assert_eq!(area.cursor(), (0, 2));
+        assert!(!area.move_up());
+    }
+
+    #[test]
+    fn is_blank_ignores_whitespace() {
+        assert!(TextArea::new().is_blank());
+        assert!(TextArea::from_text(" \n\t").is_blank());
+        assert!(!TextArea::from_text("\nx").is_blank());
+    }
+}
@@ -1,7 +1,11 @@
+use std::path::PathBuf;
+
+use crate::git::absorb::AbsorbPlan;
+use crate::git::conflict::{ConflictCheckout, ConflictSide};
 use crate::i18n;
-use crate::model::arguments::Argument;
+use crate::model::arguments::{Argument, ValueArgument};
 use crate::model::input_field::EditOp;
-use crate::model::popup::PopupContent;
+use crate::model::popup::{CommitAuthorTarget, PopupContent};
 pub use crate::model::select_popup::{OnSelect, OptionsSource};
 
 pub mod update;
@@ -18,6 +22,10 @@ pub enum FixupType {
     Alter,
     /// Create an augment commit (git commit --squash= --edit)
     Augment,
+    /// Create a fixup commit and immediately autosquash it into its target
+    InstantFixup,
+    /// Create a squash commit and immediately autosquash it into its target
+    InstantSquash,
 }
 
 /// Type of log view to display
@@ -154,15 +162,48 @@ pub enum Message {
     /// Toggle section expand/collapse
     ToggleSection,
 
-    /// Open commit in user's default EDITOR
+    /// Commit the staged changes, writing the message in the built-in
+    /// editor or in the user's EDITOR
     Commit,
-    /// Show the author picker for the commit `-A` argument, or clear the
-    /// author override if one is already set
-    ShowCommitAuthorSelect,
+    /// Runs `git commit` with the given flags, opening the user's EDITOR.
+    /// Requires the TUI to be suspended.
+    CommitWithEditor(Vec<String>),
+    /// Show the author picker for the commit `-A` argument (clearing the
+    /// author override if one is already set) or for a `-C`/`-r` trailer
+    ShowCommitAuthorSelect(CommitAuthorTarget),
+    /// Show the signing key picker for a popup's `-S` argument, or clear
+    /// the key if one is already set
+    ShowSigningKeySelect,
+    /// Show the input for a popup argument that takes a value, or clear the
+    /// value if one is already set
+    ShowArgumentValueInput(ValueArgument),
     /// Amend the last commit
     Amend(Vec<String>),
+    /// Runs `git commit --amend` with the given flags, opening the user's
+    /// EDITOR unless `--no-edit` is among them. Requires the TUI to be
+    /// suspended.
+    AmendWithEditor(Vec<String>),
+    /// Add the staged changes to HEAD, keeping its message and dates
+    Extend,
+    /// Show input popup for entering the date to reshelve HEAD to
+    ShowReshelveInput,
+    /// Rewrite the author and committer date of HEAD to the given date
+    Reshelve(String),
+    /// Built-in commit message editor messages
+    CommitEditor(CommitEditorMessage),
     /// Create a fixup or squash commit for the specified commit hash
     FixupCommit(String, FixupType),
+    /// Work out which commit each staged hunk belongs to and show the
+    /// mapping for confirmation
+    ShowAbsorbConfirm {
+        autosquash: bool,
+    },
+    /// Create the fixup commits of a confirmed absorb plan, then optionally
+    /// autosquash them
+    Absorb {
+        plan: AbsorbPlan,
+        autosquash: bool,
+    },
 
     /// Dismiss the current popup
     DismissPopup,
@@ -170,8 +211,19 @@ pub enum Message {
     StageAllModified,
     /// Stage the item under the cursor (or visual selection)
     StageSelected,
+    /// Add the untracked files under the cursor (or visual selection) with
+    /// intent to add, so their content can be staged in parts
+    IntentToAddSelected,
     /// Unstage the item under the cursor (or visual selection)
     UnstageSelected,
+    /// Split the hunk under the cursor into its blocks of changes, each
+    /// staged on its own; joins it again when it is already split
+    SplitHunk,
+    /// Edit the patch of the unstaged hunk under the cursor in the user's
+    /// editor and stage the result. Requires the TUI to be suspended.
+    EditHunk,
+    /// Sequential hunk review messages
+    HunkReview(HunkReviewMessage),
     /// Unstage all staged files
     UnstageAll,
     /// Apply the change under the cursor (or visual selection) to the working
@@ -286,8 +338,13 @@ pub enum Message {
     ShowRebasePopup,
     /// Open the interactive rebase todo editor for `base..HEAD` (base inclusive)
     ShowRebaseTodo(String),
+    /// Open the remaining todo of the stopped rebase in the todo editor
+    /// (`git rebase --edit-todo`)
+    EditRebaseTodo,
     /// Interactive rebase todo editor messages
     RebaseTodo(RebaseTodoMessage),
+    /// Edit a pending entry of the stopped rebase from the "Rebasing" section
+    RebasePending(RebasePendingMessage),
 
     /// Show revert popup
     ShowRevertPopup,
@@ -400,6 +457,11 @@ pub enum Message {
         file: String,
     },
 
+    /// Show the resolve popup for the conflicted file or hunk under the cursor
+    ShowResolvePopup,
+    /// Resolve a conflict in the working tree
+    Resolve(ResolveCommand),
+
     /// Show a select popup
     ShowSelectPopup(ShowSelectPopupConfig),
     /// Show the input popup for entering refspec(s) to push to the given remote
@@ -441,8 +503,27 @@ pub enum Message {
 
     /// Enter preview mode for the commit/stash under cursor
     ShowPreview,
-    /// Exit preview mode and return to previous view
+    /// Exit preview mode (or the blob view) and return to previous view
     ExitPreview,
+    /// Show the contents of a file at a revision (blob view)
+    ShowBlob {
+        revision: String,
+        path: String,
+    },
+    /// Blob view messages
+    Blob(BlobMessage),
+    /// Change the context and whitespace options of the status and preview
+    /// diffs
+    Diff(DiffMessage),
+    /// Visit the file under the cursor: the worktree file at the line of the
+    /// diff line, or in a commit/stash preview the blob at that revision
+    VisitFile,
+    /// Open a file in the user's editor, at the given line.
+    /// Requires the TUI to be suspended.
+    OpenInEditor {
+        file: PathBuf,
+        line: Option<usize>,
+    },
 
     /// Revise (reword) a specific commit via `git commit --fixup=reword:<hash> --edit`
     ReviseCommit(String),
@@ -588,8 +669,22 @@ pub enum RebaseTodoMessage {
     MoveEntryUp,
     /// Move the entry under the cursor down one line
     MoveEntryDown,
+    /// Insert a `break` line below the cursor
+    InsertBreak,
+    /// Open the input for an `exec` command: a new line below the cursor, or
+    /// (with `edit`) the command of the `exec` line under the cursor
+    ShowExecInput { edit: bool },
+    /// Confirmed `exec` command from the input popup; `replace` edits the
+    /// `exec` line at `index` instead of inserting after it
+    Exec {
+        index: usize,
+        replace: bool,
+        command: String,
+    },
     /// Undo the last edit
     Undo,
+    /// Write the edited todo back to the stopped rebase and close the editor
+    Save,
     /// Close the editor without rebasing
     Abort,
     /// Enter vim-style command mode (`:`)
@@ -604,6 +699,97 @@ pub enum RebaseTodoMessage {
     CommandInvalid,
 }
 
+/// Edits to a pending entry of a stopped rebase, identified by the
+/// (abbreviated) hash shown in the "Rebasing" section
+#[derive(PartialEq, Eq, Debug)]
+pub enum RebasePendingMessage {
+    /// Mark the entry `drop`, or `pick` it again if it already is
+    ToggleDrop(String),
+    /// Mark the entry `reword`, or `pick` it again if it already is
+    ToggleReword(String),
+    /// Move the entry above the previous pending commit
+    MoveUp(String),
+    /// Move the entry below the next pending commit
+    MoveDown(String),
+}
+
+/// Messages for the blob view
+#[derive(PartialEq, Eq, Debug)]
+pub enum BlobMessage {
+    /// Show the previous version of the file (the one before the commit that
+    /// made the shown version)
+    Previous,
+    /// Show the next version of the file, on the way to HEAD
+    Next,
+    /// Toggle `git blame` annotations
+    ToggleBlame,
+}
+
+/// Messages for the sequential hunk review, a `git add -p`-like walk through
+/// the hunks
+#[derive(PartialEq, Eq, Debug)]
+pub enum HunkReviewMessage {
+    /// Start reviewing the unstaged hunks, or with `staged` the staged ones
+    Start { staged: bool },
+    /// Stage the hunk (unstage it when reviewing staged hunks) and go on
+    Accept,
+    /// Leave the hunk as it is and go on
+    Skip,
+    /// Discard the unstaged hunk from the working tree and go on
+    Discard,
+    /// Split the hunk into its blocks of changes, reviewed one by one
+    Split,
+    /// Edit the unstaged hunk in the user's editor, staging the result.
+    /// Requires the TUI to be suspended.
+    Edit,
+    /// Go back to the previous hunk to reconsider it
+    Previous,
+    /// Go to the next hunk without deciding on this one
+    Next,
+    /// Apply the decisions made so far and end the review, asking first
+    /// when hunks are to be discarded
+    Finish,
+    /// Apply the decisions made so far and end the review, as confirmed
+    Apply,
+    /// End the review without applying any decision
+    Abort,
+}
+
+/// Messages changing the options of the shown diffs
+#[derive(PartialEq, Eq, Debug)]
+pub enum DiffMessage {
+    /// Show one more line of context around each change
+    MoreContext,
+    /// Show one line of context less, down to none
+    LessContext,
+    /// Go back to git's default of three lines of context
+    DefaultContext,
+    /// Toggle ignoring changes in whitespace
+    ToggleIgnoreWhitespace,
+}
+
+/// Messages for conflict resolution in the working tree
+#[derive(PartialEq, Eq, Debug)]
+pub enum ResolveCommand {
+    /// Keep one side of a conflict region, rewriting the working file
+    /// (staged once no conflict markers remain)
+    Conflict {
+        path: String,
+        index: usize,
+        side: ConflictSide,
+    },
+    /// Checkout the whole file from one side (`git checkout --ours/--theirs`,
+    /// then staged) or restore its conflict (`git checkout --merge`)
+    Checkout {
+        path: String,
+        checkout: ConflictCheckout,
+    },
+    /// Run the configured merge tool on the given files, or on every
+    /// unmerged file when empty (`git mergetool -- <paths>`).
+    /// Requires the TUI to be suspended.
+    Mergetool { paths: Vec<String> },
+}
+
 /// Messages for merge commands
 #[derive(PartialEq, Eq, Debug)]
 pub enum MergeCommand {
@@ -807,6 +993,47 @@ pub enum SelectMessage {
     Confirm,
 }
 
+/// Messages for the built-in commit message editor
+#[derive(PartialEq, Eq, Debug)]
+pub enum CommitEditorMessage {
+    /// Apply a text editing operation to the message
+    Edit(EditOp),
+    /// Split the line at the cursor
+    Newline,
+    /// Move the cursor one line up
+    MoveUp,
+    /// Move the cursor one line down
+    MoveDown,
+    /// Replace the message with the previous (older) history entry
+    HistoryPrev,
+    /// Replace the message with the next (newer) history entry
+    HistoryNext,
+    /// First key of a `C-c C-c`/`C-c C-k` chord
+    CtrlCPrefix,
+    /// A key that completes no chord after `C-c`
+    ClearPrefix,
+    /// Leave insert mode (vim-style `Esc`)
+    EnterNormalMode,
+    /// Apply the movement, then enter insert mode (`i`, `a`, `A`, `I`)
+    EnterInsertMode(Option<EditOp>),
+    /// Open a new line below the cursor and enter insert mode (`o`)
+    OpenLineBelow,
+    /// Enter vim-style command mode (`:`)
+    CommandStart,
+    /// Type a character into the command line
+    CommandChar(char),
+    /// Delete the last character from the command line (exits on empty)
+    CommandBackspace,
+    /// Leave command mode without running anything
+    CommandCancel,
+    /// Confirmed an unrecognised command — show an error and leave command mode
+    CommandInvalid,
+    /// Commit with the written message
+    Confirm,
+    /// Close the editor without committing, keeping the message in history
+    Abort,
+}
+
 /// Messages for text input popups (e.g., branch name input)
 #[derive(PartialEq, Eq, Debug)]
 pub enum InputMessage {
@@ -3,14 +3,17 @@ use crate::{
     msg::{InputMessage, Message, SearchMessage, SelectMessage},
 };
 
+mod absorb;
 mod amend;
 mod apply;
 mod apply_selected;
+mod blob;
 mod checkout_branch;
 mod checkout_new_branch;
 mod cherry_spinoff;
 mod cherry_spinout;
 mod commit;
+mod commit_editor;
 mod confirm_delete_branch;
 mod confirm_discard;
 mod confirm_drop_stash;
@@ -21,9 +24,11 @@ mod create_tag_release;
 mod credentials_input;
 mod delete_branch;
 mod delete_tag;
+mod diff_settings;
 mod discard_selected;
 mod dismiss_popup;
 mod donate;
+mod edit_hunk;
 mod enter_arg_mode;
 mod enter_search_mode;
 mod enter_visual_mode;
@@ -35,9 +40,12 @@ mod fetch;
 mod file_checkout;
 mod fixup_commit;
 mod harvest;
+mod hunk_review;
 mod input_input;
+mod intent_to_add_selected;
 mod merge;
 mod navigation;
+mod open_in_editor;
 mod open_pr;
 mod pending_g;
 mod prune_tags;
@@ -46,12 +54,14 @@ mod pull;
 mod push;
 mod quit;
 mod rebase;
+mod rebase_pending;
 mod rebase_todo;
 mod refresh;
 mod rename_branch;
 mod reset_branch;
 mod reset_index;
 mod reset_worktree;
+mod resolve;
 mod reverse_selected;
 mod revert;
 mod revise_commit;
@@ -61,7 +71,9 @@ mod select_edit;
 mod select_move_down;
 mod select_move_up;
 mod selection;
+mod show_absorb_confirm;
 mod show_apply_popup;
+mod show_argument_value_input;
 mod show_checkout_new_branch_input;
 mod show_commit_author_select;
 mod show_commit_select;
@@ -76,13 +88,17 @@ mod show_pull_popup;
 mod show_push_popup;
 mod show_rebase_popup;
 mod show_reset_popup;
+mod show_reshelve_input;
+mod show_resolve_popup;
 mod show_revert_mainline_input;
 mod show_revert_popup;
 mod show_select_popup;
+mod show_signing_key_select;
 mod show_tag_popup;
 mod show_tag_release_input;
 mod spinoff_branch;
 mod spinout_branch;
+mod split_hunk;
 mod stage_all_modified;
 mod stage_selected;
 mod stash;
@@ -90,6 +106,7 @@ mod toggle_argument;
 mod toggle_section;
 mod unstage_all;
 mod unstage_selected;
+mod visit_file;
 mod worktree_branch;
 mod worktree_checkout;
 
@@ -110,18 +127,34 @@ pub fn update(model: &mut Model, msg: Message) -> Option<Message> {
         Message::Navigation(action) => navigation::update(model, action),
         Message::PendingG => pending_g::update(model),
         Message::Commit => commit::update(model),
-        Message::ShowCommitAuthorSelect => show_commit_author_select::update(model),
+        Message::CommitWithEditor(flags) => commit::with_editor(model, flags),
+        Message::ShowCommitAuthorSelect(target) => show_commit_author_select::update(model, target),
+        Message::ShowSigningKeySelect => show_signing_key_select::update(model),
+        Message::ShowArgumentValueInput(argument) => {
+            show_argument_value_input::update(model, argument)
+        }
         Message::Amend(extra_args) => amend::update(model, extra_args),
+        Message::AmendWithEditor(flags) => amend::with_editor(model, flags),
+        Message::Extend => amend::extend(model),
+        Message::ShowReshelveInput => show_reshelve_input::update(model),
+        Message::Reshelve(date) => amend::reshelve(model, date),
+        Message::CommitEditor(msg) => commit_editor::update(model, msg),
         Message::FixupCommit(commit_hash, fixup_type) => {
             fixup_commit::update(model, commit_hash, fixup_type)
         }
+        Message::ShowAbsorbConfirm { autosquash } => show_absorb_confirm::update(model, autosquash),
+        Message::Absorb { plan, autosquash } => absorb::update(model, plan, autosquash),
         Message::DismissPopup => dismiss_popup::update(model),
         Message::ShowStashInput(stash_type) => {
             show_input_popup::update(model, InputContext::Stash(stash_type))
         }
         Message::StageAllModified => stage_all_modified::update(model),
         Message::StageSelected => stage_selected::update(model),
+        Message::IntentToAddSelected => intent_to_add_selected::update(model),
         Message::UnstageSelected => unstage_selected::update(model),
+        Message::SplitHunk => split_hunk::update(model),
+        Message::EditHunk => edit_hunk::update(model),
+        Message::HunkReview(msg) => hunk_review::update(model, msg),
         Message::UnstageAll => unstage_all::update(model),
         Message::ApplySelected => apply_selected::update(model),
         Message::ReverseSelected => reverse_selected::update(model),
@@ -201,6 +234,8 @@ pub fn update(model: &mut Model, msg: Message) -> Option<Message> {
         Message::Push(push_command) => push::update(model, push_command),
         Message::ShowRebasePopup => show_rebase_popup::update(model),
         Message::ShowRebaseTodo(base) => rebase_todo::show(model, base),
+        Message::EditRebaseTodo => rebase_todo::show_in_progress(model),
+        Message::RebasePending(msg) => rebase_pending::update(model, msg),
         Message::RebaseTodo(msg) => rebase_todo::update(model, msg),
         Message::Rebase(rebase_command) => rebase::update(model, rebase_command),
         Message::ShowRevertPopup => show_revert_popup::update(model),
@@ -285,7 +320,14 @@ pub fn update(model: &mut Model, msg: Message) -> Option<Message> {
         } => worktree_branch::update(model, starting_point, branch_name, path),
         Message::ShowPreview => show_preview::update(model),
         Message::ExitPreview => exit_preview::update(model),
+        Message::ShowBlob { revision, path } => blob::show(model, revision, path),
+        Message::Blob(msg) => blob::update(model, msg),
+        Message::Diff(msg) => diff_settings::update(model, msg),
+        Message::VisitFile => visit_file::update(model),
+        Message::OpenInEditor { file, line } => open_in_editor::update(model, file, line),
         Message::FileCheckout { revision, file } => file_checkout::update(model, revision, file),
+        Message::ShowResolvePopup => show_resolve_popup::update(model),
+        Message::Resolve(command) => resolve::update(model, command),
         Message::ReviseCommit(hash) => revise_commit::update(model, hash),
     }
 }
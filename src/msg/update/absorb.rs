@@ -0,0 +1,45 @@
+use std::time::Instant;
+
+use crate::{
+    git::absorb::{AbsorbPlan, run_absorb},
+    model::{Model, Toast, ToastStyle, popup::PopupContent},
+    msg::{
+        Message, RebaseCommand,
+        update::{commit::TOAST_DURATION, show_signing_key_select::take_signing_flags},
+    },
+};
+
+pub fn update(model: &mut Model, plan: AbsorbPlan, autosquash: bool) -> Option<Message> {
+    model.popup = None;
+    model.arguments = None;
+    // With autosquash the fixups are folded away right after, so a key
+    // picked with -S is left for the rebase that rewrites their targets
+    let flags = if autosquash {
+        vec![]
+    } else {
+        take_signing_flags(model)
+    };
+
+    match run_absorb(&model.workdir, &plan, &flags) {
+        Ok(result) => {
+            model.toast = Some(Toast {
+                message: result.message,
+                style: ToastStyle::Success,
+                expires_at: Instant::now() + TOAST_DURATION,
+            });
+            match plan.targets.first() {
+                Some(oldest) if autosquash => Some(Message::Rebase(RebaseCommand::AutosquashInto(
+                    oldest.hash.clone(),
+                ))),
+                _ => Some(Message::Refresh),
+            }
+        }
+        Err(e) => {
+            model.signing_key = None;
+            model.popup = Some(PopupContent::Error {
+                message: format!("Absorb failed: {}", e),
+            });
+            Some(Message::Refresh)
+        }
+    }
+}
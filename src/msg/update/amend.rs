@@ -1,6 +1,7 @@
 use std::time::Instant;
 
 use crate::{
+    errors::MagiResult,
     git::commit::{self, CommitResult},
     model::{
         Model, Toast, ToastStyle,
@@ -10,11 +11,27 @@ use crate::{
     msg::Message,
 };
 
-use super::commit::{TOAST_DURATION, take_commit_author};
+use super::{
+    commit::{TOAST_DURATION, take_commit_popup_flags},
+    commit_editor,
+    show_signing_key_select::take_signing_flags,
+};
 
 pub fn update(model: &mut Model, extra_args: Vec<String>) -> Option<Message> {
-    // Dismiss the commit popup, keeping the author override it carries
-    let author = take_commit_author(model);
+    let flags = take_amend_flags(model, extra_args);
+
+    // `--no-edit` keeps the message, so there is nothing to write
+    if model.config.builtin_commit_editor && !flags.iter().any(|f| f == "--no-edit") {
+        return commit_editor::open(model, true, flags);
+    }
+    Some(Message::AmendWithEditor(flags))
+}
+
+/// Dismisses the commit popup and collects the flags for `git commit
+/// --amend`: `extra_args`, the popup's arguments, author override and
+/// trailers, and `--gpg-sign`.
+fn take_amend_flags(model: &mut Model, extra_args: Vec<String>) -> Vec<String> {
+    let popup_flags = take_commit_popup_flags(model);
 
     let mut flags: Vec<String> = vec![];
 
@@ -23,11 +40,37 @@ pub fn update(model: &mut Model, extra_args: Vec<String>) -> Option<Message> {
     if let Some(CommitArguments(arguments)) = model.arguments.take() {
         flags.extend(arguments.into_iter().map(|a| a.flag().to_string()))
     };
-    if let Some(author) = author {
-        flags.push(format!("--author={}", author));
-    }
+    flags.extend(popup_flags);
+    flags.extend(take_signing_flags(model));
+    flags
+}
+
+/// Runs `git commit --amend` with stdio inherited so the user's configured
+/// editor can open for the commit message. Requires the TUI to be suspended.
+pub fn with_editor(model: &mut Model, flags: Vec<String>) -> Option<Message> {
+    let result = commit::run_amend_commit_with_editor(&model.workdir, flags);
+    report(model, result)
+}
+
+/// Adds the staged changes to HEAD without editing its message or changing
+/// its dates. Never opens an editor, so the TUI stays up.
+pub fn extend(model: &mut Model) -> Option<Message> {
+    let flags = take_amend_flags(model, vec![]);
+    let result = commit::run_extend_commit(&model.workdir, flags);
+    report(model, result)
+}
+
+/// Rewrites the author and committer date of HEAD to `date`, re-signing it
+/// when a key was picked with `-S`.
+pub fn reshelve(model: &mut Model, date: String) -> Option<Message> {
+    model.arguments = None;
+    let flags = take_signing_flags(model);
+    let result = commit::run_reshelve_commit(&model.workdir, &date, flags);
+    report(model, result)
+}
 
-    match commit::run_amend_commit_with_editor(&model.workdir, flags) {
+fn report(model: &mut Model, result: MagiResult<CommitResult>) -> Option<Message> {
+    match result {
         Ok(CommitResult { success, message }) => {
             model.toast = Some(Toast {
                 message,
@@ -1,6 +1,17 @@
 use crate::{
-    model::Model,
-    msg::{ApplyCommand, Message, update::pty_helper::execute_pty_command},
+    git::revert::any_is_merge_commit,
+    model::{
+        Model,
+        arguments::{ApplyArgument, Arguments::ApplyArguments, PopupArgument, ValueArgument},
+    },
+    msg::{
+        ApplyCommand, Message,
+        update::{
+            pty_helper::execute_pty_command,
+            show_argument_value_input::{take_value_flags, value_flags},
+            show_signing_key_select::take_signing_flags,
+        },
+    },
 };
 
 pub fn update(model: &mut Model, cmd: ApplyCommand) -> Option<Message> {
@@ -14,20 +25,60 @@ pub fn update(model: &mut Model, cmd: ApplyCommand) -> Option<Message> {
     }
 }
 
+/// Consumes the apply popup arguments, returning the `git cherry-pick`
+/// flags in a stable order: `--mainline`, the toggles, `--strategy`,
+/// `--strategy-option` and `--gpg-sign`. Harvest, donate, spinout and
+/// spinoff cherry-pick with these too. The mainline is left set, so the
+/// choice is remembered for the next pick or revert, but it's only passed
+/// when one of `hashes` is a merge, since git refuses it otherwise.
+pub fn take_apply_flags(model: &mut Model, hashes: &[String]) -> Vec<String> {
+    let mut flags = if any_is_merge_commit(&model.workdir, hashes) {
+        value_flags(model, &[ValueArgument::Mainline])
+    } else {
+        Vec::new()
+    };
+    if let Some(ApplyArguments(arguments)) = model.arguments.take() {
+        // git refuses --ff together with -x or --signoff, which both need a new commit
+        let rewrites = arguments.contains(&ApplyArgument::RecordOrigin)
+            || arguments.contains(&ApplyArgument::Signoff);
+        flags.extend(
+            ApplyArgument::all()
+                .into_iter()
+                .filter(|arg| arguments.contains(arg))
+                .filter(|arg| !(rewrites && *arg == ApplyArgument::FastForward))
+                .map(|arg| arg.flag().to_string()),
+        );
+    }
+    flags.extend(take_value_flags(
+        model,
+        &[ValueArgument::Strategy, ValueArgument::StrategyOption],
+    ));
+    flags.extend(take_signing_flags(model));
+    flags
+}
+
 fn pick(model: &mut Model, hashes: Vec<String>) -> Option<Message> {
     if hashes.is_empty() {
         return None;
     }
     let mut args = vec!["cherry-pick".to_string()];
+    args.extend(take_apply_flags(model, &hashes));
     args.extend(hashes);
     execute_pty_command(model, args, "Apply".to_string())
 }
 
+/// Nothing is committed, so only the flags that affect how the changes are
+/// replayed (`--mainline` and the strategy) are kept.
 fn apply_no_commit(model: &mut Model, hashes: Vec<String>) -> Option<Message> {
     if hashes.is_empty() {
         return None;
     }
     let mut args = vec!["cherry-pick".to_string(), "--no-commit".to_string()];
+    args.extend(
+        take_apply_flags(model, &hashes)
+            .into_iter()
+            .filter(|flag| flag.starts_with("--mainline=") || flag.starts_with("--strategy")),
+    );
     args.extend(hashes);
     execute_pty_command(model, args, "Apply".to_string())
 }
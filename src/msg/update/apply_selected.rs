@@ -67,7 +67,7 @@ fn apply_from_preview(model: &mut Model) -> Option<Message> {
 
     let patch = build_preview_patch(&model.ui_model.lines, start, end)?;
 
-    match apply_patch(&model.workdir, &patch) {
+    match apply_patch(&model.workdir, &patch, &model.git_info.diff_settings) {
         Ok(()) => {
             model.toast = Some(Toast {
                 message: "Changes applied to the working tree".to_string(),
@@ -103,7 +103,10 @@ pub(super) fn build_preview_patch(lines: &[Line], start: usize, end: usize) -> O
     let mut hunk_start: Option<usize> = None;
 
     for (i, line) in lines.iter().enumerate() {
-        let LineContent::PreviewLine { content, line_type } = &line.content else {
+        let LineContent::PreviewLine {
+            content, line_type, ..
+        } = &line.content
+        else {
             continue;
         };
 
@@ -0,0 +1,106 @@
+use std::time::Instant;
+
+use crate::{
+    git::blob::{blob_lines, next_revision, previous_revision, short_revision},
+    model::{Model, Toast, ToastStyle, ViewMode, popup::PopupContent},
+    msg::{BlobMessage, Message, update::commit::TOAST_DURATION},
+};
+
+/// Opens the blob view for `path` at `revision`, remembering the view to
+/// return to (shared with the preview, which it replaces).
+pub fn show(model: &mut Model, revision: String, path: String) -> Option<Message> {
+    let revision = match short_revision(&model.workdir, &revision) {
+        Ok(short) => short,
+        Err(err) => {
+            model.popup = Some(PopupContent::error(err.to_string()));
+            return None;
+        }
+    };
+    let lines = match blob_lines(&model.workdir, &revision, &path, false) {
+        Ok(lines) => lines,
+        Err(err) => {
+            model.popup = Some(PopupContent::error(err.to_string()));
+            return None;
+        }
+    };
+
+    if !matches!(model.view_mode, ViewMode::Blob { .. } | ViewMode::Preview) {
+        model.preview_return_mode = Some(model.view_mode.clone());
+        model.preview_return_ui_model = Some(model.ui_model.clone());
+    }
+    model.ui_model.lines = lines;
+    model.ui_model.cursor_position = 0;
+    model.ui_model.scroll_offset = 0;
+    model.ui_model.visual_mode_anchor = None;
+    model.view_mode = ViewMode::Blob {
+        revision,
+        path,
+        blame: false,
+    };
+    None
+}
+
+pub fn update(model: &mut Model, msg: BlobMessage) -> Option<Message> {
+    let ViewMode::Blob {
+        revision,
+        path,
+        blame,
+    } = model.view_mode.clone()
+    else {
+        return None;
+    };
+
+    let step = match msg {
+        BlobMessage::Previous => previous_revision(&model.workdir, &revision, &path)
+            .map(|found| found.ok_or("No older version of this file")),
+        BlobMessage::Next => next_revision(&model.workdir, &revision, &path)
+            .map(|found| found.ok_or("No newer version of this file")),
+        BlobMessage::ToggleBlame => {
+            model.view_mode = ViewMode::Blob {
+                revision,
+                path,
+                blame: !blame,
+            };
+            return Some(Message::Refresh);
+        }
+    };
+
+    match step {
+        Ok(Ok((revision, path))) => {
+            let revision = short_revision(&model.workdir, &revision).unwrap_or(revision);
+            model.view_mode = ViewMode::Blob {
+                revision,
+                path,
+                blame,
+            };
+            model.ui_model.cursor_position = 0;
+            model.ui_model.scroll_offset = 0;
+            Some(Message::Refresh)
+        }
+        Ok(Err(message)) => {
+            model.toast = Some(Toast {
+                message: message.to_string(),
+                style: ToastStyle::Info,
+                expires_at: Instant::now() + TOAST_DURATION,
+            });
+            None
+        }
+        Err(err) => {
+            model.popup = Some(PopupContent::error(err.to_string()));
+            None
+        }
+    }
+}
+
+/// Reloads the blob view's lines, keeping the cursor where it was.
+pub fn reload(model: &mut Model, revision: &str, path: &str, blame: bool) -> Option<Message> {
+    match blob_lines(&model.workdir, revision, path, blame) {
+        Ok(lines) => {
+            let last = lines.len().saturating_sub(1);
+            model.ui_model.lines = lines;
+            model.ui_model.cursor_position = model.ui_model.cursor_position.min(last);
+        }
+        Err(err) => model.popup = Some(PopupContent::error(err.to_string())),
+    }
+    None
+}
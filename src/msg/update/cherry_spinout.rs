@@ -1,7 +1,7 @@
 use crate::{
     git::git_cmd,
     model::{Model, popup::PopupContent},
-    msg::Message,
+    msg::{Message, update::apply::take_apply_flags},
 };
 
 pub fn update(
@@ -11,6 +11,7 @@ pub fn update(
     root: String,
 ) -> Option<Message> {
     model.popup = None;
+    let flags = take_apply_flags(model, &commits);
 
     if commits.is_empty() {
         return None;
@@ -74,6 +75,7 @@ pub fn update(
 
     // Step 3: cherry-pick the commits onto the new branch
     let mut cherry_pick_args: Vec<&str> = vec!["cherry-pick"];
+    cherry_pick_args.extend(flags.iter().map(String::as_str));
     let commits_refs: Vec<&str> = commits.iter().map(|s| s.as_str()).collect();
     cherry_pick_args.extend(commits_refs.iter().copied());
 
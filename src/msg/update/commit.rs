@@ -10,21 +10,23 @@ use crate::{
     msg::Message,
 };
 
+use super::{commit_editor, show_signing_key_select::take_signing_flags};
+
 /// Duration for toast notifications
 pub const TOAST_DURATION: Duration = Duration::from_secs(5);
 
-/// Dismisses the commit popup and returns the author override (`-A`) it held,
-/// if any.
-pub fn take_commit_author(model: &mut Model) -> Option<String> {
+/// Dismisses the commit popup and returns the flags for the author override
+/// (`-A`) and trailers (`-C`, `-r`) it held.
+pub fn take_commit_popup_flags(model: &mut Model) -> Vec<String> {
     match model.popup.take() {
-        Some(PopupContent::Command(PopupContentCommand::Commit(state))) => state.author,
-        _ => None,
+        Some(PopupContent::Command(PopupContentCommand::Commit(state))) => state.flags(),
+        _ => vec![],
     }
 }
 
 pub fn update(model: &mut Model) -> Option<Message> {
-    // Dismiss the commit popup, keeping the author override it carries
-    let author = take_commit_author(model);
+    // Dismiss the commit popup, keeping the author override and trailers it carries
+    let popup_flags = take_commit_popup_flags(model);
 
     let allow_no_staged: bool = if let Some(CommitArguments(ref args)) = model.arguments {
         args.contains(&CommitArgument::StageAll) || args.contains(&CommitArgument::AllowEmpty)
@@ -42,8 +44,6 @@ pub fn update(model: &mut Model) -> Option<Message> {
         return None;
     }
 
-    let repo_path = &model.workdir;
-
     let mut flags: Vec<String> = if let Some(CommitArguments(arguments)) = model.arguments.take() {
         arguments
             .into_iter()
@@ -52,11 +52,19 @@ pub fn update(model: &mut Model) -> Option<Message> {
     } else {
         vec![]
     };
-    if let Some(author) = author {
-        flags.push(format!("--author={}", author));
+    flags.extend(popup_flags);
+    flags.extend(take_signing_flags(model));
+
+    if model.config.builtin_commit_editor {
+        return commit_editor::open(model, false, flags);
     }
+    Some(Message::CommitWithEditor(flags))
+}
 
-    match commit::run_commit_with_editor(repo_path, flags) {
+/// Runs `git commit` with stdio inherited so the user's configured editor
+/// can open for the commit message. Requires the TUI to be suspended.
+pub fn with_editor(model: &mut Model, flags: Vec<String>) -> Option<Message> {
+    match commit::run_commit_with_editor(&model.workdir, flags) {
         Ok(CommitResult { success, message }) => {
             model.toast = Some(Toast {
                 message,
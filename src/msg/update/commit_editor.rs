@@ -0,0 +1,232 @@
+use std::time::Instant;
+
+use crate::{
+    git::{
+        commit::{self, CommitResult},
+        preview::get_commit_editor_diff_lines,
+    },
+    model::{
+        EditOp, Model, Toast, ToastStyle, UiModel, ViewMode,
+        commit_editor::{CommitEditorMode, CommitEditorState},
+        popup::PopupContent,
+    },
+    msg::{CommitEditorMessage, Message},
+};
+
+use super::commit::TOAST_DURATION;
+
+/// How many previous commit messages can be recalled with M-p.
+const HISTORY_COMMITS: usize = 50;
+
+/// Opens the built-in commit message editor, showing the diff that will be
+/// committed below it. When amending, the editor starts with HEAD's message.
+pub fn open(model: &mut Model, amend: bool, flags: Vec<String>) -> Option<Message> {
+    let message = if amend {
+        commit::head_commit_message(&model.workdir)
+    } else {
+        String::new()
+    };
+    let mut history = model.commit_message_history.clone();
+    history.extend(commit::recent_commit_messages(
+        &model.workdir,
+        HISTORY_COMMITS,
+    ));
+
+    let diff_ui_model = UiModel {
+        lines: get_commit_editor_diff_lines(&model.workdir, amend, &flags),
+        viewport_height: model.ui_model.viewport_height,
+        ..Default::default()
+    };
+    let return_ui_model = std::mem::replace(&mut model.ui_model, diff_ui_model);
+    let return_mode = std::mem::replace(&mut model.view_mode, ViewMode::CommitMessage);
+
+    model.commit_editor = Some(CommitEditorState::new(
+        &message,
+        amend,
+        flags,
+        history,
+        return_mode,
+        return_ui_model,
+    ));
+    None
+}
+
+pub fn update(model: &mut Model, msg: CommitEditorMessage) -> Option<Message> {
+    let state = model.commit_editor.as_mut()?;
+    // A `C-c` chord only spans a single following key
+    if !matches!(msg, CommitEditorMessage::CtrlCPrefix) {
+        state.pending_ctrl_c = false;
+    }
+
+    match msg {
+        CommitEditorMessage::Edit(op) => {
+            state.message.apply(op);
+            None
+        }
+        CommitEditorMessage::Newline => {
+            state.message.insert_newline();
+            None
+        }
+        CommitEditorMessage::MoveUp => {
+            state.message.move_up();
+            None
+        }
+        CommitEditorMessage::MoveDown => {
+            state.message.move_down();
+            None
+        }
+        CommitEditorMessage::HistoryPrev => {
+            if !state.history_prev() {
+                return history_end_toast(model, "Beginning of history; no preceding item");
+            }
+            None
+        }
+        CommitEditorMessage::HistoryNext => {
+            if !state.history_next() {
+                return history_end_toast(model, "End of history; no next item");
+            }
+            None
+        }
+        CommitEditorMessage::CtrlCPrefix => {
+            state.pending_ctrl_c = true;
+            None
+        }
+        CommitEditorMessage::ClearPrefix => None,
+        CommitEditorMessage::EnterNormalMode => {
+            state.mode = CommitEditorMode::Normal;
+            None
+        }
+        CommitEditorMessage::EnterInsertMode(op) => {
+            if let Some(op) = op {
+                state.message.apply(op);
+            }
+            state.mode = CommitEditorMode::Insert;
+            None
+        }
+        CommitEditorMessage::OpenLineBelow => {
+            state.message.apply(EditOp::MoveToEnd);
+            state.message.insert_newline();
+            state.mode = CommitEditorMode::Insert;
+            None
+        }
+        CommitEditorMessage::CommandStart => {
+            state.command_input = Some(String::new());
+            None
+        }
+        CommitEditorMessage::CommandChar(c) => {
+            if let Some(input) = state.command_input.as_mut() {
+                input.push(c);
+            }
+            None
+        }
+        CommitEditorMessage::CommandBackspace => {
+            // Backspacing past the ':' leaves command mode, like in vim
+            match state.command_input.as_mut() {
+                Some(input) if input.is_empty() => state.command_input = None,
+                Some(input) => {
+                    input.pop();
+                }
+                None => {}
+            }
+            None
+        }
+        CommitEditorMessage::CommandCancel => {
+            state.command_input = None;
+            None
+        }
+        CommitEditorMessage::CommandInvalid => {
+            if let Some(cmd) = state.command_input.take() {
+                model.toast = Some(Toast {
+                    message: format!("Not an editor command: :{}", cmd),
+                    style: ToastStyle::Warning,
+                    expires_at: Instant::now() + TOAST_DURATION,
+                });
+            }
+            None
+        }
+        CommitEditorMessage::Confirm => confirm(model),
+        CommitEditorMessage::Abort => abort(model),
+    }
+}
+
+fn history_end_toast(model: &mut Model, message: &str) -> Option<Message> {
+    model.toast = Some(Toast {
+        message: message.to_string(),
+        style: ToastStyle::Info,
+        expires_at: Instant::now() + TOAST_DURATION,
+    });
+    None
+}
+
+/// Commits with the written message. On failure (e.g. a rejecting hook) the
+/// editor stays open so the message isn't lost.
+fn confirm(model: &mut Model) -> Option<Message> {
+    let state = model.commit_editor.as_mut()?;
+    state.command_input = None;
+    if state.message.is_blank() {
+        model.toast = Some(Toast {
+            message: "Aborting commit due to empty commit message".to_string(),
+            style: ToastStyle::Warning,
+            expires_at: Instant::now() + TOAST_DURATION,
+        });
+        return None;
+    }
+
+    let message = state.message.text();
+    match commit::run_commit_with_message(
+        &model.workdir,
+        &message,
+        state.amend,
+        state.flags.clone(),
+    ) {
+        Ok(CommitResult {
+            success: true,
+            message,
+        }) => {
+            close(model);
+            model.toast = Some(Toast {
+                message,
+                style: ToastStyle::Success,
+                expires_at: Instant::now() + TOAST_DURATION,
+            });
+            Some(Message::Refresh)
+        }
+        Ok(CommitResult { message, .. }) => {
+            model.popup = Some(PopupContent::Error { message });
+            None
+        }
+        Err(e) => {
+            model.popup = Some(PopupContent::Error {
+                message: e.to_string(),
+            });
+            None
+        }
+    }
+}
+
+/// Closes the editor without committing. A non-empty message is kept so it
+/// can be recalled with M-p the next time the editor opens.
+fn abort(model: &mut Model) -> Option<Message> {
+    let state = model.commit_editor.as_ref()?;
+    if !state.message.is_blank() {
+        let message = state.message.text();
+        model.commit_message_history.retain(|m| *m != message);
+        model.commit_message_history.insert(0, message);
+    }
+    let op = if state.amend { "Amend" } else { "Commit" };
+    close(model);
+    model.toast = Some(Toast {
+        message: format!("{} aborted", op),
+        style: ToastStyle::Warning,
+        expires_at: Instant::now() + TOAST_DURATION,
+    });
+    Some(Message::Refresh)
+}
+
+/// Restores the view the editor was opened from.
+fn close(model: &mut Model) {
+    if let Some(state) = model.commit_editor.take() {
+        model.view_mode = state.return_mode;
+        model.ui_model = state.return_ui_model;
+    }
+}
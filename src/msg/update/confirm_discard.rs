@@ -1,7 +1,10 @@
 use crate::{
-    git::discard::{
-        discard_files, discard_hunk, discard_lines, discard_staged_files, discard_staged_hunk,
-        discard_staged_lines, discard_untracked_files,
+    git::{
+        DiffSettings,
+        discard::{
+            discard_files, discard_hunk, discard_lines, discard_staged_files, discard_staged_hunk,
+            discard_staged_lines, discard_untracked_files,
+        },
     },
     model::{Model, popup::PopupContent},
     msg::{DiscardSource, DiscardTarget, Message},
@@ -13,7 +16,7 @@ pub fn update(model: &mut Model, target: DiscardTarget) -> Option<Message> {
     model.popup = None;
 
     let repo_path = model.workdir.clone();
-    let result = apply_discard(&repo_path, target);
+    let result = apply_discard(&repo_path, &model.git_info.diff_settings, target);
 
     if let Err(e) = result {
         model.popup = Some(PopupContent::Error {
@@ -26,6 +29,7 @@ pub fn update(model: &mut Model, target: DiscardTarget) -> Option<Message> {
 
 fn apply_discard(
     repo_path: &std::path::Path,
+    settings: &DiffSettings,
     target: DiscardTarget,
 ) -> Result<(), crate::errors::MagiError> {
     match target {
@@ -42,8 +46,8 @@ fn apply_discard(
             hunk_index,
             source,
         } => match source {
-            DiscardSource::Unstaged => discard_hunk(repo_path, &path, hunk_index),
-            DiscardSource::Staged => discard_staged_hunk(repo_path, &path, hunk_index),
+            DiscardSource::Unstaged => discard_hunk(repo_path, &path, hunk_index, settings),
+            DiscardSource::Staged => discard_staged_hunk(repo_path, &path, hunk_index, settings),
             DiscardSource::Untracked => unreachable!("Untracked files have no diffs"),
         },
         DiscardTarget::Hunks {
@@ -54,8 +58,8 @@ fn apply_discard(
             // Apply hunks in reverse order (highest index first) to avoid index shifts
             for idx in hunk_indices {
                 match source {
-                    DiscardSource::Unstaged => discard_hunk(repo_path, &path, idx)?,
-                    DiscardSource::Staged => discard_staged_hunk(repo_path, &path, idx)?,
+                    DiscardSource::Unstaged => discard_hunk(repo_path, &path, idx, settings)?,
+                    DiscardSource::Staged => discard_staged_hunk(repo_path, &path, idx, settings)?,
                     DiscardSource::Untracked => unreachable!("Untracked files have no diffs"),
                 }
             }
@@ -67,9 +71,11 @@ fn apply_discard(
             line_indices,
             source,
         } => match source {
-            DiscardSource::Unstaged => discard_lines(repo_path, &path, hunk_index, &line_indices),
+            DiscardSource::Unstaged => {
+                discard_lines(repo_path, &path, hunk_index, &line_indices, settings)
+            }
             DiscardSource::Staged => {
-                discard_staged_lines(repo_path, &path, hunk_index, &line_indices)
+                discard_staged_lines(repo_path, &path, hunk_index, &line_indices, settings)
             }
             DiscardSource::Untracked => unreachable!("Untracked files have no diffs"),
         },
@@ -1,9 +1,12 @@
 use std::time::Instant;
 
 use crate::{
-    git::reverse::{
-        reverse_patch, reverse_staged_files, reverse_staged_hunk, reverse_staged_hunks,
-        reverse_staged_lines,
+    git::{
+        DiffSettings,
+        reverse::{
+            reverse_patch, reverse_staged_files, reverse_staged_hunk, reverse_staged_hunks,
+            reverse_staged_lines,
+        },
     },
     model::{Model, Toast, ToastStyle, popup::PopupContent},
     msg::{Message, ReverseTarget, update::commit::TOAST_DURATION},
@@ -15,7 +18,7 @@ pub fn update(model: &mut Model, target: ReverseTarget) -> Option<Message> {
     model.popup = None;
 
     let repo_path = model.workdir.clone();
-    match apply_reverse(&repo_path, target) {
+    match apply_reverse(&repo_path, &model.git_info.diff_settings, target) {
         Ok(()) => {
             model.toast = Some(Toast {
                 message: "Changes reversed in the working tree".to_string(),
@@ -35,24 +38,25 @@ pub fn update(model: &mut Model, target: ReverseTarget) -> Option<Message> {
 
 fn apply_reverse(
     repo_path: &std::path::Path,
+    settings: &DiffSettings,
     target: ReverseTarget,
 ) -> Result<(), crate::errors::MagiError> {
     match target {
-        ReverseTarget::Patch { patch } => reverse_patch(repo_path, &patch),
+        ReverseTarget::Patch { patch } => reverse_patch(repo_path, &patch, settings),
         ReverseTarget::Files { paths } => {
             let file_refs: Vec<&str> = paths.iter().map(String::as_str).collect();
             reverse_staged_files(repo_path, &file_refs)
         }
         ReverseTarget::Hunk { path, hunk_index } => {
-            reverse_staged_hunk(repo_path, &path, hunk_index)
+            reverse_staged_hunk(repo_path, &path, hunk_index, settings)
         }
         ReverseTarget::Hunks { path, hunk_indices } => {
-            reverse_staged_hunks(repo_path, &path, &hunk_indices)
+            reverse_staged_hunks(repo_path, &path, &hunk_indices, settings)
         }
         ReverseTarget::Lines {
             path,
             hunk_index,
             line_indices,
-        } => reverse_staged_lines(repo_path, &path, hunk_index, &line_indices),
+        } => reverse_staged_lines(repo_path, &path, hunk_index, &line_indices, settings),
     }
 }
@@ -0,0 +1,59 @@
+use crate::{
+    git::DEFAULT_CONTEXT_LINES,
+    model::{Line, LineContent, Model, ViewMode},
+    msg::{DiffMessage, Message, update::show_preview},
+};
+
+pub fn update(model: &mut Model, msg: DiffMessage) -> Option<Message> {
+    let settings = &mut model.git_info.diff_settings;
+    match msg {
+        DiffMessage::MoreContext => settings.context_lines += 1,
+        DiffMessage::LessContext => {
+            settings.context_lines = settings.context_lines.saturating_sub(1)
+        }
+        DiffMessage::DefaultContext => settings.context_lines = DEFAULT_CONTEXT_LINES,
+        DiffMessage::ToggleIgnoreWhitespace => {
+            settings.ignore_whitespace = !settings.ignore_whitespace
+        }
+    }
+
+    match model.view_mode {
+        ViewMode::Status => Some(Message::Refresh),
+        ViewMode::Preview => {
+            refresh_preview(model);
+            None
+        }
+        // Other views show no diff; the settings apply once they do
+        _ => None,
+    }
+}
+
+/// Regenerates the preview of the line it was opened from. Previews that
+/// did not come from that line (like a merge preview) are left as they are.
+fn refresh_preview(model: &mut Model) {
+    let Some(return_model) = &model.preview_return_ui_model else {
+        return;
+    };
+    let Some(line) = return_model.lines.get(return_model.cursor_position) else {
+        return;
+    };
+    let Some(lines) = show_preview::preview_lines(model, line) else {
+        return;
+    };
+    if first_preview_line(&lines) != first_preview_line(&model.ui_model.lines) {
+        return;
+    }
+
+    model.ui_model.lines = lines;
+    let last = model.ui_model.lines.len().saturating_sub(1);
+    model.ui_model.cursor_position = model.ui_model.cursor_position.min(last);
+    model.ui_model.scroll_offset = model.ui_model.scroll_offset.min(last);
+}
+
+/// The first line of a preview names the commit or stash it shows.
+fn first_preview_line(lines: &[Line]) -> Option<&str> {
+    match &lines.first()?.content {
+        LineContent::PreviewLine { content, .. } => Some(content),
+        _ => None,
+    }
+}
@@ -78,12 +78,16 @@ pub fn update(model: &mut Model) -> Option<Message> {
 pub(super) fn determine_source(section: Option<&SectionType>) -> Option<DiscardSource> {
     match section {
         Some(SectionType::UnstagedChanges)
+        | Some(SectionType::UnstagedDirectory { .. })
         | Some(SectionType::UnstagedFile { .. })
         | Some(SectionType::UnstagedHunk { .. }) => Some(DiscardSource::Unstaged),
         Some(SectionType::StagedChanges)
+        | Some(SectionType::StagedDirectory { .. })
         | Some(SectionType::StagedFile { .. })
         | Some(SectionType::StagedHunk { .. }) => Some(DiscardSource::Staged),
-        Some(SectionType::UntrackedFiles) => Some(DiscardSource::Untracked),
+        Some(SectionType::UntrackedFiles)
+        | Some(SectionType::UntrackedDirectory { .. })
+        | Some(SectionType::UntrackedFile { .. }) => Some(DiscardSource::Untracked),
         _ => None,
     }
 }
@@ -2,7 +2,9 @@ use crate::{
     git::credential::CredentialResponse,
     model::{
         Model,
+        arguments::ValueArgument,
         popup::{PopupContent, PopupContentCommand, SelectResult},
+        select_popup::{OnSelect, SelectPopupState},
     },
     msg::Message,
 };
@@ -11,6 +13,17 @@ pub fn update(model: &mut Model) -> Option<Message> {
     // If dismissing a select popup, mark it as cancelled
     if let Some(PopupContent::Command(PopupContentCommand::Select(_))) = &model.popup {
         model.select_result = Some(SelectResult::Cancelled);
+
+        // Cancelling the author or trailer picker returns to the commit
+        // popup, keeping its arguments
+        if let Some(PopupContent::Command(PopupContentCommand::Select(SelectPopupState {
+            on_select: OnSelect::CommitAuthor { state, .. },
+            ..
+        }))) = model.popup.take()
+        {
+            model.popup = Some(PopupContent::Command(PopupContentCommand::Commit(state)));
+            return None;
+        }
     }
 
     // If dismissing a credential popup, send cancelled response to PTY thread
@@ -23,6 +36,11 @@ pub fn update(model: &mut Model) -> Option<Message> {
     // If dismissing a push popup, reset the arguments
     model.arg_mode = false;
     model.arguments = None;
+    model.signing_key = None;
+    // The mainline choice is remembered across the apply and revert popups
+    model
+        .argument_values
+        .retain(|argument, _| *argument == ValueArgument::Mainline);
     model.popup = None;
     None
 }
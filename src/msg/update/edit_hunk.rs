@@ -0,0 +1,63 @@
+use std::{fs, time::Instant};
+
+use crate::{
+    errors::MagiResult,
+    git::{
+        stage::{editable_hunk_patch, stage_edited_hunk},
+        visit::open_in_editor,
+    },
+    model::{Model, SectionType, Toast, ToastStyle, popup::PopupContent},
+    msg::{Message, update::commit::TOAST_DURATION},
+};
+
+/// File in the git directory the hunk is edited in
+const EDIT_HUNK_FILE: &str = "MAGI_EDIT_HUNK.diff";
+
+pub fn update(model: &mut Model) -> Option<Message> {
+    model.popup = None;
+    let Some(SectionType::UnstagedHunk { path, hunk_index }) = model
+        .ui_model
+        .lines
+        .get(model.ui_model.cursor_position)
+        .and_then(|line| line.section.clone())
+    else {
+        return None;
+    };
+
+    match edit_hunk(model, &path, hunk_index) {
+        Ok(true) => {}
+        Ok(false) => {
+            model.toast = Some(Toast {
+                message: "The edited hunk has no changes; nothing staged".to_string(),
+                style: ToastStyle::Info,
+                expires_at: Instant::now() + TOAST_DURATION,
+            });
+        }
+        Err(err) => {
+            model.popup = Some(PopupContent::Error {
+                message: format!("Error staging edited hunk: {}", err),
+            });
+        }
+    }
+    Some(Message::Refresh)
+}
+
+/// Opens the hunk's patch in the editor and stages what it was edited to.
+fn edit_hunk(model: &Model, path: &str, hunk_index: usize) -> MagiResult<bool> {
+    let settings = &model.git_info.diff_settings;
+    let patch = editable_hunk_patch(&model.workdir, path, hunk_index, settings)?;
+    let edited = edit_patch(model, &patch)?;
+    stage_edited_hunk(&model.workdir, &edited, settings)
+}
+
+/// Opens `patch` in the editor, from a file in the git directory, and
+/// returns what it was edited to.
+pub fn edit_patch(model: &Model, patch: &str) -> MagiResult<String> {
+    let file = model.git_info.repository.path().join(EDIT_HUNK_FILE);
+    fs::write(&file, patch)?;
+
+    let edited = open_in_editor(&model.workdir, &file, None)
+        .and_then(|()| fs::read_to_string(&file).map_err(Into::into));
+    let _ = fs::remove_file(&file);
+    edited
+}
@@ -1,7 +1,7 @@
 use crate::{
     git::commit::{run_alter_commit, run_augment_commit, run_fixup_commit, run_squash_commit},
     model::{Model, popup::PopupContent},
-    msg::{FixupType, Message},
+    msg::{FixupType, Message, RebaseCommand},
 };
 
 pub fn update(model: &mut Model, commit_ref: String, fixup_type: FixupType) -> Option<Message> {
@@ -25,16 +25,26 @@ pub fn update(model: &mut Model, commit_ref: String, fixup_type: FixupType) -> O
         .to_string();
 
     let result = match fixup_type {
-        FixupType::Fixup => run_fixup_commit(repo_path, commit_hash),
-        FixupType::Squash => run_squash_commit(repo_path, commit_hash),
-        FixupType::Alter => run_alter_commit(repo_path, commit_hash),
-        FixupType::Augment => run_augment_commit(repo_path, commit_hash),
+        FixupType::Fixup | FixupType::InstantFixup => {
+            run_fixup_commit(repo_path, commit_hash.clone())
+        }
+        FixupType::Squash | FixupType::InstantSquash => {
+            run_squash_commit(repo_path, commit_hash.clone())
+        }
+        FixupType::Alter => run_alter_commit(repo_path, commit_hash.clone()),
+        FixupType::Augment => run_augment_commit(repo_path, commit_hash.clone()),
     };
 
     match result {
         Ok(result) => {
             if result.success {
-                Some(Message::Refresh)
+                match fixup_type {
+                    // Fold the new commit into its target right away
+                    FixupType::InstantFixup | FixupType::InstantSquash => {
+                        Some(Message::Rebase(RebaseCommand::AutosquashInto(commit_hash)))
+                    }
+                    _ => Some(Message::Refresh),
+                }
             } else {
                 model.popup = Some(PopupContent::Error {
                     message: result.message,
@@ -44,8 +54,8 @@ pub fn update(model: &mut Model, commit_ref: String, fixup_type: FixupType) -> O
         }
         Err(err) => {
             let operation = match fixup_type {
-                FixupType::Fixup => "Fixup",
-                FixupType::Squash => "Squash",
+                FixupType::Fixup | FixupType::InstantFixup => "Fixup",
+                FixupType::Squash | FixupType::InstantSquash => "Squash",
                 FixupType::Alter => "Alter",
                 FixupType::Augment => "Augment",
             };
@@ -1,11 +1,12 @@
 use crate::{
     git::git_cmd,
     model::{Model, popup::PopupContent},
-    msg::Message,
+    msg::{Message, update::apply::take_apply_flags},
 };
 
 pub fn update(model: &mut Model, commits: Vec<String>, source: String) -> Option<Message> {
     model.popup = None;
+    let flags = take_apply_flags(model, &commits);
 
     if commits.is_empty() {
         return None;
@@ -22,6 +23,7 @@ pub fn update(model: &mut Model, commits: Vec<String>, source: String) -> Option
 
     // Step 1: cherry-pick the commits onto the current branch
     let mut cherry_pick_args: Vec<&str> = vec!["cherry-pick"];
+    cherry_pick_args.extend(flags.iter().map(String::as_str));
     let commits_refs: Vec<&str> = commits.iter().map(|s| s.as_str()).collect();
     cherry_pick_args.extend(commits_refs.iter().copied());
 
@@ -0,0 +1,346 @@
+use std::{collections::HashMap, time::Instant};
+
+use crate::{
+    errors::MagiResult,
+    git::discard::{apply_patch_reverse, discard_hunk},
+    git::stage::{
+        EDIT_HUNK_GUIDE, apply_patch_cached, apply_patch_cached_reverse, build_block_patch,
+        edited_hunk_patch, get_file_diff, stage_hunk, staged_file_diff, staged_renames,
+        unstage_hunk,
+    },
+    i18n,
+    model::{
+        Line, LineContent, Model, SectionType, Toast, ToastStyle, UiModel, ViewMode,
+        hunk_review::{HunkReviewState, ReviewDecision, ReviewFile, ReviewHunk, ReviewPatch},
+        popup::{ConfirmAction, ConfirmPopupState, PopupContent},
+    },
+    msg::{
+        HunkReviewMessage, Message,
+        update::{commit::TOAST_DURATION, edit_hunk::edit_patch},
+    },
+};
+
+pub fn update(model: &mut Model, msg: HunkReviewMessage) -> Option<Message> {
+    match msg {
+        HunkReviewMessage::Start { staged } => start(model, staged),
+        HunkReviewMessage::Accept => decide(model, ReviewDecision::Accept),
+        HunkReviewMessage::Skip => decide(model, ReviewDecision::Skip),
+        HunkReviewMessage::Discard => {
+            if model.hunk_review.as_ref()?.staged {
+                show_toast(model, "Only unstaged hunks can be discarded in a review");
+                return None;
+            }
+            decide(model, ReviewDecision::Discard)
+        }
+        HunkReviewMessage::Split => {
+            let state = model.hunk_review.as_mut()?;
+            if state.split_current() {
+                show(model);
+            } else {
+                show_toast(model, "This hunk has a single block of changes");
+            }
+            None
+        }
+        HunkReviewMessage::Edit => edit(model),
+        HunkReviewMessage::Previous => {
+            if model.hunk_review.as_mut()?.previous_hunk() {
+                show(model);
+            } else {
+                show_toast(model, "This is the first hunk");
+            }
+            None
+        }
+        HunkReviewMessage::Next => {
+            if model.hunk_review.as_mut()?.next_hunk() {
+                show(model);
+            } else {
+                show_toast(model, "This is the last hunk");
+            }
+            None
+        }
+        HunkReviewMessage::Finish => finish(model),
+        HunkReviewMessage::Apply => apply(model),
+        HunkReviewMessage::Abort => {
+            let state = model.hunk_review.take()?;
+            leave(model, &state);
+            show_toast(model, "Review aborted: nothing was applied");
+            Some(Message::Refresh)
+        }
+    }
+}
+
+/// Opens the review of the unstaged (or staged) hunks, taking them and
+/// their files' diffs from the repository as they are now.
+fn start(model: &mut Model, staged: bool) -> Option<Message> {
+    model.popup = None;
+    let state = match review_state(model, staged) {
+        Ok(state) => state,
+        Err(err) => {
+            model.popup = Some(PopupContent::Error {
+                message: format!("Error starting the review: {}", err),
+            });
+            return None;
+        }
+    };
+    if state.hunks.is_empty() {
+        show_toast(
+            model,
+            if staged {
+                "No staged hunks to review"
+            } else {
+                "No unstaged hunks to review"
+            },
+        );
+        return None;
+    }
+
+    model.hunk_review = Some(state);
+    model.view_mode = ViewMode::HunkReview;
+    show(model);
+    None
+}
+
+fn review_state(model: &Model, staged: bool) -> MagiResult<HunkReviewState> {
+    let settings = &model.git_info.diff_settings;
+    let mut files: Vec<ReviewFile> = Vec::new();
+    let mut hunks: Vec<ReviewHunk> = Vec::new();
+    let renames = if staged {
+        staged_renames(&model.workdir)?
+    } else {
+        HashMap::new()
+    };
+
+    // A file is only reviewed once one of its hunks comes up
+    let mut file_line: Option<&Line> = None;
+    let lines = model.git_info.get_lines()?;
+    for line in &lines {
+        match (&line.content, &line.section) {
+            (LineContent::UnstagedFile(_), _) if !staged => file_line = Some(line),
+            (LineContent::StagedFile(_), _) if staged => file_line = Some(line),
+            (
+                LineContent::DiffHunk(_),
+                Some(
+                    SectionType::UnstagedHunk { path, hunk_index }
+                    | SectionType::StagedHunk { path, hunk_index },
+                ),
+            ) if matches!(line.section, Some(SectionType::StagedHunk { .. })) == staged => {
+                if files.last().is_none_or(|file| file.path != *path) {
+                    let diff = if staged {
+                        staged_file_diff(&model.workdir, path, &renames, settings)?
+                    } else {
+                        get_file_diff(&model.workdir, path, settings)?
+                    };
+                    files.push(ReviewFile {
+                        path: path.clone(),
+                        line: file_line.cloned().unwrap_or_else(|| line.clone()),
+                        diff,
+                    });
+                }
+                hunks.push(ReviewHunk {
+                    file: files.len() - 1,
+                    hunk_index: *hunk_index,
+                    line_indices: Vec::new(),
+                    lines: vec![line.clone()],
+                    decision: None,
+                });
+            }
+            (LineContent::DiffLine(_), Some(section)) => {
+                if let Some(hunk) = hunks
+                    .last_mut()
+                    .filter(|hunk| hunk.lines[0].section.as_ref() == Some(section))
+                {
+                    hunk.line_indices.push(hunk.line_indices.len());
+                    hunk.lines.push(line.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // The status view is restored with its cursor when the review ends
+    let return_ui_model = model.ui_model.clone();
+    Ok(HunkReviewState::new(staged, files, hunks, return_ui_model))
+}
+
+/// Shows the current hunk below its file, followed by the review keys.
+fn show(model: &mut Model) {
+    let Some(state) = &model.hunk_review else {
+        return;
+    };
+    let Some(hunk) = state.current_hunk() else {
+        return;
+    };
+    let t = i18n::t();
+
+    let mut lines = vec![state.files[hunk.file].line.clone()];
+    lines.extend(hunk.lines.iter().cloned());
+    lines.push(Line {
+        content: LineContent::EmptyLine,
+        section: None,
+    });
+    let hints = if state.staged {
+        vec![
+            ("y", t.review_hint_unstage),
+            ("n", t.review_hint_skip),
+            ("s", t.review_hint_split),
+            ("k/j", t.review_hint_move),
+            ("q", t.review_hint_finish),
+            ("Esc", t.review_hint_abort),
+        ]
+    } else {
+        vec![
+            ("y", t.review_hint_stage),
+            ("n", t.review_hint_skip),
+            ("d", t.review_hint_discard),
+            ("s", t.review_hint_split),
+            ("e", t.review_hint_edit),
+            ("k/j", t.review_hint_move),
+            ("q", t.review_hint_finish),
+            ("Esc", t.review_hint_abort),
+        ]
+    };
+    lines.extend(hints.into_iter().map(|(key, description)| Line {
+        content: LineContent::KeyHint { key, description },
+        section: None,
+    }));
+
+    model.ui_model = UiModel {
+        lines,
+        // On the hunk header, below the file
+        cursor_position: 1,
+        viewport_height: model.ui_model.viewport_height,
+        ..Default::default()
+    };
+}
+
+fn decide(model: &mut Model, decision: ReviewDecision) -> Option<Message> {
+    let state = model.hunk_review.as_mut()?;
+    state.decide(decision);
+    if state.is_finished() {
+        return finish(model);
+    }
+    show(model);
+    None
+}
+
+/// Opens the current unstaged hunk in the editor; the edited hunk is
+/// staged when the review finishes. A block of a split hunk is edited on
+/// its own, so it still applies after the blocks accepted around it.
+fn edit(model: &mut Model) -> Option<Message> {
+    let state = model.hunk_review.as_ref()?;
+    if state.staged {
+        show_toast(model, "Only unstaged hunks can be edited in a review");
+        return None;
+    }
+    let hunk = state.current_hunk()?;
+
+    let edited = build_block_patch(
+        &state.files[hunk.file].diff,
+        hunk.hunk_index,
+        &hunk.line_indices,
+    )
+    .and_then(|patch| edit_patch(model, &format!("{}{}", patch, EDIT_HUNK_GUIDE)))
+    .and_then(|edited| edited_hunk_patch(&edited));
+    match edited {
+        Ok(Some(patch)) => decide(model, ReviewDecision::Edited(patch)),
+        Ok(None) => {
+            show_toast(model, "The edited hunk has no changes; nothing decided");
+            None
+        }
+        Err(err) => {
+            model.popup = Some(PopupContent::Error {
+                message: format!("Error editing hunk: {}", err),
+            });
+            None
+        }
+    }
+}
+
+/// Ends the review, first asking to confirm when hunks are to be discarded.
+fn finish(model: &mut Model) -> Option<Message> {
+    let discarded = model
+        .hunk_review
+        .as_ref()?
+        .count(|decision| *decision == ReviewDecision::Discard);
+    if discarded == 0 {
+        return apply(model);
+    }
+    let t = i18n::t();
+    model.popup = Some(PopupContent::Confirm(ConfirmPopupState {
+        message: t.fmt1(t.review_confirm_discard_fmt, &discarded.to_string()),
+        on_confirm: ConfirmAction::ApplyHunkReview,
+    }));
+    None
+}
+
+/// Goes back to the status view the review started from.
+fn leave(model: &mut Model, state: &HunkReviewState) {
+    model.view_mode = ViewMode::Status;
+    model.ui_model = UiModel {
+        viewport_height: model.ui_model.viewport_height,
+        ..state.return_ui_model.clone()
+    };
+}
+
+/// Applies the decisions made and goes back to the status view.
+fn apply(model: &mut Model) -> Option<Message> {
+    model.popup = None;
+    let state = model.hunk_review.take()?;
+    leave(model, &state);
+
+    match apply_patches(model, &state) {
+        Ok(()) => {
+            let accepted = state.count(|decision| {
+                matches!(decision, ReviewDecision::Accept | ReviewDecision::Edited(_))
+            });
+            let message = if state.staged {
+                format!("Review finished: {} unstaged", accepted)
+            } else {
+                let discarded = state.count(|decision| *decision == ReviewDecision::Discard);
+                format!(
+                    "Review finished: {} staged, {} discarded",
+                    accepted, discarded
+                )
+            };
+            show_toast(model, &message);
+        }
+        Err(err) => {
+            model.popup = Some(PopupContent::Error {
+                message: format!("Error applying the review: {}", err),
+            });
+        }
+    }
+    Some(Message::Refresh)
+}
+
+/// Applies the review's patches. Whole hunks go through the same functions
+/// as staging, unstaging and discarding them in the status view.
+fn apply_patches(model: &Model, state: &HunkReviewState) -> MagiResult<()> {
+    let workdir = &model.workdir;
+    let settings = &model.git_info.diff_settings;
+    for patch in state.patches()? {
+        match patch {
+            ReviewPatch::StageHunk { path, hunk_index } => {
+                stage_hunk(workdir, &path, hunk_index, settings)?
+            }
+            ReviewPatch::UnstageHunk { path, hunk_index } => {
+                unstage_hunk(workdir, &path, hunk_index, settings)?
+            }
+            ReviewPatch::DiscardHunk { path, hunk_index } => {
+                discard_hunk(workdir, &path, hunk_index, settings)?
+            }
+            ReviewPatch::Stage(patch) => apply_patch_cached(workdir, &patch, settings)?,
+            ReviewPatch::Unstage(patch) => apply_patch_cached_reverse(workdir, &patch, settings)?,
+            ReviewPatch::Discard(patch) => apply_patch_reverse(workdir, &patch, settings)?,
+        }
+    }
+    Ok(())
+}
+
+fn show_toast(model: &mut Model, message: &str) {
+    model.toast = Some(Toast {
+        message: message.to_string(),
+        style: ToastStyle::Info,
+        expires_at: Instant::now() + TOAST_DURATION,
+    });
+}
@@ -1,11 +1,12 @@
 use crate::{
     model::{
         EditOp, Model,
+        arguments::ValueArgument,
         popup::{InputContext, PopupContent, PopupContentCommand},
     },
     msg::{
-        FetchCommand, Message, OnSelect, OptionsSource, PushCommand, ShowSelectPopupConfig,
-        StashCommand,
+        FetchCommand, Message, OnSelect, OptionsSource, PushCommand, RebaseTodoMessage,
+        ShowSelectPopupConfig, StashCommand,
     },
 };
 
@@ -34,7 +35,15 @@ pub fn confirm(model: &mut Model) -> Option<Message> {
     // RevertMainline allows empty input (empty = clear the mainline value)
     if let InputContext::RevertMainline { mut revert_state } = state.context {
         let input = state.input.as_str().trim().to_string();
-        revert_state.mainline = if input.is_empty() { None } else { Some(input) };
+        revert_state.mainline = if input.is_empty() {
+            model.argument_values.remove(&ValueArgument::Mainline);
+            None
+        } else {
+            model
+                .argument_values
+                .insert(ValueArgument::Mainline, input.clone());
+            Some(input)
+        };
         model.popup = Some(PopupContent::Command(PopupContentCommand::Revert(
             revert_state,
         )));
@@ -109,6 +118,22 @@ pub fn confirm(model: &mut Model) -> Option<Message> {
             on_select: OnSelect::CreateTagTarget { name: input },
         })),
         InputContext::TagRelease { .. } => Some(Message::CreateTagRelease { name: input }),
+        InputContext::Reshelve => Some(Message::Reshelve(input)),
+        InputContext::ArgumentValue {
+            argument,
+            return_to,
+        } => {
+            model.argument_values.insert(argument, input);
+            model.popup = Some(PopupContent::Command(*return_to));
+            None
+        }
+        InputContext::RebaseExec { index, replace } => {
+            Some(Message::RebaseTodo(RebaseTodoMessage::Exec {
+                index,
+                replace,
+                command: input,
+            }))
+        }
         InputContext::Stash(_) | InputContext::RevertMainline { .. } => unreachable!(),
     }
 }
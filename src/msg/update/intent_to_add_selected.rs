@@ -0,0 +1,75 @@
+use std::time::Instant;
+
+use crate::{
+    git::stage::intent_to_add_files,
+    i18n,
+    model::{
+        LineContent, Model, Toast, ToastStyle, cursor_context::CursorContext, popup::PopupContent,
+    },
+    msg::{Message, update::commit::TOAST_DURATION},
+};
+
+use super::selection::{
+    Selection, SelectionContext, get_normal_mode_selection, get_visual_mode_selection,
+};
+
+pub fn update(model: &mut Model) -> Option<Message> {
+    let selection = if model.ui_model.is_visual_mode() {
+        let (start, end) = model.ui_model.visual_selection_range()?;
+        get_visual_mode_selection(
+            &model.ui_model.lines,
+            start,
+            end,
+            &model.ui_model.collapsed_sections,
+            SelectionContext::Stageable,
+        )
+    } else {
+        get_normal_mode_selection(
+            &model.ui_model.lines,
+            model.ui_model.cursor_position,
+            SelectionContext::Stageable,
+        )
+    };
+
+    // Only untracked files can be added with intent to add
+    let files: Vec<String> = match selection {
+        Selection::Files(files) if files.iter().all(|file| is_untracked(model, file)) => {
+            files.into_iter().map(String::from).collect()
+        }
+        _ => {
+            model.toast = Some(Toast {
+                message: i18n::t().intent_to_add_untracked_only.to_string(),
+                style: ToastStyle::Info,
+                expires_at: Instant::now() + TOAST_DURATION,
+            });
+            return None;
+        }
+    };
+
+    // Exit visual mode after adding
+    model.ui_model.visual_mode_anchor = None;
+
+    model.cursor_reposition_context = Some(CursorContext::capture(
+        &model.ui_model.lines,
+        model.ui_model.cursor_position,
+    ));
+
+    let files: Vec<&str> = files.iter().map(String::as_str).collect();
+    if let Err(e) = intent_to_add_files(&model.workdir, &files) {
+        model.popup = Some(PopupContent::Error {
+            message: format!("Error adding with intent to add: {}", e),
+        });
+        model.cursor_reposition_context = None;
+        return None;
+    }
+
+    Some(Message::Refresh)
+}
+
+fn is_untracked(model: &Model, file: &str) -> bool {
+    model
+        .ui_model
+        .lines
+        .iter()
+        .any(|line| matches!(&line.content, LineContent::UntrackedFile(path) if path == file))
+}
@@ -2,36 +2,67 @@ use std::time::Instant;
 
 use crate::{
     git::{commit::CommitResult, merge, preview},
-    model::{Model, PopupContent, Toast, ToastStyle, ViewMode},
+    model::{
+        Model, PopupContent, Toast, ToastStyle, ViewMode,
+        arguments::{Arguments::MergeArguments, MergeArgument, PopupArgument, ValueArgument},
+    },
     msg::{
         MergeCommand, Message,
         update::{
             commit::TOAST_DURATION, pty_helper::execute_pty_command,
-            show_merge_popup::merge_in_progress,
+            show_argument_value_input::take_value_flags, show_merge_popup::merge_in_progress,
+            show_signing_key_select::take_signing_flags,
         },
     },
 };
 
 pub fn update(model: &mut Model, cmd: MergeCommand) -> Option<Message> {
+    let flags = take_merge_flags(model);
     match cmd {
-        MergeCommand::Branch(branch) => merge_branch(model, branch, false),
-        MergeCommand::EditMessage(branch) => merge_branch(model, branch, true),
-        MergeCommand::NoCommit(branch) => merge_no_commit(model, branch),
-        MergeCommand::Absorb(branch) => absorb_branch(model, branch),
+        MergeCommand::Branch(branch) => merge_branch(model, branch, false, flags),
+        MergeCommand::EditMessage(branch) => merge_branch(model, branch, true, flags),
+        MergeCommand::NoCommit(branch) => merge_no_commit(model, branch, flags),
+        MergeCommand::Absorb(branch) => absorb_branch(model, branch, flags),
         MergeCommand::Preview(branch) => preview_merge(model, branch),
-        MergeCommand::Squash(branch) => squash_merge(model, branch),
-        MergeCommand::Dissolve(branch) => dissolve_branch(model, branch),
+        MergeCommand::Squash(branch) => squash_merge(model, branch, flags),
+        MergeCommand::Dissolve(branch) => dissolve_branch(model, branch, flags),
         MergeCommand::Continue => continue_merge(model),
         MergeCommand::Abort => abort_merge(model),
     }
 }
 
-fn merge_branch(model: &mut Model, branch: String, edit_message: bool) -> Option<Message> {
+/// Consumes the merge popup arguments, returning their git flags in a
+/// stable order, followed by `--strategy`, `--strategy-option` and
+/// `--gpg-sign`.
+fn take_merge_flags(model: &mut Model) -> Vec<String> {
+    let mut flags: Vec<String> = if let Some(MergeArguments(arguments)) = model.arguments.take() {
+        MergeArgument::all()
+            .into_iter()
+            .filter(|arg| arguments.contains(arg))
+            .map(|arg| arg.flag().to_string())
+            .collect()
+    } else {
+        vec![]
+    };
+    flags.extend(take_value_flags(
+        model,
+        &[ValueArgument::Strategy, ValueArgument::StrategyOption],
+    ));
+    flags.extend(take_signing_flags(model));
+    flags
+}
+
+fn merge_branch(
+    model: &mut Model,
+    branch: String,
+    edit_message: bool,
+    flags: Vec<String>,
+) -> Option<Message> {
     model.popup = None;
     let result = if edit_message {
-        merge::run_merge_edit_with_editor(&model.workdir, &branch)
+        merge::run_merge_edit_with_editor(&model.workdir, &branch, &flags)
     } else {
-        merge::run_merge_with_editor(&model.workdir, &branch)
+        merge::run_merge_with_editor(&model.workdir, &branch, &flags)
     };
     match result {
         Ok(CommitResult { success, message }) => {
@@ -82,9 +113,9 @@ fn conflict_message(headline: &str, conflicts: &[String]) -> String {
     message
 }
 
-fn absorb_branch(model: &mut Model, branch: String) -> Option<Message> {
+fn absorb_branch(model: &mut Model, branch: String, flags: Vec<String>) -> Option<Message> {
     model.popup = None;
-    match merge::run_merge_absorb(&model.workdir, &branch) {
+    match merge::run_merge_absorb(&model.workdir, &branch, &flags) {
         Ok(CommitResult { success, message }) => {
             if let Some(conflicts) = (!success).then(|| unresolved_conflicts(model)).flatten() {
                 model.popup = Some(PopupContent::Error {
@@ -114,7 +145,7 @@ fn absorb_branch(model: &mut Model, branch: String) -> Option<Message> {
     Some(Message::Refresh)
 }
 
-fn dissolve_branch(model: &mut Model, target: String) -> Option<Message> {
+fn dissolve_branch(model: &mut Model, target: String, flags: Vec<String>) -> Option<Message> {
     model.popup = None;
     // On a detached HEAD `current_branch()` reports "HEAD", which is not a
     // branch that can be dissolved.
@@ -125,7 +156,7 @@ fn dissolve_branch(model: &mut Model, target: String) -> Option<Message> {
         });
         return Some(Message::Refresh);
     };
-    match merge::run_merge_dissolve(&model.workdir, &current, &target) {
+    match merge::run_merge_dissolve(&model.workdir, &current, &target, &flags) {
         Ok(CommitResult { success, message }) => {
             if let Some(conflicts) = (!success).then(|| unresolved_conflicts(model)).flatten() {
                 model.popup = Some(PopupContent::Error {
@@ -182,25 +213,28 @@ fn preview_merge(model: &mut Model, branch: String) -> Option<Message> {
     None
 }
 
-fn merge_no_commit(model: &mut Model, branch: String) -> Option<Message> {
-    execute_pty_command(
-        model,
-        vec![
-            "merge".to_string(),
-            "--no-commit".to_string(),
-            "--no-ff".to_string(),
-            branch,
-        ],
-        "Merge".to_string(),
-    )
+/// `--no-commit` always stops before a merge commit, so `--no-ff` is forced
+/// and a contradicting `--ff-only` is dropped.
+fn merge_no_commit(model: &mut Model, branch: String, flags: Vec<String>) -> Option<Message> {
+    let mut args = vec![
+        "merge".to_string(),
+        "--no-commit".to_string(),
+        "--no-ff".to_string(),
+    ];
+    args.extend(flags.into_iter().filter(|flag| flag != "--ff-only"));
+    args.push(branch);
+    execute_pty_command(model, args, "Merge".to_string())
 }
 
-fn squash_merge(model: &mut Model, branch: String) -> Option<Message> {
-    execute_pty_command(
-        model,
-        vec!["merge".to_string(), "--squash".to_string(), branch],
-        "Merge".to_string(),
-    )
+/// `git merge --squash` never commits, so `--no-ff` (which git rejects
+/// alongside it) and the commit-only flags are dropped.
+fn squash_merge(model: &mut Model, branch: String, flags: Vec<String>) -> Option<Message> {
+    let mut args = vec!["merge".to_string(), "--squash".to_string()];
+    args.extend(flags.into_iter().filter(|flag| {
+        flag != "--no-ff" && flag != "--signoff" && !flag.starts_with("--gpg-sign")
+    }));
+    args.push(branch);
+    execute_pty_command(model, args, "Merge".to_string())
 }
 
 fn continue_merge(model: &mut Model) -> Option<Message> {
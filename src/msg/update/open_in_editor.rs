@@ -0,0 +1,17 @@
+use std::path::PathBuf;
+
+use crate::{
+    git::visit::open_in_editor,
+    model::{Model, popup::PopupContent},
+    msg::Message,
+};
+
+pub fn update(model: &mut Model, file: PathBuf, line: Option<usize>) -> Option<Message> {
+    if let Err(err) = open_in_editor(&model.workdir, &file, line) {
+        model.popup = Some(PopupContent::Error {
+            message: format!("Failed to open {}: {}", file.display(), err),
+        });
+    }
+    // The file may have been edited
+    Some(Message::Refresh)
+}
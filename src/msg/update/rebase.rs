@@ -8,21 +8,34 @@ use crate::{
     },
     model::{
         Model, Toast, ToastStyle, ViewMode,
-        arguments::{Arguments::RebaseArguments, PopupArgument},
+        arguments::{Arguments::RebaseArguments, PopupArgument, ValueArgument},
         popup::{PopupContent, PopupContentCommand},
     },
-    msg::{Message, RebaseCommand, update::pty_helper::execute_pty_command},
+    msg::{
+        Message, RebaseCommand,
+        update::{
+            pty_helper::execute_pty_command, show_argument_value_input::take_value_flags,
+            show_signing_key_select::take_signing_flags,
+        },
+    },
 };
 
+/// The value-taking arguments of the rebase popup
+pub const REBASE_VALUE_ARGUMENTS: [ValueArgument; 2] =
+    [ValueArgument::Exec, ValueArgument::StrategyOption];
+
 pub fn update(model: &mut Model, rebase_command: RebaseCommand) -> Option<Message> {
-    let extra_args: Vec<String> = if let Some(RebaseArguments(arguments)) = model.arguments.take() {
-        arguments
-            .into_iter()
-            .map(|a| a.flag().to_string())
-            .collect()
-    } else {
-        vec![]
-    };
+    let mut extra_args: Vec<String> =
+        if let Some(RebaseArguments(arguments)) = model.arguments.take() {
+            arguments
+                .into_iter()
+                .map(|a| a.flag().to_string())
+                .collect()
+        } else {
+            vec![]
+        };
+    extra_args.extend(take_value_flags(model, &REBASE_VALUE_ARGUMENTS));
+    extra_args.extend(take_signing_flags(model));
     match rebase_command {
         RebaseCommand::OntoPushRemote(remote) => onto_push_remote(model, remote, extra_args),
         RebaseCommand::OntoUpstream => onto_upstream(model, extra_args),
@@ -31,12 +44,17 @@ pub fn update(model: &mut Model, rebase_command: RebaseCommand) -> Option<Messag
         }
         RebaseCommand::Elsewhere(target) => elsewhere(model, target, extra_args),
         RebaseCommand::Subset { newbase, start } => subset(model, newbase, start, extra_args),
-        RebaseCommand::ExecuteInteractive => execute_interactive(model),
-        RebaseCommand::ModifyCommit(commit) => modify_commit(model, commit),
-        RebaseCommand::RewordCommit(commit) => reword_commit(model, commit),
-        RebaseCommand::RemoveCommit(commit) => remove_commit(model, commit),
-        RebaseCommand::Autosquash(base) => autosquash(model, base, false),
-        RebaseCommand::AutosquashInto(commit) => autosquash(model, commit, true),
+        RebaseCommand::ExecuteInteractive => execute_interactive(model, extra_args),
+        RebaseCommand::ModifyCommit(commit) => modify_commit(model, commit, extra_args),
+        RebaseCommand::RewordCommit(commit) => reword_commit(model, commit, extra_args),
+        RebaseCommand::RemoveCommit(commit) => remove_commit(model, commit, extra_args),
+        RebaseCommand::Autosquash(base) => autosquash(model, base, false, extra_args),
+        // Instant fixups come from the commit popup, which has no autostash
+        // argument, so like Magit's they always autostash
+        RebaseCommand::AutosquashInto(commit) => {
+            extra_args.push("--autostash".to_string());
+            autosquash(model, commit, true, extra_args)
+        }
         RebaseCommand::Continue => continue_rebase(model),
         RebaseCommand::Skip => skip_rebase(model),
         RebaseCommand::Abort => abort_rebase(model),
@@ -46,7 +64,7 @@ pub fn update(model: &mut Model, rebase_command: RebaseCommand) -> Option<Messag
 /// Runs the interactive rebase prepared in the todo editor. This is an
 /// external command (the TUI is suspended) because git may open the user's
 /// editor for reword/squash commit messages.
-fn execute_interactive(model: &mut Model) -> Option<Message> {
+fn execute_interactive(model: &mut Model, extra_args: Vec<String>) -> Option<Message> {
     let Some(state) = model.rebase_todo.take() else {
         return Some(Message::Refresh);
     };
@@ -57,6 +75,7 @@ fn execute_interactive(model: &mut Model) -> Option<Message> {
         &state.base,
         state.base_has_parent,
         &state.entries,
+        &extra_args,
     ) {
         Ok(CommitResult { success, message }) => {
             model.toast = Some(Toast {
@@ -81,10 +100,10 @@ fn execute_interactive(model: &mut Model) -> Option<Message> {
 /// Starts an interactive rebase that stops at `commit` for editing. This is
 /// an external command (the TUI is suspended) because git prints rebase
 /// progress and stop instructions to the terminal.
-fn modify_commit(model: &mut Model, commit: String) -> Option<Message> {
+fn modify_commit(model: &mut Model, commit: String, extra_args: Vec<String>) -> Option<Message> {
     model.popup = None;
 
-    match rebase::run_modify_commit(&model.workdir, &commit) {
+    match rebase::run_modify_commit(&model.workdir, &commit, &extra_args) {
         Ok(CommitResult { success, message }) => {
             model.toast = Some(Toast {
                 message,
@@ -108,10 +127,10 @@ fn modify_commit(model: &mut Model, commit: String) -> Option<Message> {
 /// Starts an interactive rebase that rewords `commit`. This is an external
 /// command (the TUI is suspended) because git opens the user's editor for
 /// the new commit message.
-fn reword_commit(model: &mut Model, commit: String) -> Option<Message> {
+fn reword_commit(model: &mut Model, commit: String, extra_args: Vec<String>) -> Option<Message> {
     model.popup = None;
 
-    match rebase::run_reword_commit(&model.workdir, &commit) {
+    match rebase::run_reword_commit(&model.workdir, &commit, &extra_args) {
         Ok(CommitResult { success, message }) => {
             model.toast = Some(Toast {
                 message,
@@ -135,10 +154,10 @@ fn reword_commit(model: &mut Model, commit: String) -> Option<Message> {
 /// Starts an interactive rebase that removes `commit`. This is an external
 /// command (the TUI is suspended) because git prints rebase progress and,
 /// on conflict, stop instructions to the terminal.
-fn remove_commit(model: &mut Model, commit: String) -> Option<Message> {
+fn remove_commit(model: &mut Model, commit: String, extra_args: Vec<String>) -> Option<Message> {
     model.popup = None;
 
-    match rebase::run_remove_commit(&model.workdir, &commit) {
+    match rebase::run_remove_commit(&model.workdir, &commit, &extra_args) {
         Ok(CommitResult { success, message }) => {
             model.toast = Some(Toast {
                 message,
@@ -162,10 +181,15 @@ fn remove_commit(model: &mut Model, commit: String) -> Option<Message> {
 /// Runs `git rebase --autosquash` from `base` (see [`rebase::run_autosquash`]).
 /// This is an external command (the TUI is suspended) because `squash!`
 /// commits open the user's editor for the combined message.
-fn autosquash(model: &mut Model, base: String, include_base: bool) -> Option<Message> {
+fn autosquash(
+    model: &mut Model,
+    base: String,
+    include_base: bool,
+    extra_args: Vec<String>,
+) -> Option<Message> {
     model.popup = None;
 
-    match rebase::run_autosquash(&model.workdir, &base, include_base) {
+    match rebase::run_autosquash(&model.workdir, &base, include_base, &extra_args) {
         Ok(CommitResult { success, message }) => {
             model.toast = Some(Toast {
                 message,
@@ -195,6 +219,7 @@ fn elsewhere(model: &mut Model, target: String, extra_args: Vec<String>) -> Opti
 
 /// Rebase a subset of the current branch's history onto a new base:
 /// `git rebase --onto <newbase> <start>^` (or `--root` when start has no parent).
+/// Git rejects `--keep-base` together with `--onto`, so it's left out.
 fn subset(
     model: &mut Model,
     newbase: String,
@@ -202,7 +227,7 @@ fn subset(
     extra_args: Vec<String>,
 ) -> Option<Message> {
     let mut args = vec!["rebase".to_string()];
-    args.extend(extra_args);
+    args.extend(extra_args.into_iter().filter(|arg| arg != "--keep-base"));
     args.extend(["--onto".to_string(), newbase.clone()]);
     if rebase::commit_has_parent(&model.workdir, &start) {
         args.push(format!("{}^", start));
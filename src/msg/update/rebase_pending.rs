@@ -0,0 +1,98 @@
+use std::time::Instant;
+
+use crate::{
+    git::rebase::{self, RebaseAction},
+    model::{Model, Toast, ToastStyle, popup::PopupContent, rebase_todo::RebaseTodoState},
+    msg::{Message, RebasePendingMessage, update::commit::TOAST_DURATION},
+};
+
+/// Applies an edit to a pending entry of the stopped rebase and writes the
+/// todo back. The edits go through [`RebaseTodoState`], so they are held to
+/// the same rules as in the todo editor.
+pub fn update(model: &mut Model, msg: RebasePendingMessage) -> Option<Message> {
+    let entries = match rebase::read_rebase_todo(&model.workdir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            model.popup = Some(PopupContent::Error {
+                message: e.to_string(),
+            });
+            return None;
+        }
+    };
+    let mut state = RebaseTodoState::for_stopped_rebase(entries);
+
+    let hash = match &msg {
+        RebasePendingMessage::ToggleDrop(hash)
+        | RebasePendingMessage::ToggleReword(hash)
+        | RebasePendingMessage::MoveUp(hash)
+        | RebasePendingMessage::MoveDown(hash) => hash.clone(),
+    };
+    // The section shows abbreviated hashes, and git may abbreviate the todo
+    let index = state.entries.iter().position(|entry| {
+        entry.action.is_commit()
+            && !entry.hash.is_empty()
+            && (entry.hash.starts_with(&hash) || hash.starts_with(&entry.hash))
+    })?;
+
+    let changed = match msg {
+        RebasePendingMessage::ToggleDrop(_) => toggle(&mut state, index, RebaseAction::Drop),
+        RebasePendingMessage::ToggleReword(_) => toggle(&mut state, index, RebaseAction::Reword),
+        RebasePendingMessage::MoveUp(_) => move_past_commit(&mut state, index, true),
+        RebasePendingMessage::MoveDown(_) => move_past_commit(&mut state, index, false),
+    };
+    if !changed {
+        return None;
+    }
+
+    if let Err(e) = rebase::write_rebase_todo(&model.workdir, &state.entries) {
+        model.popup = Some(PopupContent::Error {
+            message: e.to_string(),
+        });
+        return None;
+    }
+    model.toast = Some(Toast {
+        message: "Rebase todo updated".to_string(),
+        style: ToastStyle::Success,
+        expires_at: Instant::now() + TOAST_DURATION,
+    });
+    Some(Message::Refresh)
+}
+
+fn toggle(state: &mut RebaseTodoState, index: usize, action: RebaseAction) -> bool {
+    let new_action = if state.entries[index].action == action {
+        RebaseAction::Pick
+    } else {
+        action
+    };
+    state.set_action(index, new_action)
+}
+
+/// Moves the entry at `index` until it has swapped places with one commit
+/// line, passing any exec/break/... lines in between (they are not shown in
+/// the section). Nothing is kept unless the whole move is valid.
+fn move_past_commit(state: &mut RebaseTodoState, index: usize, up: bool) -> bool {
+    let mut index = index;
+    loop {
+        let neighbour = if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1)
+        };
+        let Some(neighbour) = neighbour.filter(|&i| i < state.entries.len()) else {
+            return false;
+        };
+        let passes_commit = state.entries[neighbour].action.is_commit();
+        let moved = if up {
+            state.move_entry_up(index)
+        } else {
+            state.move_entry_down(index)
+        };
+        if !moved {
+            return false;
+        }
+        index = neighbour;
+        if passes_commit {
+            return true;
+        }
+    }
+}
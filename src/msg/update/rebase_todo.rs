@@ -1,38 +1,59 @@
 use std::time::{Duration, Instant};
 
 use crate::{
-    git::rebase::{self, RebaseAction, RebaseTodoEntry},
+    git::rebase::{self, RebaseAction},
     i18n,
     model::{
-        Line, LineContent, Model, Toast, ToastStyle, ViewMode, popup::PopupContent,
-        rebase_todo::RebaseTodoState,
+        InputField, Line, LineContent, Model, Toast, ToastStyle, ViewMode,
+        arguments::{Arguments::RebaseArguments, PopupArgument},
+        popup::{InputContext, InputPopupState, PopupContent},
+        rebase_todo::{RebaseTodoState, is_removable},
+    },
+    msg::{
+        Message, RebaseTodoMessage,
+        update::{rebase::REBASE_VALUE_ARGUMENTS, show_argument_value_input::value_flags},
     },
-    msg::{Message, RebaseTodoMessage},
 };
 
 const TOAST_DURATION: Duration = Duration::from_secs(5);
 
 /// Opens the interactive rebase todo editor for `base..HEAD` (base inclusive).
+///
+/// When the rebase arguments ask for `--rebase-merges`, `--update-refs` or
+/// `--exec`, the todo is the one git generates, so its `label`/`reset`/
+/// `merge`, `update-ref` and `exec` lines are kept.
 pub fn show(model: &mut Model, base: String) -> Option<Message> {
     let base_has_parent = rebase::commit_has_parent(&model.workdir, &base);
-    match rebase::get_interactive_rebase_commits(&model.workdir, &base, base_has_parent) {
+    // The arguments stay in the model until the rebase is confirmed
+    let mut flags: Vec<String> = match &model.arguments {
+        Some(RebaseArguments(arguments)) => {
+            arguments.iter().map(|a| a.flag().to_string()).collect()
+        }
+        _ => vec![],
+    };
+    flags.extend(value_flags(model, &REBASE_VALUE_ARGUMENTS));
+    match rebase::get_interactive_rebase_todo(&model.workdir, &base, base_has_parent, &flags) {
         Ok(entries) if entries.is_empty() => {
             model.popup = Some(PopupContent::Error {
                 message: "No commits to rebase".to_string(),
             });
             None
         }
-        Ok(entries) => {
-            let state = RebaseTodoState::new(base, base_has_parent, entries);
-            model.ui_model.lines = todo_lines(&state.entries);
-            model.ui_model.cursor_position = 0;
-            model.ui_model.scroll_offset = 0;
-            model.ui_model.visual_mode_anchor = None;
-            model.rebase_todo = Some(state);
-            model.view_mode = ViewMode::RebaseTodo;
-            model.popup = None;
+        Ok(entries) => open(model, RebaseTodoState::new(base, base_has_parent, entries)),
+        Err(e) => {
+            model.popup = Some(PopupContent::Error {
+                message: e.to_string(),
+            });
             None
         }
+    }
+}
+
+/// Opens the remaining todo of the stopped rebase in the editor, like
+/// `git rebase --edit-todo`. Confirming writes the list back.
+pub fn show_in_progress(model: &mut Model) -> Option<Message> {
+    match rebase::read_rebase_todo(&model.workdir) {
+        Ok(entries) => open(model, RebaseTodoState::for_stopped_rebase(entries)),
         Err(e) => {
             model.popup = Some(PopupContent::Error {
                 message: e.to_string(),
@@ -42,12 +63,31 @@ pub fn show(model: &mut Model, base: String) -> Option<Message> {
     }
 }
 
+fn open(model: &mut Model, state: RebaseTodoState) -> Option<Message> {
+    model.ui_model.lines = todo_lines(&state);
+    model.ui_model.cursor_position = 0;
+    model.ui_model.scroll_offset = 0;
+    model.ui_model.visual_mode_anchor = None;
+    model.rebase_todo = Some(state);
+    model.view_mode = ViewMode::RebaseTodo;
+    model.popup = None;
+    None
+}
+
 pub fn update(model: &mut Model, msg: RebaseTodoMessage) -> Option<Message> {
     match msg {
         RebaseTodoMessage::SetAction(action) => set_action(model, action),
         RebaseTodoMessage::MoveEntryUp => move_entry(model, true),
         RebaseTodoMessage::MoveEntryDown => move_entry(model, false),
+        RebaseTodoMessage::InsertBreak => insert_break(model),
+        RebaseTodoMessage::ShowExecInput { edit } => show_exec_input(model, edit),
+        RebaseTodoMessage::Exec {
+            index,
+            replace,
+            command,
+        } => exec(model, index, replace, command),
         RebaseTodoMessage::Undo => undo(model),
+        RebaseTodoMessage::Save => save(model),
         RebaseTodoMessage::Abort => abort(model),
         RebaseTodoMessage::CommandStart => {
             let state = model.rebase_todo.as_mut()?;
@@ -97,12 +137,28 @@ fn command_invalid(model: &mut Model) -> Option<Message> {
 fn set_action(model: &mut Model, action: RebaseAction) -> Option<Message> {
     let index = model.ui_model.cursor_position;
     let state = model.rebase_todo.as_mut()?;
+    let current = state.entries.get(index)?.action;
+    // Dropping an inserted line (exec, break, update-ref) removes it
+    if action == RebaseAction::Drop && is_removable(current) {
+        if state.remove_entry(index) {
+            model.ui_model.lines = todo_lines(state);
+        }
+        return None;
+    }
+    if !current.is_commit() {
+        model.toast = Some(Toast {
+            message: format!("Cannot change the action of a {} line", current.as_str()),
+            style: ToastStyle::Warning,
+            expires_at: Instant::now() + TOAST_DURATION,
+        });
+        return None;
+    }
     if state.set_action(index, action) {
         // Auto-advance to the next entry, like Magit's rebase editor
         if index + 1 < state.entries.len() {
             model.ui_model.cursor_position = index + 1;
         }
-        model.ui_model.lines = todo_lines(&state.entries);
+        model.ui_model.lines = todo_lines(state);
     } else if index == 0 && action.is_fold() {
         model.toast = Some(Toast {
             message: "Cannot squash/fixup without a previous commit".to_string(),
@@ -122,17 +178,63 @@ fn move_entry(model: &mut Model, up: bool) -> Option<Message> {
         state.move_entry_down(index)
     };
     if moved {
-        model.ui_model.lines = todo_lines(&state.entries);
+        model.ui_model.lines = todo_lines(state);
         // Keep the cursor on the entry that was moved
         model.ui_model.cursor_position = if up { index - 1 } else { index + 1 };
     }
     None
 }
 
+fn insert_break(model: &mut Model) -> Option<Message> {
+    let index = model.ui_model.cursor_position;
+    let state = model.rebase_todo.as_mut()?;
+    if state.insert_break(index) {
+        model.ui_model.lines = todo_lines(state);
+        model.ui_model.cursor_position = (index + 1).min(state.entries.len() - 1);
+    }
+    None
+}
+
+/// Opens the input for an `exec` command. With `edit`, the command of the
+/// `exec` line under the cursor is prefilled and replaced on confirm.
+fn show_exec_input(model: &mut Model, edit: bool) -> Option<Message> {
+    let index = model.ui_model.cursor_position;
+    let state = model.rebase_todo.as_ref()?;
+    let current = match state.entries.get(index) {
+        Some(entry) if edit && entry.action == RebaseAction::Exec => entry.message.clone(),
+        _ if edit => return None,
+        _ => String::new(),
+    };
+    model.popup = Some(PopupContent::Input(InputPopupState {
+        input: InputField::from_text(current),
+        context: InputContext::RebaseExec {
+            index,
+            replace: edit,
+        },
+    }));
+    None
+}
+
+fn exec(model: &mut Model, index: usize, replace: bool, command: String) -> Option<Message> {
+    let state = model.rebase_todo.as_mut()?;
+    let changed = if replace {
+        state.set_exec_command(index, &command)
+    } else {
+        state.insert_exec(index, &command)
+    };
+    if changed {
+        model.ui_model.lines = todo_lines(state);
+        if !replace {
+            model.ui_model.cursor_position = (index + 1).min(state.entries.len() - 1);
+        }
+    }
+    None
+}
+
 fn undo(model: &mut Model) -> Option<Message> {
     let state = model.rebase_todo.as_mut()?;
     if state.undo() {
-        model.ui_model.lines = todo_lines(&state.entries);
+        model.ui_model.lines = todo_lines(state);
         let max_pos = model.ui_model.lines.len().saturating_sub(1);
         if model.ui_model.cursor_position > max_pos {
             model.ui_model.cursor_position = max_pos;
@@ -141,6 +243,27 @@ fn undo(model: &mut Model) -> Option<Message> {
     None
 }
 
+/// Writes the edited todo back to the stopped rebase and closes the editor.
+fn save(model: &mut Model) -> Option<Message> {
+    let state = model.rebase_todo.take()?;
+    model.view_mode = ViewMode::Status;
+    match rebase::write_rebase_todo(&model.workdir, &state.entries) {
+        Ok(()) => {
+            model.toast = Some(Toast {
+                message: "Rebase todo updated".to_string(),
+                style: ToastStyle::Success,
+                expires_at: Instant::now() + TOAST_DURATION,
+            });
+        }
+        Err(e) => {
+            model.popup = Some(PopupContent::Error {
+                message: e.to_string(),
+            });
+        }
+    }
+    Some(Message::Refresh)
+}
+
 /// Closes the editor without touching the repository. Nothing has been
 /// executed yet, so this simply returns to the status view.
 fn abort(model: &mut Model) -> Option<Message> {
@@ -151,9 +274,10 @@ fn abort(model: &mut Model) -> Option<Message> {
 
 /// Builds the UI lines from the todo entries (one line per entry), followed
 /// by a keybinding hint block (one line per key).
-pub fn todo_lines(entries: &[RebaseTodoEntry]) -> Vec<Line> {
+pub fn todo_lines(state: &RebaseTodoState) -> Vec<Line> {
     let t = i18n::t();
-    let mut lines: Vec<Line> = entries
+    let mut lines: Vec<Line> = state
+        .entries
         .iter()
         .map(|entry| Line {
             content: LineContent::RebaseTodoLine(entry.clone()),
@@ -171,14 +295,18 @@ pub fn todo_lines(entries: &[RebaseTodoEntry]) -> Vec<Line> {
         ("e", t.rebase_hint_edit),
         ("s", t.rebase_hint_squash),
         ("f", t.rebase_hint_fixup),
+        ("F", t.rebase_hint_fixup_keep),
+        ("S", t.rebase_hint_fixup_edit),
         ("d", t.rebase_hint_drop),
+        ("x", t.rebase_hint_exec),
+        ("b", t.rebase_hint_break),
         ("K/J", t.rebase_hint_move),
         ("u", t.rebase_hint_undo),
         ("RET", t.rebase_hint_show),
     ];
     for (key, description) in edit_hints {
         lines.push(Line {
-            content: LineContent::RebaseTodoHint { key, description },
+            content: LineContent::KeyHint { key, description },
             section: None,
         });
     }
@@ -188,9 +316,14 @@ pub fn todo_lines(entries: &[RebaseTodoEntry]) -> Vec<Line> {
         content: LineContent::EmptyLine,
         section: None,
     });
-    for (key, description) in [("R", t.rebase_hint_confirm), ("q", t.rebase_hint_abort)] {
+    let confirm = if state.in_progress {
+        t.rebase_hint_save
+    } else {
+        t.rebase_hint_confirm
+    };
+    for (key, description) in [("R", confirm), ("q", t.rebase_hint_abort)] {
         lines.push(Line {
-            content: LineContent::RebaseTodoHint { key, description },
+            content: LineContent::KeyHint { key, description },
             section: None,
         });
     }
@@ -1,7 +1,12 @@
+use std::collections::HashSet;
+
 use crate::{
-    git::log::get_log_entries,
-    model::{Line, LineContent, Model, ViewMode},
-    msg::Message,
+    git::{diff_utils::split_hunks, log::get_log_entries},
+    model::{Line, LineContent, Model, SectionType, ViewMode},
+    msg::{
+        Message,
+        update::{blob, split_hunk},
+    },
 };
 
 pub fn update(model: &mut Model) -> Option<Message> {
@@ -11,12 +16,22 @@ pub fn update(model: &mut Model) -> Option<Message> {
             log_type,
             graph,
             color,
+            signatures,
             ..
-        } => refresh_log(model, &log_type, graph, color),
+        } => refresh_log(model, &log_type, graph, color, signatures),
         // In preview mode, refresh is a no-op (preview content is static)
         ViewMode::Preview => {}
+        ViewMode::Blob {
+            revision,
+            path,
+            blame,
+        } => return blob::reload(model, &revision, &path, blame),
         // The rebase todo editor holds in-memory state; nothing to refresh
         ViewMode::RebaseTodo => {}
+        // The diff below the commit message editor is fixed while writing
+        ViewMode::CommitMessage => {}
+        // The reviewed hunks are fixed until the review finishes
+        ViewMode::HunkReview => {}
     }
     None
 }
@@ -28,7 +43,7 @@ fn refresh_status(model: &mut Model) {
         .ui_model
         .collapsed_sections
         .iter()
-        .filter_map(|section| section.file_path().map(String::from))
+        .filter_map(|section| section_path(section).map(String::from))
         .collect();
 
     // Collect all file paths that exist before the refresh (collapsed or expanded)
@@ -37,13 +52,33 @@ fn refresh_status(model: &mut Model) {
         .ui_model
         .lines
         .iter()
-        .filter_map(|line| line.section.as_ref())
-        .filter_map(|section| section.file_path().map(String::from))
+        .flat_map(Line::sections)
+        .filter_map(|section| section_path(&section).map(String::from))
+        .collect();
+
+    // Untracked files are only read for a preview once expanded
+    model.git_info.expanded_untracked = model
+        .ui_model
+        .lines
+        .iter()
+        .filter_map(|line| match &line.content {
+            LineContent::UntrackedFile(path)
+                if !model
+                    .ui_model
+                    .collapsed_sections
+                    .contains(&SectionType::UntrackedFile { path: path.clone() }) =>
+            {
+                Some(path.clone())
+            }
+            _ => None,
+        })
         .collect();
 
     // Refresh the UI model by regenerating lines from git info
     if let Ok(lines) = model.git_info.get_lines() {
-        model.ui_model.lines = lines;
+        model.ui_model.split_hunks = split_hunk::relocate(&lines, &model.ui_model.split_hunks);
+        let split: HashSet<SectionType> = model.ui_model.split_hunks.keys().cloned().collect();
+        model.ui_model.lines = split_hunks(lines, &split);
 
         // Smart cursor repositioning if context was saved
         if let Some(context) = model.cursor_reposition_context.take() {
@@ -60,9 +95,14 @@ fn refresh_status(model: &mut Model) {
         // Restore collapsed state for files based on their paths
         // This preserves collapsed state when files move between staged/unstaged.
         // New files (paths not seen before the refresh) are collapsed by default.
-        for line in &model.ui_model.lines {
-            if let Some(section) = &line.section
-                && let Some(path) = section.file_path()
+        let sections: Vec<SectionType> = model
+            .ui_model
+            .lines
+            .iter()
+            .flat_map(Line::sections)
+            .collect();
+        for section in &sections {
+            if let Some(path) = section_path(section)
                 && (collapsed_file_paths.contains(path)
                     || !existing_file_paths.contains(path) && section.default_collapsed())
             {
@@ -70,22 +110,38 @@ fn refresh_status(model: &mut Model) {
             }
         }
 
-        // Clean up old file sections that no longer exist in the new lines
-        let current_sections: std::collections::HashSet<_> = model
-            .ui_model
-            .lines
-            .iter()
-            .filter_map(|line| line.section.clone())
-            .collect();
-        model
-            .ui_model
-            .collapsed_sections
-            .retain(|section| current_sections.contains(section) || section.file_path().is_none());
+        // Clean up old file and directory sections that no longer exist in
+        // the new lines
+        let current_sections: std::collections::HashSet<_> = sections.into_iter().collect();
+        model.ui_model.collapsed_sections.retain(|section| {
+            current_sections.contains(section)
+                || section_path(section).is_none() && section.directory_path().is_none()
+        });
     }
 }
 
-fn refresh_log(model: &mut Model, log_type: &crate::msg::LogType, graph: bool, color: bool) {
-    if let Ok(entries) = get_log_entries(&model.git_info.repository, log_type, graph, color) {
+/// The path of a file section, which its collapsed state is kept by across
+/// refreshes so it follows the file between the untracked, unstaged and
+/// staged sections. Directories are kept by their whole section, as the same
+/// directory can be listed in each of them.
+fn section_path(section: &SectionType) -> Option<&str> {
+    section.file_path()
+}
+
+fn refresh_log(
+    model: &mut Model,
+    log_type: &crate::msg::LogType,
+    graph: bool,
+    color: bool,
+    signatures: bool,
+) {
+    if let Ok(entries) = get_log_entries(
+        &model.git_info.repository,
+        log_type,
+        graph,
+        color,
+        signatures,
+    ) {
         let lines: Vec<Line> = entries
             .into_iter()
             .map(|entry| Line {
@@ -0,0 +1,86 @@
+use std::time::Instant;
+
+use crate::{
+    git::conflict::{
+        ConflictCheckout, checkout_conflict, resolve_conflict, run_mergetool, unmerged_paths,
+    },
+    model::{Model, Toast, ToastStyle, popup::PopupContent},
+    msg::{Message, ResolveCommand, update::commit::TOAST_DURATION},
+};
+
+pub fn update(model: &mut Model, command: ResolveCommand) -> Option<Message> {
+    model.popup = None;
+
+    match command {
+        ResolveCommand::Conflict { path, index, side } => {
+            match resolve_conflict(&model.workdir, &path, index, side) {
+                Ok(staged) => {
+                    if staged {
+                        show_toast(model, format!("Resolved {}", path));
+                    }
+                }
+                Err(err) => {
+                    model.popup = Some(PopupContent::Error {
+                        message: format!("Failed to resolve conflict in {}: {}", path, err),
+                    });
+                }
+            }
+        }
+        ResolveCommand::Checkout { path, checkout } => {
+            match checkout_conflict(&model.workdir, &path, checkout) {
+                Ok(()) => {
+                    let message = match checkout {
+                        ConflictCheckout::Ours | ConflictCheckout::Theirs => {
+                            format!("Resolved {}", path)
+                        }
+                        ConflictCheckout::Merge => format!("Restored conflict in {}", path),
+                    };
+                    show_toast(model, message);
+                }
+                Err(err) => {
+                    model.popup = Some(PopupContent::Error {
+                        message: err.to_string(),
+                    });
+                }
+            }
+        }
+        ResolveCommand::Mergetool { paths }
+            if paths.is_empty()
+                && unmerged_paths(&model.workdir).is_ok_and(|unmerged| unmerged.is_empty()) =>
+        {
+            show_toast(model, "No conflicts".to_string());
+        }
+        ResolveCommand::Mergetool { paths } => match run_mergetool(&model.workdir, &paths) {
+            Ok(remaining) if remaining.is_empty() => {
+                let message = if paths.is_empty() {
+                    "All conflicts resolved".to_string()
+                } else {
+                    format!("Resolved {}", paths.join(", "))
+                };
+                show_toast(model, message);
+            }
+            Ok(remaining) => {
+                model.toast = Some(Toast {
+                    message: format!("Still unresolved: {}", remaining.join(", ")),
+                    style: ToastStyle::Warning,
+                    expires_at: Instant::now() + TOAST_DURATION,
+                });
+            }
+            Err(err) => {
+                model.popup = Some(PopupContent::Error {
+                    message: format!("Mergetool failed: {}", err),
+                });
+            }
+        },
+    }
+
+    Some(Message::Refresh)
+}
+
+fn show_toast(model: &mut Model, message: String) {
+    model.toast = Some(Toast {
+        message,
+        style: ToastStyle::Success,
+        expires_at: Instant::now() + TOAST_DURATION,
+    });
+}
@@ -4,11 +4,17 @@ use crate::{
     git::revert::{self, CommitResult, any_is_merge_commit, parent_count},
     model::{
         Model, Toast, ToastStyle,
-        arguments::{Arguments::RevertArguments, PopupArgument, RevertArgument},
+        arguments::{Arguments::RevertArguments, PopupArgument, RevertArgument, ValueArgument},
         popup::{PopupContent, PopupContentCommand},
         select_popup::{OnSelect, SelectPopupState},
     },
-    msg::{Message, RevertCommand, update::pty_helper::execute_pty_command},
+    msg::{
+        Message, RevertCommand,
+        update::{
+            pty_helper::execute_pty_command, show_argument_value_input::take_value_flags,
+            show_signing_key_select::take_signing_flags,
+        },
+    },
 };
 
 const TOAST_DURATION: Duration = Duration::from_secs(5);
@@ -30,9 +36,10 @@ pub fn update(model: &mut Model, cmd: RevertCommand) -> Option<Message> {
 }
 
 /// Consumes the revert popup arguments, returning their git flags in a
-/// stable order (--edit before --no-edit).
+/// stable order (--edit before --no-edit), followed by `--strategy`,
+/// `--strategy-option` and `--gpg-sign`.
 fn take_revert_flags(model: &mut Model) -> Vec<String> {
-    if let Some(RevertArguments(arguments)) = model.arguments.take() {
+    let mut flags = if let Some(RevertArguments(arguments)) = model.arguments.take() {
         RevertArgument::all()
             .into_iter()
             .filter(|arg| arguments.contains(arg))
@@ -40,7 +47,17 @@ fn take_revert_flags(model: &mut Model) -> Vec<String> {
             .collect()
     } else {
         vec![]
-    }
+    };
+    flags.extend(strategy_flags(model));
+    flags.extend(take_signing_flags(model));
+    flags
+}
+
+fn strategy_flags(model: &mut Model) -> Vec<String> {
+    take_value_flags(
+        model,
+        &[ValueArgument::Strategy, ValueArgument::StrategyOption],
+    )
 }
 
 /// Without --no-edit, `git revert` opens the editor for the commit message,
@@ -105,6 +122,7 @@ fn no_commit(model: &mut Model, hashes: Vec<String>, mainline: Option<String>) -
         return None;
     }
     let mut args = vec!["revert".to_string(), "--no-commit".to_string()];
+    args.extend(strategy_flags(model));
     args.extend(hashes);
     execute_pty_command(model, args, "Revert".to_string())
 }
@@ -121,6 +139,7 @@ fn commits_with_mainline(
     let mut args = vec!["revert".to_string(), "-m".to_string(), mainline.to_string()];
     if no_commit {
         args.push("--no-commit".to_string());
+        args.extend(strategy_flags(model));
         args.extend(hashes);
         return execute_pty_command(model, args, "Revert".to_string());
     }
@@ -15,11 +15,15 @@ fn line_searchable_text(line: &Line) -> String {
             Some(c) => format!("{} ({})", title, c),
             None => title.clone(),
         },
-        LineContent::UnpulledSectionHeader { remote_name, count } => {
-            format!("Unpulled from {} ({})", remote_name, count)
-        }
+        LineContent::RemoteSectionHeader {
+            prefix,
+            remote_name,
+            count,
+        } => format!("{}{} ({})", prefix, remote_name, count),
         LineContent::UntrackedFile(path) => path.clone(),
         LineContent::UnstagedFile(fc) | LineContent::StagedFile(fc) => fc.path.clone(),
+        LineContent::Directory { name, .. } => name.clone(),
+        LineContent::FileDetail(detail) => detail.description(),
         LineContent::DiffHunk(h) => h.header.clone(),
         LineContent::DiffLine(dl) => dl.content.clone(),
         LineContent::Commit(ci) => {
@@ -37,14 +41,13 @@ fn line_searchable_text(line: &Line) -> String {
             )
         }
         LineContent::Stash(se) => se.message.clone(),
+        LineContent::BlobLine { content, .. } => content.clone(),
         LineContent::RevertingEntry { hash, message, .. } => format!("{} {}", hash, message),
         LineContent::RebasingEntry { hash, message, .. } => format!("{} {}", hash, message),
         LineContent::CherryPickingEntry { hash, message, .. } => format!("{} {}", hash, message),
         LineContent::PreviewLine { content, .. } => content.clone(),
-        LineContent::RebaseTodoLine(entry) => {
-            format!("{} {} {}", entry.action.as_str(), entry.hash, entry.message)
-        }
-        LineContent::RebaseTodoHint { key, description } => format!("{} {}", key, description),
+        LineContent::RebaseTodoLine(entry) => entry.to_todo_line(),
+        LineContent::KeyHint { key, description } => format!("{} {}", key, description),
     }
 }
 
@@ -173,7 +176,7 @@ fn jump_to_match(model: &mut Model, forward: bool, include_current: bool) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::Theme;
+    use crate::config::{Config, Theme};
     use crate::git::GitInfo;
     use crate::git::test_repo::TestRepo;
     use crate::model::ViewMode;
@@ -191,15 +194,18 @@ mod tests {
         let repo_path = test_repo.repo.workdir().unwrap();
         let git_info = GitInfo::new_from_path(repo_path).unwrap();
         let workdir = repo_path.to_path_buf();
-        let mut ui = UiModel::default();
-        ui.lines = lines;
-        ui.viewport_height = 10;
+        let ui = UiModel {
+            lines,
+            viewport_height: 10,
+            ..Default::default()
+        };
         Model {
             git_info,
             workdir,
             running_state: RunningState::Running,
             ui_model: ui,
             theme: Theme::default(),
+            config: Config::default(),
             popup: None,
             toast: None,
             select_result: None,
@@ -208,12 +214,17 @@ mod tests {
             arg_mode: false,
             pending_g: false,
             arguments: None,
+            signing_key: None,
+            argument_values: std::collections::HashMap::new(),
             view_mode: ViewMode::Status,
             cursor_reposition_context: None,
             preview_return_mode: None,
             preview_return_ui_model: None,
             log_return_ui_model: None,
             rebase_todo: None,
+            commit_editor: None,
+            hunk_review: None,
+            commit_message_history: Vec::new(),
         }
     }
 
@@ -3,8 +3,8 @@ use crate::{
     model::{
         LineContent, Model, ViewMode,
         popup::{
-            CommitPopupState, ConfirmAction, ConfirmPopupState, PopupContent, PopupContentCommand,
-            SelectResult,
+            CommitAuthorTarget, ConfirmAction, ConfirmPopupState, PopupContent,
+            PopupContentCommand, SelectResult,
         },
         select_popup::OnSelect,
     },
@@ -334,6 +334,18 @@ fn route_result(
         (Some(OnSelect::ReflogOther), SelectResult::Selected(reference)) => {
             Some(Message::ShowLog(LogType::ReflogOther(reference)))
         }
+        (Some(OnSelect::BlobRevision), SelectResult::Selected(revision)) => {
+            Some(Message::ShowSelectPopup(ShowSelectPopupConfig {
+                title: "Visit file".to_string(),
+                source: OptionsSource::FilesAtRevision {
+                    revision: revision.clone(),
+                },
+                on_select: OnSelect::BlobFile { revision },
+            }))
+        }
+        (Some(OnSelect::BlobFile { revision }), SelectResult::Selected(path)) => {
+            Some(Message::ShowBlob { revision, path })
+        }
         (Some(OnSelect::CherrySpinoffCommitPick), SelectResult::Selected(hash)) => {
             Some(Message::ShowSelectPopup(ShowSelectPopupConfig {
                 title: "Spinoff root".to_string(),
@@ -389,12 +401,21 @@ fn route_result(
         (Some(OnSelect::PruneTagsRemotePick), SelectResult::Selected(remote)) => {
             Some(Message::ShowPruneTagsConfirm { remote })
         }
-        (Some(OnSelect::CommitAuthor), SelectResult::Selected(author)) => {
-            model.popup = Some(PopupContent::Command(PopupContentCommand::Commit(
-                CommitPopupState {
-                    author: Some(author),
-                },
-            )));
+        (Some(OnSelect::CommitAuthor { target, mut state }), SelectResult::Selected(author)) => {
+            match target {
+                CommitAuthorTarget::Author => state.author = Some(author),
+                CommitAuthorTarget::Trailer(kind) => state.toggle_trailer(kind, author),
+            }
+            model.popup = Some(PopupContent::Command(PopupContentCommand::Commit(state)));
+            None
+        }
+        (Some(OnSelect::SigningKey { keys, return_to }), SelectResult::Selected(selection)) => {
+            let key = keys
+                .into_iter()
+                .find(|key| key.label == selection)
+                .map_or(selection, |key| key.key);
+            model.signing_key = Some(key);
+            model.popup = Some(PopupContent::Command(*return_to));
             None
         }
         (
@@ -419,7 +440,7 @@ fn route_result(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::Theme;
+    use crate::config::{Config, Theme};
     use crate::git::GitInfo;
     use crate::git::test_repo::TestRepo;
     use crate::model::log_view::LogEntry;
@@ -438,6 +459,7 @@ mod tests {
             running_state: RunningState::Running,
             ui_model: UiModel::default(),
             theme: Theme::default(),
+            config: Config::default(),
             popup: None,
             toast: None,
             select_result: None,
@@ -446,12 +468,17 @@ mod tests {
             arg_mode: false,
             pending_g: false,
             arguments: None,
+            signing_key: None,
+            argument_values: std::collections::HashMap::new(),
             view_mode: ViewMode::Status,
             cursor_reposition_context: None,
             preview_return_mode: None,
             preview_return_ui_model: None,
             log_return_ui_model: None,
             rebase_todo: None,
+            commit_editor: None,
+            hunk_review: None,
+            commit_message_history: Vec::new(),
         }
     }
 
@@ -464,6 +491,7 @@ mod tests {
                 time: None,
                 refs: vec![],
                 graph: String::new(),
+                signature: None,
             }),
             section: None,
         }
@@ -477,6 +505,7 @@ mod tests {
             picking: true,
             graph: true,
             color: false,
+            signatures: false,
         };
         model.ui_model.lines = vec![
             make_log_line("abc1234", "First commit"),
@@ -506,6 +535,7 @@ mod tests {
             picking: true,
             graph: true,
             color: false,
+            signatures: false,
         };
         model.ui_model.lines = vec![
             make_log_line("abc1234", "First commit"),
@@ -534,6 +564,7 @@ mod tests {
             picking: true,
             graph: true,
             color: false,
+            signatures: false,
         };
         model.ui_model.lines = vec![Line {
             content: LineContent::LogLine(LogEntry {
@@ -543,6 +574,7 @@ mod tests {
                 time: None,
                 refs: vec![],
                 graph: String::new(),
+                signature: None,
             }),
             section: None,
         }];
@@ -566,6 +598,7 @@ mod tests {
             picking: true,
             graph: true,
             color: false,
+            signatures: false,
         };
         model.ui_model.lines = vec![make_log_line("deadbeef", "Some commit")];
         model.ui_model.cursor_position = 0;
@@ -637,6 +670,7 @@ mod tests {
             picking: false,
             graph: true,
             color: false,
+            signatures: false,
         };
         model.ui_model.lines = vec![make_log_line("abc1234", "First commit")];
         model.log_pick_on_select = Some(OnSelect::FixupCommit(FixupType::Fixup));
@@ -652,7 +686,8 @@ mod tests {
                 log_type: LogType::Current,
                 picking: false,
                 graph: true,
-                color: false
+                color: false,
+                signatures: false
             }
         );
         // log_pick_on_select still set (not consumed)
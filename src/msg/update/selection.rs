@@ -52,11 +52,64 @@ pub fn get_normal_mode_selection<'a>(
 
     let line = &lines[cursor_position];
 
-    match context {
+    let selection = match context {
         SelectionContext::Stageable => get_stageable_normal_selection(lines, line),
         SelectionContext::Unstageable => get_unstageable_normal_selection(lines, line),
         SelectionContext::Discardable => get_discardable_normal_selection(lines, line),
+    };
+
+    // In a split hunk, the part under the cursor stands for the hunk
+    match selection {
+        Selection::Hunk { path, hunk_index } => {
+            match split_part_line_indices(lines, cursor_position) {
+                Some(line_indices) => Selection::Lines {
+                    path,
+                    hunk_index,
+                    line_indices,
+                },
+                None => Selection::Hunk { path, hunk_index },
+            }
+        }
+        selection => selection,
+    }
+}
+
+/// The indices (within their hunk) of the diff lines of the split hunk part
+/// at `position`. `None` when the hunk at `position` is not split.
+fn split_part_line_indices(lines: &[Line], position: usize) -> Option<Vec<usize>> {
+    let section = lines[position].section.as_ref()?;
+    let in_hunk = |line: &Line| line.section.as_ref() == Some(section);
+    let start = lines[..position]
+        .iter()
+        .rposition(|line| !in_hunk(line))
+        .map_or(0, |index| index + 1);
+    let end = lines[position..]
+        .iter()
+        .position(|line| !in_hunk(line))
+        .map_or(lines.len(), |offset| position + offset);
+    let hunk = &lines[start..end];
+
+    let is_header = |line: &Line| matches!(line.content, LineContent::DiffHunk(_));
+    if hunk.iter().filter(|line| is_header(line)).count() < 2 {
+        return None;
     }
+
+    // Lines are counted through the whole hunk, but only those after the
+    // last header before `position` belong to its part
+    let mut line_index = 0;
+    let mut indices = Vec::new();
+    for (offset, line) in hunk.iter().enumerate() {
+        if is_header(line) {
+            if start + offset > position {
+                break;
+            }
+            indices.clear();
+        } else if matches!(line.content, LineContent::DiffLine(_)) {
+            indices.push(line_index);
+            line_index += 1;
+        }
+    }
+    Some(indices)
 }
 
 fn get_stageable_normal_selection<'a>(lines: &'a [Line], line: &'a Line) -> Selection<'a> {
@@ -91,10 +144,19 @@ fn get_stageable_normal_selection<'a>(lines: &'a [Line], line: &'a Line) -> Sele
                 Selection::Files(files)
             }
         }
-        // Untracked file → that file
-        (LineContent::UntrackedFile(path), _) => Selection::Files(vec![path.as_str()]),
-        // Unstaged file → that file
+        // Untracked file, or a line of its preview → that file
+        (LineContent::UntrackedFile(path), _) | (_, Some(SectionType::UntrackedFile { path })) => {
+            Selection::Files(vec![path.as_str()])
+        }
+        // Directory of untracked or unstaged files → the files below it
+        (LineContent::Directory { .. }, Some(section)) => {
+            directory_selection(lines, section, &[FileKind::Untracked, FileKind::Unstaged])
+        }
+        // Unstaged file, or a detail of it → that file
         (LineContent::UnstagedFile(fc), _) => Selection::Files(vec![fc.path.as_str()]),
+        (LineContent::FileDetail(_), Some(SectionType::UnstagedFile { path })) => {
+            Selection::Files(vec![path.as_str()])
+        }
         // Diff hunk in unstaged section → that hunk
         (LineContent::DiffHunk(_), Some(SectionType::UnstagedHunk { path, hunk_index })) => {
             Selection::Hunk {
@@ -130,8 +192,15 @@ fn get_unstageable_normal_selection<'a>(lines: &'a [Line], line: &'a Line) -> Se
                 Selection::Files(files)
             }
         }
-        // Staged file → that file
+        // Staged file, or a detail of it → that file
         (LineContent::StagedFile(fc), _) => Selection::Files(vec![fc.path.as_str()]),
+        (LineContent::FileDetail(_), Some(SectionType::StagedFile { path })) => {
+            Selection::Files(vec![path.as_str()])
+        }
+        // Directory of staged files → the files below it
+        (LineContent::Directory { .. }, Some(section)) => {
+            directory_selection(lines, section, &[FileKind::Staged])
+        }
         // Diff hunk in staged section → that hunk
         (LineContent::DiffHunk(_), Some(SectionType::StagedHunk { path, hunk_index })) => {
             Selection::Hunk {
@@ -167,8 +236,10 @@ fn get_discardable_normal_selection<'a>(lines: &'a [Line], line: &'a Line) -> Se
                 Selection::Files(files)
             }
         }
-        // Untracked file → that file
-        (LineContent::UntrackedFile(path), _) => Selection::Files(vec![path.as_str()]),
+        // Untracked file, or a line of its preview → that file
+        (LineContent::UntrackedFile(path), _) | (_, Some(SectionType::UntrackedFile { path })) => {
+            Selection::Files(vec![path.as_str()])
+        }
         // Section header for unstaged changes → all unstaged files
         (LineContent::SectionHeader { .. }, Some(SectionType::UnstagedChanges)) => {
             let files: Vec<&str> = lines
@@ -203,6 +274,17 @@ fn get_discardable_normal_selection<'a>(lines: &'a [Line], line: &'a Line) -> Se
         (LineContent::UnstagedFile(fc), _) => Selection::Files(vec![fc.path.as_str()]),
         // Staged file → that file
         (LineContent::StagedFile(fc), _) => Selection::Files(vec![fc.path.as_str()]),
+        // Detail of an unstaged or staged file → that file
+        (
+            LineContent::FileDetail(_),
+            Some(SectionType::UnstagedFile { path } | SectionType::StagedFile { path }),
+        ) => Selection::Files(vec![path.as_str()]),
+        // Directory → the files below it
+        (LineContent::Directory { .. }, Some(section)) => directory_selection(
+            lines,
+            section,
+            &[FileKind::Untracked, FileKind::Unstaged, FileKind::Staged],
+        ),
         // Diff hunk in unstaged section → that hunk
         (LineContent::DiffHunk(_), Some(SectionType::UnstagedHunk { path, hunk_index })) => {
             Selection::Hunk {
@@ -235,6 +317,98 @@ fn get_discardable_normal_selection<'a>(lines: &'a [Line], line: &'a Line) -> Se
     }
 }
 
+/// The status sections listing files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileKind {
+    Untracked,
+    Unstaged,
+    Staged,
+}
+
+impl FileKind {
+    /// The kind of the files below a directory of the tree layout
+    fn of_directory(section: &SectionType) -> Option<FileKind> {
+        match section {
+            SectionType::UntrackedDirectory { .. } => Some(FileKind::Untracked),
+            SectionType::UnstagedDirectory { .. } => Some(FileKind::Unstaged),
+            SectionType::StagedDirectory { .. } => Some(FileKind::Staged),
+            _ => None,
+        }
+    }
+
+    /// The path of `line` if it is the line of a file of this kind
+    fn file_path(self, line: &Line) -> Option<&str> {
+        match (self, &line.content) {
+            (FileKind::Untracked, LineContent::UntrackedFile(path)) => Some(path),
+            (FileKind::Unstaged, LineContent::UnstagedFile(fc))
+            | (FileKind::Staged, LineContent::StagedFile(fc)) => Some(&fc.path),
+            _ => None,
+        }
+    }
+}
+
+/// The files of `kind` below the directory line with section `directory`,
+/// at any depth.
+fn files_below<'a>(lines: &'a [Line], kind: FileKind, directory: &SectionType) -> Vec<&'a str> {
+    lines
+        .iter()
+        .filter(|line| {
+            line.section
+                .as_ref()
+                .is_some_and(|section| section == directory || section.is_below(directory))
+        })
+        .filter_map(|line| kind.file_path(line))
+        .collect()
+}
+
+/// A directory line of one of the `kinds` → the files below it
+fn directory_selection<'a>(
+    lines: &'a [Line],
+    directory: &SectionType,
+    kinds: &[FileKind],
+) -> Selection<'a> {
+    let files = match FileKind::of_directory(directory) {
+        Some(kind) if kinds.contains(&kind) => files_below(lines, kind, directory),
+        _ => Vec::new(),
+    };
+    if files.is_empty() {
+        Selection::None
+    } else {
+        Selection::Files(files)
+    }
+}
+
+/// The files of a visual selection made only of files of `kind`, their
+/// directories and section headers. A directory stands for the files below
+/// it, and the preview of an untracked file for the file.
+fn selected_files<'a>(
+    lines: &'a [Line],
+    selected: &[(usize, &'a Line)],
+    kind: FileKind,
+) -> Option<Vec<&'a str>> {
+    let mut files: Vec<&str> = Vec::new();
+    for (_, line) in selected {
+        let paths = match (&line.content, &line.section) {
+            (LineContent::SectionHeader { .. }, _) => continue,
+            (LineContent::Directory { .. }, Some(section))
+                if FileKind::of_directory(section) == Some(kind) =>
+            {
+                files_below(lines, kind, section)
+            }
+            (_, Some(SectionType::UntrackedFile { path })) if kind == FileKind::Untracked => {
+                vec![path.as_str()]
+            }
+            _ => vec![kind.file_path(line)?],
+        };
+        for path in paths {
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+    }
+    (!files.is_empty()).then_some(files)
+}
+
 /// Determines what is selected based on visual selection range.
 pub fn get_visual_mode_selection<'a>(
     lines: &'a [Line],
@@ -278,44 +452,14 @@ fn get_stageable_visual_selection<'a>(
     sel_start: usize,
     sel_end: usize,
 ) -> Selection<'a> {
-    // Check if all are untracked files
-    let all_untracked = selected.iter().all(|(_, l)| {
-        matches!(
-            l.content,
-            LineContent::UntrackedFile(_) | LineContent::SectionHeader { .. }
-        )
-    });
-    if all_untracked {
-        let files: Vec<&str> = selected
-            .iter()
-            .filter_map(|(_, l)| match &l.content {
-                LineContent::UntrackedFile(path) => Some(path.as_str()),
-                _ => None,
-            })
-            .collect();
-        if !files.is_empty() {
-            return Selection::Files(files);
-        }
+    // Check if all are untracked files (or their directories and previews)
+    if let Some(files) = selected_files(lines, selected, FileKind::Untracked) {
+        return Selection::Files(files);
     }
 
-    // Check if all are unstaged files
-    let all_unstaged_files = selected.iter().all(|(_, l)| {
-        matches!(
-            l.content,
-            LineContent::UnstagedFile(_) | LineContent::SectionHeader { .. }
-        )
-    });
-    if all_unstaged_files {
-        let files: Vec<&str> = selected
-            .iter()
-            .filter_map(|(_, l)| match &l.content {
-                LineContent::UnstagedFile(fc) => Some(fc.path.as_str()),
-                _ => None,
-            })
-            .collect();
-        if !files.is_empty() {
-            return Selection::Files(files);
-        }
+    // Check if all are unstaged files (or their directories)
+    if let Some(files) = selected_files(lines, selected, FileKind::Unstaged) {
+        return Selection::Files(files);
     }
 
     // Check if all are diff lines in the same unstaged hunk
@@ -356,24 +500,9 @@ fn get_unstageable_visual_selection<'a>(
     sel_start: usize,
     sel_end: usize,
 ) -> Selection<'a> {
-    // Check if all are staged files
-    let all_staged_files = selected.iter().all(|(_, l)| {
-        matches!(
-            l.content,
-            LineContent::StagedFile(_) | LineContent::SectionHeader { .. }
-        )
-    });
-    if all_staged_files {
-        let files: Vec<&str> = selected
-            .iter()
-            .filter_map(|(_, l)| match &l.content {
-                LineContent::StagedFile(fc) => Some(fc.path.as_str()),
-                _ => None,
-            })
-            .collect();
-        if !files.is_empty() {
-            return Selection::Files(files);
-        }
+    // Check if all are staged files (or their directories)
+    if let Some(files) = selected_files(lines, selected, FileKind::Staged) {
+        return Selection::Files(files);
     }
 
     // Check if all are diff lines in the same staged hunk
@@ -414,64 +543,19 @@ fn get_discardable_visual_selection<'a>(
     sel_start: usize,
     sel_end: usize,
 ) -> Selection<'a> {
-    // Check if all are untracked files
-    let all_untracked = selected.iter().all(|(_, l)| {
-        matches!(
-            l.content,
-            LineContent::UntrackedFile(_) | LineContent::SectionHeader { .. }
-        )
-    });
-    if all_untracked {
-        let files: Vec<&str> = selected
-            .iter()
-            .filter_map(|(_, l)| match &l.content {
-                LineContent::UntrackedFile(path) => Some(path.as_str()),
-                _ => None,
-            })
-            .collect();
-        if !files.is_empty() {
-            return Selection::Files(files);
-        }
+    // Check if all are untracked files (or their directories and previews)
+    if let Some(files) = selected_files(lines, selected, FileKind::Untracked) {
+        return Selection::Files(files);
     }
 
-    // Check if all are unstaged files
-    let all_unstaged_files = selected.iter().all(|(_, l)| {
-        matches!(
-            l.content,
-            LineContent::UnstagedFile(_) | LineContent::SectionHeader { .. }
-        )
-    });
-    if all_unstaged_files {
-        let files: Vec<&str> = selected
-            .iter()
-            .filter_map(|(_, l)| match &l.content {
-                LineContent::UnstagedFile(fc) => Some(fc.path.as_str()),
-                _ => None,
-            })
-            .collect();
-        if !files.is_empty() {
-            return Selection::Files(files);
-        }
+    // Check if all are unstaged files (or their directories)
+    if let Some(files) = selected_files(lines, selected, FileKind::Unstaged) {
+        return Selection::Files(files);
     }
 
-    // Check if all are staged files
-    let all_staged_files = selected.iter().all(|(_, l)| {
-        matches!(
-            l.content,
-            LineContent::StagedFile(_) | LineContent::SectionHeader { .. }
-        )
-    });
-    if all_staged_files {
-        let files: Vec<&str> = selected
-            .iter()
-            .filter_map(|(_, l)| match &l.content {
-                LineContent::StagedFile(fc) => Some(fc.path.as_str()),
-                _ => None,
-            })
-            .collect();
-        if !files.is_empty() {
-            return Selection::Files(files);
-        }
+    // Check if all are staged files (or their directories)
+    if let Some(files) = selected_files(lines, selected, FileKind::Staged) {
+        return Selection::Files(files);
     }
 
     // Check if all are diff lines in the same hunk (unstaged or staged)
@@ -0,0 +1,57 @@
+use std::time::Instant;
+
+use crate::{
+    git::absorb::plan_absorb,
+    model::{
+        Model, Toast, ToastStyle,
+        popup::{ConfirmAction, ConfirmPopupState, PopupContent},
+    },
+    msg::{Message, update::commit::TOAST_DURATION},
+};
+
+pub fn update(model: &mut Model, autosquash: bool) -> Option<Message> {
+    if let Ok(false) = model.git_info.has_staged_changes() {
+        return warn(model, "Nothing staged to absorb");
+    }
+
+    let plan = match plan_absorb(&model.workdir) {
+        Ok(plan) => plan,
+        Err(e) => {
+            model.popup = Some(PopupContent::Error {
+                message: format!("Absorb failed: {}", e),
+            });
+            return None;
+        }
+    };
+    if plan.targets.is_empty() {
+        return warn(model, "No staged hunk belongs to an unpushed commit");
+    }
+
+    let mut lines = vec![if autosquash {
+        "Absorb staged hunks and autosquash:".to_string()
+    } else {
+        "Absorb staged hunks into fixup commits:".to_string()
+    }];
+    for hunk in &plan.hunks {
+        let target = match &hunk.target {
+            Some(target) => format!("{} {}", target.short_hash(), target.summary),
+            None => "stays staged".to_string(),
+        };
+        lines.push(format!("  {}:{} → {}", hunk.path, hunk.new_start, target));
+    }
+
+    model.popup = Some(PopupContent::Confirm(ConfirmPopupState {
+        message: lines.join("\n"),
+        on_confirm: ConfirmAction::Absorb { plan, autosquash },
+    }));
+    None
+}
+
+fn warn(model: &mut Model, message: &str) -> Option<Message> {
+    model.toast = Some(Toast {
+        message: message.to_string(),
+        style: ToastStyle::Warning,
+        expires_at: Instant::now() + TOAST_DURATION,
+    });
+    Some(Message::DismissPopup)
+}
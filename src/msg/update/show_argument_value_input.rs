@@ -0,0 +1,53 @@
+use crate::{
+    model::{
+        Model,
+        arguments::ValueArgument,
+        popup::{InputContext, InputPopupState, PopupContent},
+    },
+    msg::Message,
+};
+
+/// Handles a popup argument that takes a value (e.g. `-x` for `--exec=`):
+/// shows an input for the value, or clears the value if one is already set.
+/// The popup is reopened once the value is entered.
+pub fn update(model: &mut Model, argument: ValueArgument) -> Option<Message> {
+    let Some(PopupContent::Command(command)) = model.popup.take() else {
+        return None;
+    };
+    model.arg_mode = false;
+
+    // Selecting the argument when a value is already set clears it
+    if model.argument_values.remove(&argument).is_some() {
+        model.popup = Some(PopupContent::Command(command));
+        return None;
+    }
+
+    model.popup = Some(PopupContent::Input(InputPopupState::new(
+        InputContext::ArgumentValue {
+            argument,
+            return_to: Box::new(command),
+        },
+    )));
+    None
+}
+
+/// Returns the flags (`--exec=<cmd>`, ...) for the values set for
+/// `arguments`, in that order, leaving them in the model.
+pub fn value_flags(model: &Model, arguments: &[ValueArgument]) -> Vec<String> {
+    arguments
+        .iter()
+        .filter_map(|argument| {
+            let value = model.argument_values.get(argument)?;
+            Some(format!("{}{}", argument.flag_prefix(), value))
+        })
+        .collect()
+}
+
+/// Like [`value_flags`], but clears the values so they are used only once.
+pub fn take_value_flags(model: &mut Model, arguments: &[ValueArgument]) -> Vec<String> {
+    let flags = value_flags(model, arguments);
+    for argument in arguments {
+        model.argument_values.remove(argument);
+    }
+    flags
+}
@@ -2,29 +2,35 @@ use crate::{
     git::commit::list_authors,
     model::{
         Model,
-        popup::{PopupContent, PopupContentCommand},
+        popup::{CommitAuthorTarget, PopupContent, PopupContentCommand},
         select_popup::{OnSelect, SelectPopupState},
     },
     msg::Message,
 };
 
-pub fn update(model: &mut Model) -> Option<Message> {
+pub fn update(model: &mut Model, target: CommitAuthorTarget) -> Option<Message> {
     let Some(PopupContent::Command(PopupContentCommand::Commit(mut state))) = model.popup.take()
     else {
         return None;
     };
     model.arg_mode = false;
 
-    // Selecting the argument when a value is already set clears it
-    if state.author.is_some() {
+    // Selecting the argument when a value is already set clears it.
+    // Trailers can hold several people, so picking one that is already
+    // present removes it instead.
+    if target == CommitAuthorTarget::Author && state.author.is_some() {
         state.author = None;
         model.popup = Some(PopupContent::Command(PopupContentCommand::Commit(state)));
         return None;
     }
 
+    let title = match target {
+        CommitAuthorTarget::Author => "Commit author".to_string(),
+        CommitAuthorTarget::Trailer(kind) => kind.token().to_string(),
+    };
     let authors = list_authors(&model.workdir).unwrap_or_default();
     let select_state =
-        SelectPopupState::new("Commit author".to_string(), authors, OnSelect::CommitAuthor);
+        SelectPopupState::new(title, authors, OnSelect::CommitAuthor { target, state });
     model.popup = Some(PopupContent::Command(PopupContentCommand::Select(
         select_state,
     )));
@@ -197,7 +197,7 @@ pub fn show_select_revise_commit(model: &mut Model) -> Option<Message> {
 
 fn show_log_select(model: &mut Model, log_type: LogType, on_select: OnSelect) -> Option<Message> {
     // Commit picking always shows the graph, without color
-    match get_log_entries(&model.git_info.repository, &log_type, true, false) {
+    match get_log_entries(&model.git_info.repository, &log_type, true, false, false) {
         Ok(mut commits) => {
             commits.retain(|entry| entry.is_commit());
 
@@ -223,6 +223,7 @@ fn show_log_select(model: &mut Model, log_type: LogType, on_select: OnSelect) ->
                     picking: true,
                     graph: true,
                     color: false,
+                    signatures: false,
                 };
                 model.popup = None;
                 model.log_pick_on_select = Some(on_select);
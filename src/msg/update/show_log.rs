@@ -9,12 +9,13 @@ use crate::{
 
 pub fn update(model: &mut Model, log_type: LogType) -> Option<Message> {
     // Graph is shown by default; only disabled when toggled off in the log popup
-    let (graph, color) = match model.arguments.take() {
+    let (graph, color, signatures) = match model.arguments.take() {
         Some(LogArguments(args)) => (
             args.contains(&LogArgument::Graph),
             args.contains(&LogArgument::Color),
+            args.contains(&LogArgument::Signatures),
         ),
-        _ => (true, false),
+        _ => (true, false, false),
     };
     // Reflogs cannot be drawn as a graph (git rejects --graph with --walk-reflogs)
     let graph = graph
@@ -22,7 +23,13 @@ pub fn update(model: &mut Model, log_type: LogType) -> Option<Message> {
             log_type,
             LogType::Reflog | LogType::ReflogOther(_) | LogType::Stashes
         );
-    match get_log_entries(&model.git_info.repository, &log_type, graph, color) {
+    match get_log_entries(
+        &model.git_info.repository,
+        &log_type,
+        graph,
+        color,
+        signatures,
+    ) {
         Ok(entries) => {
             // Convert log entries to lines
             let lines: Vec<Line> = entries
@@ -48,6 +55,7 @@ pub fn update(model: &mut Model, log_type: LogType) -> Option<Message> {
                 picking: false,
                 graph,
                 color,
+                signatures,
             };
 
             // Dismiss the log popup
@@ -1,24 +1,13 @@
-use crate::git::preview::{get_commit_preview_lines, get_stash_preview_lines};
-use crate::model::{LineContent, Model, ViewMode};
+use crate::git::{
+    external_diff,
+    preview::{get_commit_preview_lines, get_stash_preview_lines},
+};
+use crate::model::{Line, LineContent, Model, ViewMode};
 use crate::msg::Message;
 
 pub fn update(model: &mut Model) -> Option<Message> {
     let cursor_line = model.ui_model.lines.get(model.ui_model.cursor_position)?;
-
-    let preview_lines = match &cursor_line.content {
-        LineContent::Commit(info) => get_commit_preview_lines(&model.workdir, &info.hash),
-        LineContent::LogLine(entry) => {
-            let hash = entry.hash.as_deref()?;
-            get_commit_preview_lines(&model.workdir, hash)
-        }
-        LineContent::Stash(stash) => get_stash_preview_lines(&model.workdir, stash.index),
-        LineContent::RebaseTodoLine(entry) => get_commit_preview_lines(&model.workdir, &entry.hash),
-        _ => return None,
-    };
-
-    if preview_lines.is_empty() {
-        return None;
-    }
+    let preview_lines = preview_lines(model, cursor_line)?;
 
     model.preview_return_mode = Some(model.view_mode.clone());
     model.preview_return_ui_model = Some(model.ui_model.clone());
@@ -28,3 +17,46 @@ pub fn update(model: &mut Model) -> Option<Message> {
     model.view_mode = ViewMode::Preview;
     None
 }
+
+/// The preview of the commit or stash on `line`, rendered with the current
+/// diff settings and external renderer. `None` when the line has nothing
+/// to preview.
+pub fn preview_lines(model: &Model, line: &Line) -> Option<Vec<Line>> {
+    // The git arguments showing the diff alone, for external diff tools
+    let (mut preview_lines, diff_args) = match &line.content {
+        LineContent::Commit(info) => commit_preview(model, &info.hash),
+        LineContent::LogLine(entry) => commit_preview(model, entry.hash.as_deref()?),
+        LineContent::Stash(stash) => {
+            let stash_ref = format!("stash@{{{}}}", stash.index);
+            let mut args = vec!["stash".to_string(), "show".to_string(), "-p".to_string()];
+            args.extend(model.git_info.diff_settings.diff_args());
+            args.push(stash_ref);
+            (
+                get_stash_preview_lines(&model.workdir, stash.index, &model.git_info.diff_settings),
+                args,
+            )
+        }
+        // Instruction lines (exec, break, label, ...) have no commit to show
+        LineContent::RebaseTodoLine(entry) if !entry.hash.is_empty() => {
+            commit_preview(model, &entry.hash)
+        }
+        _ => return None,
+    };
+
+    if let Some(renderer) = model.config.diff_renderer() {
+        let diff_args: Vec<&str> = diff_args.iter().map(String::as_str).collect();
+        preview_lines = external_diff::render(&model.workdir, preview_lines, &diff_args, &renderer);
+    }
+
+    (!preview_lines.is_empty()).then_some(preview_lines)
+}
+
+fn commit_preview(model: &Model, hash: &str) -> (Vec<Line>, Vec<String>) {
+    let mut args = vec!["show".to_string(), "--format=".to_string()];
+    args.extend(model.git_info.diff_settings.diff_args());
+    args.push(hash.to_string());
+    (
+        get_commit_preview_lines(&model.workdir, hash, &model.git_info.diff_settings),
+        args,
+    )
+}
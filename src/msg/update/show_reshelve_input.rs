@@ -0,0 +1,17 @@
+use crate::{
+    model::{
+        InputField, Model,
+        popup::{InputContext, InputPopupState, PopupContent},
+    },
+    msg::Message,
+};
+
+/// Replaces the commit popup with an input for the date to reshelve HEAD
+/// to, prefilled with `now`.
+pub fn update(model: &mut Model) -> Option<Message> {
+    model.popup = Some(PopupContent::Input(InputPopupState {
+        input: InputField::from_text("now".to_string()),
+        context: InputContext::Reshelve,
+    }));
+    None
+}
@@ -0,0 +1,105 @@
+use crate::{
+    git::{
+        cherry_pick::cherry_pick_in_progress, rebase::rebase_in_progress,
+        revert::revert_in_progress,
+    },
+    model::{
+        DiffLineType, FileStatus, Line, LineContent, Model, SectionType,
+        popup::{PopupContent, PopupContentCommand, ResolvePopupState},
+    },
+    msg::{Message, update::show_merge_popup::merge_in_progress},
+};
+
+pub fn update(model: &mut Model) -> Option<Message> {
+    let lines = &model.ui_model.lines;
+    let cursor = model.ui_model.cursor_position;
+    let line = lines.get(cursor)?;
+
+    let state = match (&line.content, &line.section) {
+        (LineContent::UnstagedFile(file), _) if file.status == FileStatus::Unmerged => {
+            ResolvePopupState {
+                path: file.path.clone(),
+                conflict: None,
+                unmerged: true,
+            }
+        }
+        (_, Some(SectionType::UnstagedHunk { path, hunk_index })) if is_unmerged(lines, path) => {
+            ResolvePopupState {
+                path: path.clone(),
+                conflict: conflict_at(lines, cursor, path, *hunk_index),
+                unmerged: true,
+            }
+        }
+        // A file resolved already can still get its conflict back while
+        // the operation that caused it is in progress
+        (LineContent::StagedFile(file), _) if sequence_in_progress(model) => ResolvePopupState {
+            path: file.path.clone(),
+            conflict: None,
+            unmerged: false,
+        },
+        _ => return None,
+    };
+
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Resolve(state)));
+    None
+}
+
+fn sequence_in_progress(model: &Model) -> bool {
+    merge_in_progress(&model.workdir)
+        || rebase_in_progress(&model.workdir)
+        || cherry_pick_in_progress(&model.workdir)
+        || revert_in_progress(&model.workdir)
+}
+
+fn is_unmerged(lines: &[Line], path: &str) -> bool {
+    lines.iter().any(|line| {
+        matches!(&line.content, LineContent::UnstagedFile(file)
+            if file.path == path && file.status == FileStatus::Unmerged)
+    })
+}
+
+/// Returns true for the `<<<<<<<` marker line of a combined diff, whose
+/// content still carries the two-column prefix.
+fn is_conflict_start(line: &Line) -> bool {
+    matches!(&line.content, LineContent::DiffLine(diff)
+        if diff.line_type == DiffLineType::ConflictMarker
+            && diff.content.get(2..).is_some_and(|rest| rest.starts_with("<<<<<<<")))
+}
+
+fn is_conflict_end(line: &Line) -> bool {
+    matches!(&line.content, LineContent::DiffLine(diff)
+        if diff.line_type == DiffLineType::ConflictMarker
+            && diff.content.get(2..).is_some_and(|rest| rest.starts_with(">>>>>>>")))
+}
+
+/// Finds the index of the conflict region of `path` under the cursor,
+/// counted over all hunks of the file. Outside a region (e.g. on the hunk
+/// header) the next region of the same hunk is used.
+fn conflict_at(lines: &[Line], cursor: usize, path: &str, hunk_index: usize) -> Option<usize> {
+    let in_file = |line: &Line| matches!(&line.section, Some(SectionType::UnstagedHunk { path: p, .. }) if p == path);
+    let in_hunk = |line: &Line| {
+        matches!(&line.section, Some(SectionType::UnstagedHunk { path: p, hunk_index: h })
+            if p == path && *h == hunk_index)
+    };
+
+    let mut starts: usize = 0;
+    let mut inside = false;
+    for line in lines[..=cursor].iter().filter(|line| in_file(line)) {
+        if is_conflict_start(line) {
+            starts += 1;
+            inside = true;
+        } else if is_conflict_end(line) {
+            inside = false;
+        }
+    }
+    // The closing marker itself still belongs to its region
+    if inside || is_conflict_end(&lines[cursor]) {
+        return starts.checked_sub(1);
+    }
+
+    lines[cursor + 1..]
+        .iter()
+        .take_while(|line| in_hunk(line))
+        .any(is_conflict_start)
+        .then_some(starts)
+}
@@ -2,7 +2,7 @@ use crate::{
     git::revert::revert_in_progress,
     model::{
         LineContent, Model,
-        arguments::{Arguments::RevertArguments, RevertArgument},
+        arguments::{Arguments::RevertArguments, RevertArgument, ValueArgument},
         popup::{PopupContent, PopupContentCommand, RevertPopupState},
     },
     msg::Message,
@@ -24,7 +24,8 @@ pub fn update(model: &mut Model) -> Option<Message> {
     let state = RevertPopupState {
         in_progress,
         selected_commits,
-        mainline: None,
+        // The mainline is shared with the apply popup and kept between uses
+        mainline: model.argument_values.get(&ValueArgument::Mainline).cloned(),
     };
     model.popup = Some(PopupContent::Command(PopupContentCommand::Revert(state)));
     None
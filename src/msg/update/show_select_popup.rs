@@ -3,6 +3,7 @@ use std::time::Instant;
 use crate::{
     git::{
         CommitRefType,
+        blob::files_at_revision,
         checkout::{
             BranchEntry, get_all_branches, get_branches, get_last_checked_out_branch,
             get_local_branches, get_remote_branches_for_upstream,
@@ -140,6 +141,9 @@ fn fetch_options(model: &Model, source: &OptionsSource) -> Vec<String> {
             })
             .collect(),
         OptionsSource::TrackedFiles => get_tracked_files(&model.git_info.repository),
+        OptionsSource::FilesAtRevision { revision } => {
+            files_at_revision(&model.workdir, revision).unwrap_or_default()
+        }
     }
 }
 
@@ -265,13 +269,16 @@ fn compute_preferred(model: &Model, on_select: &OnSelect) -> Option<String> {
                 })
                 .map(|s| s.name().to_string())
         }
-        OnSelect::FileCheckoutRevision | OnSelect::LogOther | OnSelect::ReflogOther => {
+        OnSelect::FileCheckoutRevision
+        | OnSelect::LogOther
+        | OnSelect::ReflogOther
+        | OnSelect::BlobRevision => {
             // Cursor suggestion (any)
             cursor_line
                 .and_then(|line| suggestions_from_line(line).into_iter().next())
                 .map(|s| s.name().to_string())
         }
-        OnSelect::FileCheckoutFile { .. } => {
+        OnSelect::FileCheckoutFile { .. } | OnSelect::BlobFile { .. } => {
             // Cursor file line
             cursor_line.and_then(|line| match &line.content {
                 LineContent::UnstagedFile(fc) => Some(fc.path.clone()),
@@ -410,6 +417,7 @@ fn should_insert_if_missing(on_select: &OnSelect) -> bool {
         | OnSelect::FileCheckoutRevision   // can insert cursor suggestion
         | OnSelect::LogOther               // can insert cursor suggestion
         | OnSelect::ReflogOther            // can insert cursor suggestion
+        | OnSelect::BlobRevision           // can insert cursor suggestion
         | OnSelect::ResetBranchTarget { .. } // can insert cursor hash
         | OnSelect::Reset(_)               // can insert cursor hash
         | OnSelect::ResetIndex             // can insert cursor hash
@@ -599,6 +607,7 @@ fn error_msg(config: &ShowSelectPopupConfig) -> String {
         OnSelect::FileCheckoutRevision
         | OnSelect::LogOther
         | OnSelect::ReflogOther
+        | OnSelect::BlobRevision
         | OnSelect::ResetBranchTarget { .. }
         | OnSelect::Reset(_)
         | OnSelect::ResetIndex
@@ -606,6 +615,7 @@ fn error_msg(config: &ShowSelectPopupConfig) -> String {
         | OnSelect::CreateTagTarget { .. }
         | OnSelect::RebaseSubsetOnto => "No references found".to_string(),
         OnSelect::FileCheckoutFile { .. } => "No tracked files found".to_string(),
+        OnSelect::BlobFile { .. } => "No files found".to_string(),
         OnSelect::ApplyStash | OnSelect::PopStash | OnSelect::DropStash => {
             "No stashes found".to_string()
         }
@@ -0,0 +1,51 @@
+use crate::{
+    git::signing::{gpg_sign_flag, signing_keys},
+    model::{
+        Model,
+        popup::{PopupContent, PopupContentCommand},
+        select_popup::{OnSelect, SelectPopupState},
+    },
+    msg::Message,
+};
+
+/// Handles the `-S` argument shared by the commit, merge, rebase, revert and
+/// apply popups: shows a picker of signing keys, or clears the key if one
+/// is already set. The popup is reopened once a key is picked.
+pub fn update(model: &mut Model) -> Option<Message> {
+    let Some(PopupContent::Command(command)) = model.popup.take() else {
+        return None;
+    };
+    model.arg_mode = false;
+
+    // Selecting the argument when a value is already set clears it
+    if model.signing_key.take().is_some() {
+        model.popup = Some(PopupContent::Command(command));
+        return None;
+    }
+
+    let keys = signing_keys(&model.workdir);
+    let labels = keys.iter().map(|key| key.label.clone()).collect();
+    let select_state = SelectPopupState::new(
+        "Sign using key".to_string(),
+        labels,
+        OnSelect::SigningKey {
+            keys,
+            return_to: Box::new(command),
+        },
+    );
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Select(
+        select_state,
+    )));
+    None
+}
+
+/// Takes the key picked with `-S` and returns the matching `--gpg-sign`
+/// flag, if any.
+pub fn take_signing_flags(model: &mut Model) -> Vec<String> {
+    model
+        .signing_key
+        .take()
+        .map(|key| gpg_sign_flag(&key))
+        .into_iter()
+        .collect()
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use crate::{
+    model::{DiffLineType, Line, LineContent, Model, SectionType},
+    msg::Message,
+};
+
+pub fn update(model: &mut Model) -> Option<Message> {
+    model.popup = None;
+    let section = model
+        .ui_model
+        .lines
+        .get(model.ui_model.cursor_position)?
+        .section
+        .clone()
+        .filter(|section| {
+            matches!(
+                section,
+                SectionType::UnstagedHunk { .. } | SectionType::StagedHunk { .. }
+            )
+        })?;
+
+    if model.ui_model.split_hunks.remove(&section).is_none() {
+        let body = hunk_body(&model.ui_model.lines, &section);
+        model.ui_model.split_hunks.insert(section, body);
+    }
+    Some(Message::Refresh)
+}
+
+/// Finds the split hunks again in freshly loaded `lines` by their file and
+/// lines, keyed by their new sections. Hunks that changed or are gone are no
+/// longer split.
+pub fn relocate(
+    lines: &[Line],
+    split: &HashMap<SectionType, String>,
+) -> HashMap<SectionType, String> {
+    if split.is_empty() {
+        return HashMap::new();
+    }
+    lines
+        .iter()
+        .filter_map(|line| match (&line.content, &line.section) {
+            (LineContent::DiffHunk(_), Some(section)) => Some(section),
+            _ => None,
+        })
+        .filter_map(|section| {
+            let body = hunk_body(lines, section);
+            split
+                .iter()
+                .any(|(old, old_body)| *old_body == body && same_file(old, section))
+                .then(|| (section.clone(), body))
+        })
+        .collect()
+}
+
+/// The diff lines of the hunk in `section`, one per line with their origin
+fn hunk_body(lines: &[Line], section: &SectionType) -> String {
+    lines
+        .iter()
+        .filter(|line| line.section.as_ref() == Some(section))
+        .filter_map(|line| match &line.content {
+            LineContent::DiffLine(diff) => {
+                let origin = match diff.line_type {
+                    DiffLineType::Addition => '+',
+                    DiffLineType::Deletion => '-',
+                    _ => ' ',
+                };
+                Some(format!("{}{}\n", origin, diff.content))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether two hunk sections are of the same file, on the same side of the index
+fn same_file(a: &SectionType, b: &SectionType) -> bool {
+    match (a, b) {
+        (SectionType::UnstagedHunk { path: a, .. }, SectionType::UnstagedHunk { path: b, .. })
+        | (SectionType::StagedHunk { path: a, .. }, SectionType::StagedHunk { path: b, .. }) => {
+            a == b
+        }
+        _ => false,
+    }
+}
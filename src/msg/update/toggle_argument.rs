@@ -65,6 +65,28 @@ pub fn update(model: &mut Model, argument: Argument) -> Option<Message> {
             |a| a.rebase_mut(),
             Arguments::RebaseArguments,
         ),
+        Argument::Merge(arg) => {
+            let conflicting = arg.conflicting();
+            toggle_set(
+                &mut model.arguments,
+                arg.clone(),
+                |a| a.merge_mut(),
+                Arguments::MergeArguments,
+            );
+            // --ff-only and --no-ff are mutually exclusive: enabling one clears the other
+            if let Some(set) = model.arguments.as_mut().and_then(|a| a.merge_mut())
+                && set.contains(&arg)
+                && let Some(conflicting) = conflicting
+            {
+                set.remove(&conflicting);
+            }
+        }
+        Argument::Apply(arg) => toggle_set(
+            &mut model.arguments,
+            arg,
+            |a| a.apply_mut(),
+            Arguments::ApplyArguments,
+        ),
     }
     model.arg_mode = false;
     None
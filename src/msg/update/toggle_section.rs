@@ -1,4 +1,7 @@
-use crate::{model::Model, msg::Message};
+use crate::{
+    model::{Model, SectionType},
+    msg::Message,
+};
 
 pub fn update(model: &mut Model) -> Option<Message> {
     // Get the current line and check if it's a collapsible header
@@ -8,6 +11,10 @@ pub fn update(model: &mut Model) -> Option<Message> {
         // Toggle the section in collapsed_sections
         if model.ui_model.collapsed_sections.contains(&section) {
             model.ui_model.collapsed_sections.remove(&section);
+            // An untracked file's preview is only read once it's expanded
+            if matches!(section, SectionType::UntrackedFile { .. }) {
+                return Some(Message::Refresh);
+            }
         } else {
             model.ui_model.collapsed_sections.insert(section);
         }
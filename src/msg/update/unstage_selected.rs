@@ -1,5 +1,8 @@
 use crate::{
-    git::stage::{unstage_files, unstage_hunk, unstage_lines},
+    git::{
+        DiffSettings,
+        stage::{unstage_files, unstage_hunk, unstage_lines},
+    },
     model::{Model, cursor_context::CursorContext, popup::PopupContent},
     msg::Message,
 };
@@ -37,7 +40,7 @@ pub fn update(model: &mut Model) -> Option<Message> {
     // Exit visual mode after unstaging
     model.ui_model.visual_mode_anchor = None;
 
-    let result = apply_selection(&repo_path, selection);
+    let result = apply_selection(&repo_path, &model.git_info.diff_settings, selection);
 
     if let Err(e) = result {
         model.popup = Some(PopupContent::Error {
@@ -53,16 +56,17 @@ pub fn update(model: &mut Model) -> Option<Message> {
 
 fn apply_selection(
     repo_path: &std::path::Path,
+    settings: &DiffSettings,
     selection: Selection,
 ) -> Result<(), crate::errors::MagiError> {
     match selection {
         Selection::None => Ok(()),
         Selection::Files(files) => unstage_files(repo_path, &files),
-        Selection::Hunk { path, hunk_index } => unstage_hunk(repo_path, path, hunk_index),
+        Selection::Hunk { path, hunk_index } => unstage_hunk(repo_path, path, hunk_index, settings),
         Selection::Hunks { path, hunk_indices } => {
             // Apply hunks in reverse order (highest index first) to avoid index shifts
             for idx in hunk_indices {
-                unstage_hunk(repo_path, path, idx)?;
+                unstage_hunk(repo_path, path, idx, settings)?;
             }
             Ok(())
         }
@@ -70,6 +74,6 @@ fn apply_selection(
             path,
             hunk_index,
             line_indices,
-        } => unstage_lines(repo_path, path, hunk_index, &line_indices),
+        } => unstage_lines(repo_path, path, hunk_index, &line_indices, settings),
     }
 }
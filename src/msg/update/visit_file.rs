@@ -0,0 +1,190 @@
+use crate::{
+    git::{stage::parse_hunk_header_starts, visit::write_blob_to_temp},
+    model::{
+        DiffLineType, Line, LineContent, Model, PreviewLineType, SectionType, ViewMode,
+        popup::PopupContent,
+    },
+    msg::Message,
+};
+
+/// A file and line picked from a commit or stash preview.
+struct PreviewTarget {
+    path: String,
+    line: Option<usize>,
+    /// The cursor is on a removed line, which only exists in the parent
+    old_side: bool,
+}
+
+/// Works out the file and line under the cursor and asks for it to be
+/// opened in the editor: the worktree file in the status view, the blob at
+/// the previewed revision in a commit or stash preview.
+pub fn update(model: &mut Model) -> Option<Message> {
+    let cursor = model.ui_model.cursor_position;
+    let lines = &model.ui_model.lines;
+
+    let (file, line) = if model.view_mode == ViewMode::Preview {
+        let target = preview_target(lines, cursor)?;
+        match preview_revision(model) {
+            Some(revision) => {
+                let revision = if target.old_side {
+                    format!("{}^", revision)
+                } else {
+                    revision
+                };
+                match write_blob_to_temp(&model.workdir, &revision, &target.path) {
+                    Ok(file) => (file, target.line),
+                    Err(err) => {
+                        model.popup = Some(PopupContent::error(err.to_string()));
+                        return None;
+                    }
+                }
+            }
+            // Nothing to show a blob of (e.g. a merge preview): the mapped
+            // line of the worktree file is the closest match
+            None => (model.workdir.join(&target.path), target.line),
+        }
+    } else {
+        let (path, line) = status_target(lines, cursor)?;
+        (model.workdir.join(path), line)
+    };
+
+    Some(Message::OpenInEditor { file, line })
+}
+
+/// The new-side start line of a hunk header. Combined diff headers of
+/// unmerged files (`@@@ -a -b +c @@@`) list one old range per parent.
+fn hunk_new_start(header: &str) -> Option<usize> {
+    if header.starts_with("@@@") {
+        return header
+            .split_whitespace()
+            .find_map(|part| part.strip_prefix('+'))
+            .and_then(|range| range.split(',').next())
+            .and_then(|start| start.parse().ok());
+    }
+    parse_hunk_header_starts(header).ok().map(|(_, new)| new)
+}
+
+/// Whether a status diff line exists in the new version of the file.
+fn is_new_side(line: &Line) -> bool {
+    matches!(&line.content, LineContent::DiffLine(diff)
+        if !matches!(diff.line_type, DiffLineType::Deletion | DiffLineType::CombinedDeletion))
+}
+
+/// The file and line for a status view line: files open at the top, hunks
+/// and diff lines at their line in the new version (a removed line maps to
+/// where it used to be).
+fn status_target(lines: &[Line], cursor: usize) -> Option<(String, Option<usize>)> {
+    let line = lines.get(cursor)?;
+    match (&line.content, &line.section) {
+        (LineContent::UntrackedFile(path), _) => Some((path.clone(), None)),
+        (LineContent::UnstagedFile(file) | LineContent::StagedFile(file), _) => {
+            Some((file.path.clone(), None))
+        }
+        (
+            LineContent::DiffHunk(_) | LineContent::DiffLine(_),
+            Some(SectionType::UnstagedHunk { path, .. } | SectionType::StagedHunk { path, .. }),
+        ) => {
+            let header = lines[..=cursor]
+                .iter()
+                .rposition(|l| matches!(l.content, LineContent::DiffHunk(_)))?;
+            let LineContent::DiffHunk(hunk) = &lines[header].content else {
+                return None;
+            };
+            let start = hunk_new_start(&hunk.header)?;
+            let offset = lines[header..cursor]
+                .iter()
+                .filter(|l| is_new_side(l))
+                .count();
+            Some((path.clone(), Some(start + offset)))
+        }
+        _ => None,
+    }
+}
+
+/// The file and line for a preview line. Removed lines map to the old
+/// version, everything else to the new one.
+fn preview_target(lines: &[Line], cursor: usize) -> Option<PreviewTarget> {
+    let preview = |line: &Line| match &line.content {
+        LineContent::PreviewLine {
+            content, line_type, ..
+        } => Some((content.clone(), line_type.clone())),
+        _ => None,
+    };
+    let (_, line_type) = preview(lines.get(cursor)?)?;
+    if line_type == PreviewLineType::Header {
+        return None;
+    }
+    let old_side = line_type == PreviewLineType::Deletion;
+
+    // The file header of the diff the cursor is in
+    let diff_start = lines[..=cursor]
+        .iter()
+        .rposition(|l| preview(l).is_some_and(|(content, _)| content.starts_with("diff --git ")))?;
+    let prefix = if old_side { "--- a/" } else { "+++ b/" };
+    let path = lines[diff_start..]
+        .iter()
+        .filter_map(preview)
+        .take_while(|(_, line_type)| {
+            matches!(
+                line_type,
+                PreviewLineType::DiffFileHeader | PreviewLineType::FileMetadata
+            )
+        })
+        .find_map(|(content, _)| content.strip_prefix(prefix).map(str::to_string))
+        .or_else(|| {
+            // Diffs without content (e.g. pure renames) only have this line
+            let (content, _) = preview(&lines[diff_start])?;
+            content.rsplit_once(" b/").map(|(_, path)| path.to_string())
+        })?;
+
+    let header = lines[diff_start..=cursor].iter().rposition(|l| {
+        preview(l).is_some_and(|(_, line_type)| line_type == PreviewLineType::HunkHeader)
+    });
+    let line = match header.map(|h| h + diff_start) {
+        Some(header) => {
+            let (content, _) = preview(&lines[header])?;
+            let (old_start, new_start) = parse_hunk_header_starts(&content).ok()?;
+            let skipped = if old_side {
+                PreviewLineType::Addition
+            } else {
+                PreviewLineType::Deletion
+            };
+            let offset = lines[header..cursor]
+                .iter()
+                .skip(1)
+                .filter_map(preview)
+                .filter(|(_, line_type)| *line_type != skipped)
+                .count();
+            Some(if old_side { old_start } else { new_start } + offset)
+        }
+        // On the file header: open the file at the top
+        None => None,
+    };
+
+    Some(PreviewTarget {
+        path,
+        line,
+        old_side,
+    })
+}
+
+/// The revision shown in the preview: the `commit <hash>` header of a
+/// commit, or the stash the preview was opened from.
+fn preview_revision(model: &Model) -> Option<String> {
+    if let Some(LineContent::PreviewLine { content, .. }) =
+        model.ui_model.lines.first().map(|l| &l.content)
+        && let Some(hash) = content.strip_prefix("commit ")
+    {
+        return hash.split_whitespace().next().map(str::to_string);
+    }
+
+    let return_model = model.preview_return_ui_model.as_ref()?;
+    match &return_model
+        .lines
+        .get(return_model.cursor_position)?
+        .content
+    {
+        LineContent::Stash(stash) => Some(format!("stash@{{{}}}", stash.index)),
+        _ => None,
+    }
+}
@@ -38,7 +38,8 @@ pub(super) fn resolve_path(workdir: &std::path::Path, path: &str) -> PathBuf {
 
 pub(super) fn switch_to_worktree(model: &mut Model, path: PathBuf) {
     match GitInfo::new_from_path(&path) {
-        Ok(git_info) => {
+        Ok(mut git_info) => {
+            git_info.diff_settings = model.git_info.diff_settings;
             // Extract canonical workdir from the new repo
             if let Some(workdir) = git_info.repository.workdir() {
                 model.workdir = workdir.to_path_buf();
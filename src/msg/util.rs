@@ -2,7 +2,10 @@ use std::collections::HashSet;
 
 use crate::{
     model::{Line, SectionType},
-    msg::{FixupType, MergeCommand, Message, RebaseCommand, RevertCommand},
+    msg::{
+        FixupType, HunkReviewMessage, MergeCommand, Message, RebaseCommand, ResolveCommand,
+        RevertCommand,
+    },
 };
 
 /// Count visible lines between two raw line indices (exclusive of end).
@@ -25,8 +28,8 @@ pub fn visible_lines_between(
 pub fn is_external_command(msg: &Message) -> bool {
     matches!(
         msg,
-        Message::Commit
-            | Message::Amend(_)
+        Message::CommitWithEditor(_)
+            | Message::AmendWithEditor(_)
             | Message::FixupCommit(_, FixupType::Alter | FixupType::Augment)
             | Message::ReviseCommit(_)
             | Message::Revert(RevertCommand::Continue)
@@ -42,5 +45,9 @@ pub fn is_external_command(msg: &Message) -> bool {
             | Message::Merge(MergeCommand::Continue)
             | Message::Merge(MergeCommand::Branch(_))
             | Message::Merge(MergeCommand::EditMessage(_))
+            | Message::Resolve(ResolveCommand::Mergetool { .. })
+            | Message::OpenInEditor { .. }
+            | Message::EditHunk
+            | Message::HunkReview(HunkReviewMessage::Edit)
     )
 }
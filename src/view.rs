@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 use ratatui::{
     Frame,
+    layout::{Constraint, Layout},
     style::Style,
     text::{Line as TextLine, Span},
     widgets::{Block, Borders, Paragraph},
@@ -7,19 +10,26 @@ use ratatui::{
 
 use crate::{
     i18n,
-    model::{InputMode, Model, ViewMode, select_popup::OnSelect},
+    model::{
+        InputMode, LineContent, Model, SectionType, ViewMode, commit_editor::CommitEditorMode,
+        hunk_review::ReviewDecision, select_popup::OnSelect,
+    },
     view::{
-        render::{render_popup, render_toast, util::input_spans},
-        util::{apply_search_highlight, apply_selection_style, visible_scroll_offset},
+        render::{commit_editor, render_popup, render_toast, util::input_spans},
+        util::{FileEntry, apply_search_highlight, apply_selection_style, visible_scroll_offset},
     },
 };
 
 mod util;
 
+mod ansi;
+mod blob_line;
 mod cherry_picking_entry;
 mod commit;
 mod diff_hunk;
 mod diff_line;
+mod directory;
+mod file_detail;
 mod head_ref;
 mod latest_tag;
 mod log_line;
@@ -28,12 +38,14 @@ mod preview_line;
 mod push_ref;
 mod rebase_todo_line;
 mod rebasing_entry;
+mod remote_section_header;
 mod render;
 mod reverting_entry;
 mod section_header;
+mod side_by_side;
 mod staged_file;
 mod stash;
-mod unpulled_section_header;
+mod syntax;
 mod unstaged_file;
 mod untracked_file;
 
@@ -74,9 +86,22 @@ mod untracked_file;
 ///
 ///
 pub fn view(model: &Model, frame: &mut Frame) {
-    let area = frame.area();
+    let full_area = frame.area();
     let theme = &model.theme;
 
+    // The commit message editor sits above the diff being committed
+    let area = match &model.commit_editor {
+        Some(state) if model.view_mode == ViewMode::CommitMessage => {
+            let editor_height = commit_editor::editor_height(state, full_area);
+            let [editor_area, diff_area] =
+                Layout::vertical([Constraint::Length(editor_height), Constraint::Min(0)])
+                    .areas(full_area);
+            commit_editor::render(state, frame, editor_area, theme);
+            diff_area
+        }
+        _ => full_area,
+    };
+
     let mut text = Vec::new();
     let cursor_pos = model.ui_model.cursor_position;
 
@@ -92,16 +117,12 @@ pub fn view(model: &Model, frame: &mut Frame) {
     let cursor_section = cursor_line.and_then(|l| l.section.as_ref());
 
     let collapsed_sections = &model.ui_model.collapsed_sections;
+    let lines = &model.ui_model.lines;
 
-    for (index, line) in model.ui_model.lines.iter().enumerate() {
-        // Skip hidden lines (lines whose parent section is collapsed)
-        if line.is_hidden(collapsed_sections) {
-            continue;
-        }
-
-        // Determine if this line should be highlighted
-        // In visual mode, highlight all visible lines between anchor and cursor
-        let is_in_selected_section = if let Some((start, end)) = visual_range {
+    // Determine if a line should be highlighted
+    // In visual mode, highlight all visible lines between anchor and cursor
+    let is_selected = |index: usize| {
+        if let Some((start, end)) = visual_range {
             index >= start && index <= end
         } else {
             util::should_highlight_line(
@@ -109,9 +130,120 @@ pub fn view(model: &Model, frame: &mut Frame) {
                 cursor_pos,
                 cursor_content,
                 cursor_section,
-                line.section.as_ref(),
+                lines[index].section.as_ref(),
             )
-        };
+        }
+    };
+
+    // Side-by-side diffs pair lines into rows, so the row of each line is
+    // tracked for scrolling; the cursor still moves over the unified lines.
+    let side_by_side = model.config.side_by_side_diff && content_width >= side_by_side::MIN_WIDTH;
+    let mut rows_of_lines = vec![0; lines.len()];
+    let mut numbers = side_by_side::LineNumbers::default();
+    let mut paired_until = 0;
+
+    // Files below a directory of the tree layout are indented under it
+    let directory_depths: HashMap<&SectionType, usize> = lines
+        .iter()
+        .filter_map(|line| match (&line.content, &line.section) {
+            (LineContent::Directory { depth, .. }, Some(section)) => Some((section, *depth)),
+            _ => None,
+        })
+        .collect();
+
+    let highlights = if model.config.syntax_highlighting {
+        let visible = syntax::visible_range(
+            lines,
+            model.ui_model.scroll_offset,
+            area.height as usize,
+            collapsed_sections,
+        );
+        syntax::highlight_lines(lines, visible, collapsed_sections, theme)
+    } else {
+        syntax::Highlights::default()
+    };
+
+    for (index, line) in lines.iter().enumerate() {
+        if index < paired_until {
+            continue;
+        }
+        rows_of_lines[index] = text.len();
+
+        // Skip hidden lines (lines whose parent section is collapsed)
+        if line.is_hidden(collapsed_sections) {
+            continue;
+        }
+
+        if side_by_side && let Some(split) = side_by_side::split_line(line) {
+            let column = |index: usize, number: usize, split| side_by_side::Column {
+                number: Some(number),
+                content: Some(split),
+                syntax: highlights.get(index),
+                highlight: side_by_side::Highlight {
+                    selected: is_selected(index),
+                    cursor: index == cursor_pos,
+                },
+            };
+            let empty = || side_by_side::Column {
+                number: None,
+                content: None,
+                syntax: &[],
+                highlight: Default::default(),
+            };
+            let query = model.ui_model.search_query.as_str();
+
+            match split {
+                side_by_side::SplitLine::Header { old, new } => {
+                    numbers = side_by_side::LineNumbers { old, new };
+                }
+                side_by_side::SplitLine::Context(_) => {
+                    text.push(side_by_side::render_row(
+                        column(index, numbers.old, split),
+                        column(index, numbers.new, split),
+                        content_width,
+                        query,
+                        theme,
+                    ));
+                    numbers.old += 1;
+                    numbers.new += 1;
+                    continue;
+                }
+                side_by_side::SplitLine::Removed(..) | side_by_side::SplitLine::Added(..) => {
+                    let (rows, end) = side_by_side::pair_changes(lines, index, collapsed_sections);
+                    for row in rows {
+                        let old = row.old.and_then(|i| {
+                            rows_of_lines[i] = text.len();
+                            numbers.old += 1;
+                            Some(column(
+                                i,
+                                numbers.old - 1,
+                                side_by_side::split_line(&lines[i])?,
+                            ))
+                        });
+                        let new = row.new.and_then(|i| {
+                            rows_of_lines[i] = text.len();
+                            numbers.new += 1;
+                            Some(column(
+                                i,
+                                numbers.new - 1,
+                                side_by_side::split_line(&lines[i])?,
+                            ))
+                        });
+                        text.push(side_by_side::render_row(
+                            old.unwrap_or_else(empty),
+                            new.unwrap_or_else(empty),
+                            content_width,
+                            query,
+                            theme,
+                        ));
+                    }
+                    paired_until = end;
+                    continue;
+                }
+            }
+        }
+
+        let is_in_selected_section = is_selected(index);
 
         // Check if this line's section is collapsed (for showing the indicator)
         let is_section_collapsed = if let Some(section) = line.collapsible_section() {
@@ -133,21 +265,57 @@ pub fn view(model: &Model, frame: &mut Frame) {
             crate::model::LineContent::SectionHeader { title, count } => {
                 section_header::get_lines(title, *count, is_section_collapsed, theme)
             }
-            crate::model::LineContent::UnpulledSectionHeader { remote_name, count } => {
-                unpulled_section_header::get_lines(remote_name, *count, is_section_collapsed, theme)
-            }
-            crate::model::LineContent::UntrackedFile(file_path) => {
-                untracked_file::get_lines(file_path, theme)
-            }
+            crate::model::LineContent::RemoteSectionHeader {
+                prefix,
+                remote_name,
+                count,
+            } => remote_section_header::get_lines(
+                prefix,
+                remote_name,
+                *count,
+                is_section_collapsed,
+                theme,
+            ),
+            crate::model::LineContent::UntrackedFile(file_path) => untracked_file::get_lines(
+                FileEntry::new(file_path, line.section.as_ref(), &directory_depths),
+                theme,
+            ),
             crate::model::LineContent::UnstagedFile(file_change) => {
-                unstaged_file::get_lines(file_change, is_section_collapsed, theme)
+                let directory = line.section.as_ref().and_then(SectionType::parent_section);
+                unstaged_file::get_lines(
+                    file_change,
+                    FileEntry::new(&file_change.path, directory.as_ref(), &directory_depths),
+                    is_section_collapsed,
+                    theme,
+                )
             }
             crate::model::LineContent::StagedFile(file_change) => {
-                staged_file::get_lines(file_change, is_section_collapsed, theme)
+                let directory = line.section.as_ref().and_then(SectionType::parent_section);
+                staged_file::get_lines(
+                    file_change,
+                    FileEntry::new(&file_change.path, directory.as_ref(), &directory_depths),
+                    is_section_collapsed,
+                    theme,
+                )
+            }
+            crate::model::LineContent::Directory { name, depth, count } => {
+                let name_color = match line.section {
+                    Some(SectionType::UntrackedDirectory { .. }) => theme.untracked_file,
+                    _ => theme.file_path,
+                };
+                directory::get_lines(
+                    name,
+                    *depth,
+                    *count,
+                    is_section_collapsed,
+                    name_color,
+                    theme,
+                )
             }
+            crate::model::LineContent::FileDetail(detail) => file_detail::get_lines(detail, theme),
             crate::model::LineContent::DiffHunk(hunk) => diff_hunk::get_lines(hunk, theme),
             crate::model::LineContent::DiffLine(diff_line) => {
-                diff_line::get_lines(diff_line, theme)
+                diff_line::get_lines(diff_line, highlights.get(index), theme)
             }
             crate::model::LineContent::Commit(commit_info) => commit::get_lines(
                 commit_info,
@@ -163,7 +331,7 @@ pub fn view(model: &Model, frame: &mut Frame) {
             crate::model::LineContent::RebaseTodoLine(entry) => {
                 rebase_todo_line::get_lines(entry, theme)
             }
-            crate::model::LineContent::RebaseTodoHint { key, description } => {
+            crate::model::LineContent::KeyHint { key, description } => {
                 rebase_todo_line::get_hint_lines(key, description, theme)
             }
             crate::model::LineContent::Stash(stash_entry) => stash::get_lines(stash_entry, theme),
@@ -175,16 +343,24 @@ pub fn view(model: &Model, frame: &mut Frame) {
             crate::model::LineContent::RebasingEntry {
                 hash,
                 message,
+                action,
                 is_current,
-            } => rebasing_entry::get_lines(hash, message, *is_current, theme),
+            } => rebasing_entry::get_lines(hash, message, *action, *is_current, theme),
             crate::model::LineContent::CherryPickingEntry {
                 hash,
                 message,
                 is_current,
             } => cherry_picking_entry::get_lines(hash, message, *is_current, theme),
-            crate::model::LineContent::PreviewLine { content, line_type } => {
-                preview_line::get_lines(content, line_type, theme)
-            }
+            crate::model::LineContent::PreviewLine {
+                content,
+                line_type,
+                refined,
+            } => preview_line::get_lines(content, line_type, refined, highlights.get(index), theme),
+            crate::model::LineContent::BlobLine {
+                number,
+                content,
+                blame,
+            } => blob_line::get_lines(*number, content, blame.as_ref(), theme),
         };
 
         let is_cursor_line = index == cursor_pos;
@@ -215,11 +391,18 @@ pub fn view(model: &Model, frame: &mut Frame) {
         text.extend(line_texts);
     }
 
-    let scroll = visible_scroll_offset(
-        &model.ui_model.lines,
-        model.ui_model.scroll_offset,
-        collapsed_sections,
-    ) as u16;
+    let scroll = if side_by_side {
+        rows_of_lines
+            .get(model.ui_model.scroll_offset)
+            .copied()
+            .unwrap_or(text.len()) as u16
+    } else {
+        visible_scroll_offset(
+            &model.ui_model.lines,
+            model.ui_model.scroll_offset,
+            collapsed_sections,
+        ) as u16
+    };
 
     let directory = model.workdir.to_str().unwrap_or(".");
 
@@ -232,6 +415,17 @@ pub fn view(model: &Model, frame: &mut Frame) {
     };
     let mode_label = if model.view_mode == ViewMode::Preview {
         i18n::t().mode_preview
+    } else if matches!(model.view_mode, ViewMode::Blob { .. }) {
+        i18n::t().mode_blob
+    } else if model.view_mode == ViewMode::HunkReview {
+        i18n::t().mode_review
+    } else if let Some(state) = &model.commit_editor
+        && model.view_mode == ViewMode::CommitMessage
+    {
+        match state.mode {
+            CommitEditorMode::Insert => i18n::t().mode_insert,
+            CommitEditorMode::Normal => i18n::t().mode_normal,
+        }
     } else {
         match mode {
             InputMode::Normal => i18n::t().mode_normal,
@@ -259,7 +453,14 @@ pub fn view(model: &Model, frame: &mut Frame) {
             _ => "Pick commit".to_string(),
         },
         ViewMode::Preview => "Preview".to_string(),
+        ViewMode::Blob {
+            ref revision,
+            ref path,
+            ..
+        } => format!("{} @ {}", path, revision),
         ViewMode::RebaseTodo => "Rebase".to_string(),
+        ViewMode::CommitMessage => "Diff".to_string(),
+        ViewMode::HunkReview => hunk_review_title(model),
     };
 
     // Vim-style command line typed in the rebase todo editor (after ':')
@@ -320,11 +521,35 @@ pub fn view(model: &Model, frame: &mut Frame) {
 
     // Render toast in bottom-right corner if present
     if let Some(toast) = &model.toast {
-        render_toast(toast, frame, area, theme);
+        render_toast(toast, frame, full_area, theme);
     }
 
     // Render popup overlay if present (on top of toast)
     if let Some(popup) = &model.popup {
-        render_popup(model, popup, frame, area, theme);
+        render_popup(model, popup, frame, full_area, theme);
     }
 }
+
+/// The review's progress, followed by what was decided on the hunk shown.
+fn hunk_review_title(model: &Model) -> String {
+    let t = i18n::t();
+    let Some(state) = &model.hunk_review else {
+        return String::new();
+    };
+    let (file, files) = state.file_progress();
+    let title = [state.current + 1, state.hunks.len(), file, files]
+        .iter()
+        .fold(t.title_hunk_review_fmt.to_string(), |title, number| {
+            title.replacen("{}", &number.to_string(), 1)
+        });
+
+    let decision = match state.current_hunk().and_then(|hunk| hunk.decision.as_ref()) {
+        None => return title,
+        Some(ReviewDecision::Accept) if state.staged => t.review_decision_unstage,
+        Some(ReviewDecision::Accept) => t.review_decision_stage,
+        Some(ReviewDecision::Skip) => t.review_decision_skip,
+        Some(ReviewDecision::Discard) => t.review_decision_discard,
+        Some(ReviewDecision::Edited(_)) => t.review_decision_edited,
+    };
+    format!("{} · {}", title, decision)
+}
@@ -0,0 +1,176 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+
+use crate::git::ansi::{Segment, segments};
+
+/// Split text on the ANSI color codes emitted by `git log --color` or an
+/// external diff renderer into styled spans. Text outside any color code
+/// uses `default_style`.
+pub fn ansi_spans(text: &str, default_style: Style) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut style = default_style;
+    let mut current = String::new();
+
+    for segment in segments(text) {
+        match segment {
+            Segment::Text(text) => current.push_str(text),
+            Segment::Sgr(params) => {
+                let new_style = apply_sgr(params, style, default_style);
+                if new_style != style {
+                    if !current.is_empty() {
+                        spans.push(Span::styled(std::mem::take(&mut current), style));
+                    }
+                    style = new_style;
+                }
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        spans.push(Span::styled(current, style));
+    }
+
+    spans
+}
+
+/// Apply the parameters of an SGR escape sequence (e.g. "1;31") on top of
+/// `style`. Unsupported parameters are ignored; a reset restores
+/// `default_style`.
+fn apply_sgr(params: &str, style: Style, default_style: Style) -> Style {
+    let mut style = style;
+    let mut params = params.split(';').map(|p| p.parse::<u8>().unwrap_or(0));
+    while let Some(param) = params.next() {
+        match param {
+            0 => style = default_style,
+            1 => style = style.add_modifier(Modifier::BOLD),
+            2 => style = style.add_modifier(Modifier::DIM),
+            3 => style = style.add_modifier(Modifier::ITALIC),
+            4 => style = style.add_modifier(Modifier::UNDERLINED),
+            7 => style = style.add_modifier(Modifier::REVERSED),
+            22 => style = style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style = style.remove_modifier(Modifier::ITALIC),
+            24 => style = style.remove_modifier(Modifier::UNDERLINED),
+            27 => style = style.remove_modifier(Modifier::REVERSED),
+            30..=37 => style = style.fg(ansi_color(param - 30, false)),
+            90..=97 => style = style.fg(ansi_color(param - 90, true)),
+            40..=47 => style = style.bg(ansi_color(param - 40, false)),
+            100..=107 => style = style.bg(ansi_color(param - 100, true)),
+            39 => style.fg = default_style.fg,
+            49 => style.bg = default_style.bg,
+            38 | 48 => {
+                let color = match (params.next(), params.next()) {
+                    (Some(5), Some(idx)) => Some(Color::Indexed(idx)),
+                    (Some(2), Some(r)) => {
+                        let (g, b) = (params.next().unwrap_or(0), params.next().unwrap_or(0));
+                        Some(Color::Rgb(r, g, b))
+                    }
+                    _ => None,
+                };
+                match color {
+                    Some(color) if param == 38 => style = style.fg(color),
+                    Some(color) => style = style.bg(color),
+                    None => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    style
+}
+
+/// Map an ANSI color number (0-7) to a ratatui color
+fn ansi_color(number: u8, bright: bool) -> Color {
+    match (number, bright) {
+        (0, false) => Color::Black,
+        (1, false) => Color::Red,
+        (2, false) => Color::Green,
+        (3, false) => Color::Yellow,
+        (4, false) => Color::Blue,
+        (5, false) => Color::Magenta,
+        (6, false) => Color::Cyan,
+        (_, false) => Color::Gray,
+        (0, true) => Color::DarkGray,
+        (1, true) => Color::LightRed,
+        (2, true) => Color::LightGreen,
+        (3, true) => Color::LightYellow,
+        (4, true) => Color::LightBlue,
+        (5, true) => Color::LightMagenta,
+        (6, true) => Color::LightCyan,
+        (_, true) => Color::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_style() -> Style {
+        Style::default().fg(Color::Gray)
+    }
+
+    #[test]
+    fn test_ansi_spans_plain() {
+        let spans = ansi_spans("| * ", default_style());
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].content, "| * ");
+        assert_eq!(spans[0].style, default_style());
+    }
+
+    #[test]
+    fn test_ansi_spans_colored() {
+        let spans = ansi_spans("\x1b[31m|\x1b[m \x1b[1;32m*\x1b[m ", default_style());
+        assert_eq!(spans.len(), 4);
+        assert_eq!(spans[0].content, "|");
+        assert_eq!(spans[0].style, default_style().fg(Color::Red));
+        assert_eq!(spans[1].content, " ");
+        assert_eq!(spans[1].style, default_style());
+        assert_eq!(spans[2].content, "*");
+        assert_eq!(
+            spans[2].style,
+            default_style()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(spans[3].content, " ");
+        assert_eq!(spans[3].style, default_style());
+    }
+
+    #[test]
+    fn test_ansi_spans_256_and_rgb_colors() {
+        let spans = ansi_spans(
+            "\x1b[38;5;208m|\x1b[m\x1b[38;2;1;2;3m/\x1b[m",
+            default_style(),
+        );
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].style, default_style().fg(Color::Indexed(208)));
+        assert_eq!(spans[1].style, default_style().fg(Color::Rgb(1, 2, 3)));
+    }
+
+    #[test]
+    fn test_ansi_spans_backgrounds() {
+        // delta marks changed lines with a background color
+        let spans = ansi_spans(
+            "\x1b[48;2;0;64;0m+\x1b[1;32mword\x1b[49m end",
+            default_style(),
+        );
+        assert_eq!(spans[0].style, default_style().bg(Color::Rgb(0, 64, 0)));
+        assert_eq!(
+            spans[1].style,
+            default_style()
+                .fg(Color::Green)
+                .bg(Color::Rgb(0, 64, 0))
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(spans[2].content, " end");
+        assert_eq!(spans[2].style.bg, None);
+    }
+
+    #[test]
+    fn test_ansi_spans_unsupported_codes_ignored() {
+        let spans = ansi_spans("\x1b[5m|\x1b[m *", default_style());
+        let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "| *");
+    }
+}
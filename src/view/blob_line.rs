@@ -0,0 +1,40 @@
+use ratatui::{
+    style::Style,
+    text::{Line as TextLine, Span},
+};
+
+use crate::config::Theme;
+use crate::model::BlameInfo;
+use crate::view::util::expand_tabs;
+
+/// Width of the blame author column
+const AUTHOR_WIDTH: usize = 16;
+
+pub fn get_lines(
+    number: usize,
+    content: &str,
+    blame: Option<&BlameInfo>,
+    theme: &Theme,
+) -> Vec<TextLine<'static>> {
+    let mut spans = Vec::new();
+    if let Some(info) = blame {
+        let author: String = info.author.chars().take(AUTHOR_WIDTH).collect();
+        spans.push(Span::styled(
+            format!("{} ", info.hash),
+            Style::default().fg(theme.commit_hash),
+        ));
+        spans.push(Span::styled(
+            format!("{:<width$} {} ", author, info.date, width = AUTHOR_WIDTH),
+            Style::default().fg(theme.diff_context),
+        ));
+    }
+    spans.push(Span::styled(
+        format!("{:>4} ", number),
+        Style::default().fg(theme.dim_text),
+    ));
+    spans.push(Span::styled(
+        expand_tabs(content, 0),
+        Style::default().fg(theme.text),
+    ));
+    vec![TextLine::from(spans)]
+}
@@ -1,14 +1,21 @@
+use std::ops::Range;
+
 use ratatui::{
-    style::Style,
+    style::{Color, Style},
     text::{Line as TextLine, Span},
 };
 
 use crate::config::Theme;
 use crate::model::{DiffLine, DiffLineType};
-use crate::view::util::expand_tabs;
+use crate::view::util::content_spans;
 
-/// Generate the view lines for a diff line
-pub fn get_lines(diff_line: &DiffLine, theme: &Theme) -> Vec<TextLine<'static>> {
+/// Generate the view lines for a diff line, with the foreground colors of
+/// its syntax highlight
+pub fn get_lines(
+    diff_line: &DiffLine,
+    syntax: &[(Range<usize>, Color)],
+    theme: &Theme,
+) -> Vec<TextLine<'static>> {
     // Combined-diff lines already carry their origin prefix in `content`
     let (prefix, color) = match diff_line.line_type {
         DiffLineType::Addition => ("+", theme.diff_addition),
@@ -20,22 +27,53 @@ pub fn get_lines(diff_line: &DiffLine, theme: &Theme) -> Vec<TextLine<'static>>
         DiffLineType::ConflictMarker => ("", theme.diff_hunk),
     };
 
-    // Content tab stops are calculated from the column where the content
-    // starts: the leading space plus the prefix span.
-    let content = expand_tabs(&diff_line.content, 1 + prefix.len());
+    let refined_bg = match diff_line.line_type {
+        DiffLineType::Addition => theme.diff_addition_refined,
+        DiffLineType::Deletion => theme.diff_deletion_refined,
+        _ => Color::Reset,
+    };
 
-    let line = TextLine::from(vec![
+    let mut spans = vec![
         Span::raw(" "),
         Span::styled(prefix.to_string(), Style::default().fg(color)),
-        Span::styled(content, Style::default().fg(color)),
-    ]);
+    ];
+    // Content tab stops are calculated from the column where the content
+    // starts: the leading space plus the prefix span.
+    spans.extend(content_spans(
+        &diff_line.content,
+        &diff_line.refined,
+        syntax,
+        1 + prefix.len(),
+        Style::default().fg(color),
+        refined_bg,
+    ));
 
-    vec![line]
+    vec![TextLine::from(spans)]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::view::util::expand_tabs;
+
+    #[test]
+    fn refined_words_get_refined_background() {
+        let theme = Theme::default();
+        let line = DiffLine {
+            content: "let x = 2;".to_string(),
+            line_type: DiffLineType::Addition,
+            refined: vec![Range { start: 8, end: 9 }],
+        };
+
+        let spans = &get_lines(&line, &[], &theme)[0].spans;
+
+        let refined: Vec<&str> = spans
+            .iter()
+            .filter(|span| span.style.bg == Some(theme.diff_addition_refined))
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(refined, vec!["2"]);
+    }
 
     #[test]
     fn expand_tabs_no_tabs() {
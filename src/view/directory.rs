@@ -0,0 +1,29 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Line as TextLine, Span},
+};
+
+use crate::config::Theme;
+
+/// Generate the view lines for a directory of the tree layout
+pub fn get_lines(
+    name: &str,
+    depth: usize,
+    count: usize,
+    collapsed: bool,
+    name_color: Color,
+    theme: &Theme,
+) -> Vec<TextLine<'static>> {
+    // Use '>' when collapsed, '∨' when expanded
+    let indicator = if collapsed { ">" } else { "∨" };
+
+    vec![TextLine::from(vec![
+        Span::raw(indicator),
+        Span::raw("  ".repeat(depth)),
+        Span::styled(name.to_string(), Style::default().fg(name_color)),
+        Span::styled(
+            format!(" ({})", count),
+            Style::default().fg(theme.section_header),
+        ),
+    ])]
+}
@@ -0,0 +1,16 @@
+use ratatui::{
+    style::Style,
+    text::{Line as TextLine, Span},
+};
+
+use crate::config::Theme;
+use crate::model::FileDetail;
+
+/// Generate the view lines for a file's mode change, submodule commits or
+/// binary change, listed like a hunk header below the file
+pub fn get_lines(detail: &FileDetail, theme: &Theme) -> Vec<TextLine<'static>> {
+    vec![TextLine::from(vec![
+        Span::raw(" "),
+        Span::styled(detail.description(), Style::default().fg(theme.diff_hunk)),
+    ])]
+}
@@ -1,15 +1,22 @@
+use std::ops::Range;
+
 use ratatui::{
-    style::Style,
+    style::{Color, Style},
     text::{Line as TextLine, Span},
 };
 
 use crate::config::Theme;
 use crate::model::PreviewLineType;
-use crate::view::util::expand_tabs;
+use crate::view::{
+    ansi::ansi_spans,
+    util::{content_spans, expand_tabs},
+};
 
 pub fn get_lines(
     content: &str,
     line_type: &PreviewLineType,
+    refined: &[Range<usize>],
+    syntax: &[(Range<usize>, Color)],
     theme: &Theme,
 ) -> Vec<TextLine<'static>> {
     let style = match line_type {
@@ -19,7 +26,23 @@ pub fn get_lines(
         PreviewLineType::Addition => Style::default().fg(theme.diff_addition),
         PreviewLineType::Deletion => Style::default().fg(theme.diff_deletion),
         PreviewLineType::Context => Style::default().fg(theme.diff_context),
+        PreviewLineType::FileMetadata => Style::default().fg(theme.diff_hunk),
+        PreviewLineType::Rendered => Style::default().fg(theme.text),
     };
-    let content = expand_tabs(content, 0);
-    vec![TextLine::from(Span::styled(content, style))]
+    // An external renderer's output brings its own colors
+    if content.contains('\x1b') {
+        return vec![TextLine::from(ansi_spans(content, style))];
+    }
+    let refined_bg = match line_type {
+        PreviewLineType::Addition => theme.diff_addition_refined,
+        PreviewLineType::Deletion => theme.diff_deletion_refined,
+        PreviewLineType::Context if !syntax.is_empty() => Color::Reset,
+        _ => return vec![TextLine::from(Span::styled(expand_tabs(content, 0), style))],
+    };
+
+    // Refined and syntax ranges start after the origin character
+    let (origin, text) = content.split_at(content.len().min(1));
+    let mut spans = vec![Span::styled(origin.to_string(), style)];
+    spans.extend(content_spans(text, refined, syntax, 1, style, refined_bg));
+    vec![TextLine::from(spans)]
 }
@@ -10,26 +10,30 @@ use crate::{
 
 /// Render a single line in the interactive rebase todo editor.
 /// Format: `action  hash message`, with the action word colour-coded.
+/// Instruction lines have no hash and show their argument instead
+/// (`exec  cargo test`, `label  onto`).
 pub fn get_lines(entry: &RebaseTodoEntry, theme: &Theme) -> Vec<TextLine<'static>> {
-    let action_style = match entry.action {
-        RebaseAction::Pick => Style::default().fg(theme.text),
-        RebaseAction::Reword => Style::default().fg(theme.local_branch),
-        RebaseAction::Edit => Style::default().fg(theme.section_header),
-        RebaseAction::Squash | RebaseAction::Fixup => Style::default().fg(theme.remote_branch),
-        RebaseAction::Drop => Style::default().fg(theme.diff_deletion),
-    };
+    let action_style = action_style(entry.action, theme);
 
     let short_hash: String = entry.hash.chars().take(7).collect();
 
     let mut spans = vec![
         Span::raw(" "),
-        // Pad to the longest action word ("reword"/"squash") plus one space
-        Span::styled(format!("{:<7}", entry.action.as_str()), action_style),
-        Span::styled(short_hash, Style::default().fg(theme.commit_hash)),
+        // Pad to the longest plain action word ("reword"/"squash") plus one
+        // space; the longer `fixup -C` and `update-ref` push the line along
+        Span::styled(format!("{:<6} ", entry.action.as_str()), action_style),
     ];
+    if !short_hash.is_empty() {
+        spans.push(Span::styled(
+            short_hash,
+            Style::default().fg(theme.commit_hash),
+        ));
+    }
 
     if !entry.message.is_empty() {
-        spans.push(Span::raw(" "));
+        if entry.action.is_commit() || entry.action == RebaseAction::Merge {
+            spans.push(Span::raw(" "));
+        }
         spans.push(Span::styled(
             entry.message.clone(),
             Style::default().fg(theme.text),
@@ -39,6 +43,25 @@ pub fn get_lines(entry: &RebaseTodoEntry, theme: &Theme) -> Vec<TextLine<'static
     vec![TextLine::from(spans)]
 }
 
+/// The colour of an action word, shared with the "Rebasing" status section.
+pub fn action_style(action: RebaseAction, theme: &Theme) -> Style {
+    match action {
+        RebaseAction::Pick => Style::default().fg(theme.text),
+        RebaseAction::Reword => Style::default().fg(theme.local_branch),
+        RebaseAction::Edit | RebaseAction::Break => Style::default().fg(theme.section_header),
+        RebaseAction::Squash
+        | RebaseAction::Fixup
+        | RebaseAction::FixupKeepMessage
+        | RebaseAction::FixupEditMessage => Style::default().fg(theme.remote_branch),
+        RebaseAction::Drop => Style::default().fg(theme.diff_deletion),
+        RebaseAction::Exec => Style::default().fg(theme.diff_hunk),
+        RebaseAction::Label
+        | RebaseAction::Reset
+        | RebaseAction::Merge
+        | RebaseAction::UpdateRef => Style::default().fg(theme.ref_label),
+    }
+}
+
 /// Render a keybinding hint line shown below the todo entries.
 pub fn get_hint_lines(
     key: &'static str,
@@ -110,4 +133,24 @@ mod tests {
             assert_eq!(lines[0].spans[1].content.len(), 7);
         }
     }
+
+    #[test]
+    fn test_exec_line_shows_command_without_hash() {
+        let theme = Theme::default();
+        let exec = RebaseTodoEntry::instruction(RebaseAction::Exec, "cargo test");
+        let lines = get_lines(&exec, &theme);
+        assert_eq!(
+            get_span_texts(&lines[0]),
+            vec![" ", "exec   ", "cargo test"]
+        );
+    }
+
+    #[test]
+    fn test_fixup_keep_message_shows_flag() {
+        let theme = Theme::default();
+        let lines = get_lines(&entry(RebaseAction::FixupKeepMessage), &theme);
+        let texts = get_span_texts(&lines[0]);
+        assert_eq!(texts[1], "fixup -C ");
+        assert_eq!(texts[2], "abc1234");
+    }
 }
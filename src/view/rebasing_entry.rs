@@ -3,15 +3,16 @@ use ratatui::{
     text::{Line as TextLine, Span},
 };
 
-use crate::config::Theme;
+use crate::{config::Theme, git::rebase::RebaseAction, view::rebase_todo_line::action_style};
 
 /// Render a single line in the "Rebasing" sequencer section.
 ///
 /// - `is_current = true`  → the commit currently stopped on (shows "stop" in deletion color)
-/// - `is_current = false` → a pending commit in the todo (shows "pick" in normal text color)
+/// - `is_current = false` → a pending commit in the todo (shows its action, e.g. "pick")
 pub fn get_lines(
     hash: &str,
     message: &str,
+    action: RebaseAction,
     is_current: bool,
     theme: &Theme,
 ) -> Vec<TextLine<'static>> {
@@ -20,13 +21,16 @@ pub fn get_lines(
     // Indentation
     spans.push(Span::raw(" "));
 
-    // Label: "stop" for the current stopped commit, "pick" for pending
+    // Label: "stop" for the current stopped commit, the todo action for pending
     let (label, label_style) = if is_current {
-        ("stop ", Style::default().fg(theme.diff_deletion))
+        (
+            "stop ".to_string(),
+            Style::default().fg(theme.diff_deletion),
+        )
     } else {
-        ("pick ", Style::default().fg(theme.text))
+        (format!("{} ", action.as_str()), action_style(action, theme))
     };
-    spans.push(Span::styled(label.to_string(), label_style));
+    spans.push(Span::styled(label, label_style));
 
     // Short hash
     spans.push(Span::styled(
@@ -61,7 +65,7 @@ mod tests {
     #[test]
     fn test_current_entry_shows_stop_label() {
         let theme = test_theme();
-        let lines = get_lines("abc1234", "Fix bug", true, &theme);
+        let lines = get_lines("abc1234", "Fix bug", RebaseAction::Pick, true, &theme);
         assert_eq!(lines.len(), 1);
         let texts = get_span_texts(&lines[0]);
         assert!(texts.iter().any(|s| s.contains("stop")));
@@ -71,7 +75,7 @@ mod tests {
     #[test]
     fn test_pending_entry_shows_pick_label() {
         let theme = test_theme();
-        let lines = get_lines("abc1234", "Fix bug", false, &theme);
+        let lines = get_lines("abc1234", "Fix bug", RebaseAction::Pick, false, &theme);
         assert_eq!(lines.len(), 1);
         let texts = get_span_texts(&lines[0]);
         assert!(texts.iter().any(|s| s.contains("pick")));
@@ -81,7 +85,7 @@ mod tests {
     #[test]
     fn test_includes_hash() {
         let theme = test_theme();
-        let lines = get_lines("abc1234", "Fix bug", true, &theme);
+        let lines = get_lines("abc1234", "Fix bug", RebaseAction::Pick, true, &theme);
         let texts = get_span_texts(&lines[0]);
         assert!(texts.iter().any(|s| s == "abc1234"));
     }
@@ -89,7 +93,7 @@ mod tests {
     #[test]
     fn test_includes_message() {
         let theme = test_theme();
-        let lines = get_lines("abc1234", "Fix bug", false, &theme);
+        let lines = get_lines("abc1234", "Fix bug", RebaseAction::Pick, false, &theme);
         let texts = get_span_texts(&lines[0]);
         assert!(texts.iter().any(|s| s == "Fix bug"));
     }
@@ -97,7 +101,7 @@ mod tests {
     #[test]
     fn test_current_entry_uses_deletion_color() {
         let theme = test_theme();
-        let lines = get_lines("abc1234", "Fix bug", true, &theme);
+        let lines = get_lines("abc1234", "Fix bug", RebaseAction::Pick, true, &theme);
         let stop_span = lines[0]
             .spans
             .iter()
@@ -109,7 +113,7 @@ mod tests {
     #[test]
     fn test_hash_uses_commit_hash_color() {
         let theme = test_theme();
-        let lines = get_lines("abc1234", "Fix bug", false, &theme);
+        let lines = get_lines("abc1234", "Fix bug", RebaseAction::Pick, false, &theme);
         let hash_span = lines[0]
             .spans
             .iter()
@@ -117,4 +121,16 @@ mod tests {
             .unwrap();
         assert_eq!(hash_span.style.fg, Some(theme.commit_hash));
     }
+
+    #[test]
+    fn test_pending_entry_shows_its_action() {
+        let theme = test_theme();
+        let lines = get_lines("abc1234", "Fix bug", RebaseAction::Drop, false, &theme);
+        let drop_span = lines[0]
+            .spans
+            .iter()
+            .find(|s| s.content == "drop ")
+            .unwrap();
+        assert_eq!(drop_span.style.fg, Some(theme.diff_deletion));
+    }
 }
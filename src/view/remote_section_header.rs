@@ -3,11 +3,12 @@ use ratatui::{
     text::{Line as TextLine, Span},
 };
 
-use crate::{config::Theme, i18n};
+use crate::config::Theme;
 
-/// Generate the view lines for the "Unpulled from [remote]" section header
+/// Generate the view lines for a remote section header, e.g. "Unpulled from [remote]"
 /// The remote name is colored with the remote_branch color, the rest with section_header color
 pub fn get_lines(
+    prefix: &str,
     remote_name: &str,
     count: usize,
     collapsed: bool,
@@ -17,7 +18,7 @@ pub fn get_lines(
 
     let header_line = TextLine::from(vec![
         Span::styled(
-            format!("{}{}", indicator, i18n::t().section_unpulled_from_prefix),
+            format!("{}{}", indicator, prefix),
             Style::default().fg(theme.section_header),
         ),
         Span::styled(
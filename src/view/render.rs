@@ -17,8 +17,10 @@ use crate::{
 
 mod apply_popup;
 mod branch_popup;
+pub mod commit_editor;
 mod commit_popup;
 mod credential_popup;
+mod diff_popup;
 mod fetch_popup;
 mod help_popup;
 mod input_popup;
@@ -29,6 +31,7 @@ mod pull_popup;
 mod push_popup;
 mod rebase_popup;
 mod reset_popup;
+mod resolve_popup;
 mod revert_popup;
 mod select_popup;
 mod stash_popup;
@@ -111,8 +114,10 @@ pub fn render_popup(
                 PopupContentCommand::Revert(state) => revert_popup::content(theme, model, state),
                 PopupContentCommand::Merge(state) => merge_popup::content(theme, model, state),
                 PopupContentCommand::Apply(state) => apply_popup::content(theme, model, state),
+                PopupContentCommand::Resolve(state) => resolve_popup::content(theme, model, state),
                 PopupContentCommand::Tag => tag_popup::content(theme, model),
                 PopupContentCommand::Worktree => worktree_popup::content(theme),
+                PopupContentCommand::Diff => diff_popup::content(theme, model),
 
                 // Select popup uses custom rendering, not the column layout
                 PopupContentCommand::Select(state) => {
@@ -3,10 +3,14 @@ use super::popup_content::CommandPopupContent;
 use crate::{
     config::Theme,
     i18n,
-    model::{Model, popup::ApplyPopupState},
+    model::{
+        Model,
+        arguments::{ApplyArgument, ValueArgument},
+        popup::ApplyPopupState,
+    },
     view::render::{
         popup_content::{PopupColumn, PopupRow},
-        util::command_description,
+        util::{argument_lines, command_description, signing_argument_line, value_argument_line},
     },
 };
 
@@ -34,28 +38,50 @@ pub fn content<'a>(
         };
     }
 
+    let mut arguments = vec![value_argument_line(theme, model, ValueArgument::Mainline)];
+    arguments.extend(argument_lines::<ApplyArgument>(
+        theme,
+        model.arg_mode,
+        model.arguments.as_ref().and_then(|a| a.apply()),
+    ));
+    arguments.push(value_argument_line(theme, model, ValueArgument::Strategy));
+    arguments.push(value_argument_line(
+        theme,
+        model,
+        ValueArgument::StrategyOption,
+    ));
+    arguments.push(signing_argument_line(theme, model));
+
     CommandPopupContent {
         title: t.popup_apply,
-        rows: vec![PopupRow {
-            columns: vec![
-                PopupColumn {
-                    title: Some(t.col_apply_here.into()),
-                    content: vec![
-                        command_description(theme, model.arg_mode, "A", t.cmd_pick),
-                        command_description(theme, model.arg_mode, "a", t.cmd_apply),
-                        command_description(theme, model.arg_mode, "h", t.cmd_harvest),
-                        command_description(theme, model.arg_mode, "m", t.cmd_squash),
-                    ],
-                },
-                PopupColumn {
-                    title: Some(t.col_apply_elsewhere.into()),
-                    content: vec![
-                        command_description(theme, model.arg_mode, "d", t.cmd_donate),
-                        command_description(theme, model.arg_mode, "n", t.cmd_spinout),
-                        command_description(theme, model.arg_mode, "s", t.cmd_spinoff),
-                    ],
-                },
-            ],
-        }],
+        rows: vec![
+            PopupRow {
+                columns: vec![PopupColumn {
+                    title: Some(t.col_arguments.into()),
+                    content: arguments,
+                }],
+            },
+            PopupRow {
+                columns: vec![
+                    PopupColumn {
+                        title: Some(t.col_apply_here.into()),
+                        content: vec![
+                            command_description(theme, model.arg_mode, "A", t.cmd_pick),
+                            command_description(theme, model.arg_mode, "a", t.cmd_apply),
+                            command_description(theme, model.arg_mode, "h", t.cmd_harvest),
+                            command_description(theme, model.arg_mode, "m", t.cmd_squash),
+                        ],
+                    },
+                    PopupColumn {
+                        title: Some(t.col_apply_elsewhere.into()),
+                        content: vec![
+                            command_description(theme, model.arg_mode, "d", t.cmd_donate),
+                            command_description(theme, model.arg_mode, "n", t.cmd_spinout),
+                            command_description(theme, model.arg_mode, "s", t.cmd_spinoff),
+                        ],
+                    },
+                ],
+            },
+        ],
     }
 }
@@ -0,0 +1,162 @@
+//! Built-in commit message editor pane, drawn above the diff being committed.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    config::Theme,
+    i18n,
+    model::commit_editor::{CommitEditorState, SUMMARY_MAX_LENGTH},
+};
+
+/// Height of the editor pane: the message plus borders, at least a few
+/// lines and at most half of `area`.
+pub fn editor_height(state: &CommitEditorState, area: Rect) -> u16 {
+    let wanted = state.message.lines().len() as u16 + 2;
+    wanted.max(6).min(area.height / 2)
+}
+
+/// Renders the editor. The part of the summary line beyond
+/// [`SUMMARY_MAX_LENGTH`] and a non-empty second line are highlighted, like
+/// Magit's git-commit mode.
+pub fn render(state: &CommitEditorState, frame: &mut Frame, area: Rect, theme: &Theme) {
+    let t = i18n::t();
+    let (cursor_row, cursor_col) = state.message.cursor();
+    let warning = Style::default().fg(theme.diff_deletion);
+
+    let lines: Vec<Line> = state
+        .message
+        .lines()
+        .iter()
+        .enumerate()
+        .map(|(row, field)| {
+            let overflow_from = match row {
+                0 => Some(SUMMARY_MAX_LENGTH),
+                1 => Some(0),
+                _ => None,
+            };
+            let cursor = (row == cursor_row).then_some(cursor_col);
+            styled_line(field.as_str(), cursor, overflow_from, warning)
+        })
+        .collect();
+
+    let summary_len = state.summary().chars().count();
+    let counter_style = if summary_len > SUMMARY_MAX_LENGTH {
+        warning
+    } else {
+        Style::default().fg(theme.dim_text)
+    };
+    let counter = Line::from(Span::styled(
+        format!(" {}/{} ", summary_len, SUMMARY_MAX_LENGTH),
+        counter_style,
+    ))
+    .right_aligned();
+
+    let bottom = match state.command_input.as_deref() {
+        Some(cmd) => Line::from(Span::styled(
+            format!(":{}", cmd),
+            Style::default().fg(theme.search_match_bg),
+        )),
+        None => Line::from(Span::styled(
+            format!(" {} ", t.commit_editor_hint),
+            Style::default().fg(theme.dim_text),
+        )),
+    };
+
+    let title = if state.amend {
+        t.title_amend_message
+    } else {
+        t.title_commit_message
+    };
+
+    // Keep the cursor row in view for messages taller than the pane
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let scroll = cursor_row.saturating_sub(inner_height.saturating_sub(1)) as u16;
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.local_branch))
+                .title(title)
+                .title_top(counter)
+                .title_bottom(bottom),
+        )
+        .scroll((scroll, 0));
+    frame.render_widget(paragraph, area);
+}
+
+/// Builds a line where characters from `overflow_from` on get
+/// `overflow_style` and the character under `cursor` is reversed. A cursor
+/// past the end is drawn as a blinking `_`, like in the input popups.
+fn styled_line(
+    text: &str,
+    cursor: Option<usize>,
+    overflow_from: Option<usize>,
+    overflow_style: Style,
+) -> Line<'static> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut current = String::new();
+    let mut current_style = Style::default();
+
+    for (i, c) in text.chars().enumerate() {
+        let mut style = Style::default();
+        if overflow_from.is_some_and(|from| i >= from) {
+            style = overflow_style;
+        }
+        if cursor == Some(i) {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+        if style != current_style && !current.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut current), current_style));
+        }
+        current_style = style;
+        current.push(c);
+    }
+    if !current.is_empty() {
+        spans.push(Span::styled(current, current_style));
+    }
+    if cursor.is_some_and(|col| col >= text.chars().count()) {
+        spans.push(Span::styled(
+            "_",
+            Style::default().add_modifier(Modifier::SLOW_BLINK),
+        ));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn styled_line_marks_overflow_and_cursor() {
+        let warn = Style::default().fg(ratatui::style::Color::Red);
+        let line = styled_line("abcdef", Some(1), Some(4), warn);
+        let parts: Vec<(&str, Style)> = line
+            .spans
+            .iter()
+            .map(|s| (s.content.as_ref(), s.style))
+            .collect();
+        assert_eq!(
+            parts,
+            vec![
+                ("a", Style::default()),
+                ("b", Style::default().add_modifier(Modifier::REVERSED)),
+                ("cd", Style::default()),
+                ("ef", warn),
+            ]
+        );
+    }
+
+    #[test]
+    fn styled_line_cursor_at_end_is_underscore() {
+        let line = styled_line("ab", Some(2), None, Style::default());
+        assert_eq!(line.spans.last().unwrap().content, "_");
+    }
+}
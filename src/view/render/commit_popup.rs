@@ -4,10 +4,14 @@ use super::popup_content::CommandPopupContent;
 use crate::{
     config::Theme,
     i18n,
-    model::{Model, arguments::CommitArgument, popup::CommitPopupState},
+    model::{
+        Model,
+        arguments::CommitArgument,
+        popup::{CommitPopupState, CommitTrailer},
+    },
     view::render::{
-        popup_content::{PopupColumn, PopupRow},
-        util::{argument_lines, argument_value_line, command_description},
+        popup_content::{PopupColumn, PopupColumnTitle, PopupRow},
+        util::{argument_lines, argument_value_line, command_description, signing_argument_line},
     },
 };
 
@@ -30,6 +34,21 @@ pub fn content<'a>(
         state.author.as_deref(),
         model.arg_mode,
     ));
+    for (key, description, kind) in [
+        ('C', t.arg_commit_co_author, CommitTrailer::CoAuthoredBy),
+        ('r', t.arg_commit_reviewer, CommitTrailer::ReviewedBy),
+    ] {
+        let values = state.trailer_values(kind);
+        arguments.push(argument_value_line(
+            theme,
+            key,
+            description,
+            trailer_flag_prefix(kind),
+            (!values.is_empty()).then(|| values.join(", ")).as_deref(),
+            model.arg_mode,
+        ));
+    }
+    arguments.push(signing_argument_line(theme, model));
 
     let arguments_col = PopupColumn {
         title: Some(t.col_arguments.into()),
@@ -54,6 +73,7 @@ pub fn content<'a>(
             command_description(theme, model.arg_mode, "a", t.cmd_amend),
             Line::from(""),
             command_description(theme, model.arg_mode, "w", t.cmd_reword),
+            command_description(theme, model.arg_mode, "R", t.cmd_reshelve),
         ],
     };
 
@@ -68,6 +88,17 @@ pub fn content<'a>(
         ],
     };
 
+    // Empty title keeps the commands aligned with the left column's content
+    let instant_col = PopupColumn {
+        title: Some(PopupColumnTitle::Raw("")),
+        content: vec![
+            command_description(theme, model.arg_mode, "F", t.cmd_instant_fixup),
+            command_description(theme, model.arg_mode, "S", t.cmd_instant_squash),
+            command_description(theme, model.arg_mode, "b", t.cmd_absorb),
+            command_description(theme, model.arg_mode, "B", t.cmd_absorb_autosquash),
+        ],
+    };
+
     CommandPopupContent {
         title: t.popup_commit,
         rows: vec![
@@ -75,8 +106,15 @@ pub fn content<'a>(
                 columns: vec![arguments_col],
             },
             PopupRow {
-                columns: vec![create_col, edit_head_col, edit_col],
+                columns: vec![create_col, edit_head_col, edit_col, instant_col],
             },
         ],
     }
 }
+
+fn trailer_flag_prefix(kind: CommitTrailer) -> &'static str {
+    match kind {
+        CommitTrailer::CoAuthoredBy => "--trailer=Co-authored-by: ",
+        CommitTrailer::ReviewedBy => "--trailer=Reviewed-by: ",
+    }
+}
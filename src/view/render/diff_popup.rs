@@ -0,0 +1,66 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use super::popup_content::CommandPopupContent;
+use crate::{
+    config::Theme,
+    i18n,
+    model::Model,
+    view::render::{
+        popup_content::{PopupColumn, PopupColumnTitle, PopupRow},
+        util::command_description,
+    },
+};
+
+pub fn content<'a>(theme: &Theme, model: &Model) -> CommandPopupContent<'a> {
+    let t = i18n::t();
+    let settings = &model.git_info.diff_settings;
+
+    let context_title = Line::from(Span::styled(
+        t.fmt1(t.col_diff_context_fmt, &settings.context_lines.to_string()),
+        Style::default()
+            .fg(theme.section_header)
+            .add_modifier(Modifier::BOLD),
+    ));
+    let context_col = PopupColumn {
+        title: Some(PopupColumnTitle::Styled(context_title)),
+        content: vec![
+            command_description(theme, model.arg_mode, "+", t.cmd_more_context),
+            command_description(theme, model.arg_mode, "-", t.cmd_less_context),
+            command_description(theme, model.arg_mode, "0", t.cmd_default_context),
+        ],
+    };
+
+    let whitespace_col = PopupColumn {
+        title: Some(t.col_whitespace.into()),
+        content: vec![command_description(
+            theme,
+            model.arg_mode,
+            "w",
+            if settings.ignore_whitespace {
+                t.cmd_show_whitespace
+            } else {
+                t.cmd_ignore_whitespace
+            },
+        )],
+    };
+
+    let hunk_col = PopupColumn {
+        title: Some(t.col_hunk.into()),
+        content: vec![
+            command_description(theme, model.arg_mode, "s", t.cmd_split_hunk),
+            command_description(theme, model.arg_mode, "e", t.cmd_edit_hunk),
+            command_description(theme, model.arg_mode, "r", t.cmd_review_unstaged),
+            command_description(theme, model.arg_mode, "R", t.cmd_review_staged),
+        ],
+    };
+
+    CommandPopupContent {
+        title: t.popup_diff,
+        rows: vec![PopupRow {
+            columns: vec![context_col, whitespace_col, hunk_col],
+        }],
+    }
+}
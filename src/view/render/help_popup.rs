@@ -23,6 +23,7 @@ pub fn content(theme: &Theme) -> CommandPopupContent<'static> {
             command_description(theme, false, "A", t.cmd_apply),
             command_description(theme, false, "b", t.cmd_branch),
             command_description(theme, false, "c", t.cmd_commit),
+            command_description(theme, false, "D", t.cmd_diff),
             command_description(theme, false, "f", t.cmd_fetch),
             command_description(theme, false, "l", t.cmd_log),
             command_description(theme, false, "m", t.cmd_merge),
@@ -49,10 +50,12 @@ pub fn content(theme: &Theme) -> CommandPopupContent<'static> {
             command_description(theme, false, "a", t.cmd_apply),
             command_description(theme, false, "s", t.cmd_stage),
             command_description(theme, false, "S", t.cmd_stage_all),
+            command_description(theme, false, "i", t.cmd_intent_to_add),
             command_description(theme, false, "u", t.cmd_unstage),
             command_description(theme, false, "U", t.cmd_unstage_all),
             command_description(theme, false, "x", t.cmd_discard),
             command_description(theme, false, "-", t.cmd_reverse),
+            command_description(theme, false, "e", t.cmd_resolve),
         ],
     };
 
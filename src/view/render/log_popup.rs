@@ -47,6 +47,16 @@ pub fn content(theme: &Theme, model: &Model) -> CommandPopupContent<'static> {
         ],
     };
 
+    let file_col = PopupColumn {
+        title: Some(t.col_file.into()),
+        content: vec![command_description(
+            theme,
+            model.arg_mode,
+            "f",
+            t.cmd_visit_at_revision,
+        )],
+    };
+
     CommandPopupContent {
         title: t.popup_log,
         rows: vec![
@@ -54,7 +64,7 @@ pub fn content(theme: &Theme, model: &Model) -> CommandPopupContent<'static> {
                 columns: vec![formatting_col],
             },
             PopupRow {
-                columns: vec![log_col, reflog_col],
+                columns: vec![log_col, reflog_col, file_col],
             },
         ],
     }
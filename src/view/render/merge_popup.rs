@@ -3,8 +3,14 @@ use super::popup_content::{CommandPopupContent, PopupColumn, PopupRow};
 use crate::{
     config::Theme,
     i18n,
-    model::{Model, popup::MergePopupState},
-    view::render::util::command_description,
+    model::{
+        Model,
+        arguments::{MergeArgument, ValueArgument},
+        popup::MergePopupState,
+    },
+    view::render::util::{
+        argument_lines, command_description, signing_argument_line, value_argument_line,
+    },
 };
 
 pub fn content<'a>(
@@ -30,6 +36,23 @@ pub fn content<'a>(
         };
     }
 
+    let mut arguments = argument_lines::<MergeArgument>(
+        theme,
+        model.arg_mode,
+        model.arguments.as_ref().and_then(|a| a.merge()),
+    );
+    arguments.push(value_argument_line(theme, model, ValueArgument::Strategy));
+    arguments.push(value_argument_line(
+        theme,
+        model,
+        ValueArgument::StrategyOption,
+    ));
+    arguments.push(signing_argument_line(theme, model));
+    let arguments_col = PopupColumn {
+        title: Some(t.col_arguments.into()),
+        content: arguments,
+    };
+
     let actions_col = PopupColumn {
         title: Some(t.col_actions.into()),
         content: vec![
@@ -45,8 +68,13 @@ pub fn content<'a>(
 
     CommandPopupContent {
         title: t.popup_merge,
-        rows: vec![PopupRow {
-            columns: vec![actions_col],
-        }],
+        rows: vec![
+            PopupRow {
+                columns: vec![arguments_col],
+            },
+            PopupRow {
+                columns: vec![actions_col],
+            },
+        ],
     }
 }
@@ -7,11 +7,16 @@ use super::popup_content::CommandPopupContent;
 use crate::{
     config::Theme,
     i18n,
-    model::{Model, arguments::RebaseArgument, popup::RebasePopupState},
+    model::{
+        Model,
+        arguments::{RebaseArgument, ValueArgument},
+        popup::RebasePopupState,
+    },
     view::render::{
         popup_content::{PopupColumn, PopupColumnTitle, PopupRow},
         util::{
-            argument_lines, command_description, push_remote_description, upstream_description,
+            argument_lines, command_description, push_remote_description, signing_argument_line,
+            upstream_description, value_argument_line,
         },
     },
 };
@@ -28,7 +33,7 @@ pub fn content<'a>(
     let branch_style = Style::default().fg(theme.local_branch);
 
     if state.in_progress {
-        // Rebase sequence paused on a conflict — show Continue / Skip / Abort
+        // Rebase sequence paused — show Continue / Skip / Abort / Edit todo
         return CommandPopupContent {
             title: t.section_rebasing,
             rows: vec![PopupRow {
@@ -38,19 +43,28 @@ pub fn content<'a>(
                         command_description(theme, model.arg_mode, "r", t.cmd_continue),
                         command_description(theme, model.arg_mode, "s", t.cmd_skip),
                         command_description(theme, model.arg_mode, "a", t.cmd_abort),
+                        command_description(theme, model.arg_mode, "e", t.cmd_edit_todo),
                     ],
                 }],
             }],
         };
     }
 
+    let mut arguments = argument_lines::<RebaseArgument>(
+        theme,
+        model.arg_mode,
+        model.arguments.as_ref().and_then(|a| a.rebase()),
+    );
+    arguments.push(value_argument_line(theme, model, ValueArgument::Exec));
+    arguments.push(value_argument_line(
+        theme,
+        model,
+        ValueArgument::StrategyOption,
+    ));
+    arguments.push(signing_argument_line(theme, model));
     let arguments_col = PopupColumn {
         title: Some(t.col_arguments.into()),
-        content: argument_lines::<RebaseArgument>(
-            theme,
-            model.arg_mode,
-            model.arguments.as_ref().and_then(|a| a.rebase()),
-        ),
+        content: arguments,
     };
 
     let rebase_onto_title = Line::from(vec![
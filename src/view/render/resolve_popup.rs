@@ -0,0 +1,78 @@
+use super::popup_content::CommandPopupContent;
+
+use crate::{
+    config::Theme,
+    i18n,
+    model::{Model, popup::ResolvePopupState},
+    view::render::{
+        popup_content::{PopupColumn, PopupRow},
+        util::command_description,
+    },
+};
+
+pub fn content<'a>(
+    theme: &Theme,
+    model: &Model,
+    state: &ResolvePopupState,
+) -> CommandPopupContent<'a> {
+    let t = i18n::t();
+
+    let mut columns = Vec::new();
+
+    if state.unmerged && state.conflict.is_some() {
+        columns.push(PopupColumn {
+            title: Some(t.col_resolve_conflict.into()),
+            content: vec![
+                command_description(theme, model.arg_mode, "o", t.cmd_keep_ours),
+                command_description(theme, model.arg_mode, "t", t.cmd_keep_theirs),
+                command_description(theme, model.arg_mode, "b", t.cmd_keep_both),
+                command_description(theme, model.arg_mode, "B", t.cmd_keep_base),
+            ],
+        });
+    }
+
+    let mut file_col = vec![];
+    if state.unmerged {
+        file_col.push(command_description(
+            theme,
+            model.arg_mode,
+            "O",
+            t.cmd_checkout_ours,
+        ));
+        file_col.push(command_description(
+            theme,
+            model.arg_mode,
+            "T",
+            t.cmd_checkout_theirs,
+        ));
+    }
+    file_col.push(command_description(
+        theme,
+        model.arg_mode,
+        "m",
+        t.cmd_restore_conflict,
+    ));
+    if state.unmerged {
+        file_col.push(command_description(
+            theme,
+            model.arg_mode,
+            "M",
+            t.cmd_mergetool,
+        ));
+    }
+    file_col.push(command_description(
+        theme,
+        model.arg_mode,
+        "a",
+        t.cmd_mergetool_all,
+    ));
+    columns.push(PopupColumn {
+        title: Some(t.col_resolve_file.into()),
+        content: file_col,
+    });
+
+    CommandPopupContent {
+        title: t.popup_resolve,
+        rows: vec![PopupRow { columns }],
+    }
+}
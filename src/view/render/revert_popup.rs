@@ -5,10 +5,17 @@ use super::popup_content::CommandPopupContent;
 use crate::{
     config::Theme,
     i18n,
-    model::{Model, arguments::RevertArgument, popup::RevertPopupState},
+    model::{
+        Model,
+        arguments::{RevertArgument, ValueArgument},
+        popup::RevertPopupState,
+    },
     view::render::{
         popup_content::{PopupColumn, PopupRow},
-        util::{argument_lines, argument_value_line, command_description},
+        util::{
+            argument_lines, argument_value_line, command_description, signing_argument_line,
+            value_argument_line,
+        },
     },
 };
 
@@ -50,6 +57,13 @@ pub fn content<'a>(
         model.arg_mode,
         model.arguments.as_ref().and_then(|a| a.revert()),
     ));
+    arguments.push(value_argument_line(theme, model, ValueArgument::Strategy));
+    arguments.push(value_argument_line(
+        theme,
+        model,
+        ValueArgument::StrategyOption,
+    ));
+    arguments.push(signing_argument_line(theme, model));
 
     let arguments_col = PopupColumn {
         title: Some(t.col_arguments.into()),
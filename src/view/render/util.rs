@@ -8,7 +8,10 @@ use ratatui::{
 use crate::{
     config::Theme,
     i18n,
-    model::{Model, arguments::PopupArgument},
+    model::{
+        Model,
+        arguments::{PopupArgument, ValueArgument},
+    },
 };
 
 /// Renders input text with a visible cursor. At the end of the text the
@@ -172,7 +175,7 @@ pub fn argument_value_line<'a>(
     key: char,
     description: &'a str,
     flag_prefix: &'a str,
-    value: Option<&'a str>,
+    value: Option<&str>,
     arg_mode: bool,
 ) -> Line<'a> {
     let faded_style = Style::default().fg(theme.dim_text);
@@ -199,6 +202,31 @@ pub fn argument_value_line<'a>(
     ])
 }
 
+/// The `-S` (`--gpg-sign=`) argument line, showing the key picked for
+/// signing, if any.
+pub fn signing_argument_line<'a>(theme: &Theme, model: &Model) -> Line<'a> {
+    argument_value_line(
+        theme,
+        'S',
+        i18n::t().arg_gpg_sign,
+        "--gpg-sign=",
+        model.signing_key.as_deref(),
+        model.arg_mode,
+    )
+}
+
+/// The line of an argument that takes a value, showing the value, if any.
+pub fn value_argument_line<'a>(theme: &Theme, model: &Model, argument: ValueArgument) -> Line<'a> {
+    argument_value_line(
+        theme,
+        argument.key(),
+        argument.description(),
+        argument.flag_prefix(),
+        model.argument_values.get(&argument).map(String::as_str),
+        model.arg_mode,
+    )
+}
+
 pub fn argument_line<'a>(
     theme: &Theme,
     key: char,
@@ -0,0 +1,313 @@
+use std::{collections::HashSet, ops::Range};
+
+use ratatui::{
+    style::{Color, Style},
+    text::{Line as TextLine, Span},
+};
+
+use crate::{
+    config::Theme,
+    git::stage::parse_hunk_header_starts,
+    model::{DiffLineType, Line, LineContent, PreviewLineType, SectionType},
+    view::util::{apply_block_cursor, apply_search_highlight, content_spans, selection_style},
+};
+
+/// Terminals narrower than this keep the unified layout: each column would
+/// be too narrow to read.
+pub const MIN_WIDTH: usize = 100;
+
+/// Width of the line number gutter of each column, including its padding
+const GUTTER_WIDTH: usize = 5;
+
+/// What a diff line contributes to the two columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitLine<'a> {
+    /// A hunk header, with the old and new start lines
+    Header { old: usize, new: usize },
+    /// A removed line, shown in the left (old) column, with its changed words
+    Removed(&'a str, &'a [Range<usize>]),
+    /// An added line, shown in the right (new) column, with its changed words
+    Added(&'a str, &'a [Range<usize>]),
+    /// An unchanged line, shown in both columns
+    Context(&'a str),
+}
+
+/// Classifies a status or preview line for the side-by-side layout. Lines
+/// that don't belong to a two-way diff (file headers, combined diffs of
+/// conflicts, ...) return `None` and keep their unified rendering.
+pub fn split_line(line: &Line) -> Option<SplitLine<'_>> {
+    match &line.content {
+        LineContent::DiffHunk(hunk) => header(&hunk.header),
+        LineContent::DiffLine(diff) => match diff.line_type {
+            DiffLineType::Deletion => Some(SplitLine::Removed(&diff.content, &diff.refined)),
+            DiffLineType::Addition => Some(SplitLine::Added(&diff.content, &diff.refined)),
+            DiffLineType::Context => Some(SplitLine::Context(&diff.content)),
+            _ => None,
+        },
+        // Preview lines keep the diff's origin character
+        LineContent::PreviewLine {
+            content,
+            line_type,
+            refined,
+        } => match line_type {
+            PreviewLineType::HunkHeader => header(content),
+            PreviewLineType::Deletion => content
+                .strip_prefix('-')
+                .map(|text| SplitLine::Removed(text, refined)),
+            PreviewLineType::Addition => content
+                .strip_prefix('+')
+                .map(|text| SplitLine::Added(text, refined)),
+            PreviewLineType::Context if content.is_empty() => Some(SplitLine::Context("")),
+            PreviewLineType::Context => content.strip_prefix(' ').map(SplitLine::Context),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn header(content: &str) -> Option<SplitLine<'_>> {
+    if content.starts_with("@@@") {
+        return None;
+    }
+    let (old, new) = parse_hunk_header_starts(content).ok()?;
+    Some(SplitLine::Header { old, new })
+}
+
+/// A row of the side-by-side layout: the indices of the lines shown in the
+/// old and new columns. Context rows show the same line in both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Row {
+    pub old: Option<usize>,
+    pub new: Option<usize>,
+}
+
+/// Pairs the block of removed lines starting at `start` with the added lines
+/// that follow it, row by row. Returns the rows and the index of the first
+/// line after the block.
+pub fn pair_changes(
+    lines: &[Line],
+    start: usize,
+    collapsed_sections: &HashSet<SectionType>,
+) -> (Vec<Row>, usize) {
+    let kind = |index: usize| {
+        lines
+            .get(index)
+            .filter(|line| !line.is_hidden(collapsed_sections))
+            .and_then(split_line)
+    };
+
+    let mut end = start;
+    while matches!(kind(end), Some(SplitLine::Removed(..))) {
+        end += 1;
+    }
+    let removed: Vec<usize> = (start..end).collect();
+    while matches!(kind(end), Some(SplitLine::Added(..))) {
+        end += 1;
+    }
+    let added: Vec<usize> = (start + removed.len()..end).collect();
+
+    let rows = (0..removed.len().max(added.len()))
+        .map(|i| Row {
+            old: removed.get(i).copied(),
+            new: added.get(i).copied(),
+        })
+        .collect();
+    (rows, end)
+}
+
+/// Old and new line numbers of the next lines of the current hunk.
+#[derive(Debug, Default)]
+pub struct LineNumbers {
+    pub old: usize,
+    pub new: usize,
+}
+
+/// How one column of a row is highlighted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Highlight {
+    pub selected: bool,
+    pub cursor: bool,
+}
+
+/// One column of a row, before it is joined with the other.
+pub struct Column<'a> {
+    pub number: Option<usize>,
+    pub content: Option<SplitLine<'a>>,
+    /// Foreground colors of the content's syntax highlight
+    pub syntax: &'a [(Range<usize>, Color)],
+    pub highlight: Highlight,
+}
+
+/// Renders a row: the old column on the left, the new one on the right.
+pub fn render_row(
+    old: Column<'_>,
+    new: Column<'_>,
+    content_width: usize,
+    search_query: &str,
+    theme: &Theme,
+) -> TextLine<'static> {
+    // One leading space like unified lines, and a separator between columns
+    let width = content_width.saturating_sub(2) / 2;
+    let left = render_column(old, width, search_query, theme);
+    let right = render_column(new, width, search_query, theme);
+
+    let mut spans = vec![Span::raw(" ")];
+    spans.extend(left.spans);
+    spans.push(Span::styled("│", Style::default().fg(theme.dim_text)));
+    spans.extend(right.spans);
+    TextLine::from(spans)
+}
+
+fn render_column(
+    column: Column<'_>,
+    width: usize,
+    search_query: &str,
+    theme: &Theme,
+) -> TextLine<'static> {
+    let (prefix, text, refined, color, refined_bg) = match column.content {
+        Some(SplitLine::Removed(text, refined)) => (
+            "-",
+            text,
+            refined,
+            theme.diff_deletion,
+            theme.diff_deletion_refined,
+        ),
+        Some(SplitLine::Added(text, refined)) => (
+            "+",
+            text,
+            refined,
+            theme.diff_addition,
+            theme.diff_addition_refined,
+        ),
+        Some(SplitLine::Context(text)) => (" ", text, &[][..], theme.diff_context, Color::Reset),
+        _ => ("", "", &[][..], theme.diff_context, Color::Reset),
+    };
+    let number = column
+        .number
+        .map(|n| format!("{:>4} ", n))
+        .unwrap_or_else(|| " ".repeat(GUTTER_WIDTH));
+    let mut spans = vec![
+        Span::styled(number, Style::default().fg(theme.dim_text)),
+        Span::styled(prefix.to_string(), Style::default().fg(color)),
+    ];
+    // Cut the content at the column's width
+    let mut room = width.saturating_sub(GUTTER_WIDTH + prefix.len());
+    for mut span in content_spans(
+        text,
+        refined,
+        column.syntax,
+        GUTTER_WIDTH + prefix.len(),
+        Style::default().fg(color),
+        refined_bg,
+    ) {
+        let kept: String = span.content.chars().take(room).collect();
+        room -= kept.chars().count();
+        span.content = kept.into();
+        spans.push(span);
+    }
+
+    let mut line = TextLine::from(spans);
+    if !search_query.is_empty() && column.content.is_some() {
+        let highlight_style = Style::default()
+            .bg(theme.search_match_bg)
+            .fg(theme.search_match_fg);
+        apply_search_highlight(&mut line, search_query, highlight_style);
+    }
+
+    let padding = width.saturating_sub(line.width());
+    line.spans.push(Span::raw(" ".repeat(padding)));
+    if column.highlight.selected {
+        select(&mut line, theme.selection_bg);
+        if column.highlight.cursor {
+            apply_block_cursor(&mut line, GUTTER_WIDTH);
+        }
+    }
+    line
+}
+
+/// Selection is applied per span so it survives joining the two columns.
+fn select(line: &mut TextLine<'static>, selection_bg: Color) {
+    let style = selection_style(selection_bg);
+    for span in &mut line.spans {
+        span.style = span.style.patch(style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::DiffLine;
+
+    fn diff(line_type: DiffLineType, content: &str) -> Line {
+        Line {
+            content: LineContent::DiffLine(DiffLine {
+                content: content.to_string(),
+                line_type,
+                refined: Vec::new(),
+            }),
+            section: None,
+        }
+    }
+
+    #[test]
+    fn test_pair_changes_pairs_removed_with_added() {
+        let lines = vec![
+            diff(DiffLineType::Deletion, "a"),
+            diff(DiffLineType::Deletion, "b"),
+            diff(DiffLineType::Addition, "A"),
+            diff(DiffLineType::Context, "c"),
+        ];
+
+        let (rows, end) = pair_changes(&lines, 0, &HashSet::new());
+
+        assert_eq!(
+            rows,
+            vec![
+                Row {
+                    old: Some(0),
+                    new: Some(2)
+                },
+                Row {
+                    old: Some(1),
+                    new: None
+                },
+            ]
+        );
+        assert_eq!(end, 3);
+    }
+
+    #[test]
+    fn test_pair_changes_with_only_additions() {
+        let lines = vec![
+            diff(DiffLineType::Addition, "A"),
+            diff(DiffLineType::Addition, "B"),
+        ];
+
+        let (rows, end) = pair_changes(&lines, 0, &HashSet::new());
+
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| row.old.is_none()));
+        assert_eq!(end, 2);
+    }
+
+    #[test]
+    fn test_split_line_strips_preview_prefix() {
+        let line = Line {
+            content: LineContent::PreviewLine {
+                content: "-old".to_string(),
+                line_type: PreviewLineType::Deletion,
+                refined: Vec::new(),
+            },
+            section: None,
+        };
+        assert_eq!(split_line(&line), Some(SplitLine::Removed("old", &[])));
+    }
+
+    #[test]
+    fn test_split_line_ignores_combined_diffs() {
+        assert_eq!(
+            split_line(&diff(DiffLineType::CombinedAddition, "+ a")),
+            None
+        );
+    }
+}
@@ -0,0 +1,471 @@
+//! Syntax highlighting of diff content, keyed on the file of each hunk.
+//! Only the hunks on screen are highlighted. They are cached by where they
+//! are shown, so redrawing while scrolling only looks them up.
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use lazy_static::lazy_static;
+use ratatui::style::Color;
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Theme as SyntaxTheme, ThemeSet},
+    parsing::{SyntaxReference, SyntaxSet},
+};
+
+use crate::{
+    config::Theme,
+    model::{Line, LineContent, SectionType},
+    view::side_by_side::{SplitLine, split_line},
+};
+
+/// Foreground colors of the byte ranges of a line's text
+pub type LineHighlight = Vec<(Range<usize>, Color)>;
+
+/// Hunks kept in the cache before it is cleared
+const MAX_CACHED_HUNKS: usize = 512;
+
+/// Share of the syntax color when blending it with the color of an added or
+/// removed line
+const SYNTAX_WEIGHT: u16 = 60;
+
+lazy_static! {
+    static ref SYNTAXES: SyntaxSet = SyntaxSet::load_defaults_newlines();
+    static ref THEMES: ThemeSet = ThemeSet::load_defaults();
+    static ref CACHE: Mutex<HashMap<HunkKey, CachedHunk>> = Mutex::new(HashMap::new());
+}
+
+/// Syntax highlights of the diff lines on screen, looked up by line index.
+#[derive(Default)]
+pub struct Highlights {
+    hunks: Vec<Arc<Vec<LineHighlight>>>,
+    /// For each line: its hunk in `hunks` and its position in that hunk
+    lines: HashMap<usize, (usize, usize)>,
+}
+
+impl Highlights {
+    /// The highlight of the line at `index`; empty when it has none.
+    pub fn get(&self, index: usize) -> &[(Range<usize>, Color)] {
+        self.lines
+            .get(&index)
+            .and_then(|&(hunk, line)| self.hunks[hunk].get(line))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// A hunk being collected from the lines: its file, section and header, and
+/// its lines' indices, kinds and texts.
+struct Hunk<'a> {
+    path: String,
+    section: Option<SectionType>,
+    header: &'a str,
+    lines: Vec<(usize, SplitLine<'a>)>,
+}
+
+/// Colors the highlights depend on
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Palette {
+    dark: bool,
+    addition: Color,
+    deletion: Color,
+}
+
+/// Identifies a hunk between frames by where it is shown
+#[derive(PartialEq, Eq, Hash)]
+struct HunkKey {
+    palette: Palette,
+    path: String,
+    section: Option<SectionType>,
+    header: String,
+}
+
+/// A highlighted hunk, with the lines it was highlighted from so an edit
+/// that keeps the hunk's header is noticed
+struct CachedHunk {
+    lines: Vec<(char, String)>,
+    highlighted: Option<Arc<Vec<LineHighlight>>>,
+}
+
+/// The range of line indices that can be on a screen of `height` rows
+/// scrolled to `scroll_offset`. Hidden lines take no rows; side-by-side rows
+/// can hold two lines, so twice as many lines are counted.
+pub fn visible_range(
+    lines: &[Line],
+    scroll_offset: usize,
+    height: usize,
+    collapsed_sections: &HashSet<SectionType>,
+) -> Range<usize> {
+    let mut shown = 0;
+    let end = lines
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .find(|(_, line)| {
+            if !line.is_hidden(collapsed_sections) {
+                shown += 1;
+            }
+            shown > height * 2
+        })
+        .map_or(lines.len(), |(index, _)| index);
+    scroll_offset..end
+}
+
+/// Highlights the hunks of `lines` that have a line in `visible` and whose
+/// file type is known, reusing the cached result of hunks highlighted
+/// before. Lines hidden in collapsed sections are left out.
+pub fn highlight_lines(
+    lines: &[Line],
+    visible: Range<usize>,
+    collapsed_sections: &HashSet<SectionType>,
+    theme: &Theme,
+) -> Highlights {
+    let palette = Palette {
+        dark: is_dark(theme),
+        addition: theme.diff_addition,
+        deletion: theme.diff_deletion,
+    };
+    let mut highlights = Highlights::default();
+    let mut preview_path: Option<String> = None;
+    let mut current: Option<Hunk> = None;
+    let flush_visible = |hunk: Option<Hunk>, highlights: &mut Highlights| {
+        if let Some(hunk) =
+            hunk.filter(|hunk| hunk.lines.iter().any(|(index, _)| visible.contains(index)))
+        {
+            flush(hunk, &palette, highlights);
+        }
+    };
+
+    for (index, line) in lines.iter().enumerate() {
+        if index >= visible.end && current.is_none() {
+            break;
+        }
+        let header = match &line.content {
+            LineContent::PreviewLine { content, .. } => {
+                if let Some((_, path)) = content
+                    .strip_prefix("diff --git ")
+                    .and_then(|paths| paths.rsplit_once(" b/"))
+                {
+                    preview_path = Some(path.to_string());
+                }
+                content.as_str()
+            }
+            LineContent::DiffHunk(hunk) => hunk.header.as_str(),
+            _ => "",
+        };
+
+        match split_line(line) {
+            Some(SplitLine::Header { .. }) => {
+                flush_visible(current.take(), &mut highlights);
+                let path = match &line.section {
+                    Some(
+                        SectionType::UnstagedHunk { path, .. }
+                        | SectionType::StagedHunk { path, .. }
+                        | SectionType::UntrackedFile { path },
+                    ) => Some(path.clone()),
+                    _ => preview_path.clone(),
+                };
+                current = path.map(|path| Hunk {
+                    path,
+                    section: line.section.clone(),
+                    header,
+                    lines: Vec::new(),
+                });
+            }
+            Some(split) => {
+                if let Some(hunk) = &mut current
+                    && !line.is_hidden(collapsed_sections)
+                {
+                    hunk.lines.push((index, split));
+                }
+            }
+            None => flush_visible(current.take(), &mut highlights),
+        }
+    }
+    flush_visible(current, &mut highlights);
+    highlights
+}
+
+fn flush(hunk: Hunk, palette: &Palette, highlights: &mut Highlights) {
+    let Some(highlighted) = cached_hunk(&hunk, palette) else {
+        return;
+    };
+    let position = highlights.hunks.len();
+    highlights.hunks.push(highlighted);
+    for (line, (index, _)) in hunk.lines.iter().enumerate() {
+        highlights.lines.insert(*index, (position, line));
+    }
+}
+
+/// The origin character and text of a hunk line
+fn origin_and_text<'a>(split: &SplitLine<'a>) -> (char, &'a str) {
+    match *split {
+        SplitLine::Removed(text, _) => ('-', text),
+        SplitLine::Added(text, _) => ('+', text),
+        SplitLine::Context(text) => (' ', text),
+        SplitLine::Header { .. } => ('@', ""),
+    }
+}
+
+fn cached_hunk(hunk: &Hunk, palette: &Palette) -> Option<Arc<Vec<LineHighlight>>> {
+    let key = HunkKey {
+        palette: palette.clone(),
+        path: hunk.path.clone(),
+        section: hunk.section.clone(),
+        header: hunk.header.to_string(),
+    };
+
+    let mut cache = CACHE.lock().unwrap_or_else(|err| err.into_inner());
+    if let Some(cached) = cache.get(&key)
+        && cached.lines.len() == hunk.lines.len()
+        && cached
+            .lines
+            .iter()
+            .zip(&hunk.lines)
+            .all(|((origin, text), (_, split))| (*origin, text.as_str()) == origin_and_text(split))
+    {
+        return cached.highlighted.clone();
+    }
+    let highlighted = highlight_hunk(&hunk.path, &hunk.lines, palette).map(Arc::new);
+    if cache.len() >= MAX_CACHED_HUNKS {
+        cache.clear();
+    }
+    let lines = hunk
+        .lines
+        .iter()
+        .map(|(_, split)| {
+            let (origin, text) = origin_and_text(split);
+            (origin, text.to_string())
+        })
+        .collect();
+    cache.insert(
+        key,
+        CachedHunk {
+            lines,
+            highlighted: highlighted.clone(),
+        },
+    );
+    highlighted
+}
+
+fn syntax_for(path: &str) -> Option<&'static SyntaxReference> {
+    let path = Path::new(path);
+    let extension = path.extension().and_then(|e| e.to_str());
+    let file_name = path.file_name().and_then(|n| n.to_str());
+    extension
+        .and_then(|ext| SYNTAXES.find_syntax_by_extension(ext))
+        .or_else(|| file_name.and_then(|name| SYNTAXES.find_syntax_by_extension(name)))
+}
+
+/// Highlights the lines of a hunk. The old (context and removed lines) and
+/// new (context and added lines) sides are parsed separately so each sees
+/// the code as it was. Removed and added lines blend their syntax colors
+/// with the theme's diff colors.
+fn highlight_hunk(
+    path: &str,
+    lines: &[(usize, SplitLine)],
+    palette: &Palette,
+) -> Option<Vec<LineHighlight>> {
+    let syntax = syntax_for(path)?;
+    let theme = syntax_theme(palette.dark)?;
+    let mut old = HighlightLines::new(syntax, theme);
+    let mut new = HighlightLines::new(syntax, theme);
+
+    let highlight = |side: &mut HighlightLines, text: &str, tint: Option<Color>| {
+        let line = format!("{}\n", text);
+        let regions = side.highlight_line(&line, &SYNTAXES).unwrap_or_default();
+        let mut start = 0;
+        let mut ranges = LineHighlight::new();
+        for (style, region) in regions {
+            let end = (start + region.len()).min(text.len());
+            if end > start {
+                let fg = style.foreground;
+                let color = Color::Rgb(fg.r, fg.g, fg.b);
+                ranges.push((start..end, tint.map_or(color, |line| blend(color, line))));
+            }
+            start += region.len();
+        }
+        ranges
+    };
+
+    Some(
+        lines
+            .iter()
+            .map(|(_, split)| match split {
+                SplitLine::Removed(text, _) => highlight(&mut old, text, Some(palette.deletion)),
+                SplitLine::Added(text, _) => highlight(&mut new, text, Some(palette.addition)),
+                SplitLine::Context(text) => {
+                    highlight(&mut old, text, None);
+                    highlight(&mut new, text, None)
+                }
+                SplitLine::Header { .. } => LineHighlight::new(),
+            })
+            .collect(),
+    )
+}
+
+fn syntax_theme(dark: bool) -> Option<&'static SyntaxTheme> {
+    let name = if dark {
+        "base16-ocean.dark"
+    } else {
+        "InspiredGitHub"
+    };
+    THEMES.themes.get(name)
+}
+
+/// Light themes have a light selection background.
+fn is_dark(theme: &Theme) -> bool {
+    match theme.selection_bg {
+        Color::Rgb(r, g, b) => (r as u16 + g as u16 + b as u16) / 3 < 128,
+        _ => true,
+    }
+}
+
+/// Mixes a syntax color into the color of an added or removed line, so the
+/// line still reads as added or removed. Colors without an RGB value are
+/// left to the syntax color.
+fn blend(syntax: Color, line: Color) -> Color {
+    let (Color::Rgb(sr, sg, sb), Some((lr, lg, lb))) = (syntax, rgb(line)) else {
+        return syntax;
+    };
+    let mix =
+        |s: u8, l: u8| ((s as u16 * SYNTAX_WEIGHT + l as u16 * (100 - SYNTAX_WEIGHT)) / 100) as u8;
+    Color::Rgb(mix(sr, lr), mix(sg, lg), mix(sb, lb))
+}
+
+/// RGB values of a color, approximating the named terminal colors.
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    Some(match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Red => (205, 49, 49),
+        Color::Green => (13, 188, 121),
+        Color::Yellow => (229, 229, 16),
+        Color::Blue => (36, 114, 200),
+        Color::Magenta => (188, 63, 188),
+        Color::Cyan => (17, 168, 205),
+        Color::LightRed => (241, 76, 76),
+        Color::LightGreen => (35, 209, 139),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{DiffHunk, DiffLine, DiffLineType};
+
+    fn hunk_lines(path: &str, diff: &[(DiffLineType, &str)]) -> Vec<Line> {
+        let section = Some(SectionType::UnstagedHunk {
+            path: path.to_string(),
+            hunk_index: 0,
+        });
+        let mut lines = vec![Line {
+            content: LineContent::DiffHunk(DiffHunk {
+                header: "@@ -1,2 +1,2 @@".to_string(),
+                hunk_index: 0,
+            }),
+            section: section.clone(),
+        }];
+        lines.extend(diff.iter().map(|(line_type, content)| Line {
+            content: LineContent::DiffLine(DiffLine {
+                content: content.to_string(),
+                line_type: line_type.clone(),
+                refined: Vec::new(),
+            }),
+            section: section.clone(),
+        }));
+        lines
+    }
+
+    #[test]
+    fn test_highlights_known_file_types() {
+        let lines = hunk_lines(
+            "src/main.rs",
+            &[
+                (DiffLineType::Deletion, "fn main() {}"),
+                (DiffLineType::Addition, "pub fn main() {}"),
+            ],
+        );
+
+        let highlights =
+            highlight_lines(&lines, 0..lines.len(), &HashSet::new(), &Theme::default());
+
+        assert!(highlights.get(0).is_empty());
+        let added = highlights.get(2);
+        assert_eq!(added.first().map(|(range, _)| range.clone()), Some(0..3));
+        // Keywords and names get different colors
+        assert!(added.iter().any(|(_, color)| *color != added[0].1));
+    }
+
+    #[test]
+    fn test_unknown_file_types_are_not_highlighted() {
+        let lines = hunk_lines("notes.unknown-ext", &[(DiffLineType::Addition, "text")]);
+
+        let highlights =
+            highlight_lines(&lines, 0..lines.len(), &HashSet::new(), &Theme::default());
+
+        assert!(highlights.get(1).is_empty());
+    }
+
+    #[test]
+    fn test_highlighted_hunks_are_cached() {
+        let lines = hunk_lines("lib.rs", &[(DiffLineType::Context, "let x = 1;")]);
+
+        let first = highlight_lines(&lines, 0..lines.len(), &HashSet::new(), &Theme::default());
+        let second = highlight_lines(&lines, 0..lines.len(), &HashSet::new(), &Theme::default());
+
+        assert!(Arc::ptr_eq(&first.hunks[0], &second.hunks[0]));
+    }
+
+    #[test]
+    fn test_changed_hunk_with_same_header_is_highlighted_again() {
+        let before = hunk_lines("edit.rs", &[(DiffLineType::Addition, "let x = 1;")]);
+        let after = hunk_lines("edit.rs", &[(DiffLineType::Addition, "// let x = 1;")]);
+
+        let first = highlight_lines(&before, 0..2, &HashSet::new(), &Theme::default());
+        let second = highlight_lines(&after, 0..2, &HashSet::new(), &Theme::default());
+
+        assert!(!Arc::ptr_eq(&first.hunks[0], &second.hunks[0]));
+        assert_ne!(first.get(1), second.get(1));
+    }
+
+    #[test]
+    fn test_only_visible_hunks_are_highlighted() {
+        let mut lines = hunk_lines("a.rs", &[(DiffLineType::Addition, "fn a() {}")]);
+        lines.extend(hunk_lines("b.rs", &[(DiffLineType::Addition, "fn b() {}")]));
+
+        let highlights = highlight_lines(&lines, 2..4, &HashSet::new(), &Theme::default());
+
+        assert!(highlights.get(1).is_empty());
+        assert!(!highlights.get(3).is_empty());
+    }
+
+    #[test]
+    fn test_visible_range_skips_hidden_lines() {
+        let context = vec![(DiffLineType::Context, "x"); 6];
+        let lines = hunk_lines("a.rs", &context);
+        let collapsed = HashSet::from([SectionType::UnstagedHunk {
+            path: "a.rs".to_string(),
+            hunk_index: 0,
+        }]);
+
+        assert_eq!(visible_range(&lines, 1, 2, &HashSet::new()), 1..5);
+        assert_eq!(visible_range(&lines, 0, 1, &collapsed), 0..lines.len());
+    }
+
+    #[test]
+    fn test_blend_keeps_a_share_of_the_line_color() {
+        assert_eq!(
+            blend(Color::Rgb(100, 100, 100), Color::Rgb(0, 200, 0)),
+            Color::Rgb(60, 140, 60)
+        );
+        assert_eq!(
+            blend(Color::Rgb(1, 2, 3), Color::Reset),
+            Color::Rgb(1, 2, 3)
+        );
+    }
+}
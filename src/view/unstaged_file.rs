@@ -3,16 +3,18 @@ use ratatui::text::Line as TextLine;
 use crate::config::Theme;
 use crate::model::FileChange;
 
-use super::util::format_file_change;
+use super::util::{FileEntry, format_file_change};
 
 /// Generate the view lines for an unstaged file change
 pub fn get_lines(
     file_change: &FileChange,
+    entry: FileEntry,
     collapsed: bool,
     theme: &Theme,
 ) -> Vec<TextLine<'static>> {
     vec![format_file_change(
         file_change,
+        entry,
         collapsed,
         theme.unstaged_status,
         theme,
@@ -5,13 +5,16 @@ use ratatui::{
 
 use crate::config::Theme;
 
+use super::util::FileEntry;
+
 /// Generate the view lines for an untracked file
-pub fn get_lines(file_path: &str, theme: &Theme) -> Vec<TextLine<'static>> {
+pub fn get_lines(entry: FileEntry, theme: &Theme) -> Vec<TextLine<'static>> {
     // Create the untracked file line with proper indentation
     let file_line = TextLine::from(vec![
         Span::raw(" "), // Indentation space
+        Span::raw(entry.indent()),
         Span::styled(
-            file_path.to_string(),
+            entry.name.to_string(),
             Style::default().fg(theme.untracked_file),
         ),
     ]);
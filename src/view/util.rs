@@ -1,4 +1,7 @@
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
 
 use ratatui::{
     style::{Color, Modifier, Style},
@@ -26,6 +29,49 @@ pub fn expand_tabs(s: &str, initial_col: usize) -> String {
     result
 }
 
+/// Splits `content` into spans styled with `style`, expanding tabs from
+/// `initial_col`. Byte ranges in `syntax` take their foreground color from
+/// it, and the `refined` byte ranges get the `refined_bg` background.
+pub fn content_spans(
+    content: &str,
+    refined: &[Range<usize>],
+    syntax: &[(Range<usize>, Color)],
+    initial_col: usize,
+    style: Style,
+    refined_bg: Color,
+) -> Vec<Span<'static>> {
+    let mut bounds: Vec<usize> = refined
+        .iter()
+        .chain(syntax.iter().map(|(range, _)| range))
+        .flat_map(|range| [range.start, range.end])
+        .filter(|&pos| pos < content.len() && content.is_char_boundary(pos))
+        .chain([0, content.len()])
+        .collect();
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    let mut spans = Vec::new();
+    let mut col = initial_col;
+    for segment in bounds.windows(2) {
+        let (start, end) = (segment[0], segment[1]);
+        let mut segment_style = style;
+        if let Some((_, color)) = syntax.iter().find(|(range, _)| range.contains(&start)) {
+            segment_style = segment_style.fg(*color);
+        }
+        if refined.iter().any(|range| range.contains(&start)) {
+            segment_style = segment_style.bg(refined_bg);
+        }
+        let expanded = expand_tabs(&content[start..end], col);
+        col += expanded.chars().count();
+        spans.push(Span::styled(expanded, segment_style));
+    }
+
+    if spans.is_empty() {
+        spans.push(Span::styled(String::new(), style));
+    }
+    spans
+}
+
 /// Apply search highlighting to a text line.
 ///
 /// Finds all occurrences of `query` (case-sensitive, consecutive) in the concatenated
@@ -183,6 +229,21 @@ pub fn format_ref_with_colors<'a>(ref_info: &GitRef, label: &str, theme: &Theme)
     spans.push(Span::styled(ref_info.name.clone(), branch_style));
     spans.push(Span::styled(" ", Style::default()));
 
+    // Commits HEAD is ahead of and behind the compared branch, when any
+    if let Some((ahead, behind)) = ref_info.ahead_behind {
+        let counts = [("↑", ahead), ("↓", behind)]
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(arrow, count)| format!("{}{}", arrow, count))
+            .collect::<Vec<_>>();
+        if !counts.is_empty() {
+            spans.push(Span::styled(
+                format!("{} ", counts.join(" ")),
+                Style::default().fg(theme.dim_text),
+            ));
+        }
+    }
+
     // Git hash
     spans.push(Span::styled(
         ref_info.commit_hash.clone(),
@@ -200,9 +261,48 @@ pub fn format_ref_with_colors<'a>(ref_info: &GitRef, label: &str, theme: &Theme)
     spans
 }
 
+/// How a file line is listed: below its directory with just its name in the
+/// tree layout, otherwise with its whole path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileEntry<'a> {
+    pub name: &'a str,
+    /// The number of directories above the file in the tree
+    pub depth: usize,
+}
+
+impl<'a> FileEntry<'a> {
+    /// The entry of the file at `path`, whose directory's section is
+    /// `directory`. `directory_depths` has the depth of each directory line.
+    pub fn new(
+        path: &'a str,
+        directory: Option<&SectionType>,
+        directory_depths: &HashMap<&SectionType, usize>,
+    ) -> Self {
+        match directory.and_then(|directory| directory_depths.get(directory)) {
+            Some(depth) => {
+                let start = path.trim_end_matches('/').rfind('/').map_or(0, |i| i + 1);
+                Self {
+                    name: &path[start..],
+                    depth: depth + 1,
+                }
+            }
+            None => Self {
+                name: path,
+                depth: 0,
+            },
+        }
+    }
+
+    /// The indentation of the entry's name
+    pub fn indent(&self) -> String {
+        "  ".repeat(self.depth)
+    }
+}
+
 /// Generate the view line for a file change (staged or unstaged)
 pub fn format_file_change(
     file_change: &FileChange,
+    entry: FileEntry,
     collapsed: bool,
     status_color: Color,
     theme: &Theme,
@@ -211,25 +311,34 @@ pub fn format_file_change(
         FileStatus::Modified => "modified",
         FileStatus::Deleted => "deleted",
         FileStatus::New => "new file",
-        FileStatus::Renamed => "renamed",
-        FileStatus::Copied => "copied",
+        FileStatus::Renamed { .. } => "renamed",
+        FileStatus::Copied { .. } => "copied",
         FileStatus::TypeChange => "typechange",
         FileStatus::Unmerged => "unmerged",
     };
+    // Renames and copies show where the file came from: `old -> new (90%)`
+    let (source, similarity) = match &file_change.status {
+        FileStatus::Renamed { from, similarity } | FileStatus::Copied { from, similarity } => {
+            (format!("{} -> ", from), format!(" ({}%)", similarity))
+        }
+        _ => (String::new(), String::new()),
+    };
 
     // Use '>' when collapsed, '∨' when expanded
     let indicator = if collapsed { ">" } else { "∨" };
 
     TextLine::from(vec![
         Span::raw(indicator),
+        Span::raw(entry.indent()),
         Span::styled(
             format!("{} ", status_str),
             Style::default().fg(status_color),
         ),
         Span::styled(
-            file_change.path.clone(),
+            format!("{}{}", source, entry.name),
             Style::default().fg(theme.file_path),
         ),
+        Span::styled(similarity, Style::default().fg(theme.dim_text)),
     ])
 }
 
@@ -345,6 +454,19 @@ pub fn should_highlight_line(
     }
 
     match (cursor_content, cursor_section, line_section) {
+        // Cursor on SectionHeader with UntrackedFiles: highlight the files and their previews
+        (
+            Some(LineContent::SectionHeader { .. }),
+            Some(SectionType::UntrackedFiles),
+            Some(line_sec),
+        ) => {
+            matches!(
+                line_sec,
+                SectionType::UntrackedFiles
+                    | SectionType::UntrackedDirectory { .. }
+                    | SectionType::UntrackedFile { .. }
+            )
+        }
         // Cursor on SectionHeader with UnstagedChanges: highlight all unstaged lines
         (
             Some(LineContent::SectionHeader { .. }),
@@ -354,6 +476,7 @@ pub fn should_highlight_line(
             matches!(
                 line_sec,
                 SectionType::UnstagedChanges
+                    | SectionType::UnstagedDirectory { .. }
                     | SectionType::UnstagedFile { .. }
                     | SectionType::UnstagedHunk { .. }
             )
@@ -367,6 +490,7 @@ pub fn should_highlight_line(
             matches!(
                 line_sec,
                 SectionType::StagedChanges
+                    | SectionType::StagedDirectory { .. }
                     | SectionType::StagedFile { .. }
                     | SectionType::StagedHunk { .. }
             )
@@ -375,8 +499,18 @@ pub fn should_highlight_line(
         (Some(LineContent::SectionHeader { .. }), Some(cursor_sec), Some(line_sec)) => {
             cursor_sec == line_sec
         }
+        // Cursor on Directory: highlight it + everything below it
+        (Some(LineContent::Directory { .. }), Some(cursor_sec), Some(line_sec)) => {
+            line_sec == cursor_sec || line_sec.is_below(cursor_sec)
+        }
         // Cursor on HeadRef: highlight all lines in Info section
         (Some(LineContent::HeadRef(_)), _, Some(SectionType::Info)) => true,
+        // Cursor on UntrackedFile: highlight file line + its preview
+        (
+            Some(LineContent::UntrackedFile(cursor_path)),
+            _,
+            Some(SectionType::UntrackedFile { path }),
+        ) => path == cursor_path,
         // Cursor on UnstagedFile: highlight file line + all its hunks
         (
             Some(LineContent::UnstagedFile(_)),
@@ -450,6 +584,7 @@ mod tests {
                 content: LineContent::DiffLine(DiffLine {
                     content: "+ added".to_string(),
                     line_type: DiffLineType::Addition,
+                    refined: Vec::new(),
                 }),
                 section: Some(SectionType::UnstagedHunk {
                     path: "foo.rs".to_string(),
@@ -461,6 +596,7 @@ mod tests {
                 content: LineContent::DiffLine(DiffLine {
                     content: "- removed".to_string(),
                     line_type: DiffLineType::Deletion,
+                    refined: Vec::new(),
                 }),
                 section: Some(SectionType::UnstagedHunk {
                     path: "foo.rs".to_string(),
@@ -729,6 +865,7 @@ mod tests {
         let diff_line_content = LineContent::DiffLine(DiffLine {
             content: "+ new line".to_string(),
             line_type: DiffLineType::Addition,
+            refined: Vec::new(),
         });
         let cursor_section = SectionType::UnstagedHunk {
             path: "src/main.rs".to_string(),
@@ -13,7 +13,7 @@ use utils::create_model_from_test_repo;
 /// Helper to get log entries for testing (filters out graph-only entries)
 fn get_log_entries_for_test(test_repo: &TestRepo) -> Vec<magi::model::LogEntry> {
     let repo = git2::Repository::open(test_repo.repo_path()).unwrap();
-    let mut entries = get_log_entries(&repo, &LogType::Current, true, false).unwrap();
+    let mut entries = get_log_entries(&repo, &LogType::Current, true, false, false).unwrap();
     entries.retain(|e| e.is_commit());
     entries
 }
@@ -87,7 +87,8 @@ fn test_commit_select_popup_confirm_returns_hash() {
             log_type: LogType::Current,
             picking: true,
             graph: true,
-            color: false
+            color: false,
+            signatures: false
         }
     ));
 
@@ -136,7 +137,8 @@ fn test_commit_select_popup_navigation() {
             log_type: LogType::Current,
             picking: true,
             graph: true,
-            color: false
+            color: false,
+            signatures: false
         }
     ));
     assert_eq!(model.ui_model.cursor_position, 0);
@@ -1,3 +1,4 @@
+use magi::git::DiffSettings;
 use magi::git::discard::{
     discard_files, discard_hunk, discard_lines, discard_staged_files, discard_staged_hunk,
     discard_untracked_files,
@@ -89,7 +90,13 @@ fn test_discard_hunk() {
     );
 
     // Discard the hunk
-    discard_hunk(test_repo.repo_path(), "test.txt", 0).unwrap();
+    discard_hunk(
+        test_repo.repo_path(),
+        "test.txt",
+        0,
+        &DiffSettings::default(),
+    )
+    .unwrap();
 
     // Verify file is no longer modified
     let statuses = test_repo.repo.statuses(None).unwrap();
@@ -174,7 +181,14 @@ fn test_discard_lines() {
     // Discard only the first modified line (line index 0 in the hunk's diff lines)
     // The diff will have: - line 2\n + MODIFIED 2\n - line 3\n + MODIFIED 3
     // We want to discard line 2's change (indices 0 and 1 in the content lines)
-    discard_lines(test_repo.repo_path(), "test.txt", 0, &[0, 1]).unwrap();
+    discard_lines(
+        test_repo.repo_path(),
+        "test.txt",
+        0,
+        &[0, 1],
+        &DiffSettings::default(),
+    )
+    .unwrap();
 
     // Read the result
     let content = fs::read_to_string(test_repo.repo_path().join(file_name)).unwrap();
@@ -313,7 +327,13 @@ fn test_discard_staged_hunk() {
     );
 
     // Discard the staged hunk - removes from both index and working tree
-    discard_staged_hunk(test_repo.repo_path(), "test.txt", 0).unwrap();
+    discard_staged_hunk(
+        test_repo.repo_path(),
+        "test.txt",
+        0,
+        &DiffSettings::default(),
+    )
+    .unwrap();
 
     // Verify file is no longer staged
     let statuses = test_repo.repo.statuses(None).unwrap();
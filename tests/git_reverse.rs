@@ -1,3 +1,4 @@
+use magi::git::DiffSettings;
 use magi::git::reverse::{
     reverse_patch, reverse_staged_files, reverse_staged_hunk, reverse_staged_hunks,
     reverse_staged_lines,
@@ -35,7 +36,7 @@ diff --git a/test.txt b/test.txt
 ";
     // The patch describes changing "TWO" to "two"; reversing it turns
     // the committed "two" back into "TWO".
-    reverse_patch(test_repo.repo_path(), patch).unwrap();
+    reverse_patch(test_repo.repo_path(), patch, &DiffSettings::default()).unwrap();
 
     assert_eq!(file_content(&test_repo, file_name), "one\nTWO\n");
 }
@@ -100,7 +101,13 @@ fn test_reverse_staged_hunk_reverses_only_that_hunk() {
     let test_repo = TestRepo::new();
     let (_, modified) = setup_two_staged_hunks(&test_repo, file_name);
 
-    reverse_staged_hunk(test_repo.repo_path(), file_name, 0).unwrap();
+    reverse_staged_hunk(
+        test_repo.repo_path(),
+        file_name,
+        0,
+        &DiffSettings::default(),
+    )
+    .unwrap();
 
     let content = file_content(&test_repo, file_name);
     // First hunk undone, second hunk still present
@@ -120,7 +127,13 @@ fn test_reverse_staged_hunks_reverses_all_selected() {
     let (original, _) = setup_two_staged_hunks(&test_repo, file_name);
 
     // Indices arrive in reverse order from visual selection
-    reverse_staged_hunks(test_repo.repo_path(), file_name, &[1, 0]).unwrap();
+    reverse_staged_hunks(
+        test_repo.repo_path(),
+        file_name,
+        &[1, 0],
+        &DiffSettings::default(),
+    )
+    .unwrap();
 
     assert_eq!(file_content(&test_repo, file_name), original);
     assert_still_staged(&test_repo);
@@ -139,7 +152,14 @@ fn test_reverse_staged_lines_reverses_only_selected_lines() {
 
     // The hunk's diff lines are: " line 1", "+ADDED A", "+ADDED B", " line 2",
     // " line 3". Reverse only "+ADDED A" (index 1).
-    reverse_staged_lines(test_repo.repo_path(), file_name, 0, &[1]).unwrap();
+    reverse_staged_lines(
+        test_repo.repo_path(),
+        file_name,
+        0,
+        &[1],
+        &DiffSettings::default(),
+    )
+    .unwrap();
 
     assert_eq!(
         file_content(&test_repo, file_name),
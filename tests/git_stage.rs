@@ -1,7 +1,14 @@
-use magi::git::stage::{stage_files, stage_hunk, unstage_files, unstage_lines};
+use magi::git::DiffSettings;
+use magi::git::stage::{
+    build_block_patch, editable_hunk_patch, edited_hunk_patch, stage_edited_hunk, stage_files,
+    stage_hunk, unstage_files, unstage_lines,
+};
 use magi::git::test_repo::TestRepo;
 use std::fs;
 
+mod utils;
+use utils::index_content;
+
 #[test]
 fn test_stage_files_stages_modified_tracked_file() {
     let file_name = "test.txt";
@@ -209,7 +216,7 @@ fn test_unstage_lines_after_staging_hunk_with_two_hunks() {
     fs::write(&file_path, &modified).unwrap();
 
     // Stage hunk 0 (the change at line 2)
-    stage_hunk(repo_path, "test.txt", 0).unwrap();
+    stage_hunk(repo_path, "test.txt", 0, &DiffSettings::default()).unwrap();
 
     // Now try to unstage specific lines from the staged hunk
     // The staged diff should have one hunk with:
@@ -219,7 +226,7 @@ fn test_unstage_lines_after_staging_hunk_with_two_hunks() {
     //   content_lines[3]: " line 3"       (context)
     //   ...
     // Unstage the change (lines 1 and 2 = the - and + lines)
-    unstage_lines(repo_path, "test.txt", 0, &[1, 2]).unwrap();
+    unstage_lines(repo_path, "test.txt", 0, &[1, 2], &DiffSettings::default()).unwrap();
 
     // After unstaging, line 2 should be back to original in the index
     // The working tree still has MODIFIED 2
@@ -260,7 +267,7 @@ fn test_unstage_lines_no_trailing_newline() {
     fs::write(&file_path, &modified).unwrap();
 
     // Stage hunk 1 (the change at line 19, which is near the end - no trailing newline)
-    stage_hunk(repo_path, "test.txt", 1).unwrap();
+    stage_hunk(repo_path, "test.txt", 1, &DiffSettings::default()).unwrap();
 
     // Unstage the change lines from the staged hunk.
     // The staged diff has "\ No newline at end of file" markers that must be
@@ -271,7 +278,7 @@ fn test_unstage_lines_no_trailing_newline() {
     //   ui_index 3: "-line 19"       (deletion)
     //   ui_index 4: "+MODIFIED 19"   (addition)
     //   ui_index 5: " line 20"       (context)
-    unstage_lines(repo_path, "test.txt", 0, &[3, 4]).unwrap();
+    unstage_lines(repo_path, "test.txt", 0, &[3, 4], &DiffSettings::default()).unwrap();
 
     // After unstaging, the index should no longer have the staged change
     let statuses = test_repo.repo.statuses(None).unwrap();
@@ -301,7 +308,7 @@ fn test_unstage_partial_lines_with_unstaged_hunk_present() {
     fs::write(&file_path, &modified).unwrap();
 
     // Stage hunk 0 (line 2 change), leaving hunk 1 (line 19) unstaged
-    stage_hunk(repo_path, "test.txt", 0).unwrap();
+    stage_hunk(repo_path, "test.txt", 0, &DiffSettings::default()).unwrap();
 
     // Try to unstage ONLY the addition line (ui_index=2), not the deletion (ui_index=1)
     // The staged diff has:
@@ -310,7 +317,7 @@ fn test_unstage_partial_lines_with_unstaged_hunk_present() {
     //   ui_index=2: "+MODIFIED 2" (addition)
     //   ui_index=3: " line 3"     (context)
     //   ...
-    unstage_lines(repo_path, "test.txt", 0, &[2]).unwrap();
+    unstage_lines(repo_path, "test.txt", 0, &[2], &DiffSettings::default()).unwrap();
 }
 
 #[test]
@@ -334,8 +341,8 @@ fn test_unstage_lines_second_staged_hunk_with_unstaged_hunk() {
     fs::write(&file_path, &modified).unwrap();
 
     // Stage hunks 0 and 1 (line 2 and line 15 changes), leaving hunk 2 (line 28) unstaged
-    stage_hunk(repo_path, "test.txt", 0).unwrap();
-    stage_hunk(repo_path, "test.txt", 1).unwrap();
+    stage_hunk(repo_path, "test.txt", 0, &DiffSettings::default()).unwrap();
+    stage_hunk(repo_path, "test.txt", 1, &DiffSettings::default()).unwrap();
 
     // Now unstage lines from the SECOND staged hunk (hunk_index=1, the line 15 change)
     // The staged diff now has two hunks; hunk 1 is the line 15 change
@@ -348,5 +355,119 @@ fn test_unstage_lines_second_staged_hunk_with_unstaged_hunk() {
     //   ui_index=5: " line 16"    (context)
     //   ui_index=6: " line 17"    (context)
     //   ui_index=7: " line 18"    (context)
-    unstage_lines(repo_path, "test.txt", 1, &[3, 4]).unwrap();
+    unstage_lines(repo_path, "test.txt", 1, &[3, 4], &DiffSettings::default()).unwrap();
+}
+
+#[test]
+fn test_stage_hunk_ignoring_whitespace_leaves_whitespace_changes_unstaged() {
+    let test_repo = TestRepo::new();
+    let repo_path = test_repo.repo.workdir().unwrap();
+    test_repo.commit_file(
+        "test.txt",
+        "fn main() {\n    a();\n    b();\n}\n",
+        "Initial",
+    );
+
+    // Reindent a() and change b() right below it
+    fs::write(
+        repo_path.join("test.txt"),
+        "fn main() {\n\ta();\n    c();\n}\n",
+    )
+    .unwrap();
+
+    let settings = DiffSettings {
+        ignore_whitespace: true,
+        ..DiffSettings::default()
+    };
+    stage_hunk(repo_path, "test.txt", 0, &settings).unwrap();
+
+    assert_eq!(
+        index_content(&test_repo, "test.txt"),
+        "fn main() {\n    a();\n    c();\n}\n"
+    );
+}
+
+#[test]
+fn test_stage_hunk_without_context() {
+    let test_repo = TestRepo::new();
+    let repo_path = test_repo.repo.workdir().unwrap();
+    let content: String = (1..=6).map(|i| format!("line {}\n", i)).collect();
+    test_repo.commit_file("test.txt", &content, "Initial");
+
+    // Close enough to share a hunk with the default context
+    let modified = content
+        .replace("line 2\n", "MODIFIED 2\n")
+        .replace("line 5\n", "MODIFIED 5\n");
+    fs::write(repo_path.join("test.txt"), &modified).unwrap();
+
+    let settings = DiffSettings {
+        context_lines: 0,
+        ..DiffSettings::default()
+    };
+    stage_hunk(repo_path, "test.txt", 1, &settings).unwrap();
+
+    assert_eq!(
+        index_content(&test_repo, "test.txt"),
+        content.replace("line 5\n", "MODIFIED 5\n")
+    );
+}
+
+#[test]
+fn test_stage_edited_hunk_stages_the_edit() {
+    let test_repo = TestRepo::new();
+    let repo_path = test_repo.repo.workdir().unwrap();
+    test_repo.commit_file("test.txt", "one\ntwo\nthree\n", "Initial");
+    fs::write(repo_path.join("test.txt"), "one\n2\nthree\nfour\n").unwrap();
+
+    let patch = editable_hunk_patch(repo_path, "test.txt", 0, &DiffSettings::default()).unwrap();
+    // Keep "two" and stage only the added line, with a line of its own
+    let edited = patch
+        .replace("-two\n", " two\n")
+        .replace("+2\n", "")
+        .replace("+four\n", "+four\n+five\n");
+    let staged = stage_edited_hunk(repo_path, &edited, &DiffSettings::default()).unwrap();
+
+    assert!(staged);
+    assert_eq!(
+        index_content(&test_repo, "test.txt"),
+        "one\ntwo\nthree\nfour\nfive\n"
+    );
+}
+
+#[test]
+fn test_edited_hunk_patch_recounts_and_drops_comments() {
+    let edited = "diff --git a/f b/f\n--- a/f\n+++ b/f\n@@ -1,1 +1,1 @@\n a\n\n+b\n# comment\n\n";
+
+    assert_eq!(
+        edited_hunk_patch(edited).unwrap().as_deref(),
+        Some("diff --git a/f b/f\n--- a/f\n+++ b/f\n@@ -1,2 +1,3 @@\n a\n \n+b\n")
+    );
+}
+
+#[test]
+fn test_edited_hunk_patch_without_changes_stages_nothing() {
+    let edited = "diff --git a/f b/f\n--- a/f\n+++ b/f\n@@ -1,2 +1,1 @@\n a\n b\n";
+    assert_eq!(edited_hunk_patch(edited).unwrap(), None);
+}
+
+#[test]
+fn test_edited_hunk_patch_rejects_invalid_lines() {
+    let edited = "diff --git a/f b/f\n--- a/f\n+++ b/f\n@@ -1,1 +1,1 @@\n a\nstray\n";
+    assert!(edited_hunk_patch(edited).is_err());
+    assert!(edited_hunk_patch("+a\n").is_err());
+}
+
+#[test]
+fn test_build_block_patch_keeps_the_block_and_the_context_after_it() {
+    let diff = "diff --git a/f b/f\n--- a/f\n+++ b/f\n@@ -1,6 +1,6 @@\n 1\n-2\n+two\n 3\n 4\n-5\n+five\n 6\n";
+    let header = "diff --git a/f b/f\n--- a/f\n+++ b/f\n";
+
+    assert_eq!(
+        build_block_patch(diff, 0, &[0, 1, 2]).unwrap(),
+        format!("{}@@ -1,4 +1,4 @@\n 1\n-2\n+two\n 3\n 4\n", header)
+    );
+    assert_eq!(
+        build_block_patch(diff, 0, &[3, 4, 5, 6]).unwrap(),
+        format!("{}@@ -3,4 +3,4 @@\n 3\n 4\n-5\n+five\n 6\n", header)
+    );
 }
@@ -1,3 +1,4 @@
+use magi::git::DiffSettings;
 use magi::git::staged_changes::get_lines;
 use magi::git::test_repo::TestRepo;
 use magi::model::{DiffLineType, FileStatus, LineContent};
@@ -13,7 +14,7 @@ fn test_get_lines_with_staged_changes() {
         .write_file_content(file_name, "modified content\nwith new line")
         .stage_files(&[file_name]);
 
-    let lines = get_lines(&test_repo.repo).unwrap();
+    let lines = get_lines(&test_repo.repo, &DiffSettings::default()).unwrap();
 
     // Should have at least: header + file + hunk + diff lines
     assert!(lines.len() >= 3);
@@ -50,7 +51,7 @@ fn test_get_lines_no_staged_changes() {
     let test_repo = TestRepo::new();
 
     // No staging, so no staged changes
-    let lines = get_lines(&test_repo.repo).unwrap();
+    let lines = get_lines(&test_repo.repo, &DiffSettings::default()).unwrap();
 
     assert_eq!(lines.len(), 0);
 }
@@ -61,7 +62,7 @@ fn test_get_lines_with_new_staged_file() {
     let test_repo = TestRepo::new();
     test_repo.create_file(file_name).stage_files(&[file_name]);
 
-    let lines = get_lines(&test_repo.repo).unwrap();
+    let lines = get_lines(&test_repo.repo, &DiffSettings::default()).unwrap();
 
     // Should have header + file + hunk + diff lines
     assert!(lines.len() >= 3);
@@ -87,7 +88,7 @@ fn test_diff_lines_have_correct_types() {
         .write_file_content(file_name, "new content")
         .stage_files(&[file_name]);
 
-    let lines = get_lines(&test_repo.repo).unwrap();
+    let lines = get_lines(&test_repo.repo, &DiffSettings::default()).unwrap();
 
     // Find diff lines and verify they have correct types
     let diff_lines: Vec<_> = lines
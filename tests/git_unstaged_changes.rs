@@ -1,3 +1,4 @@
+use magi::git::DiffSettings;
 use magi::git::test_repo::TestRepo;
 use magi::git::unstaged_changes::get_lines;
 use magi::model::{DiffLineType, FileStatus, LineContent};
@@ -12,7 +13,7 @@ fn test_get_lines_with_unstaged_changes() {
         .commit("Add test.txt")
         .write_file_content(file_name, "modified content\nwith new line");
 
-    let lines = get_lines(&test_repo.repo).unwrap();
+    let lines = get_lines(&test_repo.repo, &DiffSettings::default()).unwrap();
 
     // Should have at least: header + file + hunk + diff lines
     assert!(lines.len() >= 3);
@@ -49,7 +50,7 @@ fn test_get_lines_no_unstaged_changes() {
     let test_repo = TestRepo::new();
 
     // No modifications, so no unstaged changes
-    let lines = get_lines(&test_repo.repo).unwrap();
+    let lines = get_lines(&test_repo.repo, &DiffSettings::default()).unwrap();
 
     assert_eq!(lines.len(), 0);
 }
@@ -64,7 +65,7 @@ fn test_get_lines_with_deleted_file() {
         .commit("Add test.txt")
         .delete_file(file_name);
 
-    let lines = get_lines(&test_repo.repo).unwrap();
+    let lines = get_lines(&test_repo.repo, &DiffSettings::default()).unwrap();
 
     // Should have header + file + hunk + deletion lines
     assert!(lines.len() >= 3);
@@ -89,7 +90,7 @@ fn test_diff_lines_have_correct_types() {
         .commit("Add test.txt")
         .write_file_content(file_name, "new content");
 
-    let lines = get_lines(&test_repo.repo).unwrap();
+    let lines = get_lines(&test_repo.repo, &DiffSettings::default()).unwrap();
 
     // Find diff lines and verify they have correct types
     let diff_lines: Vec<_> = lines
@@ -115,3 +116,34 @@ fn test_diff_lines_have_correct_types() {
             .any(|dl| dl.line_type == DiffLineType::Addition)
     );
 }
+
+#[test]
+fn test_get_lines_refines_changed_words() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("test.txt", "let x = foo(1);\n", "Add test.txt")
+        .write_file_content("test.txt", "let x = bar(1);\n");
+
+    let lines = get_lines(&test_repo.repo, &DiffSettings::default()).unwrap();
+
+    let changed_words: Vec<(DiffLineType, Vec<&str>)> = lines
+        .iter()
+        .filter_map(|line| match &line.content {
+            LineContent::DiffLine(diff) => Some((
+                diff.line_type.clone(),
+                diff.refined
+                    .iter()
+                    .map(|range| &diff.content[range.clone()])
+                    .collect(),
+            )),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        changed_words,
+        vec![
+            (DiffLineType::Deletion, vec!["foo"]),
+            (DiffLineType::Addition, vec!["bar"]),
+        ]
+    );
+}
@@ -1,6 +1,7 @@
 use magi::git::test_repo::TestRepo;
 use magi::git::untracked_files::get_lines;
 use magi::model::LineContent;
+use std::collections::HashSet;
 use std::fs;
 
 #[test]
@@ -14,9 +15,9 @@ fn test_get_lines_with_untracked_files() {
     )
     .unwrap();
 
-    let lines = get_lines(&test_repo.repo).unwrap();
+    let lines = get_lines(&test_repo.repo, &HashSet::new()).unwrap();
 
-    // Should have 2 lines: header + 1 file
+    // Should have 2 lines: header + 1 file, whose preview is only read once expanded
     assert_eq!(lines.len(), 2);
 
     // Check section header
@@ -42,7 +43,7 @@ fn test_get_lines_no_untracked_files() {
     let test_repo = TestRepo::new();
 
     // The test repo commits test.txt, so there should be no untracked files
-    let lines = get_lines(&test_repo.repo).unwrap();
+    let lines = get_lines(&test_repo.repo, &HashSet::new()).unwrap();
 
     // Should have no lines when no untracked files (test.txt is committed)
     assert_eq!(lines.len(), 0);
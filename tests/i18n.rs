@@ -5,7 +5,9 @@ use magi::i18n::{self, Language, Strings};
 /// is enforced by the compiler (every `Strings` field must be populated), but
 /// nothing stops a translation from dropping the placeholder — this list does.
 /// Keep in sync with the `*_fmt` fields in src/i18n.rs.
-const FMT_FIELDS: [(&str, fn(&Strings) -> &'static str); 8] = [
+type FmtField = (&'static str, fn(&Strings) -> &'static str);
+
+const FMT_FIELDS: [FmtField; 9] = [
     ("input_rename_branch_fmt", |s| s.input_rename_branch_fmt),
     ("help_version_fmt", |s| s.help_version_fmt),
     ("input_worktree_path_fmt", |s| s.input_worktree_path_fmt),
@@ -18,13 +20,16 @@ const FMT_FIELDS: [(&str, fn(&Strings) -> &'static str); 8] = [
     ("title_pick_rebase_subset_fmt", |s| {
         s.title_pick_rebase_subset_fmt
     }),
+    ("review_confirm_discard_fmt", |s| {
+        s.review_confirm_discard_fmt
+    }),
 ];
 
 #[test]
 fn test_fmt_fields_have_exactly_one_placeholder_in_all_languages() {
     for (lang_name, strings) in [("english", i18n::english()), ("swedish", i18n::swedish())] {
         for (field_name, get) in FMT_FIELDS {
-            let value = get(&strings);
+            let value = get(strings);
             assert_eq!(
                 value.matches("{}").count(),
                 1,
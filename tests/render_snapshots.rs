@@ -7,10 +7,10 @@ use magi::{
         LineContent,
         arguments::{Arguments, PushArgument, RebaseArgument, TagArgument},
         popup::{
-            ApplyPopupState, CommitPopupState, ConfirmAction, ConfirmPopupState,
+            ApplyPopupState, CommitPopupState, CommitTrailer, ConfirmAction, ConfirmPopupState,
             CredentialPopupState, FetchPopupState, InputContext, InputPopupState, MergePopupState,
             PopupContent, PopupContentCommand, PullPopupState, PushPopupState, RebasePopupState,
-            RevertPopupState,
+            ResolvePopupState, RevertPopupState,
         },
         select_popup::{OnSelect, SelectPopupState},
     },
@@ -98,6 +98,104 @@ fn snapshot_status_view_with_merge_conflict() {
     assert_frame_snapshot!(render_to_string(&model, 80, 24));
 }
 
+/// A model with an unstaged change to `file.txt` whose diff is expanded and
+/// rendered side by side.
+fn create_side_by_side_model(test_repo: &TestRepo) -> Model {
+    test_repo
+        .commit_file("file.txt", "one\ntwo\nthree\nfour\n", "Add file")
+        .write_file_content("file.txt", "one\nTWO\nthree\nfour\nfive\n");
+
+    let mut model = create_snapshot_model(test_repo);
+    model.config.side_by_side_diff = true;
+    model
+        .ui_model
+        .collapsed_sections
+        .remove(&magi::model::SectionType::UnstagedFile {
+            path: "file.txt".to_string(),
+        });
+    model
+}
+
+#[test]
+fn snapshot_status_view_side_by_side_diff() {
+    let test_repo = TestRepo::new();
+    let model = create_side_by_side_model(&test_repo);
+    assert_frame_snapshot!(render_to_string(&model, 120, 24));
+}
+
+#[test]
+fn snapshot_status_view_side_by_side_diff_narrow_falls_back_to_unified() {
+    let test_repo = TestRepo::new();
+    let model = create_side_by_side_model(&test_repo);
+    assert_frame_snapshot!(render_to_string(&model, 80, 24));
+}
+
+#[test]
+fn snapshot_status_view_file_tree() {
+    let test_repo = TestRepo::new();
+    std::fs::create_dir_all(test_repo.repo_path().join("src/git")).unwrap();
+    test_repo
+        .commit_file("src/git/stage.rs", "stage", "Add stage.rs")
+        .commit_file("src/main.rs", "main", "Add main.rs")
+        .write_file_content("src/git/stage.rs", "stage modified")
+        .write_file_content("src/main.rs", "main modified")
+        .write_file_content("README.md", "readme")
+        .write_file_content("src/git/new.rs", "new");
+
+    let mut model = create_snapshot_model(&test_repo);
+    model.git_info.file_tree = true;
+    update(&mut model, Message::Refresh);
+    assert_frame_snapshot!(render_to_string(&model, 80, 24));
+}
+
+#[test]
+fn snapshot_status_view_file_metadata() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("old.txt", "1\n2\n3\n4\n5\n", "Add old.txt")
+        .commit_file("image.bin", "a\0b", "Add image.bin")
+        .commit_file("run.sh", "echo hi\n", "Add run.sh")
+        .write_file_content("image.bin", "a\0bcd");
+    let run_sh = test_repo.repo_path().join("run.sh");
+    let mut permissions = std::fs::metadata(&run_sh).unwrap().permissions();
+    std::os::unix::fs::PermissionsExt::set_mode(&mut permissions, 0o755);
+    std::fs::set_permissions(&run_sh, permissions).unwrap();
+    std::fs::rename(
+        test_repo.repo_path().join("old.txt"),
+        test_repo.repo_path().join("new.txt"),
+    )
+    .unwrap();
+    test_repo.stage_files(&["old.txt", "new.txt"]);
+
+    let mut model = create_snapshot_model(&test_repo);
+    // Expanded files show their details
+    model.ui_model.collapsed_sections.clear();
+    assert_frame_snapshot!(render_to_string(&model, 80, 24));
+}
+
+#[test]
+fn snapshot_status_view_remote_sections() {
+    let test_repo = TestRepo::new();
+    let git = |args: &[&str]| {
+        let status = magi::git::git_cmd(test_repo.repo_path(), args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    };
+    git(&["remote", "add", "origin", "https://example.com/origin.git"]);
+    git(&["remote", "add", "fork", "https://example.com/fork.git"]);
+    git(&["config", "remote.pushDefault", "fork"]);
+    git(&["update-ref", "refs/remotes/fork/main", "HEAD"]);
+    test_repo.commit_file("remote.txt", "remote", "Add remote file");
+    git(&["update-ref", "refs/remotes/origin/main", "HEAD"]);
+    git(&["reset", "--hard", "HEAD~1"]);
+    git(&["branch", "--set-upstream-to", "origin/main"]);
+    test_repo.commit_file("local.txt", "local", "Add local file");
+
+    let model = create_snapshot_model(&test_repo);
+    assert_frame_snapshot!(render_to_string(&model, 80, 24));
+}
+
 #[test]
 fn snapshot_help_popup() {
     let test_repo = TestRepo::new();
@@ -265,11 +363,22 @@ fn snapshot_commit_popup_with_author() {
         &test_repo,
         PopupContentCommand::Commit(CommitPopupState {
             author: Some("André Danielsson <andre@example.com>".to_string()),
+            ..Default::default()
         }),
     );
     assert_frame_snapshot!(render_to_string(&model, 80, 24));
 }
 
+#[test]
+fn snapshot_commit_popup_with_trailers() {
+    let test_repo = TestRepo::new();
+    let mut state = CommitPopupState::default();
+    state.toggle_trailer(CommitTrailer::CoAuthoredBy, "Ada <ada@example.com>".into());
+    state.toggle_trailer(CommitTrailer::CoAuthoredBy, "Bo <bo@example.com>".into());
+    let model = create_command_popup_model(&test_repo, PopupContentCommand::Commit(state));
+    assert_frame_snapshot!(render_to_string(&model, 100, 24));
+}
+
 #[test]
 fn snapshot_push_popup() {
     let test_repo = TestRepo::new();
@@ -346,6 +455,14 @@ fn snapshot_worktree_popup() {
     assert_frame_snapshot!(render_to_string(&model, 80, 24));
 }
 
+#[test]
+fn snapshot_diff_popup() {
+    let test_repo = TestRepo::new();
+    let mut model = create_command_popup_model(&test_repo, PopupContentCommand::Diff);
+    model.git_info.diff_settings.context_lines = 5;
+    assert_frame_snapshot!(render_to_string(&model, 80, 24));
+}
+
 #[test]
 fn snapshot_log_popup() {
     let test_repo = TestRepo::new();
@@ -472,6 +589,29 @@ fn snapshot_rebase_todo_view() {
     assert_frame_snapshot!(render_to_string(&model, 100, 24));
 }
 
+#[test]
+fn snapshot_hunk_review_view() {
+    use magi::msg::HunkReviewMessage;
+
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("a.txt", "one\ntwo\nthree\n", "Add a")
+        .commit_file("b.txt", "b\n", "Add b")
+        .write_file_content("a.txt", "one\n2\nthree\n")
+        .write_file_content("b.txt", "bee\n");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    update(
+        &mut model,
+        Message::HunkReview(HunkReviewMessage::Start { staged: false }),
+    );
+    update(&mut model, Message::HunkReview(HunkReviewMessage::Accept));
+    update(&mut model, Message::HunkReview(HunkReviewMessage::Previous));
+    model.workdir = std::path::PathBuf::from("/repo/magi/");
+
+    assert_frame_snapshot!(render_to_string(&model, 80, 20));
+}
+
 #[test]
 fn snapshot_revert_popup() {
     let test_repo = TestRepo::new();
@@ -509,6 +649,20 @@ fn snapshot_apply_popup() {
     assert_frame_snapshot!(render_to_string(&model, 80, 24));
 }
 
+#[test]
+fn snapshot_resolve_popup() {
+    let test_repo = TestRepo::new();
+    let model = create_command_popup_model(
+        &test_repo,
+        PopupContentCommand::Resolve(ResolvePopupState {
+            path: "conflict.txt".to_string(),
+            conflict: Some(0),
+            unmerged: true,
+        }),
+    );
+    assert_frame_snapshot!(render_to_string(&model, 80, 24));
+}
+
 #[test]
 fn snapshot_tag_popup() {
     let test_repo = TestRepo::new();
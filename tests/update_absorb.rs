@@ -0,0 +1,134 @@
+use magi::{
+    git::{git_cmd, test_repo::TestRepo},
+    model::{
+        Toast,
+        popup::{ConfirmAction, PopupContent},
+    },
+    msg::{Message, RebaseCommand, update::update},
+};
+
+mod utils;
+use utils::create_model_from_test_repo;
+
+fn log_subjects(test_repo: &TestRepo) -> Vec<String> {
+    let output = git_cmd(test_repo.repo_path(), &["log", "--format=%s"])
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Two commits owning different halves of a file, with a staged change in
+/// each half.
+fn repo_with_review_fixes() -> TestRepo {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file.txt", "one\ntwo\nthree\n", "Add start");
+    test_repo.commit_file("file.txt", "one\ntwo\nthree\nfour\nfive\n", "Add end");
+    test_repo
+        .write_file_content("file.txt", "one\nTWO\nthree\nfour\nFIVE\n")
+        .stage_files(&["file.txt"]);
+    test_repo
+}
+
+#[test]
+fn test_absorb_shows_hunk_to_commit_mapping() {
+    let test_repo = repo_with_review_fixes();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    update(&mut model, Message::ShowAbsorbConfirm { autosquash: false });
+
+    let Some(PopupContent::Confirm(state)) = &model.popup else {
+        panic!("expected the absorb confirmation, got {:?}", model.popup);
+    };
+    let lines: Vec<&str> = state.message.lines().collect();
+    assert_eq!(lines[0], "Absorb staged hunks into fixup commits:");
+    assert!(lines[1].starts_with("  file.txt:2 → "), "{}", lines[1]);
+    assert!(lines[1].ends_with(" Add start"), "{}", lines[1]);
+    assert!(lines[2].starts_with("  file.txt:5 → "), "{}", lines[2]);
+    assert!(lines[2].ends_with(" Add end"), "{}", lines[2]);
+    assert!(matches!(
+        state.on_confirm,
+        ConfirmAction::Absorb {
+            autosquash: false,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_confirming_absorb_creates_fixup_commits() {
+    let test_repo = repo_with_review_fixes();
+    let mut model = create_model_from_test_repo(&test_repo);
+    update(&mut model, Message::ShowAbsorbConfirm { autosquash: false });
+    let Some(PopupContent::Confirm(state)) = model.popup.clone() else {
+        panic!("expected the absorb confirmation");
+    };
+    let ConfirmAction::Absorb { plan, autosquash } = state.on_confirm else {
+        panic!("expected an absorb action");
+    };
+
+    let result = update(&mut model, Message::Absorb { plan, autosquash });
+
+    assert_eq!(result, Some(Message::Refresh));
+    assert!(model.popup.is_none());
+    assert_eq!(
+        log_subjects(&test_repo),
+        vec![
+            "fixup! Add end",
+            "fixup! Add start",
+            "Add end",
+            "Add start",
+            "Initial commit"
+        ]
+    );
+}
+
+#[test]
+fn test_absorb_and_autosquash_folds_fixups_into_targets() {
+    let test_repo = repo_with_review_fixes();
+    let mut model = create_model_from_test_repo(&test_repo);
+    update(&mut model, Message::ShowAbsorbConfirm { autosquash: true });
+    let Some(PopupContent::Confirm(state)) = model.popup.clone() else {
+        panic!("expected the absorb confirmation");
+    };
+    let ConfirmAction::Absorb { plan, autosquash } = state.on_confirm else {
+        panic!("expected an absorb action");
+    };
+    let oldest = plan.targets[0].hash.clone();
+
+    let result = update(&mut model, Message::Absorb { plan, autosquash });
+
+    assert_eq!(
+        result,
+        Some(Message::Rebase(RebaseCommand::AutosquashInto(oldest)))
+    );
+    update(&mut model, result.unwrap());
+    assert_eq!(
+        log_subjects(&test_repo),
+        vec!["Add end", "Add start", "Initial commit"]
+    );
+    let show = |rev: &str| {
+        let output = git_cmd(test_repo.repo_path(), &["show", rev])
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    assert_eq!(show("HEAD~1:file.txt"), "one\nTWO\nthree\n");
+    assert_eq!(show("HEAD:file.txt"), "one\nTWO\nthree\nfour\nFIVE\n");
+}
+
+#[test]
+fn test_absorb_without_staged_changes_shows_toast() {
+    let test_repo = TestRepo::new();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    let result = update(&mut model, Message::ShowAbsorbConfirm { autosquash: false });
+
+    assert_eq!(result, Some(Message::DismissPopup));
+    assert!(matches!(
+        &model.toast,
+        Some(Toast { message, .. }) if message == "Nothing staged to absorb"
+    ));
+}
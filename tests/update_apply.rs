@@ -4,6 +4,7 @@ use magi::{
     keys::handle_key,
     model::{
         Line, LineContent,
+        arguments::{ApplyArgument, Argument, ValueArgument},
         popup::{ApplyPopupState, PopupContent, PopupContentCommand},
         select_popup::OnSelect,
     },
@@ -318,6 +319,70 @@ fn test_a_in_apply_popup_in_progress_triggers_abort() {
     assert_eq!(result, Some(Message::Apply(ApplyCommand::Abort)));
 }
 
+// ── Apply popup keys — arg mode ──────────────────────────────────────────────
+
+#[test]
+fn test_apply_arguments_in_arg_mode() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Apply(
+        ApplyPopupState {
+            in_progress: false,
+            selected_commits: vec![],
+        },
+    )));
+    model.arg_mode = true;
+
+    assert_eq!(
+        handle_key(key(KeyCode::Char('x')), &model),
+        Some(Message::ToggleArgument(Argument::Apply(
+            ApplyArgument::RecordOrigin
+        )))
+    );
+    assert_eq!(
+        handle_key(shift_key(KeyCode::Char('F')), &model),
+        Some(Message::ToggleArgument(Argument::Apply(
+            ApplyArgument::FastForward
+        )))
+    );
+    assert_eq!(
+        handle_key(key(KeyCode::Char('m')), &model),
+        Some(Message::ShowArgumentValueInput(ValueArgument::Mainline))
+    );
+    assert_eq!(
+        handle_key(key(KeyCode::Char('s')), &model),
+        Some(Message::ShowArgumentValueInput(ValueArgument::Strategy))
+    );
+    assert_eq!(
+        handle_key(key(KeyCode::Char('z')), &model),
+        Some(Message::ExitArgMode)
+    );
+}
+
+#[test]
+fn test_toggle_apply_argument_updates_arguments() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arg_mode = true;
+
+    update(
+        &mut model,
+        Message::ToggleArgument(Argument::Apply(ApplyArgument::AllowEmpty)),
+    );
+
+    let args = model
+        .arguments
+        .as_ref()
+        .and_then(|a| a.apply())
+        .expect("Expected apply arguments");
+    assert!(args.contains(&ApplyArgument::AllowEmpty));
+    assert!(!model.arg_mode);
+}
+
 // ── cherry_pick_in_progress ───────────────────────────────────────────────────
 
 #[test]
@@ -372,7 +437,7 @@ fn test_show_apply_popup_cursor_on_log_line_collects_hash() {
 
     // Switch to log view
     let repo = git2::Repository::open(test_repo.repo_path()).unwrap();
-    let log_lines: Vec<Line> = get_log_entries(&repo, &LogType::Current, true, false)
+    let log_lines: Vec<Line> = get_log_entries(&repo, &LogType::Current, true, false, false)
         .unwrap()
         .into_iter()
         .map(|entry| Line {
@@ -476,3 +541,32 @@ fn test_a_in_apply_popup_with_multiple_commits_applies_all() {
         ])))
     );
 }
+
+// ── Mainline — remembered between uses ────────────────────────────────────────
+
+#[test]
+fn test_mainline_is_remembered_after_pick_and_shared_with_revert_popup() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model
+        .argument_values
+        .insert(ValueArgument::Mainline, "2".to_string());
+
+    update(
+        &mut model,
+        Message::Apply(ApplyCommand::Pick(vec![test_repo.head_hash()])),
+    );
+    assert_eq!(
+        model.argument_values.get(&ValueArgument::Mainline),
+        Some(&"2".to_string())
+    );
+
+    update(&mut model, Message::DismissPopup);
+    update(&mut model, Message::ShowRevertPopup);
+    let Some(PopupContent::Command(PopupContentCommand::Revert(state))) = &model.popup else {
+        panic!("Expected Revert popup");
+    };
+    assert_eq!(state.mainline.as_deref(), Some("2"));
+}
@@ -0,0 +1,230 @@
+use crossterm::event::KeyCode;
+use magi::{
+    git::{git_cmd, test_repo::TestRepo},
+    keys::handle_key,
+    model::{
+        LineContent, Model, ViewMode,
+        popup::{PopupContent, PopupContentCommand},
+        select_popup::OnSelect,
+    },
+    msg::{BlobMessage, Message, OptionsSource, ShowSelectPopupConfig},
+};
+
+mod utils;
+use utils::{assert_no_popup, create_model_from_test_repo, expect_select_popup, key, run};
+
+fn blob_contents(model: &Model) -> Vec<String> {
+    model
+        .ui_model
+        .lines
+        .iter()
+        .filter_map(|line| match &line.content {
+            LineContent::BlobLine { content, .. } => Some(content.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn blob_revision(model: &Model) -> (String, bool) {
+    match &model.view_mode {
+        ViewMode::Blob {
+            revision, blame, ..
+        } => (revision.clone(), *blame),
+        other => panic!("Expected blob view, got {:?}", other),
+    }
+}
+
+fn history_repo() -> TestRepo {
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("file.txt", "one\n", "First")
+        .commit_file("other.txt", "other\n", "Unrelated")
+        .commit_file("file.txt", "one\ntwo\n", "Second");
+    test_repo
+}
+
+fn show_blob(model: &mut Model, revision: &str) {
+    run(
+        model,
+        Message::ShowBlob {
+            revision: revision.to_string(),
+            path: "file.txt".to_string(),
+        },
+    );
+}
+
+// ── Picking a revision and file ────────────────────────────────────────────────
+
+#[test]
+fn test_f_in_log_popup_shows_revision_select() {
+    let test_repo = history_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Log));
+
+    assert_eq!(
+        handle_key(key(KeyCode::Char('f')), &model),
+        Some(Message::ShowSelectPopup(ShowSelectPopupConfig {
+            title: "Visit file at revision".to_string(),
+            source: OptionsSource::FileCheckoutRevisions,
+            on_select: OnSelect::BlobRevision,
+        }))
+    );
+}
+
+#[test]
+fn test_file_select_lists_files_at_revision() {
+    let test_repo = history_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    run(
+        &mut model,
+        Message::ShowSelectPopup(ShowSelectPopupConfig {
+            title: "Visit file".to_string(),
+            source: OptionsSource::FilesAtRevision {
+                revision: "HEAD~2".to_string(),
+            },
+            on_select: OnSelect::BlobFile {
+                revision: "HEAD~2".to_string(),
+            },
+        }),
+    );
+
+    let state = expect_select_popup(&model);
+    assert!(state.all_options.contains(&"file.txt".to_string()));
+    assert!(!state.all_options.contains(&"other.txt".to_string()));
+}
+
+// ── Showing and stepping ───────────────────────────────────────────────────────
+
+#[test]
+fn test_show_blob_displays_file_at_revision() {
+    let test_repo = history_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    show_blob(&mut model, "HEAD~2");
+
+    assert_no_popup(&model);
+    assert_eq!(blob_contents(&model), vec!["one"]);
+    assert_eq!(model.preview_return_mode, Some(ViewMode::Status));
+}
+
+#[test]
+fn test_show_blob_missing_file_shows_error() {
+    let test_repo = history_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    run(
+        &mut model,
+        Message::ShowBlob {
+            revision: "HEAD".to_string(),
+            path: "missing.txt".to_string(),
+        },
+    );
+
+    assert!(matches!(model.popup, Some(PopupContent::Error { .. })));
+    assert_eq!(model.view_mode, ViewMode::Status);
+}
+
+#[test]
+fn test_previous_and_next_step_over_unrelated_commits() {
+    let test_repo = history_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+    show_blob(&mut model, "HEAD");
+
+    run(&mut model, Message::Blob(BlobMessage::Previous));
+    assert_eq!(blob_contents(&model), vec!["one"]);
+
+    run(&mut model, Message::Blob(BlobMessage::Next));
+    assert_eq!(blob_contents(&model), vec!["one", "two"]);
+}
+
+#[test]
+fn test_previous_at_oldest_revision_shows_toast() {
+    let test_repo = history_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+    show_blob(&mut model, "HEAD~2");
+    let (revision, _) = blob_revision(&model);
+
+    run(&mut model, Message::Blob(BlobMessage::Previous));
+
+    assert_eq!(blob_revision(&model).0, revision);
+    assert!(model.toast.is_some());
+}
+
+#[test]
+fn test_next_when_file_is_deleted_shows_toast() {
+    let test_repo = history_repo();
+    let workdir = test_repo.repo_path();
+    for args in [&["rm", "file.txt"][..], &["commit", "-m", "Delete"]] {
+        assert!(git_cmd(workdir, args).output().unwrap().status.success());
+    }
+    let mut model = create_model_from_test_repo(&test_repo);
+    show_blob(&mut model, "HEAD~1");
+    let (revision, _) = blob_revision(&model);
+
+    run(&mut model, Message::Blob(BlobMessage::Next));
+
+    assert_no_popup(&model);
+    assert_eq!(blob_revision(&model).0, revision);
+    assert!(model.toast.is_some());
+}
+
+#[test]
+fn test_toggle_blame_annotates_lines() {
+    let test_repo = history_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+    show_blob(&mut model, "HEAD");
+
+    run(&mut model, Message::Blob(BlobMessage::ToggleBlame));
+
+    assert!(blob_revision(&model).1);
+    assert!(
+        model
+            .ui_model
+            .lines
+            .iter()
+            .all(|line| matches!(&line.content, LineContent::BlobLine { blame: Some(_), .. }))
+    );
+
+    // Blame is kept while stepping through history
+    run(&mut model, Message::Blob(BlobMessage::Previous));
+    assert!(blob_revision(&model).1);
+}
+
+// ── Keys ───────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_blob_view_keys() {
+    let test_repo = history_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+    show_blob(&mut model, "HEAD");
+
+    assert_eq!(
+        handle_key(key(KeyCode::Char('p')), &model),
+        Some(Message::Blob(BlobMessage::Previous))
+    );
+    assert_eq!(
+        handle_key(key(KeyCode::Char('n')), &model),
+        Some(Message::Blob(BlobMessage::Next))
+    );
+    assert_eq!(
+        handle_key(key(KeyCode::Char('b')), &model),
+        Some(Message::Blob(BlobMessage::ToggleBlame))
+    );
+    assert_eq!(
+        handle_key(key(KeyCode::Char('q')), &model),
+        Some(Message::ExitPreview)
+    );
+}
+
+#[test]
+fn test_exit_blob_view_returns_to_status() {
+    let test_repo = history_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+    show_blob(&mut model, "HEAD");
+
+    run(&mut model, Message::ExitPreview);
+
+    assert_eq!(model.view_mode, ViewMode::Status);
+    assert!(blob_contents(&model).is_empty());
+}
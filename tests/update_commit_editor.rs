@@ -0,0 +1,190 @@
+use magi::{
+    git::{commit::head_commit_message, test_repo::TestRepo},
+    model::{EditOp, LineContent, Model, PreviewLineType, ViewMode},
+    msg::{CommitEditorMessage, Message, update::update, util::is_external_command},
+};
+
+mod utils;
+use utils::create_model_from_test_repo;
+
+fn builtin_editor_model(test_repo: &TestRepo) -> Model {
+    let mut model = create_model_from_test_repo(test_repo);
+    model.config.builtin_commit_editor = true;
+    model
+}
+
+fn type_text(model: &mut Model, text: &str) {
+    for c in text.chars() {
+        let msg = if c == '\n' {
+            CommitEditorMessage::Newline
+        } else {
+            CommitEditorMessage::Edit(EditOp::Insert(c))
+        };
+        update(model, Message::CommitEditor(msg));
+    }
+}
+
+fn editor_text(model: &Model) -> String {
+    model.commit_editor.as_ref().unwrap().message.text()
+}
+
+#[test]
+fn test_commit_without_builtin_editor_launches_external_editor() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .write_file_content("file.txt", "content")
+        .stage_files(&["file.txt"]);
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    assert!(!is_external_command(&Message::Commit));
+    let result = update(&mut model, Message::Commit);
+
+    let follow_up = result.expect("expected the external editor message");
+    assert_eq!(follow_up, Message::CommitWithEditor(vec![]));
+    assert!(is_external_command(&follow_up));
+    assert!(model.commit_editor.is_none());
+}
+
+#[test]
+fn test_commit_opens_builtin_editor_with_staged_diff() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .write_file_content("file.txt", "staged line\n")
+        .stage_files(&["file.txt"]);
+    let mut model = builtin_editor_model(&test_repo);
+
+    let result = update(&mut model, Message::Commit);
+
+    assert_eq!(result, None);
+    assert_eq!(model.view_mode, ViewMode::CommitMessage);
+    assert_eq!(editor_text(&model), "");
+    assert!(model.ui_model.lines.iter().any(|line| matches!(
+        &line.content,
+        LineContent::PreviewLine { content, line_type: PreviewLineType::Addition, .. }
+            if content == "+staged line"
+    )));
+}
+
+#[test]
+fn test_confirm_commits_written_message_and_restores_status() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .write_file_content("file.txt", "content")
+        .stage_files(&["file.txt"]);
+    let mut model = builtin_editor_model(&test_repo);
+
+    update(&mut model, Message::Commit);
+    type_text(&mut model, "Add file\n\nBody text");
+    let result = update(
+        &mut model,
+        Message::CommitEditor(CommitEditorMessage::Confirm),
+    );
+
+    assert_eq!(result, Some(Message::Refresh));
+    assert_eq!(model.view_mode, ViewMode::Status);
+    assert!(model.commit_editor.is_none());
+    assert_eq!(
+        head_commit_message(test_repo.repo_path()),
+        "Add file\n\nBody text"
+    );
+}
+
+#[test]
+fn test_confirm_with_empty_message_keeps_editor_open() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .write_file_content("file.txt", "content")
+        .stage_files(&["file.txt"]);
+    let mut model = builtin_editor_model(&test_repo);
+
+    update(&mut model, Message::Commit);
+    let result = update(
+        &mut model,
+        Message::CommitEditor(CommitEditorMessage::Confirm),
+    );
+
+    assert_eq!(result, None);
+    assert_eq!(model.view_mode, ViewMode::CommitMessage);
+    assert!(model.toast.is_some());
+    assert_eq!(head_commit_message(test_repo.repo_path()), "Initial commit");
+}
+
+#[test]
+fn test_aborted_message_is_recalled_with_history_prev() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .write_file_content("file.txt", "content")
+        .stage_files(&["file.txt"]);
+    let mut model = builtin_editor_model(&test_repo);
+
+    update(&mut model, Message::Commit);
+    type_text(&mut model, "Draft message");
+    let result = update(
+        &mut model,
+        Message::CommitEditor(CommitEditorMessage::Abort),
+    );
+    assert_eq!(result, Some(Message::Refresh));
+    assert_eq!(model.view_mode, ViewMode::Status);
+
+    update(&mut model, Message::Commit);
+    update(
+        &mut model,
+        Message::CommitEditor(CommitEditorMessage::HistoryPrev),
+    );
+    assert_eq!(editor_text(&model), "Draft message");
+
+    // Older entries come from previous commits
+    update(
+        &mut model,
+        Message::CommitEditor(CommitEditorMessage::HistoryPrev),
+    );
+    assert_eq!(editor_text(&model), "Initial commit");
+
+    update(
+        &mut model,
+        Message::CommitEditor(CommitEditorMessage::HistoryNext),
+    );
+    update(
+        &mut model,
+        Message::CommitEditor(CommitEditorMessage::HistoryNext),
+    );
+    assert_eq!(editor_text(&model), "");
+}
+
+#[test]
+fn test_amend_prefills_head_message() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file.txt", "content", "Original message");
+    let mut model = builtin_editor_model(&test_repo);
+
+    update(&mut model, Message::Amend(vec![]));
+    assert_eq!(model.view_mode, ViewMode::CommitMessage);
+    assert_eq!(editor_text(&model), "Original message");
+
+    update(
+        &mut model,
+        Message::CommitEditor(CommitEditorMessage::Edit(EditOp::DeleteToEnd)),
+    );
+    type_text(&mut model, "Reworded");
+    update(
+        &mut model,
+        Message::CommitEditor(CommitEditorMessage::Confirm),
+    );
+
+    assert_eq!(head_commit_message(test_repo.repo_path()), "Reworded");
+}
+
+#[test]
+fn test_amend_no_edit_skips_builtin_editor() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file.txt", "content", "Original message");
+    let mut model = builtin_editor_model(&test_repo);
+
+    let result = update(&mut model, Message::Amend(vec!["--no-edit".to_string()]));
+
+    assert_eq!(
+        result,
+        Some(Message::AmendWithEditor(vec!["--no-edit".to_string()]))
+    );
+    assert!(model.commit_editor.is_none());
+}
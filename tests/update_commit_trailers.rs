@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+
+use magi::{
+    git::{commit::head_commit_message, test_repo::TestRepo},
+    model::{
+        EditOp, Model,
+        arguments::{Arguments, CommitArgument},
+        popup::{
+            CommitAuthorTarget, CommitPopupState, CommitTrailer, PopupContent, PopupContentCommand,
+        },
+        select_popup::OnSelect,
+    },
+    msg::{CommitEditorMessage, Message, SelectMessage, update::update},
+};
+
+mod utils;
+use utils::create_model_from_test_repo;
+
+const AUTHOR: &str = "Test User <test@example.com>";
+
+fn open_commit_popup(model: &mut Model, state: CommitPopupState) {
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Commit(state)));
+}
+
+fn commit_popup_state(model: &Model) -> &CommitPopupState {
+    match &model.popup {
+        Some(PopupContent::Command(PopupContentCommand::Commit(state))) => state,
+        other => panic!("expected the commit popup, got {:?}", other),
+    }
+}
+
+fn pick_author(model: &mut Model, target: CommitAuthorTarget) {
+    update(model, Message::ShowCommitAuthorSelect(target));
+    update(model, Message::Select(SelectMessage::Confirm));
+}
+
+#[test]
+fn test_co_author_picker_lists_authors_and_keeps_popup_state() {
+    let test_repo = TestRepo::new();
+    let mut model = create_model_from_test_repo(&test_repo);
+    open_commit_popup(
+        &mut model,
+        CommitPopupState {
+            author: Some("Someone <someone@example.com>".to_string()),
+            ..Default::default()
+        },
+    );
+
+    let target = CommitAuthorTarget::Trailer(CommitTrailer::CoAuthoredBy);
+    update(&mut model, Message::ShowCommitAuthorSelect(target));
+
+    match &model.popup {
+        Some(PopupContent::Command(PopupContentCommand::Select(state))) => {
+            assert_eq!(state.title, "Co-authored-by");
+            assert_eq!(state.all_options, vec![AUTHOR.to_string()]);
+            assert!(matches!(state.on_select, OnSelect::CommitAuthor { .. }));
+        }
+        other => panic!("expected the author select popup, got {:?}", other),
+    }
+
+    update(&mut model, Message::Select(SelectMessage::Confirm));
+
+    let state = commit_popup_state(&model);
+    assert_eq!(
+        state.author.as_deref(),
+        Some("Someone <someone@example.com>")
+    );
+    assert_eq!(
+        state.trailer_values(CommitTrailer::CoAuthoredBy),
+        vec![AUTHOR]
+    );
+}
+
+#[test]
+fn test_picking_present_trailer_person_removes_it() {
+    let test_repo = TestRepo::new();
+    let mut model = create_model_from_test_repo(&test_repo);
+    open_commit_popup(&mut model, CommitPopupState::default());
+
+    let target = CommitAuthorTarget::Trailer(CommitTrailer::ReviewedBy);
+    pick_author(&mut model, target);
+    assert_eq!(
+        commit_popup_state(&model).trailer_values(CommitTrailer::ReviewedBy),
+        vec![AUTHOR]
+    );
+
+    pick_author(&mut model, target);
+    assert!(commit_popup_state(&model).trailers.is_empty());
+}
+
+#[test]
+fn test_cancelling_trailer_picker_returns_to_commit_popup() {
+    let test_repo = TestRepo::new();
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arguments = Some(Arguments::CommitArguments(HashSet::from([
+        CommitArgument::Signoff,
+    ])));
+    let mut state = CommitPopupState::default();
+    state.toggle_trailer(CommitTrailer::ReviewedBy, AUTHOR.into());
+    open_commit_popup(&mut model, state);
+
+    let target = CommitAuthorTarget::Trailer(CommitTrailer::CoAuthoredBy);
+    update(&mut model, Message::ShowCommitAuthorSelect(target));
+    update(&mut model, Message::DismissPopup);
+
+    assert_eq!(
+        commit_popup_state(&model).trailer_values(CommitTrailer::ReviewedBy),
+        vec![AUTHOR]
+    );
+    assert!(matches!(
+        &model.arguments,
+        Some(Arguments::CommitArguments(args)) if args.contains(&CommitArgument::Signoff)
+    ));
+}
+
+#[test]
+fn test_commit_passes_signoff_and_trailer_flags() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .write_file_content("file.txt", "content")
+        .stage_files(&["file.txt"]);
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arguments = Some(Arguments::CommitArguments(HashSet::from([
+        CommitArgument::Signoff,
+    ])));
+    let mut state = CommitPopupState::default();
+    state.toggle_trailer(
+        CommitTrailer::CoAuthoredBy,
+        "Jane <jane@example.com>".into(),
+    );
+    open_commit_popup(&mut model, state);
+
+    let result = update(&mut model, Message::Commit);
+
+    assert_eq!(
+        result,
+        Some(Message::CommitWithEditor(vec![
+            "--signoff".to_string(),
+            "--trailer=Co-authored-by: Jane <jane@example.com>".to_string(),
+        ]))
+    );
+}
+
+#[test]
+fn test_builtin_editor_commit_appends_trailers() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .write_file_content("file.txt", "content")
+        .stage_files(&["file.txt"]);
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.config.builtin_commit_editor = true;
+    model.arguments = Some(Arguments::CommitArguments(HashSet::from([
+        CommitArgument::Signoff,
+    ])));
+    let mut state = CommitPopupState::default();
+    state.toggle_trailer(
+        CommitTrailer::CoAuthoredBy,
+        "Jane <jane@example.com>".into(),
+    );
+    state.toggle_trailer(CommitTrailer::ReviewedBy, "Bob <bob@example.com>".into());
+    open_commit_popup(&mut model, state);
+
+    update(&mut model, Message::Commit);
+    for c in "Add file".chars() {
+        update(
+            &mut model,
+            Message::CommitEditor(CommitEditorMessage::Edit(EditOp::Insert(c))),
+        );
+    }
+    update(
+        &mut model,
+        Message::CommitEditor(CommitEditorMessage::Confirm),
+    );
+
+    let message = head_commit_message(test_repo.repo_path());
+    assert!(message.starts_with("Add file\n\n"), "{message}");
+    assert!(message.contains("Signed-off-by: Test User <test@example.com>"));
+    assert!(message.contains("Co-authored-by: Jane <jane@example.com>"));
+    assert!(message.contains("Reviewed-by: Bob <bob@example.com>"));
+}
+
+#[test]
+fn test_extend_adds_trailer_to_head_without_editing() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file.txt", "content", "Original message");
+    let mut model = create_model_from_test_repo(&test_repo);
+    let mut state = CommitPopupState::default();
+    state.toggle_trailer(CommitTrailer::ReviewedBy, "Bob <bob@example.com>".into());
+    open_commit_popup(&mut model, state);
+
+    let follow_up = update(&mut model, Message::Amend(vec!["--no-edit".to_string()]))
+        .expect("expected the amend message");
+    update(&mut model, follow_up);
+
+    assert_eq!(
+        head_commit_message(test_repo.repo_path()),
+        "Original message\n\nReviewed-by: Bob <bob@example.com>"
+    );
+}
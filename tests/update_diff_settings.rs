@@ -0,0 +1,88 @@
+use magi::{
+    git::test_repo::TestRepo,
+    model::{LineContent, Model, ViewMode},
+    msg::{DiffMessage, Message},
+};
+
+mod utils;
+use utils::{create_model_from_test_repo, find_commit_line, run};
+
+fn count_lines(model: &Model, predicate: impl Fn(&LineContent) -> bool) -> usize {
+    model
+        .ui_model
+        .lines
+        .iter()
+        .filter(|line| predicate(&line.content))
+        .count()
+}
+
+fn ten_lines() -> String {
+    (1..=10).map(|i| format!("line {}\n", i)).collect()
+}
+
+#[test]
+fn test_context_keys_change_status_hunks() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file.txt", &ten_lines(), "Initial");
+    test_repo.write_file_content("file.txt", &ten_lines().replace("line 5\n", "five\n"));
+    let mut model = create_model_from_test_repo(&test_repo);
+    let diff_lines = |model: &Model| count_lines(model, |c| matches!(c, LineContent::DiffLine(_)));
+    // 3 lines of context on each side
+    assert_eq!(diff_lines(&model), 8);
+
+    run(&mut model, Message::Diff(DiffMessage::MoreContext));
+    assert_eq!(model.git_info.diff_settings.context_lines, 4);
+    assert_eq!(diff_lines(&model), 10);
+
+    run(&mut model, Message::Diff(DiffMessage::DefaultContext));
+    for _ in 0..4 {
+        run(&mut model, Message::Diff(DiffMessage::LessContext));
+    }
+    // Never below zero
+    assert_eq!(model.git_info.diff_settings.context_lines, 0);
+    assert_eq!(diff_lines(&model), 2);
+}
+
+#[test]
+fn test_ignoring_whitespace_hides_whitespace_only_changes() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file.txt", "a\nb\n", "Initial");
+    test_repo.write_file_content("file.txt", "a \n  b\n");
+    let mut model = create_model_from_test_repo(&test_repo);
+    let hunks = |model: &Model| count_lines(model, |c| matches!(c, LineContent::DiffHunk(_)));
+    assert_eq!(hunks(&model), 1);
+
+    run(
+        &mut model,
+        Message::Diff(DiffMessage::ToggleIgnoreWhitespace),
+    );
+    assert!(model.git_info.diff_settings.ignore_whitespace);
+    assert_eq!(hunks(&model), 0);
+
+    run(
+        &mut model,
+        Message::Diff(DiffMessage::ToggleIgnoreWhitespace),
+    );
+    assert_eq!(hunks(&model), 1);
+}
+
+#[test]
+fn test_context_keys_regenerate_commit_preview() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("file.txt", &ten_lines(), "Initial")
+        .commit_file(
+            "file.txt",
+            &ten_lines().replace("line 5\n", "five\n"),
+            "Change",
+        );
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.ui_model.cursor_position = find_commit_line(&model).expect("No commit line found");
+    run(&mut model, Message::ShowPreview);
+    let before = model.ui_model.lines.len();
+
+    run(&mut model, Message::Diff(DiffMessage::MoreContext));
+
+    assert_eq!(model.view_mode, ViewMode::Preview);
+    assert_eq!(model.ui_model.lines.len(), before + 2);
+}
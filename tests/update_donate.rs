@@ -3,6 +3,7 @@ use magi::{
     git::test_repo::TestRepo,
     keys::handle_key,
     model::{
+        arguments::{ApplyArgument, Arguments, ValueArgument},
         popup::{ApplyPopupState, PopupContent, PopupContentCommand},
         select_popup::{OnSelect, SelectPopupState},
     },
@@ -172,6 +173,78 @@ fn test_donate_commit_to_target_branch() {
     assert!(!main_messages.contains("Commit to donate"));
 }
 
+#[test]
+fn test_donate_uses_apply_arguments_for_cherry_pick() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("base.txt", "base content", "Base commit");
+    test_repo.create_branch("target-branch");
+    test_repo.commit_file("donated.txt", "donated content", "Commit to donate");
+    let donate_hash = test_repo.head_hash();
+
+    let workdir = test_repo.repo.workdir().unwrap().to_path_buf();
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arguments = Some(Arguments::ApplyArguments(
+        [ApplyArgument::RecordOrigin, ApplyArgument::Signoff]
+            .into_iter()
+            .collect(),
+    ));
+
+    update(
+        &mut model,
+        Message::Donate {
+            commits: vec![donate_hash.clone()],
+            target: "target-branch".to_string(),
+        },
+    );
+
+    let target_log = git(&workdir, &["log", "-1", "--format=%B", "target-branch"]);
+    let message = String::from_utf8_lossy(&target_log.stdout).to_string();
+    assert!(
+        message.contains(&format!("(cherry picked from commit {donate_hash})")),
+        "unexpected message: {message}"
+    );
+    assert!(
+        message.contains("Signed-off-by:"),
+        "unexpected message: {message}"
+    );
+    assert!(model.arguments.is_none());
+}
+
+/// A remembered mainline only applies to merges; git rejects it for others
+#[test]
+fn test_donate_skips_remembered_mainline_for_non_merge_commits() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("base.txt", "base content", "Base commit");
+    test_repo.create_branch("target-branch");
+    test_repo.commit_file("donated.txt", "donated content", "Commit to donate");
+    let donate_hash = test_repo.head_hash();
+
+    let workdir = test_repo.repo.workdir().unwrap().to_path_buf();
+    let mut model = create_model_from_test_repo(&test_repo);
+    model
+        .argument_values
+        .insert(ValueArgument::Mainline, "1".to_string());
+
+    update(
+        &mut model,
+        Message::Donate {
+            commits: vec![donate_hash],
+            target: "target-branch".to_string(),
+        },
+    );
+
+    assert!(model.popup.is_none(), "unexpected popup: {:?}", model.popup);
+    let target_log = git(&workdir, &["log", "-1", "--format=%s", "target-branch"]);
+    assert_eq!(
+        String::from_utf8_lossy(&target_log.stdout).trim(),
+        "Commit to donate"
+    );
+    assert_eq!(
+        model.argument_values.get(&ValueArgument::Mainline),
+        Some(&"1".to_string())
+    );
+}
+
 // ── Donate execution — invalid commit shows error ─────────────────────────────
 
 #[test]
@@ -256,3 +329,39 @@ fn test_donate_target_branch_on_select_routes_to_donate_message() {
         })
     );
 }
+
+// ── Donate — --ff is dropped when the pick must create a new commit ───────────
+
+#[test]
+fn test_donate_ignores_ff_with_record_origin() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("base.txt", "base content", "Base commit");
+    test_repo.create_branch("target-branch");
+    test_repo.commit_file("donated.txt", "donated content", "Commit to donate");
+    let donate_hash = test_repo.head_hash();
+
+    let workdir = test_repo.repo.workdir().unwrap().to_path_buf();
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arguments = Some(Arguments::ApplyArguments(
+        [ApplyArgument::RecordOrigin, ApplyArgument::FastForward]
+            .into_iter()
+            .collect(),
+    ));
+
+    update(
+        &mut model,
+        Message::Donate {
+            commits: vec![donate_hash.clone()],
+            target: "target-branch".to_string(),
+        },
+    );
+
+    // git refuses --ff together with -x, so the pick only succeeds without it
+    assert!(model.popup.is_none(), "unexpected popup: {:?}", model.popup);
+    let target_log = git(&workdir, &["log", "-1", "--format=%B", "target-branch"]);
+    let message = String::from_utf8_lossy(&target_log.stdout).to_string();
+    assert!(
+        message.contains(&format!("(cherry picked from commit {donate_hash})")),
+        "unexpected message: {message}"
+    );
+}
@@ -0,0 +1,180 @@
+use magi::{
+    git::{git_cmd, test_repo::TestRepo},
+    model::popup::{CommitPopupState, InputContext, PopupContent, PopupContentCommand},
+    msg::{InputMessage, Message, update::update},
+};
+
+mod utils;
+use utils::create_model_from_test_repo;
+
+/// Returns `git log -1 --format=<format>` for HEAD.
+fn head_format(test_repo: &TestRepo, format: &str) -> String {
+    let output = git_cmd(
+        test_repo.repo_path(),
+        &["log", "-1", "--date=raw", &format!("--format={}", format)],
+    )
+    .output()
+    .unwrap();
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Commits with fixed author and committer dates far in the past, so
+/// rewriting them is easy to detect.
+fn commit_in_the_past(test_repo: &TestRepo, message: &str) {
+    let output = git_cmd(
+        test_repo.repo_path(),
+        &[
+            "commit",
+            "--allow-empty",
+            "-m",
+            message,
+            "--date=@1500000000 +0000",
+        ],
+    )
+    .env("GIT_COMMITTER_DATE", "@1500000000 +0000")
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+}
+
+fn open_commit_popup(model: &mut magi::model::Model) {
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Commit(
+        CommitPopupState::default(),
+    )));
+}
+
+#[test]
+fn test_extend_adds_staged_changes_keeping_message_and_dates() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .write_file_content("file.txt", "content")
+        .stage_files(&["file.txt"]);
+    commit_in_the_past(&test_repo, "Old commit");
+    test_repo
+        .write_file_content("other.txt", "more")
+        .stage_files(&["other.txt"]);
+    let mut model = create_model_from_test_repo(&test_repo);
+    open_commit_popup(&mut model);
+
+    let result = update(&mut model, Message::Extend);
+
+    assert_eq!(result, Some(Message::Refresh));
+    assert!(model.popup.is_none());
+    assert_eq!(head_format(&test_repo, "%s"), "Old commit");
+    assert_eq!(
+        head_format(&test_repo, "%ad|%cd"),
+        "1500000000 +0000|1500000000 +0000"
+    );
+    let files = git_cmd(
+        test_repo.repo_path(),
+        &["show", "--name-only", "--format=", "HEAD"],
+    )
+    .output()
+    .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&files.stdout).trim(),
+        "file.txt\nother.txt"
+    );
+}
+
+#[test]
+fn test_reshelve_input_is_prefilled_with_now() {
+    let test_repo = TestRepo::new();
+    let mut model = create_model_from_test_repo(&test_repo);
+    open_commit_popup(&mut model);
+
+    update(&mut model, Message::ShowReshelveInput);
+
+    match &model.popup {
+        Some(PopupContent::Input(state)) => {
+            assert_eq!(state.context, InputContext::Reshelve);
+            assert_eq!(state.input.as_str(), "now");
+        }
+        other => panic!("expected the reshelve input popup, got {:?}", other),
+    }
+
+    let result = update(&mut model, Message::Input(InputMessage::Confirm));
+    assert_eq!(result, Some(Message::Reshelve("now".to_string())));
+}
+
+#[test]
+fn test_reshelve_sets_author_and_committer_date() {
+    let test_repo = TestRepo::new();
+    commit_in_the_past(&test_repo, "Old commit");
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    let result = update(
+        &mut model,
+        Message::Reshelve("2021-06-01 12:00:00 +0200".to_string()),
+    );
+
+    assert_eq!(result, Some(Message::Refresh));
+    assert_eq!(head_format(&test_repo, "%s"), "Old commit");
+    assert_eq!(
+        head_format(&test_repo, "%ad|%cd"),
+        "1622541600 +0200|1622541600 +0200"
+    );
+}
+
+#[test]
+fn test_reshelve_to_now_moves_both_dates() {
+    let test_repo = TestRepo::new();
+    commit_in_the_past(&test_repo, "Old commit");
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    update(&mut model, Message::Reshelve("now".to_string()));
+
+    let dates = head_format(&test_repo, "%ad|%cd");
+    let (author, committer) = dates.split_once('|').unwrap();
+    assert_eq!(author, committer);
+    assert!(!author.starts_with("1500000000"), "{dates}");
+}
+
+#[test]
+fn test_reshelve_with_invalid_date_shows_error() {
+    let test_repo = TestRepo::new();
+    commit_in_the_past(&test_repo, "Old commit");
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    update(&mut model, Message::Reshelve("not a date".to_string()));
+
+    assert!(model.toast.is_some());
+    assert_eq!(
+        head_format(&test_repo, "%ad|%cd"),
+        "1500000000 +0000|1500000000 +0000"
+    );
+}
+
+#[test]
+fn test_reshelve_to_a_relative_date_moves_both_dates() {
+    let test_repo = TestRepo::new();
+    commit_in_the_past(&test_repo, "Old commit");
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    update(&mut model, Message::Reshelve("3 days ago".to_string()));
+
+    let dates = head_format(&test_repo, "%ad|%cd");
+    let (author, committer) = dates.split_once('|').unwrap();
+    assert_eq!(author, committer);
+    assert!(!author.starts_with("1500000000"), "{dates}");
+}
+
+// The hook is a shell script
+#[cfg(unix)]
+#[test]
+fn test_reshelve_runs_commit_hooks_once() {
+    let test_repo = TestRepo::new();
+    commit_in_the_past(&test_repo, "Old commit");
+    let hook = test_repo.repo.path().join("hooks/pre-commit");
+    std::fs::create_dir_all(hook.parent().unwrap()).unwrap();
+    std::fs::write(&hook, "#!/bin/sh\necho run >> .git/hook_runs\n").unwrap();
+    let mut permissions = std::fs::metadata(&hook).unwrap().permissions();
+    std::os::unix::fs::PermissionsExt::set_mode(&mut permissions, 0o755);
+    std::fs::set_permissions(&hook, permissions).unwrap();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    update(&mut model, Message::Reshelve("yesterday".to_string()));
+
+    let runs = std::fs::read_to_string(test_repo.repo.path().join("hook_runs")).unwrap();
+    assert_eq!(runs, "run\n");
+}
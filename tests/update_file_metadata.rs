@@ -0,0 +1,167 @@
+use magi::{
+    git::{git_cmd, test_repo::TestRepo},
+    model::{FileDetail, FileStatus, LineContent, SectionType},
+    msg::{DiscardSource, DiscardTarget, Message},
+};
+
+mod utils;
+use utils::{create_model_from_test_repo, find_line, find_staged_file_line, run};
+
+fn git(test_repo: &TestRepo, args: &[&str]) -> String {
+    let output = git_cmd(test_repo.repo_path(), args).output().unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+const CONTENT: &str = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\n17\n18\n19\n20\n";
+
+/// `old.txt` renamed to `new.txt` with its last line changed, both staged
+fn renamed_repo() -> TestRepo {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("old.txt", CONTENT, "Add old");
+    git(&test_repo, &["mv", "old.txt", "new.txt"]);
+    test_repo
+        .write_file_content("new.txt", &CONTENT.replace("20\n", "twenty\n"))
+        .stage_files(&["new.txt"]);
+    test_repo
+}
+
+#[test]
+fn test_staged_rename_shows_old_path_and_similarity() {
+    let test_repo = renamed_repo();
+    let model = create_model_from_test_repo(&test_repo);
+
+    let line = find_staged_file_line(&model, "new.txt").expect("Expected the renamed file");
+    assert!(matches!(
+        &model.ui_model.lines[line].content,
+        LineContent::StagedFile(fc) if fc.status == FileStatus::Renamed {
+            from: "old.txt".to_string(),
+            similarity: 87,
+        }
+    ));
+    assert!(find_staged_file_line(&model, "old.txt").is_none());
+}
+
+/// Renames are the ones git finds, even where libgit2 would pair the files
+#[test]
+fn test_staged_renames_are_the_ones_git_finds() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("old.txt", &format!("{}\nend\n", "x".repeat(31)), "Add old");
+    git(&test_repo, &["mv", "old.txt", "new.txt"]);
+    test_repo
+        .write_file_content("new.txt", &format!("{}\nend\n", "y".repeat(31)))
+        .stage_files(&["new.txt"]);
+    let model = create_model_from_test_repo(&test_repo);
+
+    let status = |path: &str| {
+        let line = find_staged_file_line(&model, path).expect("Expected the staged file");
+        match &model.ui_model.lines[line].content {
+            LineContent::StagedFile(fc) => fc.status.clone(),
+            _ => unreachable!(),
+        }
+    };
+    assert_eq!(status("new.txt"), FileStatus::New);
+    assert_eq!(status("old.txt"), FileStatus::Deleted);
+}
+
+#[test]
+fn test_unstaging_a_rename_unstages_both_paths() {
+    let test_repo = renamed_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    model.ui_model.cursor_position = find_staged_file_line(&model, "new.txt").unwrap();
+    run(&mut model, Message::UnstageSelected);
+
+    assert_eq!(git(&test_repo, &["diff", "--cached", "--name-only"]), "");
+}
+
+#[test]
+fn test_unstaging_a_hunk_of_a_rename_keeps_the_rename_staged() {
+    let test_repo = renamed_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    model.ui_model.cursor_position = find_line(&model, |content| {
+        matches!(content, LineContent::DiffHunk(_))
+    })
+    .unwrap();
+    run(&mut model, Message::UnstageSelected);
+
+    assert_eq!(
+        git(&test_repo, &["diff", "--cached", "-M", "--name-status"]),
+        "R100\told.txt\tnew.txt\n"
+    );
+    assert_eq!(git(&test_repo, &["show", ":new.txt"]), CONTENT);
+}
+
+#[test]
+fn test_discarding_a_staged_rename_moves_the_file_back() {
+    let test_repo = renamed_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    run(
+        &mut model,
+        Message::ConfirmDiscard(DiscardTarget::Files {
+            paths: vec!["new.txt".to_string()],
+            source: DiscardSource::Staged,
+        }),
+    );
+
+    assert_eq!(git(&test_repo, &["status", "--porcelain"]), "");
+    let content = std::fs::read_to_string(test_repo.repo_path().join("old.txt")).unwrap();
+    assert_eq!(content, CONTENT);
+}
+
+#[test]
+fn test_binary_file_is_listed_with_its_sizes() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("image.bin", "a\0b", "Add binary")
+        .write_file_content("image.bin", "a\0bcd");
+    let model = create_model_from_test_repo(&test_repo);
+
+    let line = find_line(&model, |content| {
+        matches!(content, LineContent::FileDetail(_))
+    })
+    .expect("Expected the binary file's detail");
+    assert!(matches!(
+        model.ui_model.lines[line].content,
+        LineContent::FileDetail(FileDetail::Binary {
+            old_size: 3,
+            new_size: 5,
+        })
+    ));
+    assert_eq!(
+        model.ui_model.lines[line].section,
+        Some(SectionType::UnstagedFile {
+            path: "image.bin".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_staging_from_a_mode_change_stages_the_file() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("run.sh", "echo hi\n", "Add script");
+    let path = test_repo.repo_path().join("run.sh");
+    let mut permissions = std::fs::metadata(&path).unwrap().permissions();
+    std::os::unix::fs::PermissionsExt::set_mode(&mut permissions, 0o755);
+    std::fs::set_permissions(&path, permissions).unwrap();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    model.ui_model.cursor_position = find_line(&model, |content| {
+        matches!(
+            content,
+            LineContent::FileDetail(FileDetail::ModeChange {
+                old_mode: 0o100644,
+                new_mode: 0o100755,
+            })
+        )
+    })
+    .expect("Expected the mode change");
+    run(&mut model, Message::StageSelected);
+
+    assert_eq!(
+        git(&test_repo, &["diff", "--cached", "--summary"]),
+        " mode change 100644 => 100755 run.sh\n"
+    );
+}
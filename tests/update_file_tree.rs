@@ -0,0 +1,199 @@
+use magi::{
+    git::{git_cmd, test_repo::TestRepo},
+    model::{LineContent, Model, SectionType, popup::ConfirmAction},
+    msg::{DiscardTarget, Message},
+};
+
+mod utils;
+use utils::{
+    create_model_from_test_repo, expect_confirm_popup, find_line, find_staged_file_line,
+    find_unstaged_file_line, find_untracked_file_line, run,
+};
+
+fn staged_files(test_repo: &TestRepo) -> Vec<String> {
+    let output = git_cmd(test_repo.repo_path(), &["diff", "--cached", "--name-only"])
+        .output()
+        .unwrap();
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+/// Changes to `src/a.rs` and `src/b.rs`, and `README.md`; `src/new.rs` is untracked
+fn tree_repo() -> TestRepo {
+    let test_repo = TestRepo::new();
+    std::fs::create_dir(test_repo.repo_path().join("src")).unwrap();
+    test_repo
+        .write_file_content("src/a.rs", "a\n")
+        .write_file_content("src/b.rs", "b\n")
+        .write_file_content("README.md", "readme\n")
+        .stage_files(&["src/a.rs", "src/b.rs", "README.md"])
+        .commit("Initial")
+        .write_file_content("src/a.rs", "a2\n")
+        .write_file_content("src/b.rs", "b2\n")
+        .write_file_content("README.md", "readme2\n")
+        .write_file_content("src/new.rs", "new\n");
+    test_repo
+}
+
+fn tree_model(test_repo: &TestRepo) -> Model {
+    let mut model = create_model_from_test_repo(test_repo);
+    model.git_info.file_tree = true;
+    run(&mut model, Message::Refresh);
+    model
+}
+
+fn find_directory(model: &Model, section: &SectionType) -> usize {
+    model
+        .ui_model
+        .lines
+        .iter()
+        .position(|line| {
+            matches!(line.content, LineContent::Directory { .. })
+                && line.section.as_ref() == Some(section)
+        })
+        .expect("Expected the directory in the view")
+}
+
+fn unstaged_src() -> SectionType {
+    SectionType::UnstagedDirectory {
+        path: "src/".to_string(),
+    }
+}
+
+#[test]
+fn test_files_are_listed_below_their_directories() {
+    let test_repo = tree_repo();
+    let model = tree_model(&test_repo);
+
+    let src = find_directory(&model, &unstaged_src());
+    assert!(matches!(
+        &model.ui_model.lines[src].content,
+        LineContent::Directory { name, depth: 0, count: 2 } if name == "src/"
+    ));
+    assert!(find_unstaged_file_line(&model, "src/a.rs").unwrap() > src);
+    find_directory(
+        &model,
+        &SectionType::UntrackedDirectory {
+            path: "src/".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_collapsing_a_directory_hides_its_files() {
+    let test_repo = tree_repo();
+    let mut model = tree_model(&test_repo);
+
+    model.ui_model.cursor_position = find_directory(&model, &unstaged_src());
+    run(&mut model, Message::ToggleSection);
+
+    let collapsed = &model.ui_model.collapsed_sections;
+    let a = find_unstaged_file_line(&model, "src/a.rs").unwrap();
+    let readme = find_unstaged_file_line(&model, "README.md").unwrap();
+    assert!(model.ui_model.lines[a].is_hidden(collapsed));
+    assert!(!model.ui_model.lines[readme].is_hidden(collapsed));
+    assert!(!model.ui_model.lines[model.ui_model.cursor_position].is_hidden(collapsed));
+}
+
+#[test]
+fn test_collapsed_directory_stays_collapsed_across_refresh() {
+    let test_repo = tree_repo();
+    let mut model = tree_model(&test_repo);
+    model.ui_model.cursor_position = find_directory(&model, &unstaged_src());
+    run(&mut model, Message::ToggleSection);
+
+    test_repo.write_file_content("src/c.rs", "c\n");
+    run(&mut model, Message::Refresh);
+
+    assert!(model.ui_model.collapsed_sections.contains(&unstaged_src()));
+}
+
+#[test]
+fn test_collapsing_a_directory_leaves_it_expanded_in_other_sections() {
+    let test_repo = tree_repo();
+    let mut model = tree_model(&test_repo);
+    model.ui_model.cursor_position = find_directory(&model, &unstaged_src());
+    run(&mut model, Message::ToggleSection);
+
+    run(&mut model, Message::Refresh);
+
+    let collapsed = &model.ui_model.collapsed_sections;
+    assert!(collapsed.contains(&unstaged_src()));
+    assert!(!collapsed.contains(&SectionType::UntrackedDirectory {
+        path: "src/".to_string(),
+    }));
+    let new = find_untracked_file_line(&model, "src/new.rs").unwrap();
+    assert!(!model.ui_model.lines[new].is_hidden(collapsed));
+}
+
+#[test]
+fn test_staging_a_directory_stages_everything_below_it() {
+    let test_repo = tree_repo();
+    let mut model = tree_model(&test_repo);
+
+    model.ui_model.cursor_position = find_directory(&model, &unstaged_src());
+    run(&mut model, Message::StageSelected);
+
+    assert_eq!(staged_files(&test_repo), vec!["src/a.rs", "src/b.rs"]);
+    assert!(find_staged_file_line(&model, "src/a.rs").is_some());
+    find_directory(
+        &model,
+        &SectionType::StagedDirectory {
+            path: "src/".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_staging_an_untracked_directory_stages_its_files() {
+    let test_repo = tree_repo();
+    let mut model = tree_model(&test_repo);
+
+    model.ui_model.cursor_position = find_directory(
+        &model,
+        &SectionType::UntrackedDirectory {
+            path: "src/".to_string(),
+        },
+    );
+    run(&mut model, Message::StageSelected);
+
+    assert_eq!(staged_files(&test_repo), vec!["src/new.rs"]);
+    assert!(find_untracked_file_line(&model, "src/new.rs").is_none());
+}
+
+#[test]
+fn test_unstaging_a_directory_unstages_everything_below_it() {
+    let test_repo = tree_repo();
+    test_repo.stage_files(&["src/a.rs", "src/b.rs", "README.md"]);
+    let mut model = tree_model(&test_repo);
+
+    model.ui_model.cursor_position = find_directory(
+        &model,
+        &SectionType::StagedDirectory {
+            path: "src/".to_string(),
+        },
+    );
+    run(&mut model, Message::UnstageSelected);
+
+    assert_eq!(staged_files(&test_repo), vec!["README.md"]);
+}
+
+#[test]
+fn test_discarding_a_directory_asks_for_everything_below_it() {
+    let test_repo = tree_repo();
+    let mut model = tree_model(&test_repo);
+
+    model.ui_model.cursor_position = find_directory(&model, &unstaged_src());
+    run(&mut model, Message::DiscardSelected);
+
+    let popup = expect_confirm_popup(&model);
+    assert!(matches!(
+        &popup.on_confirm,
+        ConfirmAction::DiscardChanges(DiscardTarget::Files { paths, .. })
+            if paths == &vec!["src/a.rs".to_string(), "src/b.rs".to_string()]
+    ));
+    assert!(find_line(&model, |c| matches!(c, LineContent::Directory { .. })).is_some());
+}
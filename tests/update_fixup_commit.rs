@@ -1,7 +1,7 @@
 use magi::{
     git::{log::get_log_entries, test_repo::TestRepo},
     model::{Toast, ViewMode, popup::PopupContent, select_popup::OnSelect},
-    msg::{CommitSelect, FixupType, LogType, Message, update::update},
+    msg::{CommitSelect, FixupType, LogType, Message, RebaseCommand, update::update},
 };
 
 mod utils;
@@ -10,7 +10,7 @@ use utils::create_model_from_test_repo;
 /// Helper to get log entries for testing (filters out graph-only entries)
 fn get_log_entries_for_test(test_repo: &TestRepo) -> Vec<magi::model::LogEntry> {
     let repo = git2::Repository::open(test_repo.repo_path()).unwrap();
-    let mut entries = get_log_entries(&repo, &LogType::Current, true, false).unwrap();
+    let mut entries = get_log_entries(&repo, &LogType::Current, true, false, false).unwrap();
     entries.retain(|e| e.is_commit());
     entries
 }
@@ -296,3 +296,73 @@ fn test_squash_commit_extracts_hash_from_selection() {
         Some("squash! First commit")
     );
 }
+
+#[test]
+fn test_instant_fixup_autosquashes_into_target() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+    let target = test_repo.head_hash();
+    test_repo.commit_file("file2.txt", "content2", "Second commit");
+
+    test_repo
+        .write_file_content("file1.txt", "modified content")
+        .stage_files(&["file1.txt"]);
+
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    let result = update(
+        &mut model,
+        Message::FixupCommit(target.clone(), FixupType::InstantFixup),
+    );
+
+    assert_eq!(
+        result,
+        Some(Message::Rebase(RebaseCommand::AutosquashInto(target)))
+    );
+    update(&mut model, result.unwrap());
+
+    let messages: Vec<_> = get_log_entries_for_test(&test_repo)
+        .into_iter()
+        .map(|e| e.message.unwrap())
+        .collect();
+    assert_eq!(
+        messages,
+        vec!["Second commit", "First commit", "Initial commit"]
+    );
+    let content = git2::Repository::open(test_repo.repo_path())
+        .unwrap()
+        .revparse_single("HEAD~1:file1.txt")
+        .unwrap()
+        .peel_to_blob()
+        .unwrap()
+        .content()
+        .to_vec();
+    assert_eq!(content, b"modified content");
+}
+
+#[test]
+fn test_instant_squash_creates_squash_then_autosquashes() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+    let target = test_repo.head_hash();
+
+    test_repo
+        .write_file_content("file1.txt", "modified content")
+        .stage_files(&["file1.txt"]);
+
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    let result = update(
+        &mut model,
+        Message::FixupCommit(target.clone(), FixupType::InstantSquash),
+    );
+
+    assert_eq!(
+        result,
+        Some(Message::Rebase(RebaseCommand::AutosquashInto(target)))
+    );
+    assert_eq!(
+        get_log_entries_for_test(&test_repo)[0].message.as_deref(),
+        Some("squash! First commit")
+    );
+}
@@ -0,0 +1,221 @@
+use magi::{
+    git::test_repo::TestRepo,
+    model::{
+        Model, ViewMode,
+        hunk_review::ReviewDecision,
+        popup::{ConfirmAction, ConfirmPopupState, PopupContent},
+    },
+    msg::{HunkReviewMessage, Message},
+};
+
+mod utils;
+use utils::{create_model_from_test_repo, index_content, run};
+
+fn review(model: &mut Model, msg: HunkReviewMessage) {
+    run(model, Message::HunkReview(msg));
+}
+
+fn worktree_content(test_repo: &TestRepo, path: &str) -> String {
+    std::fs::read_to_string(test_repo.repo_path().join(path)).unwrap()
+}
+
+fn numbered_lines(range: std::ops::RangeInclusive<usize>) -> String {
+    range.map(|n| format!("{}\n", n)).collect()
+}
+
+/// `a.txt` has two hunks (lines 2 and 19 changed), `b.txt` one
+fn three_hunk_repo() -> TestRepo {
+    let test_repo = TestRepo::new();
+    let a = numbered_lines(1..=20);
+    test_repo
+        .write_file_content("a.txt", &a)
+        .write_file_content("b.txt", "b\n")
+        .stage_files(&["a.txt", "b.txt"])
+        .commit("Initial")
+        .write_file_content(
+            "a.txt",
+            &a.replace("\n2\n", "\ntwo\n")
+                .replace("\n19\n", "\nnineteen\n"),
+        )
+        .write_file_content("b.txt", "bee\n");
+    test_repo
+}
+
+fn current(model: &Model) -> (usize, usize, (usize, usize)) {
+    let state = model.hunk_review.as_ref().expect("review in progress");
+    (state.current, state.hunks.len(), state.file_progress())
+}
+
+#[test]
+fn test_review_walks_hunks_and_applies_decisions() {
+    let test_repo = three_hunk_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    review(&mut model, HunkReviewMessage::Start { staged: false });
+    assert_eq!(model.view_mode, ViewMode::HunkReview);
+    assert_eq!(current(&model), (0, 3, (1, 2)));
+
+    review(&mut model, HunkReviewMessage::Accept);
+    review(&mut model, HunkReviewMessage::Skip);
+    assert_eq!(current(&model), (2, 3, (2, 2)));
+    // Nothing is applied before the review finishes
+    assert_eq!(index_content(&test_repo, "a.txt"), numbered_lines(1..=20));
+
+    review(&mut model, HunkReviewMessage::Discard);
+
+    // Discarding asks first; nothing is applied until confirmed
+    assert!(matches!(
+        model.popup,
+        Some(PopupContent::Confirm(ConfirmPopupState {
+            on_confirm: ConfirmAction::ApplyHunkReview,
+            ..
+        }))
+    ));
+    assert_eq!(index_content(&test_repo, "a.txt"), numbered_lines(1..=20));
+    review(&mut model, HunkReviewMessage::Apply);
+
+    assert!(model.popup.is_none());
+    assert_eq!(model.view_mode, ViewMode::Status);
+    assert!(model.hunk_review.is_none());
+    assert_eq!(
+        index_content(&test_repo, "a.txt"),
+        numbered_lines(1..=20).replace("\n2\n", "\ntwo\n")
+    );
+    assert!(worktree_content(&test_repo, "a.txt").contains("nineteen"));
+    assert_eq!(worktree_content(&test_repo, "b.txt"), "b\n");
+    assert_eq!(
+        model.toast.as_ref().map(|toast| toast.message.as_str()),
+        Some("Review finished: 1 staged, 1 discarded")
+    );
+}
+
+#[test]
+fn test_abort_drops_the_decisions() {
+    let test_repo = three_hunk_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    review(&mut model, HunkReviewMessage::Start { staged: false });
+    review(&mut model, HunkReviewMessage::Accept);
+    review(&mut model, HunkReviewMessage::Discard);
+    review(&mut model, HunkReviewMessage::Abort);
+
+    assert_eq!(model.view_mode, ViewMode::Status);
+    assert!(model.hunk_review.is_none());
+    assert_eq!(index_content(&test_repo, "a.txt"), numbered_lines(1..=20));
+    assert!(worktree_content(&test_repo, "a.txt").contains("nineteen"));
+}
+
+#[test]
+fn test_going_back_reconsiders_a_decision() {
+    let test_repo = three_hunk_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    review(&mut model, HunkReviewMessage::Start { staged: false });
+    review(&mut model, HunkReviewMessage::Accept);
+    review(&mut model, HunkReviewMessage::Previous);
+    assert_eq!(
+        model.hunk_review.as_ref().unwrap().hunks[0].decision,
+        Some(ReviewDecision::Accept)
+    );
+    review(&mut model, HunkReviewMessage::Skip);
+    review(&mut model, HunkReviewMessage::Finish);
+
+    assert_eq!(model.view_mode, ViewMode::Status);
+    assert_eq!(index_content(&test_repo, "a.txt"), numbered_lines(1..=20));
+}
+
+#[test]
+fn test_split_blocks_are_decided_on_their_own() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("file.txt", &numbered_lines(1..=8), "Initial")
+        .write_file_content("file.txt", "1\ntwo\n3\n4\n5\nsix\n7\n8\n");
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    review(&mut model, HunkReviewMessage::Start { staged: false });
+    review(&mut model, HunkReviewMessage::Split);
+    assert_eq!(current(&model), (0, 2, (1, 1)));
+    review(&mut model, HunkReviewMessage::Skip);
+    review(&mut model, HunkReviewMessage::Accept);
+
+    assert_eq!(
+        index_content(&test_repo, "file.txt"),
+        "1\n2\n3\n4\n5\nsix\n7\n8\n"
+    );
+}
+
+#[test]
+fn test_reviewing_staged_hunks_unstages_accepted_ones() {
+    let test_repo = three_hunk_repo();
+    test_repo.stage_files(&["a.txt", "b.txt"]);
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    review(&mut model, HunkReviewMessage::Start { staged: true });
+    assert_eq!(current(&model), (0, 3, (1, 2)));
+    review(&mut model, HunkReviewMessage::Skip);
+    review(&mut model, HunkReviewMessage::Skip);
+    // Discarding is only offered for unstaged hunks
+    review(&mut model, HunkReviewMessage::Discard);
+    assert_eq!(current(&model), (2, 3, (2, 2)));
+    review(&mut model, HunkReviewMessage::Accept);
+
+    assert_eq!(index_content(&test_repo, "b.txt"), "b\n");
+    assert!(index_content(&test_repo, "a.txt").contains("nineteen"));
+    assert_eq!(worktree_content(&test_repo, "b.txt"), "bee\n");
+}
+
+/// The editor of the edit tests, which run in parallel
+const EDITOR: &str = "sed -i -e 's/^+2$/+TWO/' -e 's/^+six$/+SIX/'";
+
+#[test]
+fn test_edited_hunk_is_staged_when_the_review_finishes() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("file.txt", "one\ntwo\n", "Initial")
+        .write_file_content("file.txt", "one\n2\n");
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    unsafe { std::env::set_var("GIT_EDITOR", EDITOR) };
+    review(&mut model, HunkReviewMessage::Start { staged: false });
+    review(&mut model, HunkReviewMessage::Edit);
+
+    assert_eq!(model.view_mode, ViewMode::Status);
+    assert_eq!(index_content(&test_repo, "file.txt"), "one\nTWO\n");
+    assert_eq!(worktree_content(&test_repo, "file.txt"), "one\n2\n");
+}
+
+#[test]
+fn test_accepted_and_edited_blocks_of_a_split_hunk_are_both_staged() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("file.txt", &numbered_lines(1..=8), "Initial")
+        .write_file_content("file.txt", "1\ntwo\n3\n4\n5\nsix\n7\n8\n");
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    unsafe { std::env::set_var("GIT_EDITOR", EDITOR) };
+    review(&mut model, HunkReviewMessage::Start { staged: false });
+    review(&mut model, HunkReviewMessage::Split);
+    review(&mut model, HunkReviewMessage::Accept);
+    review(&mut model, HunkReviewMessage::Edit);
+
+    assert!(model.popup.is_none(), "unexpected popup: {:?}", model.popup);
+    assert_eq!(
+        index_content(&test_repo, "file.txt"),
+        "1\ntwo\n3\n4\n5\nSIX\n7\n8\n"
+    );
+}
+
+#[test]
+fn test_review_without_hunks_stays_in_status() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file.txt", "one\n", "Initial");
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    review(&mut model, HunkReviewMessage::Start { staged: false });
+
+    assert_eq!(model.view_mode, ViewMode::Status);
+    assert_eq!(
+        model.toast.as_ref().map(|toast| toast.message.as_str()),
+        Some("No unstaged hunks to review")
+    );
+}
@@ -0,0 +1,116 @@
+use magi::{
+    git::test_repo::TestRepo,
+    model::{FileStatus, LineContent, Model, SectionType},
+    msg::Message,
+};
+
+mod utils;
+use utils::{
+    create_model_from_test_repo, find_line, find_staged_file_line, find_unstaged_file_line,
+    find_untracked_file_line, index_content, run,
+};
+
+fn find_diff_line(model: &Model, content: &str) -> usize {
+    find_line(
+        model,
+        |c| matches!(c, LineContent::DiffLine(line) if line.content == content),
+    )
+    .expect("Expected the diff line in the view")
+}
+
+fn untracked_repo() -> TestRepo {
+    let test_repo = TestRepo::new();
+    test_repo.write_file_content("new.txt", "a\nb\nc\n");
+    test_repo
+}
+
+#[test]
+fn test_untracked_file_preview_expands_inline() {
+    let test_repo = untracked_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+    let preview = SectionType::UntrackedFile {
+        path: "new.txt".to_string(),
+    };
+    assert!(model.ui_model.collapsed_sections.contains(&preview));
+    // A collapsed file isn't read
+    assert!(find_line(&model, |c| matches!(c, LineContent::DiffLine(_))).is_none());
+
+    model.ui_model.cursor_position = find_untracked_file_line(&model, "new.txt").unwrap();
+    run(&mut model, Message::ToggleSection);
+
+    assert!(!model.ui_model.collapsed_sections.contains(&preview));
+    let b = find_diff_line(&model, "b");
+    assert!(!model.ui_model.lines[b].is_hidden(&model.ui_model.collapsed_sections));
+
+    // Collapsing it again keeps it collapsed across refreshes
+    run(&mut model, Message::ToggleSection);
+    run(&mut model, Message::Refresh);
+    assert!(model.ui_model.collapsed_sections.contains(&preview));
+    assert!(find_line(&model, |c| matches!(c, LineContent::DiffLine(_))).is_none());
+}
+
+#[test]
+fn test_staging_from_preview_stages_the_file() {
+    let test_repo = untracked_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.ui_model.cursor_position = find_untracked_file_line(&model, "new.txt").unwrap();
+    run(&mut model, Message::ToggleSection);
+
+    model.ui_model.cursor_position = find_diff_line(&model, "b");
+    run(&mut model, Message::StageSelected);
+
+    assert_eq!(index_content(&test_repo, "new.txt"), "a\nb\nc\n");
+}
+
+#[test]
+fn test_intent_to_add_shows_content_as_unstaged_changes() {
+    let test_repo = untracked_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    model.ui_model.cursor_position = find_untracked_file_line(&model, "new.txt").unwrap();
+    run(&mut model, Message::IntentToAddSelected);
+
+    assert!(find_untracked_file_line(&model, "new.txt").is_none());
+    let file = find_unstaged_file_line(&model, "new.txt").unwrap();
+    assert!(matches!(
+        &model.ui_model.lines[file].content,
+        LineContent::UnstagedFile(fc) if fc.status == FileStatus::New
+    ));
+    assert!(find_staged_file_line(&model, "new.txt").is_none());
+    assert!(model.ui_model.lines.iter().any(|line| matches!(
+        &line.section,
+        Some(SectionType::UnstagedHunk { path, .. }) if path == "new.txt"
+    )));
+    // Nothing of the file's content is staged yet
+    assert_eq!(index_content(&test_repo, "new.txt"), "");
+}
+
+#[test]
+fn test_lines_of_intent_to_add_file_can_be_staged() {
+    let test_repo = untracked_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.ui_model.cursor_position = find_untracked_file_line(&model, "new.txt").unwrap();
+    run(&mut model, Message::IntentToAddSelected);
+    model.ui_model.cursor_position = find_unstaged_file_line(&model, "new.txt").unwrap();
+    run(&mut model, Message::ToggleSection);
+
+    let b = find_diff_line(&model, "b");
+    model.ui_model.visual_mode_anchor = Some(b);
+    model.ui_model.cursor_position = b;
+    run(&mut model, Message::StageSelected);
+
+    assert_eq!(index_content(&test_repo, "new.txt"), "b\n");
+}
+
+#[test]
+fn test_intent_to_add_ignores_tracked_files() {
+    let test_repo = untracked_repo();
+    test_repo.write_file_content("initial_file.txt", "changed");
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    model.ui_model.cursor_position = find_unstaged_file_line(&model, "initial_file.txt").unwrap();
+    run(&mut model, Message::IntentToAddSelected);
+
+    assert!(model.toast.is_some());
+    assert!(find_untracked_file_line(&model, "new.txt").is_some());
+}
@@ -4,6 +4,7 @@ use magi::{
     keys::handle_key,
     model::{
         LineContent, ToastStyle, ViewMode,
+        arguments::{Argument, Arguments, MergeArgument, ValueArgument},
         popup::{MergePopupState, PopupContent, PopupContentCommand},
         select_popup::OnSelect,
     },
@@ -11,7 +12,9 @@ use magi::{
 };
 
 mod utils;
-use utils::{create_model_from_test_repo, expect_error_popup, expect_select_popup, find_line, key};
+use utils::{
+    create_model_from_test_repo, expect_error_popup, expect_select_popup, find_line, key, shift_key,
+};
 
 // ── ShowMergePopup — key binding ───────────────────────────────────────────────
 
@@ -266,6 +269,103 @@ fn test_q_dismisses_merge_popup_in_progress() {
     assert_eq!(result, Some(Message::DismissPopup));
 }
 
+// ── Merge popup keys — arg mode ────────────────────────────────────────────────
+
+#[test]
+fn test_merge_arguments_in_arg_mode() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Merge(
+        MergePopupState { in_progress: false },
+    )));
+    model.arg_mode = true;
+
+    assert_eq!(
+        handle_key(key(KeyCode::Char('f')), &model),
+        Some(Message::ToggleArgument(Argument::Merge(
+            MergeArgument::FfOnly
+        )))
+    );
+    assert_eq!(
+        handle_key(key(KeyCode::Char('n')), &model),
+        Some(Message::ToggleArgument(Argument::Merge(
+            MergeArgument::NoFf
+        )))
+    );
+    assert_eq!(
+        handle_key(key(KeyCode::Char('s')), &model),
+        Some(Message::ShowArgumentValueInput(ValueArgument::Strategy))
+    );
+    assert_eq!(
+        handle_key(shift_key(KeyCode::Char('X')), &model),
+        Some(Message::ShowArgumentValueInput(
+            ValueArgument::StrategyOption
+        ))
+    );
+    assert_eq!(
+        handle_key(key(KeyCode::Char('z')), &model),
+        Some(Message::ExitArgMode)
+    );
+}
+
+#[test]
+fn test_toggle_merge_argument_updates_arguments() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Merge(
+        MergePopupState { in_progress: false },
+    )));
+    model.arg_mode = true;
+
+    update(
+        &mut model,
+        Message::ToggleArgument(Argument::Merge(MergeArgument::NoFf)),
+    );
+
+    let args = model
+        .arguments
+        .as_ref()
+        .and_then(|a| a.merge())
+        .expect("Expected merge arguments");
+    assert!(args.contains(&MergeArgument::NoFf));
+    assert!(!model.arg_mode);
+}
+
+#[test]
+fn test_toggle_merge_fast_forward_arguments_are_mutually_exclusive() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Merge(
+        MergePopupState { in_progress: false },
+    )));
+
+    for arg in [
+        MergeArgument::Signoff,
+        MergeArgument::NoFf,
+        MergeArgument::FfOnly,
+    ] {
+        update(&mut model, Message::ToggleArgument(Argument::Merge(arg)));
+    }
+    let args = model.arguments.as_ref().and_then(|a| a.merge()).unwrap();
+    assert!(args.contains(&MergeArgument::FfOnly));
+    assert!(!args.contains(&MergeArgument::NoFf));
+    assert!(args.contains(&MergeArgument::Signoff));
+
+    update(
+        &mut model,
+        Message::ToggleArgument(Argument::Merge(MergeArgument::NoFf)),
+    );
+    let args = model.arguments.as_ref().and_then(|a| a.merge()).unwrap();
+    assert!(args.contains(&MergeArgument::NoFf));
+    assert!(!args.contains(&MergeArgument::FfOnly));
+}
+
 // ── Cursor-line suggestion for MergeElsewhere ─────────────────────────────────
 
 #[test]
@@ -489,6 +589,69 @@ fn test_merge_branch_message_creates_merge_commit() {
     assert!(test_repo.repo_path().join("feature.txt").exists());
 }
 
+#[test]
+fn test_merge_branch_with_no_ff_creates_merge_commit_for_fast_forward() {
+    let test_repo = TestRepo::new();
+    disable_editor(&test_repo);
+    test_repo.commit_file("base.txt", "base\n", "Base commit");
+    test_repo.create_branch("feature");
+    assert!(
+        git_cmd(test_repo.repo_path(), &["checkout", "feature"])
+            .output()
+            .unwrap()
+            .status
+            .success()
+    );
+    test_repo.commit_file("feature.txt", "feature\n", "Feature commit");
+    assert!(
+        git_cmd(test_repo.repo_path(), &["checkout", "main"])
+            .output()
+            .unwrap()
+            .status
+            .success()
+    );
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arguments = Some(Arguments::MergeArguments(
+        [MergeArgument::NoFf].into_iter().collect(),
+    ));
+
+    update(
+        &mut model,
+        Message::Merge(MergeCommand::Branch("feature".to_string())),
+    );
+
+    let head = test_repo.repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.parent_count(), 2);
+    assert!(model.arguments.is_none());
+}
+
+#[test]
+fn test_merge_branch_with_ff_only_refuses_divergent_branches() {
+    let test_repo = TestRepo::new();
+    disable_editor(&test_repo);
+    setup_divergent_branches(
+        &test_repo,
+        ("main.txt", "main content\n"),
+        ("feature.txt", "feature content\n"),
+    );
+    let head_before = test_repo.head_hash();
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arguments = Some(Arguments::MergeArguments(
+        [MergeArgument::FfOnly].into_iter().collect(),
+    ));
+
+    update(
+        &mut model,
+        Message::Merge(MergeCommand::Branch("feature".to_string())),
+    );
+
+    assert_eq!(test_repo.head_hash(), head_before);
+    let toast = model.toast.expect("Expected a toast after merging");
+    assert_eq!(toast.style, ToastStyle::Warning);
+}
+
 // ── MergeCommand::EditMessage — execution ─────────────────────────────────────
 
 #[test]
@@ -539,6 +702,44 @@ fn test_merge_edit_message_creates_merge_commit_even_when_fast_forward() {
     assert!(test_repo.repo_path().join("feature.txt").exists());
 }
 
+#[test]
+fn test_merge_edit_message_drops_ff_only_argument() {
+    let test_repo = TestRepo::new();
+    disable_editor(&test_repo);
+    test_repo.commit_file("base.txt", "base\n", "Base commit");
+    assert!(
+        git_cmd(test_repo.repo_path(), &["checkout", "-b", "feature"])
+            .output()
+            .unwrap()
+            .status
+            .success()
+    );
+    test_repo.commit_file("feature.txt", "feature content\n", "Feature commit");
+    assert!(
+        git_cmd(test_repo.repo_path(), &["checkout", "main"])
+            .output()
+            .unwrap()
+            .status
+            .success()
+    );
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arguments = Some(Arguments::MergeArguments(
+        [MergeArgument::FfOnly].into_iter().collect(),
+    ));
+
+    update(
+        &mut model,
+        Message::Merge(MergeCommand::EditMessage("feature".to_string())),
+    );
+
+    // git rejects --ff-only with --no-ff, so it must not reach the command.
+    let toast = model.toast.expect("Expected a toast after merging");
+    assert_eq!(toast.style, ToastStyle::Success, "{}", toast.message);
+    let head = test_repo.repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.parent_count(), 2);
+}
+
 #[test]
 fn test_merge_edit_message_with_conflicts_shows_conflict_dialog() {
     let test_repo = TestRepo::new();
@@ -693,6 +894,34 @@ fn test_absorb_creates_merge_commit_and_deletes_branch() {
     );
 }
 
+#[test]
+fn test_absorb_with_strategy_option_resolves_conflicts() {
+    let test_repo = TestRepo::new();
+    // Both branches modify the same file: `-X theirs` takes the feature side.
+    setup_divergent_branches(
+        &test_repo,
+        ("shared.txt", "main version\n"),
+        ("shared.txt", "feature version\n"),
+    );
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model
+        .argument_values
+        .insert(ValueArgument::StrategyOption, "theirs".to_string());
+
+    update(
+        &mut model,
+        Message::Merge(MergeCommand::Absorb("feature".to_string())),
+    );
+
+    assert!(model.popup.is_none(), "unexpected popup: {:?}", model.popup);
+    assert_eq!(
+        std::fs::read_to_string(test_repo.repo_path().join("shared.txt")).unwrap(),
+        "feature version\n"
+    );
+    assert!(model.argument_values.is_empty());
+}
+
 #[test]
 fn test_absorb_with_conflicts_shows_conflict_dialog_and_keeps_branch() {
     let test_repo = TestRepo::new();
@@ -1036,6 +1265,34 @@ fn test_squash_merge_stages_changes_without_committing() {
     );
 }
 
+#[test]
+fn test_squash_merge_ignores_no_ff_argument() {
+    let test_repo = TestRepo::new();
+    setup_divergent_branches(
+        &test_repo,
+        ("main.txt", "main content\n"),
+        ("feature.txt", "feature content\n"),
+    );
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arguments = Some(Arguments::MergeArguments(
+        [MergeArgument::NoFf].into_iter().collect(),
+    ));
+
+    update(
+        &mut model,
+        Message::Merge(MergeCommand::Squash("feature".to_string())),
+    );
+
+    // git rejects `--squash --no-ff`, so the squash only succeeds if the
+    // flag was dropped
+    wait_for(
+        || test_repo.repo.path().join("SQUASH_MSG").exists(),
+        "SQUASH_MSG to appear",
+    );
+    assert!(test_repo.repo_path().join("feature.txt").exists());
+}
+
 #[test]
 fn test_squash_merge_fast_forward_still_stops_before_committing() {
     let test_repo = TestRepo::new();
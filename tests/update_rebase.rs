@@ -2,26 +2,27 @@ use crossterm::event::KeyCode;
 use magi::{
     git::{
         log::get_log_entries,
-        rebase::{RebaseAction, rebase_in_progress},
+        rebase::{RebaseAction, read_rebase_todo, rebase_in_progress, run_modify_commit},
         test_repo::TestRepo,
     },
     keys::handle_key,
     model::{
-        Line, LineContent, Model, SectionType, ViewMode,
-        arguments::{Argument, RebaseArgument},
-        popup::{ConfirmAction, PopupContent, PopupContentCommand, RebasePopupState},
+        EditOp, Line, LineContent, Model, SectionType, ViewMode,
+        arguments::{Argument, Arguments, RebaseArgument, ValueArgument},
+        popup::{ConfirmAction, InputContext, PopupContent, PopupContentCommand, RebasePopupState},
         select_popup::{OnSelect, SelectPopupState},
     },
     msg::{
-        CommitSelect, LogType, Message, OptionsSource, RebaseCommand, RebaseTodoMessage,
-        SearchMessage, SelectMessage, ShowSelectPopupConfig, update::update,
+        CommitSelect, InputMessage, LogType, Message, OptionsSource, RebaseCommand,
+        RebasePendingMessage, RebaseTodoMessage, SearchMessage, SelectMessage,
+        ShowSelectPopupConfig, update::update,
     },
 };
 
 mod utils;
 use utils::{
     create_model_from_test_repo, cursor_to_commit, expect_confirm_popup, expect_select_popup,
-    find_commit_line, find_line, key,
+    find_commit_line, find_line, key, shift_key,
 };
 
 // ── ShowRebasePopup ────────────────────────────────────────────────────────────
@@ -346,7 +347,7 @@ fn test_select_confirm_rebase_elsewhere_context_returns_rebase_message() {
     test_repo.commit_file("file1.txt", "content1", "First commit");
 
     let repo = git2::Repository::open(test_repo.repo_path()).unwrap();
-    let mut commits = get_log_entries(&repo, &LogType::Current, true, false).unwrap();
+    let mut commits = get_log_entries(&repo, &LogType::Current, true, false, false).unwrap();
     commits.retain(|e| e.is_commit());
 
     let mut model = create_model_from_test_repo(&test_repo);
@@ -367,6 +368,7 @@ fn test_select_confirm_rebase_elsewhere_context_returns_rebase_message() {
         picking: true,
         graph: true,
         color: false,
+        signatures: false,
     };
     model.log_pick_on_select = Some(OnSelect::RebaseElsewhere);
 
@@ -500,7 +502,7 @@ fn test_a_key_in_in_progress_popup_aborts_rebase() {
 }
 
 #[test]
-fn test_e_key_has_no_effect_in_in_progress_popup() {
+fn test_e_key_in_in_progress_popup_edits_todo() {
     let test_repo = TestRepo::new();
     test_repo.commit_file("file1.txt", "content1", "First commit");
 
@@ -515,9 +517,9 @@ fn test_e_key_has_no_effect_in_in_progress_popup() {
         },
     )));
 
-    // 'e' should not trigger elsewhere in in_progress mode
+    // 'e' edits the remaining todo rather than rebasing elsewhere
     let result = handle_key(key(KeyCode::Char('e')), &model);
-    assert_eq!(result, None);
+    assert_eq!(result, Some(Message::EditRebaseTodo));
 }
 
 // ── Interactive rebase — popup key and base selection ─────────────────────────
@@ -638,7 +640,7 @@ fn test_select_confirm_rebase_subset_start_returns_rebase_message() {
     test_repo.commit_file("file1.txt", "content1", "First commit");
 
     let repo = git2::Repository::open(test_repo.repo_path()).unwrap();
-    let mut commits = get_log_entries(&repo, &LogType::Current, true, false).unwrap();
+    let mut commits = get_log_entries(&repo, &LogType::Current, true, false, false).unwrap();
     commits.retain(|e| e.is_commit());
 
     let mut model = create_model_from_test_repo(&test_repo);
@@ -658,6 +660,7 @@ fn test_select_confirm_rebase_subset_start_returns_rebase_message() {
         picking: true,
         graph: true,
         color: false,
+        signatures: false,
     };
     model.log_pick_on_select = Some(OnSelect::RebaseSubsetStart {
         newbase: "origin/main".to_string(),
@@ -883,24 +886,26 @@ fn test_show_rebase_todo_opens_editor() {
         .lines
         .iter()
         .filter_map(|l| match &l.content {
-            LineContent::RebaseTodoHint { key, .. } => Some(*key),
+            LineContent::KeyHint { key, .. } => Some(*key),
             _ => None,
         })
         .collect();
     assert_eq!(
         hint_keys,
-        vec!["p", "r", "e", "s", "f", "d", "K/J", "u", "RET", "R", "q"]
+        vec![
+            "p", "r", "e", "s", "f", "F", "S", "d", "x", "b", "K/J", "u", "RET", "R", "q"
+        ]
     );
 
     // Confirm rebase and Abort are grouped, separated by an empty line
     let last = model.ui_model.lines.len() - 1;
     assert!(matches!(
         &model.ui_model.lines[last].content,
-        LineContent::RebaseTodoHint { key: "q", .. }
+        LineContent::KeyHint { key: "q", .. }
     ));
     assert!(matches!(
         &model.ui_model.lines[last - 1].content,
-        LineContent::RebaseTodoHint { key: "R", .. }
+        LineContent::KeyHint { key: "R", .. }
     ));
     assert!(matches!(
         &model.ui_model.lines[last - 2].content,
@@ -908,6 +913,32 @@ fn test_show_rebase_todo_opens_editor() {
     ));
 }
 
+#[test]
+fn test_show_rebase_todo_with_update_refs_config_and_staged_changes() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("a.txt", "a", "Commit A");
+    let base = test_repo.head_hash();
+    test_repo.commit_file("b.txt", "b", "Commit B");
+    magi::git::git_cmd(
+        test_repo.repo_path(),
+        &["config", "rebase.updateRefs", "true"],
+    )
+    .status()
+    .unwrap();
+    test_repo
+        .write_file_content("a.txt", "staged")
+        .stage_files(&["a.txt"]);
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    // No other branch is rebased, so git adds no lines and the changes
+    // can be autostashed when the rebase starts
+    update(&mut model, Message::ShowRebaseTodo(base));
+
+    assert!(model.popup.is_none(), "{:?}", model.popup);
+    assert_eq!(model.view_mode, ViewMode::RebaseTodo);
+    assert_eq!(todo_actions(&model), vec![RebaseAction::Pick; 2]);
+}
+
 #[test]
 fn test_rebase_todo_actions_on_hint_lines_are_inert() {
     let (_test_repo, mut model) = model_with_todo_editor();
@@ -1448,7 +1479,7 @@ fn test_select_confirm_modify_commit_returns_rebase_message() {
     test_repo.commit_file("file1.txt", "content1", "First commit");
 
     let repo = git2::Repository::open(test_repo.repo_path()).unwrap();
-    let mut commits = get_log_entries(&repo, &LogType::Current, true, false).unwrap();
+    let mut commits = get_log_entries(&repo, &LogType::Current, true, false, false).unwrap();
     commits.retain(|e| e.is_commit());
 
     let mut model = create_model_from_test_repo(&test_repo);
@@ -1468,6 +1499,7 @@ fn test_select_confirm_modify_commit_returns_rebase_message() {
         picking: true,
         graph: true,
         color: false,
+        signatures: false,
     };
     model.log_pick_on_select = Some(OnSelect::ModifyCommit);
 
@@ -1615,7 +1647,7 @@ fn test_select_confirm_reword_commit_returns_rebase_message() {
     test_repo.commit_file("file1.txt", "content1", "First commit");
 
     let repo = git2::Repository::open(test_repo.repo_path()).unwrap();
-    let mut commits = get_log_entries(&repo, &LogType::Current, true, false).unwrap();
+    let mut commits = get_log_entries(&repo, &LogType::Current, true, false, false).unwrap();
     commits.retain(|e| e.is_commit());
 
     let mut model = create_model_from_test_repo(&test_repo);
@@ -1635,6 +1667,7 @@ fn test_select_confirm_reword_commit_returns_rebase_message() {
         picking: true,
         graph: true,
         color: false,
+        signatures: false,
     };
     model.log_pick_on_select = Some(OnSelect::RewordCommit);
 
@@ -1798,7 +1831,7 @@ fn test_select_confirm_remove_commit_returns_rebase_message() {
     test_repo.commit_file("file1.txt", "content1", "First commit");
 
     let repo = git2::Repository::open(test_repo.repo_path()).unwrap();
-    let mut commits = get_log_entries(&repo, &LogType::Current, true, false).unwrap();
+    let mut commits = get_log_entries(&repo, &LogType::Current, true, false, false).unwrap();
     commits.retain(|e| e.is_commit());
 
     let mut model = create_model_from_test_repo(&test_repo);
@@ -1818,6 +1851,7 @@ fn test_select_confirm_remove_commit_returns_rebase_message() {
         picking: true,
         graph: true,
         color: false,
+        signatures: false,
     };
     model.log_pick_on_select = Some(OnSelect::RemoveCommit);
 
@@ -1971,7 +2005,7 @@ fn test_select_confirm_autosquash_returns_autosquash_into_message() {
     test_repo.commit_file("file1.txt", "content1", "First commit");
 
     let repo = git2::Repository::open(test_repo.repo_path()).unwrap();
-    let mut commits = get_log_entries(&repo, &LogType::Current, true, false).unwrap();
+    let mut commits = get_log_entries(&repo, &LogType::Current, true, false, false).unwrap();
     commits.retain(|e| e.is_commit());
 
     let mut model = create_model_from_test_repo(&test_repo);
@@ -1991,6 +2025,7 @@ fn test_select_confirm_autosquash_returns_autosquash_into_message() {
         picking: true,
         graph: true,
         color: false,
+        signatures: false,
     };
     model.log_pick_on_select = Some(OnSelect::AutosquashCommit);
 
@@ -2092,7 +2127,7 @@ fn test_unknown_key_in_arg_mode_exits_arg_mode() {
     )));
     model.arg_mode = true;
 
-    let result = handle_key(key(KeyCode::Char('x')), &model);
+    let result = handle_key(key(KeyCode::Char('z')), &model);
     assert_eq!(result, Some(Message::ExitArgMode));
 }
 
@@ -2163,6 +2198,261 @@ fn test_dash_ignored_while_rebase_in_progress() {
     assert_eq!(result, None);
 }
 
+#[test]
+fn test_x_in_arg_mode_shows_exec_input() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Rebase(
+        rebase_popup_state(),
+    )));
+    model.arg_mode = true;
+
+    let result = handle_key(key(KeyCode::Char('x')), &model);
+    assert_eq!(
+        result,
+        Some(Message::ShowArgumentValueInput(ValueArgument::Exec))
+    );
+    let result = handle_key(shift_key(KeyCode::Char('X')), &model);
+    assert_eq!(
+        result,
+        Some(Message::ShowArgumentValueInput(
+            ValueArgument::StrategyOption
+        ))
+    );
+}
+
+#[test]
+fn test_argument_value_input_sets_value_and_reopens_popup() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Rebase(
+        rebase_popup_state(),
+    )));
+    model.arg_mode = true;
+
+    update(
+        &mut model,
+        Message::ShowArgumentValueInput(ValueArgument::Exec),
+    );
+    assert!(matches!(
+        &model.popup,
+        Some(PopupContent::Input(state))
+            if matches!(state.context, InputContext::ArgumentValue { argument: ValueArgument::Exec, .. })
+    ));
+    assert!(!model.arg_mode);
+
+    confirm_input(&mut model, "make test");
+
+    assert_eq!(
+        model
+            .argument_values
+            .get(&ValueArgument::Exec)
+            .map(String::as_str),
+        Some("make test")
+    );
+    assert!(matches!(
+        model.popup,
+        Some(PopupContent::Command(PopupContentCommand::Rebase(_)))
+    ));
+}
+
+#[test]
+fn test_argument_value_selected_again_clears_value() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Rebase(
+        rebase_popup_state(),
+    )));
+    model
+        .argument_values
+        .insert(ValueArgument::Exec, "make test".to_string());
+
+    update(
+        &mut model,
+        Message::ShowArgumentValueInput(ValueArgument::Exec),
+    );
+
+    assert!(model.argument_values.is_empty());
+    assert!(matches!(
+        model.popup,
+        Some(PopupContent::Command(PopupContentCommand::Rebase(_)))
+    ));
+}
+
+#[test]
+fn test_dismissing_rebase_popup_clears_argument_values() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Rebase(
+        rebase_popup_state(),
+    )));
+    model
+        .argument_values
+        .insert(ValueArgument::Exec, "make test".to_string());
+
+    update(&mut model, Message::DismissPopup);
+
+    assert!(model.argument_values.is_empty());
+}
+
+#[test]
+fn test_interactive_todo_includes_exec_argument_lines() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+    let base = test_repo.head_hash();
+    test_repo.commit_file("file2.txt", "content2", "Second commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model
+        .argument_values
+        .insert(ValueArgument::Exec, "true".to_string());
+
+    update(&mut model, Message::ShowRebaseTodo(base));
+
+    assert_eq!(
+        todo_actions(&model),
+        vec![
+            RebaseAction::Pick,
+            RebaseAction::Exec,
+            RebaseAction::Pick,
+            RebaseAction::Exec,
+        ]
+    );
+    // The value is kept until the rebase is confirmed
+    assert!(model.argument_values.contains_key(&ValueArgument::Exec));
+}
+
+#[test]
+fn test_remove_commit_with_update_refs_moves_stacked_branch() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+    let target = test_repo.head_hash();
+    test_repo.commit_file("file2.txt", "content2", "Second commit");
+    test_repo.create_branch("stacked");
+    test_repo.commit_file("file3.txt", "content3", "Third commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arguments = Some(Arguments::RebaseArguments(
+        [RebaseArgument::UpdateRefs].into_iter().collect(),
+    ));
+
+    update(
+        &mut model,
+        Message::Rebase(RebaseCommand::RemoveCommit(target)),
+    );
+
+    assert!(!rebase_in_progress(test_repo.repo_path()));
+    let stacked = magi::git::git_cmd(test_repo.repo_path(), &["log", "--format=%s", "stacked"])
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&stacked.stdout),
+        "Second commit\nInitial commit\n"
+    );
+}
+
+#[test]
+fn test_remove_root_commit_ignores_keep_base() {
+    let test_repo = TestRepo::new();
+    let root = test_repo.head_hash();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arguments = Some(Arguments::RebaseArguments(
+        [RebaseArgument::KeepBase].into_iter().collect(),
+    ));
+
+    update(
+        &mut model,
+        Message::Rebase(RebaseCommand::RemoveCommit(root)),
+    );
+
+    assert!(!rebase_in_progress(test_repo.repo_path()));
+    let log = magi::git::git_cmd(test_repo.repo_path(), &["log", "--format=%s"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&log.stdout), "First commit\n");
+}
+
+#[test]
+fn test_remove_commit_honors_exec_argument() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+    let target = test_repo.head_hash();
+    test_repo.commit_file("file2.txt", "content2", "Second commit");
+    test_repo.commit_file("file3.txt", "content3", "Third commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model
+        .argument_values
+        .insert(ValueArgument::Exec, "echo ran >> exec-log".to_string());
+
+    update(
+        &mut model,
+        Message::Rebase(RebaseCommand::RemoveCommit(target)),
+    );
+
+    assert!(!rebase_in_progress(test_repo.repo_path()));
+    let log = std::fs::read_to_string(test_repo.repo_path().join("exec-log")).unwrap();
+    assert_eq!(log.lines().count(), 2, "Expected one exec per kept commit");
+    assert!(model.argument_values.is_empty());
+}
+
+#[test]
+fn test_modify_commit_passes_rebase_arguments_to_git() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+    let target = test_repo.head_hash();
+    test_repo.commit_file("file2.txt", "content2", "Second commit");
+    test_repo.write_file_content("file2.txt", "dirty");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arguments = Some(Arguments::RebaseArguments(
+        [RebaseArgument::Autostash, RebaseArgument::UpdateRefs]
+            .into_iter()
+            .collect(),
+    ));
+
+    update(
+        &mut model,
+        Message::Rebase(RebaseCommand::ModifyCommit(target)),
+    );
+
+    assert!(rebase_in_progress(test_repo.repo_path()));
+    assert!(model.arguments.is_none());
+}
+
+/// Without the autostash argument git refuses to start on a dirty tree
+#[test]
+fn test_modify_commit_without_autostash_leaves_dirty_tree_alone() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+    let target = test_repo.head_hash();
+    test_repo.commit_file("file2.txt", "content2", "Second commit");
+    test_repo.write_file_content("file2.txt", "dirty");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    update(
+        &mut model,
+        Message::Rebase(RebaseCommand::ModifyCommit(target)),
+    );
+
+    assert!(!rebase_in_progress(test_repo.repo_path()));
+    assert_eq!(
+        std::fs::read_to_string(test_repo.repo_path().join("file2.txt")).unwrap(),
+        "dirty"
+    );
+}
+
 #[test]
 fn test_autosquash_into_update_squashes_into_selected_commit() {
     let test_repo = TestRepo::new();
@@ -2190,3 +2480,370 @@ fn test_autosquash_into_update_squashes_into_selected_commit() {
         "fixed"
     );
 }
+
+/// Types `command` into the open input popup and confirms it, feeding the
+/// resulting message back into `update`.
+fn confirm_input(model: &mut Model, command: &str) {
+    for c in command.chars() {
+        update(model, Message::Input(InputMessage::Edit(EditOp::Insert(c))));
+    }
+    let next = update(model, Message::Input(InputMessage::Confirm));
+    if let Some(next) = next {
+        update(model, next);
+    }
+}
+
+#[test]
+fn test_rebase_todo_insert_exec_below_cursor() {
+    let (_test_repo, mut model) = model_with_todo_editor();
+
+    let msg = handle_key(key(KeyCode::Char('x')), &model);
+    assert_eq!(
+        msg,
+        Some(Message::RebaseTodo(RebaseTodoMessage::ShowExecInput {
+            edit: false
+        }))
+    );
+    update(&mut model, msg.unwrap());
+    assert!(matches!(
+        &model.popup,
+        Some(PopupContent::Input(state))
+            if state.context == InputContext::RebaseExec { index: 0, replace: false }
+    ));
+
+    confirm_input(&mut model, "cargo test");
+
+    assert_eq!(
+        todo_actions(&model),
+        vec![RebaseAction::Pick, RebaseAction::Exec, RebaseAction::Pick]
+    );
+    assert_eq!(model.ui_model.cursor_position, 1);
+    assert!(matches!(
+        &model.ui_model.lines[1].content,
+        LineContent::RebaseTodoLine(entry) if entry.message == "cargo test"
+    ));
+}
+
+#[test]
+fn test_rebase_todo_enter_on_exec_edits_command() {
+    let (_test_repo, mut model) = model_with_todo_editor();
+    update(
+        &mut model,
+        Message::RebaseTodo(RebaseTodoMessage::Exec {
+            index: 0,
+            replace: false,
+            command: "make".to_string(),
+        }),
+    );
+    model.ui_model.cursor_position = 1;
+
+    let msg = handle_key(key(KeyCode::Enter), &model);
+    assert_eq!(
+        msg,
+        Some(Message::RebaseTodo(RebaseTodoMessage::ShowExecInput {
+            edit: true
+        }))
+    );
+    update(&mut model, msg.unwrap());
+    confirm_input(&mut model, " check");
+
+    let entries = &model.rebase_todo.as_ref().unwrap().entries;
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[1].message, "make check");
+}
+
+#[test]
+fn test_rebase_todo_drop_removes_break_line() {
+    let (_test_repo, mut model) = model_with_todo_editor();
+
+    let msg = handle_key(key(KeyCode::Char('b')), &model).unwrap();
+    update(&mut model, msg);
+    assert_eq!(
+        todo_actions(&model),
+        vec![RebaseAction::Pick, RebaseAction::Break, RebaseAction::Pick]
+    );
+    assert_eq!(model.ui_model.cursor_position, 1);
+
+    update(
+        &mut model,
+        Message::RebaseTodo(RebaseTodoMessage::SetAction(RebaseAction::Drop)),
+    );
+    assert_eq!(todo_actions(&model), vec![RebaseAction::Pick; 2]);
+}
+
+#[test]
+fn test_rebase_todo_instruction_line_rejects_commit_action() {
+    let (_test_repo, mut model) = model_with_todo_editor();
+    update(
+        &mut model,
+        Message::RebaseTodo(RebaseTodoMessage::InsertBreak),
+    );
+
+    update(
+        &mut model,
+        Message::RebaseTodo(RebaseTodoMessage::SetAction(RebaseAction::Reword)),
+    );
+
+    assert_eq!(todo_actions(&model)[1], RebaseAction::Break);
+    assert!(model.toast.is_some());
+}
+
+#[test]
+fn test_rebase_todo_fixup_keep_message_key() {
+    let (_test_repo, model) = model_with_todo_editor();
+    assert_eq!(
+        handle_key(utils::shift_key(KeyCode::Char('F')), &model),
+        Some(Message::RebaseTodo(RebaseTodoMessage::SetAction(
+            RebaseAction::FixupKeepMessage
+        )))
+    );
+    assert_eq!(
+        handle_key(utils::shift_key(KeyCode::Char('S')), &model),
+        Some(Message::RebaseTodo(RebaseTodoMessage::SetAction(
+            RebaseAction::FixupEditMessage
+        )))
+    );
+    let alt_f = crossterm::event::KeyEvent::new(
+        KeyCode::Char('F'),
+        crossterm::event::KeyModifiers::ALT | crossterm::event::KeyModifiers::SHIFT,
+    );
+    assert_eq!(handle_key(alt_f, &model), None);
+}
+
+#[test]
+fn test_execute_interactive_runs_inserted_exec() {
+    let (test_repo, mut model) = model_with_todo_editor();
+    model.ui_model.cursor_position = 1;
+    update(
+        &mut model,
+        Message::RebaseTodo(RebaseTodoMessage::Exec {
+            index: 1,
+            replace: false,
+            command: "touch exec-ran".to_string(),
+        }),
+    );
+
+    update(
+        &mut model,
+        Message::Rebase(RebaseCommand::ExecuteInteractive),
+    );
+
+    assert!(test_repo.repo_path().join("exec-ran").exists());
+    assert!(!rebase_in_progress(test_repo.repo_path()));
+}
+
+// ── Editing the todo of a stopped rebase ──────────────────────────────────────
+
+/// Three commits with a rebase stopped (`edit`) at Commit A, leaving
+/// Commit B and Commit C pending.
+fn model_with_stopped_rebase() -> (TestRepo, Model) {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("a.txt", "a", "Commit A");
+    let commit_a = test_repo.head_hash();
+    test_repo.commit_file("b.txt", "b", "Commit B");
+    test_repo.commit_file("c.txt", "c", "Commit C");
+    let result = run_modify_commit(test_repo.repo_path(), &commit_a, &[]).unwrap();
+    assert!(result.success);
+    assert!(rebase_in_progress(test_repo.repo_path()));
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    update(&mut model, Message::Refresh);
+    (test_repo, model)
+}
+
+fn pending_messages(test_repo: &TestRepo) -> Vec<(RebaseAction, String)> {
+    read_rebase_todo(test_repo.repo_path())
+        .unwrap()
+        .into_iter()
+        .map(|e| (e.action, e.message))
+        .collect()
+}
+
+fn cursor_to_pending(model: &mut Model, message: &str) {
+    model.ui_model.cursor_position = model
+        .ui_model
+        .lines
+        .iter()
+        .position(|l| {
+            matches!(&l.content, LineContent::RebasingEntry { message: m, is_current: false, .. }
+                if m == message)
+        })
+        .expect("pending entry in the Rebasing section");
+}
+
+#[test]
+fn test_edit_rebase_todo_opens_remaining_todo() {
+    let (_test_repo, mut model) = model_with_stopped_rebase();
+
+    update(&mut model, Message::EditRebaseTodo);
+
+    assert_eq!(model.view_mode, ViewMode::RebaseTodo);
+    let state = model.rebase_todo.as_ref().unwrap();
+    assert!(state.in_progress);
+    let messages: Vec<&str> = state.entries.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(messages, vec!["Commit B", "Commit C"]);
+    assert!(model.ui_model.lines.iter().any(|l| matches!(
+        &l.content,
+        LineContent::KeyHint { key: "R", description } if *description == "Save todo"
+    )));
+    assert_eq!(
+        handle_key(utils::shift_key(KeyCode::Char('R')), &model),
+        Some(Message::RebaseTodo(RebaseTodoMessage::Save))
+    );
+}
+
+#[test]
+fn test_edit_rebase_todo_allows_fixup_on_first_pending_entry() {
+    let (test_repo, mut model) = model_with_stopped_rebase();
+    update(&mut model, Message::EditRebaseTodo);
+
+    update(
+        &mut model,
+        Message::RebaseTodo(RebaseTodoMessage::SetAction(RebaseAction::Fixup)),
+    );
+    let result = update(&mut model, Message::RebaseTodo(RebaseTodoMessage::Save));
+
+    assert_eq!(result, Some(Message::Refresh));
+    assert_eq!(model.view_mode, ViewMode::Status);
+    assert!(model.rebase_todo.is_none());
+    assert_eq!(
+        pending_messages(&test_repo),
+        vec![
+            (RebaseAction::Fixup, "Commit B".to_string()),
+            (RebaseAction::Pick, "Commit C".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_saved_rebase_todo_is_used_on_continue() {
+    let (test_repo, mut model) = model_with_stopped_rebase();
+    update(&mut model, Message::EditRebaseTodo);
+    model.ui_model.cursor_position = 1;
+    update(
+        &mut model,
+        Message::RebaseTodo(RebaseTodoMessage::SetAction(RebaseAction::Drop)),
+    );
+    update(&mut model, Message::RebaseTodo(RebaseTodoMessage::Save));
+
+    let status = magi::git::git_cmd(test_repo.repo_path(), &["rebase", "--continue"])
+        .env("GIT_EDITOR", "true")
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+    assert!(!rebase_in_progress(test_repo.repo_path()));
+    assert!(test_repo.repo_path().join("b.txt").exists());
+    assert!(!test_repo.repo_path().join("c.txt").exists());
+}
+
+#[test]
+fn test_pending_entry_keys_in_status_view() {
+    let (_test_repo, mut model) = model_with_stopped_rebase();
+    cursor_to_pending(&mut model, "Commit C");
+    let hash = match &model.ui_model.lines[model.ui_model.cursor_position].content {
+        LineContent::RebasingEntry { hash, .. } => hash.clone(),
+        _ => unreachable!(),
+    };
+
+    assert_eq!(
+        handle_key(key(KeyCode::Char('x')), &model),
+        Some(Message::RebasePending(RebasePendingMessage::ToggleDrop(
+            hash.clone()
+        )))
+    );
+    assert_eq!(
+        handle_key(utils::shift_key(KeyCode::Char('R')), &model),
+        Some(Message::RebasePending(RebasePendingMessage::ToggleReword(
+            hash.clone()
+        )))
+    );
+    assert_eq!(
+        handle_key(utils::shift_key(KeyCode::Char('K')), &model),
+        Some(Message::RebasePending(RebasePendingMessage::MoveUp(
+            hash.clone()
+        )))
+    );
+    assert_eq!(
+        handle_key(utils::shift_key(KeyCode::Char('J')), &model),
+        Some(Message::RebasePending(RebasePendingMessage::MoveDown(hash)))
+    );
+
+    // The stopped commit keeps the normal status keys
+    model.ui_model.cursor_position = model
+        .ui_model
+        .lines
+        .iter()
+        .position(|l| {
+            matches!(
+                &l.content,
+                LineContent::RebasingEntry {
+                    is_current: true,
+                    ..
+                }
+            )
+        })
+        .unwrap();
+    assert_eq!(
+        handle_key(key(KeyCode::Char('x')), &model),
+        Some(Message::DiscardSelected)
+    );
+}
+
+#[test]
+fn test_pending_entry_toggle_drop_rewrites_todo() {
+    let (test_repo, mut model) = model_with_stopped_rebase();
+    cursor_to_pending(&mut model, "Commit B");
+    let msg = handle_key(key(KeyCode::Char('x')), &model).unwrap();
+
+    let result = update(&mut model, msg);
+    assert_eq!(result, Some(Message::Refresh));
+    update(&mut model, Message::Refresh);
+
+    assert_eq!(
+        pending_messages(&test_repo)[0],
+        (RebaseAction::Drop, "Commit B".to_string())
+    );
+    assert!(model.ui_model.lines.iter().any(|l| matches!(
+        &l.content,
+        LineContent::RebasingEntry { action: RebaseAction::Drop, message, .. } if message == "Commit B"
+    )));
+
+    // Toggling again picks it back up
+    cursor_to_pending(&mut model, "Commit B");
+    let msg = handle_key(key(KeyCode::Char('x')), &model).unwrap();
+    update(&mut model, msg);
+    assert_eq!(
+        pending_messages(&test_repo)[0],
+        (RebaseAction::Pick, "Commit B".to_string())
+    );
+}
+
+#[test]
+fn test_pending_entry_move_up_passes_exec_lines() {
+    let (test_repo, mut model) = model_with_stopped_rebase();
+    let todo = test_repo
+        .repo_path()
+        .join(".git")
+        .join("rebase-merge")
+        .join("git-rebase-todo");
+    let content = std::fs::read_to_string(&todo).unwrap();
+    let mut lines: Vec<&str> = content.lines().filter(|l| !l.starts_with('#')).collect();
+    lines.insert(1, "exec true");
+    std::fs::write(&todo, lines.join("\n")).unwrap();
+    update(&mut model, Message::Refresh);
+
+    cursor_to_pending(&mut model, "Commit C");
+    let msg = handle_key(utils::shift_key(KeyCode::Char('K')), &model).unwrap();
+    update(&mut model, msg);
+
+    let entries = read_rebase_todo(test_repo.repo_path()).unwrap();
+    let summary: Vec<&str> = entries.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(summary, vec!["Commit C", "Commit B", "true"]);
+
+    // Moving the first pending commit further up is a no-op
+    update(&mut model, Message::Refresh);
+    cursor_to_pending(&mut model, "Commit C");
+    let msg = handle_key(utils::shift_key(KeyCode::Char('K')), &model).unwrap();
+    assert_eq!(update(&mut model, msg), None);
+}
@@ -0,0 +1,132 @@
+use crossterm::event::KeyCode;
+use magi::{
+    git::test_repo::TestRepo,
+    keys::handle_key,
+    model::{
+        LineContent, Model, SectionType,
+        popup::{PopupContent, PopupContentCommand},
+    },
+    msg::{Message, update::update},
+};
+
+mod utils;
+use utils::{create_model_from_test_repo, key};
+
+/// Points `<remote>/main` at `oid`, creating the remote
+fn remote_branch_at(test_repo: &TestRepo, remote: &str, oid: git2::Oid) {
+    if test_repo.repo.find_remote(remote).is_err() {
+        test_repo
+            .repo
+            .remote(remote, &format!("https://example.com/{}.git", remote))
+            .unwrap();
+    }
+    test_repo
+        .repo
+        .reference(
+            &format!("refs/remotes/{}/main", remote),
+            oid,
+            true,
+            "move remote branch",
+        )
+        .unwrap();
+}
+
+/// origin/main as the upstream and fork/main as where main is pushed, both
+/// at the current HEAD
+fn setup_remotes(test_repo: &TestRepo) {
+    test_repo.set_upstream_at_head();
+    let head = test_repo.repo.head().unwrap().target().unwrap();
+    remote_branch_at(test_repo, "fork", head);
+    test_repo
+        .repo
+        .config()
+        .unwrap()
+        .set_str("remote.pushDefault", "fork")
+        .unwrap();
+}
+
+/// Moves the cursor to the commit listed in `section`, returning its hash
+fn cursor_to_commit_in(model: &mut Model, section: SectionType) -> String {
+    let (pos, hash) = model
+        .ui_model
+        .lines
+        .iter()
+        .enumerate()
+        .find_map(|(i, line)| match &line.content {
+            LineContent::Commit(info) if line.section.as_ref() == Some(&section) => {
+                Some((i, info.hash.clone()))
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("Expected a commit in {:?}", section));
+    model.ui_model.cursor_position = pos;
+    hash
+}
+
+/// The commit actions use the commit at the cursor
+fn assert_commit_actions(model: &mut Model, hash: &str) {
+    assert_eq!(
+        handle_key(key(KeyCode::Enter), model),
+        Some(Message::ShowPreview)
+    );
+
+    update(model, Message::ShowApplyPopup);
+    match &model.popup {
+        Some(PopupContent::Command(PopupContentCommand::Apply(state))) => {
+            assert_eq!(state.selected_commits, vec![hash.to_string()]);
+        }
+        _ => panic!("Expected Apply popup"),
+    }
+    model.popup = None;
+
+    update(model, Message::ShowRevertPopup);
+    match &model.popup {
+        Some(PopupContent::Command(PopupContentCommand::Revert(state))) => {
+            assert_eq!(state.selected_commits, vec![hash.to_string()]);
+        }
+        _ => panic!("Expected Revert popup"),
+    }
+    model.popup = None;
+}
+
+#[test]
+fn test_commit_actions_on_unmerged_and_unpushed_commits() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("base.txt", "base", "Base");
+    setup_remotes(&test_repo);
+    test_repo.commit_file("local.txt", "local", "Local commit");
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    for section in [
+        SectionType::UnmergedIntoUpstream,
+        SectionType::UnpushedToPushRemote,
+    ] {
+        let hash = cursor_to_commit_in(&mut model, section);
+        assert!(test_repo.head_hash().starts_with(&hash));
+        assert_commit_actions(&mut model, &hash);
+    }
+}
+
+#[test]
+fn test_commit_actions_on_unpulled_commits() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("base.txt", "base", "Base");
+    setup_remotes(&test_repo);
+    test_repo.commit_file("remote.txt", "remote", "Remote commit");
+    let remote_oid = test_repo.repo.head().unwrap().target().unwrap();
+    remote_branch_at(&test_repo, "origin", remote_oid);
+    remote_branch_at(&test_repo, "fork", remote_oid);
+    // main goes back to the base, leaving the remote commit unpulled
+    let base = test_repo.repo.revparse_single("HEAD~1").unwrap();
+    test_repo
+        .repo
+        .reset(&base, git2::ResetType::Hard, None)
+        .unwrap();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    for section in [SectionType::Unpulled, SectionType::UnpulledFromPushRemote] {
+        let hash = cursor_to_commit_in(&mut model, section);
+        assert!(remote_oid.to_string().starts_with(&hash));
+        assert_commit_actions(&mut model, &hash);
+    }
+}
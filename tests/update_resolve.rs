@@ -0,0 +1,294 @@
+use crossterm::event::KeyCode;
+use magi::{
+    git::{
+        conflict::{ConflictCheckout, ConflictSide},
+        test_repo::TestRepo,
+    },
+    keys::handle_key,
+    model::{
+        DiffLineType, LineContent, Model, SectionType, ToastStyle,
+        popup::{PopupContent, PopupContentCommand, ResolvePopupState},
+    },
+    msg::{Message, ResolveCommand, update::update, util::is_external_command},
+};
+
+mod utils;
+use utils::{
+    assert_no_popup, create_model_from_test_repo, find_commit_line, find_line,
+    find_staged_file_line, find_unstaged_file_line, key, run, shift_key,
+};
+
+/// Create a conflicted `conflict.txt` and a model with its hunk expanded.
+fn conflicted_model(test_repo: &TestRepo) -> Model {
+    test_repo.create_merge_conflict("conflict.txt");
+    let mut model = create_model_from_test_repo(test_repo);
+    model
+        .ui_model
+        .collapsed_sections
+        .remove(&SectionType::UnstagedFile {
+            path: "conflict.txt".to_string(),
+        });
+    model
+}
+
+fn expect_resolve_popup(model: &Model) -> &ResolvePopupState {
+    match &model.popup {
+        Some(PopupContent::Command(PopupContentCommand::Resolve(state))) => state,
+        other => panic!("Expected resolve popup, got {:?}", other),
+    }
+}
+
+fn marker_line(model: &Model, marker: &str) -> usize {
+    find_line(model, |line| {
+        matches!(line, LineContent::DiffLine(diff)
+            if diff.line_type == DiffLineType::ConflictMarker && diff.content.contains(marker))
+    })
+    .expect("conflict marker line")
+}
+
+// ── ShowResolvePopup — key binding ─────────────────────────────────────────────
+
+#[test]
+fn test_e_key_shows_resolve_popup() {
+    let test_repo = TestRepo::new();
+    let model = conflicted_model(&test_repo);
+
+    let result = handle_key(key(KeyCode::Char('e')), &model);
+    assert_eq!(result, Some(Message::ShowResolvePopup));
+}
+
+// ── ShowResolvePopup — state ───────────────────────────────────────────────────
+
+#[test]
+fn test_show_resolve_popup_on_unmerged_file() {
+    let test_repo = TestRepo::new();
+    let mut model = conflicted_model(&test_repo);
+    model.ui_model.cursor_position = find_unstaged_file_line(&model, "conflict.txt").unwrap();
+
+    update(&mut model, Message::ShowResolvePopup);
+
+    assert_eq!(
+        expect_resolve_popup(&model),
+        &ResolvePopupState {
+            path: "conflict.txt".to_string(),
+            conflict: None,
+            unmerged: true,
+        }
+    );
+}
+
+#[test]
+fn test_show_resolve_popup_inside_conflict_region() {
+    let test_repo = TestRepo::new();
+    let mut model = conflicted_model(&test_repo);
+    model.ui_model.cursor_position = marker_line(&model, "=======");
+
+    update(&mut model, Message::ShowResolvePopup);
+
+    assert_eq!(expect_resolve_popup(&model).conflict, Some(0));
+}
+
+#[test]
+fn test_show_resolve_popup_on_hunk_header_uses_next_conflict() {
+    let test_repo = TestRepo::new();
+    let mut model = conflicted_model(&test_repo);
+    model.ui_model.cursor_position =
+        find_line(&model, |line| matches!(line, LineContent::DiffHunk(_))).unwrap();
+
+    update(&mut model, Message::ShowResolvePopup);
+
+    assert_eq!(expect_resolve_popup(&model).conflict, Some(0));
+}
+
+#[test]
+fn test_show_resolve_popup_ignores_other_lines() {
+    let test_repo = TestRepo::new();
+    let mut model = conflicted_model(&test_repo);
+    model.ui_model.cursor_position = find_commit_line(&model).unwrap();
+
+    let result = update(&mut model, Message::ShowResolvePopup);
+
+    assert_eq!(result, None);
+    assert_no_popup(&model);
+}
+
+// ── Resolve popup — keys ───────────────────────────────────────────────────────
+
+#[test]
+fn test_resolve_popup_keys() {
+    let test_repo = TestRepo::new();
+    let mut model = conflicted_model(&test_repo);
+    model.ui_model.cursor_position = marker_line(&model, "<<<<<<<");
+    update(&mut model, Message::ShowResolvePopup);
+
+    assert_eq!(
+        handle_key(key(KeyCode::Char('t')), &model),
+        Some(Message::Resolve(ResolveCommand::Conflict {
+            path: "conflict.txt".to_string(),
+            index: 0,
+            side: ConflictSide::Theirs,
+        }))
+    );
+    assert_eq!(
+        handle_key(shift_key(KeyCode::Char('O')), &model),
+        Some(Message::Resolve(ResolveCommand::Checkout {
+            path: "conflict.txt".to_string(),
+            checkout: ConflictCheckout::Ours,
+        }))
+    );
+}
+
+#[test]
+fn test_resolve_popup_on_file_has_no_region_actions() {
+    let test_repo = TestRepo::new();
+    let mut model = conflicted_model(&test_repo);
+    model.ui_model.cursor_position = find_unstaged_file_line(&model, "conflict.txt").unwrap();
+    update(&mut model, Message::ShowResolvePopup);
+
+    assert_eq!(handle_key(key(KeyCode::Char('o')), &model), None);
+}
+
+// ── Resolve — execution ────────────────────────────────────────────────────────
+
+#[test]
+fn test_resolve_conflict_keeps_side_and_stages_file() {
+    let test_repo = TestRepo::new();
+    let mut model = conflicted_model(&test_repo);
+
+    run(
+        &mut model,
+        Message::Resolve(ResolveCommand::Conflict {
+            path: "conflict.txt".to_string(),
+            index: 0,
+            side: ConflictSide::Both,
+        }),
+    );
+
+    let content = std::fs::read_to_string(test_repo.repo_path().join("conflict.txt")).unwrap();
+    assert_eq!(content, "main content\nother content\n");
+    assert!(find_unstaged_file_line(&model, "conflict.txt").is_none());
+    assert!(find_staged_file_line(&model, "conflict.txt").is_some());
+    assert_eq!(model.toast.as_ref().unwrap().style, ToastStyle::Success);
+}
+
+#[test]
+fn test_resolve_base_without_diff3_shows_error() {
+    let test_repo = TestRepo::new();
+    let mut model = conflicted_model(&test_repo);
+
+    run(
+        &mut model,
+        Message::Resolve(ResolveCommand::Conflict {
+            path: "conflict.txt".to_string(),
+            index: 0,
+            side: ConflictSide::Base,
+        }),
+    );
+
+    assert!(matches!(model.popup, Some(PopupContent::Error { .. })));
+    assert!(find_unstaged_file_line(&model, "conflict.txt").is_some());
+}
+
+#[test]
+fn test_checkout_theirs_then_restore_conflict() {
+    let test_repo = TestRepo::new();
+    let mut model = conflicted_model(&test_repo);
+
+    run(
+        &mut model,
+        Message::Resolve(ResolveCommand::Checkout {
+            path: "conflict.txt".to_string(),
+            checkout: ConflictCheckout::Theirs,
+        }),
+    );
+    let content = std::fs::read_to_string(test_repo.repo_path().join("conflict.txt")).unwrap();
+    assert_eq!(content, "other content\n");
+
+    // The resolved file is staged; its conflict can be brought back from there
+    model.ui_model.cursor_position = find_staged_file_line(&model, "conflict.txt").unwrap();
+    update(&mut model, Message::ShowResolvePopup);
+    assert!(!expect_resolve_popup(&model).unmerged);
+    assert_eq!(handle_key(shift_key(KeyCode::Char('O')), &model), None);
+
+    let msg = handle_key(key(KeyCode::Char('m')), &model).unwrap();
+    run(&mut model, msg);
+
+    let content = std::fs::read_to_string(test_repo.repo_path().join("conflict.txt")).unwrap();
+    assert!(content.contains("<<<<<<<"));
+    assert!(find_unstaged_file_line(&model, "conflict.txt").is_some());
+}
+
+// ── Mergetool ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_mergetool_keys_and_external_command() {
+    let test_repo = TestRepo::new();
+    let mut model = conflicted_model(&test_repo);
+    model.ui_model.cursor_position = find_unstaged_file_line(&model, "conflict.txt").unwrap();
+    update(&mut model, Message::ShowResolvePopup);
+
+    let this_file = handle_key(shift_key(KeyCode::Char('M')), &model).unwrap();
+    assert_eq!(
+        this_file,
+        Message::Resolve(ResolveCommand::Mergetool {
+            paths: vec!["conflict.txt".to_string()],
+        })
+    );
+    assert!(is_external_command(&this_file));
+    assert_eq!(
+        handle_key(key(KeyCode::Char('a')), &model),
+        Some(Message::Resolve(ResolveCommand::Mergetool {
+            paths: vec![]
+        }))
+    );
+}
+
+#[test]
+fn test_mergetool_resolves_file() {
+    let test_repo = TestRepo::new();
+    let mut model = conflicted_model(&test_repo);
+    test_repo.with_mergetool("cp \"$REMOTE\" \"$MERGED\"");
+
+    run(
+        &mut model,
+        Message::Resolve(ResolveCommand::Mergetool {
+            paths: vec!["conflict.txt".to_string()],
+        }),
+    );
+
+    let content = std::fs::read_to_string(test_repo.repo_path().join("conflict.txt")).unwrap();
+    assert_eq!(content, "other content\n");
+    assert!(find_staged_file_line(&model, "conflict.txt").is_some());
+    assert_eq!(model.toast.as_ref().unwrap().style, ToastStyle::Success);
+}
+
+#[test]
+fn test_mergetool_reports_remaining_conflicts() {
+    let test_repo = TestRepo::new();
+    let mut model = conflicted_model(&test_repo);
+    test_repo.with_mergetool("false");
+
+    run(
+        &mut model,
+        Message::Resolve(ResolveCommand::Mergetool { paths: vec![] }),
+    );
+
+    let toast = model.toast.as_ref().unwrap();
+    assert_eq!(toast.style, ToastStyle::Warning);
+    assert!(toast.message.contains("conflict.txt"));
+    assert!(find_unstaged_file_line(&model, "conflict.txt").is_some());
+}
+
+#[test]
+fn test_mergetool_with_nothing_unmerged_reports_no_conflicts() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file.txt", "content\n", "Initial commit");
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    run(
+        &mut model,
+        Message::Resolve(ResolveCommand::Mergetool { paths: vec![] }),
+    );
+
+    assert_eq!(model.toast.as_ref().unwrap().message, "No conflicts");
+}
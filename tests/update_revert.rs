@@ -4,7 +4,7 @@ use magi::{
     keys::handle_key,
     model::{
         Line, LineContent, ToastStyle, ViewMode,
-        arguments::{Argument, Arguments, RevertArgument},
+        arguments::{Argument, Arguments, RevertArgument, ValueArgument},
         popup::{PopupContent, PopupContentCommand, RevertPopupState},
     },
     msg::{LogType, Message, RevertCommand, update::update, util::is_external_command},
@@ -162,7 +162,7 @@ fn test_show_revert_popup_on_log_line_selects_hash() {
 
     // Populate the model with log-view lines (as ShowLog does)
     let repo = git2::Repository::open(test_repo.repo_path()).unwrap();
-    let log_lines: Vec<Line> = get_log_entries(&repo, &LogType::Current, true, false)
+    let log_lines: Vec<Line> = get_log_entries(&repo, &LogType::Current, true, false, false)
         .unwrap()
         .into_iter()
         .map(|entry| Line {
@@ -189,6 +189,7 @@ fn test_show_revert_popup_on_log_line_selects_hash() {
         picking: false,
         graph: true,
         color: false,
+        signatures: false,
     };
     model.ui_model.cursor_position = log_commit_pos;
 
@@ -467,6 +468,64 @@ fn test_e_in_arg_mode_toggles_edit_argument() {
     );
 }
 
+#[test]
+fn test_signoff_and_strategy_arguments_in_arg_mode() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+
+    let mut model = revert_popup_model(&test_repo);
+    model.arg_mode = true;
+
+    assert_eq!(
+        handle_key(key(KeyCode::Char('o')), &model),
+        Some(Message::ToggleArgument(Argument::Revert(
+            RevertArgument::Signoff
+        )))
+    );
+    assert_eq!(
+        handle_key(key(KeyCode::Char('s')), &model),
+        Some(Message::ShowArgumentValueInput(ValueArgument::Strategy))
+    );
+}
+
+#[test]
+fn test_revert_commits_passes_signoff_and_strategy_flags() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("file1.txt", "content1", "First commit");
+    test_repo.commit_file("file1.txt", "content2", "Second commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.arguments = Some(Arguments::RevertArguments(
+        [RevertArgument::Edit, RevertArgument::Signoff]
+            .into_iter()
+            .collect(),
+    ));
+    model
+        .argument_values
+        .insert(ValueArgument::StrategyOption, "theirs".to_string());
+
+    let hash = test_repo.repo.head().unwrap().target().unwrap().to_string();
+    let result = update(
+        &mut model,
+        Message::Revert(RevertCommand::Commits {
+            hashes: vec![hash.clone()],
+            mainline: None,
+        }),
+    );
+
+    let expected = Message::Revert(RevertCommand::WithEditor {
+        args: vec![
+            "revert".to_string(),
+            "--edit".to_string(),
+            "--signoff".to_string(),
+            "--strategy-option=theirs".to_string(),
+            hash,
+        ],
+    });
+    assert_eq!(result, Some(expected));
+    assert!(model.argument_values.is_empty());
+}
+
 #[test]
 fn test_toggle_edit_argument_disables_default() {
     let test_repo = TestRepo::new();
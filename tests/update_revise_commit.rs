@@ -15,7 +15,7 @@ use utils::create_model_from_test_repo;
 /// Helper to get log entries for testing (filters out graph-only entries)
 fn get_log_entries_for_test(test_repo: &TestRepo) -> Vec<magi::model::LogEntry> {
     let repo = git2::Repository::open(test_repo.repo_path()).unwrap();
-    let mut entries = get_log_entries(&repo, &LogType::Current, true, false).unwrap();
+    let mut entries = get_log_entries(&repo, &LogType::Current, true, false, false).unwrap();
     entries.retain(|e| e.is_commit());
     entries
 }
@@ -29,6 +29,7 @@ fn make_log_line(hash: &str, message: &str) -> Line {
             time: None,
             refs: vec![],
             graph: String::new(),
+            signature: None,
         }),
         section: None,
     }
@@ -161,6 +162,7 @@ fn test_revise_commit_select_confirm_routes_to_revise() {
         picking: true,
         graph: true,
         color: false,
+        signatures: false,
     };
     model.ui_model.lines = vec![make_log_line(&commit_hash, "First commit")];
     model.ui_model.cursor_position = 0;
@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+
+use magi::{
+    git::{
+        DiffSettings,
+        commit::head_commit_message,
+        git_cmd,
+        preview::get_commit_preview_lines,
+        signing::{SignatureStatus, commit_signature},
+        test_repo::TestRepo,
+    },
+    model::{
+        EditOp, LineContent, Model, PreviewLineType,
+        arguments::{Arguments, LogArgument},
+        popup::{CommitPopupState, MergePopupState, PopupContent, PopupContentCommand},
+        select_popup::OnSelect,
+    },
+    msg::{CommitEditorMessage, LogType, MergeCommand, Message, SelectMessage, update::update},
+};
+
+mod utils;
+use utils::create_model_from_test_repo;
+
+fn open_commit_popup(model: &mut Model) {
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Commit(
+        CommitPopupState::default(),
+    )));
+}
+
+fn pick_signing_key(model: &mut Model) {
+    update(model, Message::ShowSigningKeySelect);
+    update(model, Message::Select(SelectMessage::Confirm));
+}
+
+#[test]
+fn test_signing_key_picker_lists_configured_key_and_restores_popup() {
+    let test_repo = TestRepo::new();
+    let key = test_repo.with_ssh_signing_key();
+    let mut model = create_model_from_test_repo(&test_repo);
+    open_commit_popup(&mut model);
+
+    update(&mut model, Message::ShowSigningKeySelect);
+
+    match &model.popup {
+        Some(PopupContent::Command(PopupContentCommand::Select(state))) => {
+            assert_eq!(state.title, "Sign using key");
+            assert_eq!(
+                state.all_options,
+                vec![format!("{} (user.signingKey)", key)]
+            );
+            assert!(matches!(state.on_select, OnSelect::SigningKey { .. }));
+        }
+        other => panic!("expected the signing key select popup, got {:?}", other),
+    }
+
+    update(&mut model, Message::Select(SelectMessage::Confirm));
+
+    assert_eq!(model.signing_key.as_deref(), Some(key.as_str()));
+    assert!(matches!(
+        model.popup,
+        Some(PopupContent::Command(PopupContentCommand::Commit(_)))
+    ));
+}
+
+#[test]
+fn test_selecting_signing_argument_again_clears_key() {
+    let test_repo = TestRepo::new();
+    test_repo.with_ssh_signing_key();
+    let mut model = create_model_from_test_repo(&test_repo);
+    open_commit_popup(&mut model);
+    pick_signing_key(&mut model);
+    assert!(model.signing_key.is_some());
+
+    update(&mut model, Message::ShowSigningKeySelect);
+
+    assert_eq!(model.signing_key, None);
+    assert!(matches!(
+        model.popup,
+        Some(PopupContent::Command(PopupContentCommand::Commit(_)))
+    ));
+}
+
+#[test]
+fn test_dismissing_popup_clears_signing_key() {
+    let test_repo = TestRepo::new();
+    test_repo.with_ssh_signing_key();
+    let mut model = create_model_from_test_repo(&test_repo);
+    open_commit_popup(&mut model);
+    pick_signing_key(&mut model);
+
+    update(&mut model, Message::DismissPopup);
+
+    assert_eq!(model.signing_key, None);
+}
+
+#[test]
+fn test_commit_with_signing_key_is_signed() {
+    let test_repo = TestRepo::new();
+    test_repo.with_ssh_signing_key();
+    test_repo
+        .write_file_content("file.txt", "content")
+        .stage_files(&["file.txt"]);
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.config.builtin_commit_editor = true;
+    open_commit_popup(&mut model);
+    pick_signing_key(&mut model);
+
+    update(&mut model, Message::Commit);
+    for c in "Signed commit".chars() {
+        update(
+            &mut model,
+            Message::CommitEditor(CommitEditorMessage::Edit(EditOp::Insert(c))),
+        );
+    }
+    update(
+        &mut model,
+        Message::CommitEditor(CommitEditorMessage::Confirm),
+    );
+
+    assert_eq!(head_commit_message(test_repo.repo_path()), "Signed commit");
+    assert_eq!(
+        commit_signature(test_repo.repo_path(), "HEAD"),
+        Some((SignatureStatus::Good, "test@example.com".to_string()))
+    );
+    assert_eq!(model.signing_key, None);
+}
+
+#[test]
+fn test_merge_absorb_with_signing_key_signs_merge_commit() {
+    let test_repo = TestRepo::new();
+    test_repo.with_ssh_signing_key();
+    let workdir = test_repo.repo_path();
+    test_repo.create_branch("feature");
+    git_cmd(workdir, &["checkout", "-q", "feature"])
+        .status()
+        .unwrap();
+    test_repo.commit_file("feature.txt", "feature", "Feature commit");
+    git_cmd(workdir, &["checkout", "-q", "-"]).status().unwrap();
+    test_repo.commit_file("main.txt", "main", "Main commit");
+
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.popup = Some(PopupContent::Command(PopupContentCommand::Merge(
+        MergePopupState { in_progress: false },
+    )));
+    pick_signing_key(&mut model);
+
+    update(
+        &mut model,
+        Message::Merge(MergeCommand::Absorb("feature".to_string())),
+    );
+
+    assert!(
+        head_commit_message(workdir).starts_with("Merge branch 'feature'"),
+        "expected a merge commit"
+    );
+    assert_eq!(
+        commit_signature(workdir, "HEAD").map(|(status, _)| status),
+        Some(SignatureStatus::Good)
+    );
+}
+
+#[test]
+fn test_commit_preview_shows_signature_header() {
+    let test_repo = TestRepo::new();
+    let key = test_repo.with_ssh_signing_key();
+    let workdir = test_repo.repo_path();
+    let commit = git_cmd(
+        workdir,
+        &[
+            "commit",
+            "--allow-empty",
+            "-m",
+            "Signed",
+            &format!("--gpg-sign={}", key),
+        ],
+    )
+    .output()
+    .unwrap();
+    assert!(commit.status.success());
+
+    let lines = get_commit_preview_lines(workdir, "HEAD", &DiffSettings::default());
+
+    assert!(matches!(
+        &lines[1].content,
+        LineContent::PreviewLine { content, line_type: PreviewLineType::Header, .. }
+            if content == "Signature: ✓ Good signature from test@example.com"
+    ));
+}
+
+#[test]
+fn test_log_view_shows_signatures_when_asked() {
+    let test_repo = TestRepo::new();
+    let key = test_repo.with_ssh_signing_key();
+    let workdir = test_repo.repo_path();
+    for message in ["Signed 1", "Signed 2"] {
+        let commit = git_cmd(
+            workdir,
+            &[
+                "commit",
+                "--allow-empty",
+                "-m",
+                message,
+                &format!("--gpg-sign={}", key),
+            ],
+        )
+        .output()
+        .unwrap();
+        assert!(commit.status.success());
+    }
+    let mut model = create_model_from_test_repo(&test_repo);
+    let signatures = |model: &Model| -> Vec<Option<SignatureStatus>> {
+        model
+            .ui_model
+            .lines
+            .iter()
+            .filter_map(|line| match &line.content {
+                LineContent::LogLine(entry) if entry.hash.is_some() => Some(entry.signature),
+                _ => None,
+            })
+            .take(2)
+            .collect()
+    };
+
+    update(&mut model, Message::ShowLog(LogType::Current));
+    assert_eq!(signatures(&model), vec![None, None]);
+
+    model.arguments = Some(Arguments::LogArguments(HashSet::from([
+        LogArgument::Signatures,
+    ])));
+    update(&mut model, Message::ShowLog(LogType::Current));
+    assert_eq!(
+        signatures(&model),
+        vec![Some(SignatureStatus::Good), Some(SignatureStatus::Good)]
+    );
+
+    // Refreshing keeps showing them
+    update(&mut model, Message::Refresh);
+    assert_eq!(
+        signatures(&model),
+        vec![Some(SignatureStatus::Good), Some(SignatureStatus::Good)]
+    );
+}
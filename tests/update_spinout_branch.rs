@@ -234,6 +234,7 @@ fn test_spinout_with_upstream_resets_current_branch_in_place() {
             ..Default::default()
         },
         theme: magi::config::Theme::default(),
+        config: magi::config::Config::default(),
         popup: None,
         toast: None,
         select_result: None,
@@ -242,12 +243,17 @@ fn test_spinout_with_upstream_resets_current_branch_in_place() {
         arg_mode: false,
         pending_g: false,
         arguments: None,
+        signing_key: None,
+        argument_values: std::collections::HashMap::new(),
         view_mode: magi::model::ViewMode::Status,
         cursor_reposition_context: None,
         preview_return_mode: None,
         preview_return_ui_model: None,
         log_return_ui_model: None,
         rebase_todo: None,
+        commit_editor: None,
+        hunk_review: None,
+        commit_message_history: Vec::new(),
     };
 
     let result = update(&mut model, Message::SpinoutBranch("feature".to_string()));
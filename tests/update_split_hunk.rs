@@ -0,0 +1,148 @@
+use magi::{
+    git::test_repo::TestRepo,
+    model::{DiffHunk, LineContent, Model, SectionType},
+    msg::Message,
+};
+
+mod utils;
+use utils::{create_model_from_test_repo, find_line, index_content, run};
+
+/// The unstaged hunk headers of `path`, in order
+fn unstaged_hunk_headers(model: &Model) -> Vec<(usize, String)> {
+    model
+        .ui_model
+        .lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| match (&line.content, &line.section) {
+            (
+                LineContent::DiffHunk(DiffHunk { header, .. }),
+                Some(SectionType::UnstagedHunk { .. }),
+            ) => Some((index, header.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A file with two blocks of changes in a single hunk
+fn two_block_repo() -> TestRepo {
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("file.txt", "1\n2\n3\n4\n5\n6\n7\n8\n", "Initial")
+        .write_file_content("file.txt", "1\ntwo\n3\n4\n5\nsix\n7\n8\n");
+    test_repo
+}
+
+#[test]
+fn test_split_hunk_shows_each_block_as_a_hunk() {
+    let test_repo = two_block_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+    assert_eq!(unstaged_hunk_headers(&model).len(), 1);
+
+    model.ui_model.cursor_position = unstaged_hunk_headers(&model)[0].0;
+    run(&mut model, Message::SplitHunk);
+
+    let headers: Vec<String> = unstaged_hunk_headers(&model)
+        .into_iter()
+        .map(|(_, header)| header)
+        .collect();
+    assert_eq!(headers, vec!["@@ -1,2 +1,2 @@", "@@ -3,6 +3,6 @@"]);
+
+    // Splitting again joins the blocks
+    model.ui_model.cursor_position = unstaged_hunk_headers(&model)[1].0;
+    run(&mut model, Message::SplitHunk);
+    assert_eq!(unstaged_hunk_headers(&model).len(), 1);
+}
+
+#[test]
+fn test_staging_a_split_part_stages_only_its_block() {
+    let test_repo = two_block_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.ui_model.cursor_position = unstaged_hunk_headers(&model)[0].0;
+    run(&mut model, Message::SplitHunk);
+
+    model.ui_model.cursor_position = unstaged_hunk_headers(&model)[1].0;
+    run(&mut model, Message::StageSelected);
+
+    assert_eq!(
+        index_content(&test_repo, "file.txt"),
+        "1\n2\n3\n4\n5\nsix\n7\n8\n"
+    );
+    // What is left unstaged is a single block, shown as one hunk
+    let remaining: Vec<String> = unstaged_hunk_headers(&model)
+        .into_iter()
+        .map(|(_, header)| header)
+        .collect();
+    assert_eq!(remaining.len(), 1);
+}
+
+#[test]
+fn test_split_hunk_stays_split_when_an_earlier_hunk_is_staged() {
+    let test_repo = TestRepo::new();
+    let original: String = (1..=30).map(|n| format!("{}\n", n)).collect();
+    let changed: String = (1..=30)
+        .map(|n| match n {
+            2 | 20 | 24 => format!("changed {}\n", n),
+            _ => format!("{}\n", n),
+        })
+        .collect();
+    test_repo
+        .commit_file("file.txt", &original, "Initial")
+        .write_file_content("file.txt", &changed);
+    let mut model = create_model_from_test_repo(&test_repo);
+    assert_eq!(unstaged_hunk_headers(&model).len(), 2);
+
+    // Split the second hunk, then stage the first, which shifts its index
+    model.ui_model.cursor_position = unstaged_hunk_headers(&model)[1].0;
+    run(&mut model, Message::SplitHunk);
+    assert_eq!(unstaged_hunk_headers(&model).len(), 3);
+    model.ui_model.cursor_position = unstaged_hunk_headers(&model)[0].0;
+    run(&mut model, Message::StageSelected);
+
+    let headers: Vec<String> = unstaged_hunk_headers(&model)
+        .into_iter()
+        .map(|(_, header)| header)
+        .collect();
+    assert_eq!(headers.len(), 2, "{:?}", headers);
+    assert!(
+        model
+            .ui_model
+            .split_hunks
+            .contains_key(&SectionType::UnstagedHunk {
+                path: "file.txt".to_string(),
+                hunk_index: 0,
+            })
+    );
+}
+
+#[test]
+fn test_edit_hunk_stages_the_edited_hunk() {
+    let test_repo = two_block_repo();
+    let mut model = create_model_from_test_repo(&test_repo);
+
+    // Stage "TWO" in place of "two", and leave "six" out
+    unsafe {
+        std::env::set_var(
+            "GIT_EDITOR",
+            "sed -i -e 's/^+two$/+TWO/' -e 's/^-6$/ 6/' -e '/^+six$/d'",
+        )
+    };
+    model.ui_model.cursor_position = find_line(
+        &model,
+        |content| matches!(content, LineContent::DiffLine(diff) if diff.content == "two"),
+    )
+    .unwrap();
+    run(&mut model, Message::EditHunk);
+
+    assert_eq!(
+        index_content(&test_repo, "file.txt"),
+        "1\nTWO\n3\n4\n5\n6\n7\n8\n"
+    );
+    assert!(model.popup.is_none());
+    assert!(
+        !test_repo
+            .repo_path()
+            .join(".git/MAGI_EDIT_HUNK.diff")
+            .exists()
+    );
+}
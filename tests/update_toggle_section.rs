@@ -46,6 +46,7 @@ fn create_both_files_collapsed_lines() -> Vec<Line> {
             content: LineContent::DiffLine(DiffLine {
                 content: format!(" line {}", i),
                 line_type: DiffLineType::Context,
+                refined: Vec::new(),
             }),
             section: Some(SectionType::UnstagedHunk {
                 path: "file1.rs".to_string(),
@@ -71,6 +72,7 @@ fn create_both_files_collapsed_lines() -> Vec<Line> {
             content: LineContent::DiffLine(DiffLine {
                 content: format!(" line {}", i),
                 line_type: DiffLineType::Context,
+                refined: Vec::new(),
             }),
             section: Some(SectionType::UnstagedHunk {
                 path: "file2.rs".to_string(),
@@ -183,7 +185,7 @@ fn test_toggle_section_on_file() {
 fn test_toggle_section_on_non_header_does_nothing() {
     let mut model = create_test_model_with_lines(0);
     model.ui_model.lines = create_section_lines();
-    model.ui_model.cursor_position = 1; // On untracked file (not a collapsible header)
+    model.ui_model.cursor_position = 6; // On a hunk line (not a collapsible header)
 
     // Toggle on non-header should do nothing
     update(&mut model, Message::ToggleSection);
@@ -278,10 +280,12 @@ fn test_half_page_up_with_collapsed_sections() {
 fn test_commit_without_staged_changes_shows_toast() {
     // This test verifies that trying to commit without staged changes
     // shows a toast instead of launching the editor
-    let mut model = create_test_model();
+    let test_repo = TestRepo::new();
+    let mut model = create_model_from_test_repo(&test_repo);
 
     // Send commit message (no staged changes in test repo)
-    update(&mut model, Message::Commit);
+    let result = update(&mut model, Message::Commit);
+    assert_eq!(result, None, "Should not launch the editor");
 
     // Should show a toast warning about no staged changes
     assert!(
@@ -324,12 +328,10 @@ fn test_collapsed_state_preserved_when_staging_all() {
     // Find the unstaged file section and collapse it
     let mut collapsed_sections = HashSet::new();
     for line in &lines {
-        if let Some(section) = &line.section {
-            if let SectionType::UnstagedFile { path } = section {
-                if path == "test.txt" {
-                    collapsed_sections.insert(section.clone());
-                }
-            }
+        if let Some(section @ SectionType::UnstagedFile { path }) = &line.section
+            && path == "test.txt"
+        {
+            collapsed_sections.insert(section.clone());
         }
     }
 
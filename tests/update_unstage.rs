@@ -1,3 +1,4 @@
+use magi::git::DiffSettings;
 use magi::git::stage::{stage_files, stage_hunk};
 use magi::git::test_repo::TestRepo;
 use magi::model::{LineContent, SectionType};
@@ -142,7 +143,13 @@ fn test_visual_unstage_lines_from_staged_hunk_when_other_hunk_is_unstaged() {
     test_repo.write_file_content(file_name, &modified);
 
     // Stage only hunk 0 (the line 2 change); hunk 1 (line 19) remains unstaged
-    stage_hunk(test_repo.repo_path(), "test.txt", 0).unwrap();
+    stage_hunk(
+        test_repo.repo_path(),
+        "test.txt",
+        0,
+        &DiffSettings::default(),
+    )
+    .unwrap();
 
     // Build the model (unstaged section first, then staged section in the UI)
     let mut model = create_model_from_test_repo(&test_repo);
@@ -0,0 +1,168 @@
+use std::sync::Once;
+
+use crossterm::event::KeyCode;
+use magi::{
+    git::test_repo::TestRepo,
+    keys::handle_key,
+    model::{DiffLineType, LineContent, Model, PreviewLineType, ViewMode},
+    msg::{Message, update::update, util::is_external_command},
+};
+
+mod utils;
+use utils::{
+    create_model_from_test_repo, cursor_to_commit, find_line, find_unstaged_file_line, key,
+};
+
+const ORIGINAL: &str = "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\nnine\nten\n";
+const CHANGED: &str = "one\ntwo\nthree\nfour\nFIVE\nsix\nseven\neight\nnine\nten\n";
+
+fn diff_line(model: &Model, line_type: DiffLineType) -> usize {
+    find_line(
+        model,
+        |line| matches!(line, LineContent::DiffLine(diff) if diff.line_type == line_type),
+    )
+    .expect("diff line")
+}
+
+fn preview_line(model: &Model, line_type: PreviewLineType) -> usize {
+    find_line(
+        model,
+        |line| matches!(line, LineContent::PreviewLine { line_type: t, .. } if *t == line_type),
+    )
+    .expect("preview line")
+}
+
+fn expect_open_in_editor(msg: Option<Message>) -> (std::path::PathBuf, Option<usize>) {
+    match msg {
+        Some(Message::OpenInEditor { file, line }) => (file, line),
+        other => panic!("Expected OpenInEditor, got {:?}", other),
+    }
+}
+
+// ── Key bindings ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_enter_on_diff_line_visits_file() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("numbers.txt", ORIGINAL, "Add numbers")
+        .write_file_content("numbers.txt", CHANGED);
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.ui_model.cursor_position = diff_line(&model, DiffLineType::Addition);
+
+    assert_eq!(
+        handle_key(key(KeyCode::Enter), &model),
+        Some(Message::VisitFile)
+    );
+}
+
+#[test]
+fn test_enter_in_preview_visits_file() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("numbers.txt", ORIGINAL, "Add numbers");
+    let mut model = create_model_from_test_repo(&test_repo);
+    model.view_mode = ViewMode::Preview;
+
+    assert_eq!(
+        handle_key(key(KeyCode::Enter), &model),
+        Some(Message::VisitFile)
+    );
+}
+
+// ── VisitFile — status view ────────────────────────────────────────────────────
+
+#[test]
+fn test_visit_file_maps_diff_lines_to_worktree_lines() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("numbers.txt", ORIGINAL, "Add numbers")
+        .write_file_content("numbers.txt", CHANGED);
+    let mut model = create_model_from_test_repo(&test_repo);
+    let file = test_repo.repo_path().join("numbers.txt");
+
+    model.ui_model.cursor_position = diff_line(&model, DiffLineType::Addition);
+    let msg = update(&mut model, Message::VisitFile);
+    assert!(is_external_command(msg.as_ref().unwrap()));
+    assert_eq!(expect_open_in_editor(msg), (file.clone(), Some(5)));
+
+    // A removed line maps to where it used to be
+    model.ui_model.cursor_position = diff_line(&model, DiffLineType::Deletion);
+    let msg = update(&mut model, Message::VisitFile);
+    assert_eq!(expect_open_in_editor(msg), (file.clone(), Some(5)));
+
+    // The hunk header maps to the first line of the hunk
+    model.ui_model.cursor_position =
+        find_line(&model, |line| matches!(line, LineContent::DiffHunk(_))).unwrap();
+    let msg = update(&mut model, Message::VisitFile);
+    assert_eq!(expect_open_in_editor(msg), (file.clone(), Some(2)));
+
+    model.ui_model.cursor_position = find_unstaged_file_line(&model, "numbers.txt").unwrap();
+    let msg = update(&mut model, Message::VisitFile);
+    assert_eq!(expect_open_in_editor(msg), (file, None));
+}
+
+#[test]
+fn test_visit_file_ignores_other_lines() {
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("numbers.txt", ORIGINAL, "Add numbers");
+    let mut model = create_model_from_test_repo(&test_repo);
+    cursor_to_commit(&mut model);
+
+    assert_eq!(update(&mut model, Message::VisitFile), None);
+}
+
+// ── VisitFile — commit preview ─────────────────────────────────────────────────
+
+#[test]
+fn test_visit_file_in_preview_opens_blob_at_revision() {
+    let test_repo = TestRepo::new();
+    test_repo
+        .commit_file("numbers.txt", ORIGINAL, "Add numbers")
+        .commit_file("numbers.txt", CHANGED, "Shout five");
+    let mut model = create_model_from_test_repo(&test_repo);
+    cursor_to_commit(&mut model);
+    update(&mut model, Message::ShowPreview);
+    assert_eq!(model.view_mode, ViewMode::Preview);
+
+    model.ui_model.cursor_position = preview_line(&model, PreviewLineType::Addition);
+    let (file, line) = expect_open_in_editor(update(&mut model, Message::VisitFile));
+    assert_eq!(line, Some(5));
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), CHANGED);
+
+    // Removed lines open the parent's version of the file
+    model.ui_model.cursor_position = preview_line(&model, PreviewLineType::Deletion);
+    let (file, line) = expect_open_in_editor(update(&mut model, Message::VisitFile));
+    assert_eq!(line, Some(5));
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), ORIGINAL);
+}
+
+// ── OpenInEditor ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_open_in_editor_passes_line_and_file() {
+    static ONCE: Once = Once::new();
+    // Record the editor's arguments in the repo instead of opening anything
+    ONCE.call_once(|| unsafe {
+        std::env::set_var(
+            "GIT_EDITOR",
+            "f() { printf '%s\\n' \"$@\" > editor-args.txt; }; f",
+        )
+    });
+
+    let test_repo = TestRepo::new();
+    test_repo.commit_file("numbers.txt", ORIGINAL, "Add numbers");
+    let mut model = create_model_from_test_repo(&test_repo);
+    let file = test_repo.repo_path().join("numbers.txt");
+
+    let result = update(
+        &mut model,
+        Message::OpenInEditor {
+            file: file.clone(),
+            line: Some(5),
+        },
+    );
+
+    assert_eq!(result, Some(Message::Refresh));
+    let args = std::fs::read_to_string(test_repo.repo_path().join("editor-args.txt")).unwrap();
+    assert_eq!(args, format!("+5\n{}\n", file.display()));
+}
@@ -7,14 +7,15 @@ use std::{
 
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
 use magi::{
-    config::Theme,
-    git::{GitInfo, stage::stage_files, test_repo::TestRepo},
+    config::{Config, Theme},
+    git::{GitInfo, git_cmd, stage::stage_files, test_repo::TestRepo},
     model::{
         DiffHunk, DiffLine, DiffLineType, FileChange, FileStatus, Line, LineContent, Model,
         RunningState, SectionType, UiModel, ViewMode,
         popup::{ConfirmPopupState, InputPopupState, PopupContent, PopupContentCommand},
         select_popup::{OnSelect, SelectPopupState},
     },
+    msg::{Message, update::update},
 };
 
 // ── Key event builders ────────────────────────────────────────────────────────
@@ -174,6 +175,7 @@ pub fn create_test_model() -> Model {
             ..Default::default()
         },
         theme: Theme::default(),
+        config: Config::default(),
         popup: None,
         toast: None,
         select_result: None,
@@ -182,12 +184,17 @@ pub fn create_test_model() -> Model {
         arg_mode: false,
         pending_g: false,
         arguments: None,
+        signing_key: None,
+        argument_values: std::collections::HashMap::new(),
         view_mode: ViewMode::Status,
         cursor_reposition_context: None,
         preview_return_mode: None,
         preview_return_ui_model: None,
         log_return_ui_model: None,
         rebase_todo: None,
+        commit_editor: None,
+        hunk_review: None,
+        commit_message_history: Vec::new(),
     }
 }
 
@@ -284,7 +291,7 @@ pub fn create_model_from_test_repo(test_repo: &TestRepo) -> Model {
     // Initialize collapsed sections with default collapsed items (UnstagedFile, StagedFile)
     let collapsed_sections = lines
         .iter()
-        .filter_map(|line| line.section.clone())
+        .flat_map(Line::sections)
         .filter(|section| section.default_collapsed())
         .collect();
 
@@ -297,6 +304,22 @@ pub fn create_model_from_test_repo(test_repo: &TestRepo) -> Model {
     model
 }
 
+/// Runs `msg` and every follow-up message it produces.
+pub fn run(model: &mut Model, msg: Message) {
+    let mut next = Some(msg);
+    while let Some(msg) = next {
+        next = update(model, msg);
+    }
+}
+
+/// The content of `path` in the index
+pub fn index_content(test_repo: &TestRepo, path: &str) -> String {
+    let output = git_cmd(test_repo.repo_path(), &["show", &format!(":{}", path)])
+        .output()
+        .unwrap();
+    String::from_utf8(output.stdout).unwrap()
+}
+
 /// Create lines simulating two files with many diff lines each
 pub fn create_two_file_lines() -> Vec<Line> {
     let mut lines = Vec::new();
@@ -339,6 +362,7 @@ pub fn create_two_file_lines() -> Vec<Line> {
             content: LineContent::DiffLine(DiffLine {
                 content: format!(" context line {}", i),
                 line_type: DiffLineType::Context,
+                refined: Vec::new(),
             }),
             section: Some(SectionType::UnstagedHunk {
                 path: "file1.rs".to_string(),
@@ -376,6 +400,7 @@ pub fn create_two_file_lines() -> Vec<Line> {
             content: LineContent::DiffLine(DiffLine {
                 content: format!(" context line {}", i),
                 line_type: DiffLineType::Context,
+                refined: Vec::new(),
             }),
             section: Some(SectionType::UnstagedHunk {
                 path: "file2.rs".to_string(),